@@ -0,0 +1,55 @@
+#![feature(test)]
+
+extern crate json_api;
+extern crate serde_json;
+extern crate test;
+
+use json_api::doc::{Document, Object};
+use json_api::value::Value;
+use test::Bencher;
+
+/// Returns a compound document of roughly 1MB.
+fn source() -> String {
+    let mut data = Vec::new();
+
+    for id in 0..2000 {
+        data.push(format!(
+            r#"{{
+                "type": "articles",
+                "id": "{id}",
+                "attributes": {{
+                    "title": "JSON API paints my bikeshed!",
+                    "body": "The shortest article. Ever.",
+                    "published-at": "2015-05-22T14:56:29.000Z",
+                    "word-count": {id},
+                    "tags": ["json", "api", "bikeshed"],
+                    "metadata": {{ "read-time": 1, "featured": false }}
+                }},
+                "relationships": {{
+                    "author": {{
+                        "data": {{ "type": "people", "id": "{id}" }}
+                    }}
+                }}
+            }}"#,
+            id = id
+        ));
+    }
+
+    format!(r#"{{ "data": [{}] }}"#, data.join(","))
+}
+
+#[bench]
+fn parse_document(b: &mut Bencher) {
+    let source = source();
+
+    b.bytes = source.len() as u64;
+    b.iter(|| json_api::parse_str::<Object>(&source).unwrap() as Document<Object>)
+}
+
+#[bench]
+fn parse_value(b: &mut Bencher) {
+    let source = source();
+
+    b.bytes = source.len() as u64;
+    b.iter(|| serde_json::from_str::<Value>(&source).unwrap())
+}
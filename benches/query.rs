@@ -0,0 +1,36 @@
+#![feature(test)]
+
+extern crate json_api;
+extern crate test;
+
+use json_api::query;
+use test::Bencher;
+
+/// Returns a query string with 20 include paths of 1 to 3 segments and a few sort
+/// fields.
+fn source() -> String {
+    let include = (0..20)
+        .map(|n| match n % 3 {
+            0 => format!("author{}", n),
+            1 => format!("comments{}.author", n),
+            _ => format!("comments{}.author.avatar", n),
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!("include={}&sort=-published-at,author.name,title", include)
+}
+
+#[bench]
+fn from_str(b: &mut Bencher) {
+    let source = source();
+
+    b.bytes = source.len() as u64;
+    b.iter(|| query::from_str(&source).unwrap());
+}
+
+#[bench]
+fn to_string(b: &mut Bencher) {
+    let query = query::from_str(&source()).unwrap();
+    b.iter(|| query::to_string(&query).unwrap());
+}
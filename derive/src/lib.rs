@@ -0,0 +1,345 @@
+//! Derive support for the [`Resource`] trait of the json-api crate.
+//!
+//! This crate is re-exported by json-api when the `derive` feature is enabled. For
+//! more information, check out the documentation of [`Resource`].
+//!
+//! [`Resource`]: https://docs.rs/json-api/0.4/json_api/trait.Resource.html
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as Tokens};
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Error, Field, Fields, Ident, LitStr};
+
+#[proc_macro_derive(Resource, attributes(json_api))]
+pub fn derive_resource(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => compile_errors(e).into(),
+    }
+}
+
+/// Converts `e` to a `compile_error!` invocation for each message. Unlike
+/// `Error::to_compile_error`, the invocations do not rely on the `core` crate being in
+/// the extern prelude, which is not the case for crates using the 2015 edition.
+fn compile_errors(e: Error) -> Tokens {
+    e.into_iter()
+        .map(|error| {
+            let message = error.to_string();
+            quote_spanned!(error.span()=> compile_error!(#message);)
+        })
+        .collect()
+}
+
+/// The role of a field in the rendered resource object.
+enum Member {
+    Attr(LitStr),
+    HasMany(LitStr),
+    HasOne(LitStr),
+    Id,
+}
+
+fn expand(input: &DeriveInput) -> Result<Tokens, Error> {
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => return Err(Error::new(input.span(), "expected a struct with named fields")),
+        },
+        _ => return Err(Error::new(input.span(), "expected a struct with named fields")),
+    };
+
+    let mut errors: Option<Error> = None;
+    let kind = container_kind(input)
+        .map_err(|e| combine(&mut errors, e))
+        .ok();
+    let mut id = None;
+    let mut attrs = Vec::new();
+    let mut rels = Vec::new();
+
+    for field in fields {
+        let member = match field_member(field) {
+            Ok(Some(member)) => member,
+            Ok(None) => continue,
+            Err(e) => {
+                combine(&mut errors, e);
+                continue;
+            }
+        };
+
+        let name = field.ident.as_ref().unwrap();
+
+        match member {
+            Member::Id if id.is_some() => {
+                let e = Error::new(field.span(), "duplicate #[json_api(id)] field");
+                combine(&mut errors, e);
+            }
+            Member::Id => id = Some(name),
+            Member::Attr(key) => attrs.push(expand_attr(name, &key)),
+            Member::HasMany(key) => rels.push(expand_has_many(name, &key)),
+            Member::HasOne(key) => rels.push(expand_has_one(name, &key)),
+        }
+    }
+
+    if id.is_none() {
+        let e = Error::new(input.ident.span(), "missing a #[json_api(id)] field");
+        combine(&mut errors, e);
+    }
+
+    if let Some(e) = errors {
+        return Err(e);
+    }
+
+    let id = id.unwrap();
+    let kind = kind.unwrap();
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::json_api::Resource for #ident #ty_generics #where_clause {
+            fn kind() -> ::json_api::value::Key {
+                ::json_api::value::Key::from_raw(#kind.to_owned())
+            }
+
+            fn id(&self) -> String {
+                self.#id.to_string()
+            }
+
+            fn to_ident(
+                &self,
+                _: &mut ::json_api::view::Context,
+            ) -> Result<::json_api::doc::Identifier, ::json_api::Error> {
+                let kind = ::json_api::Resource::instance_kind(self);
+                let id = ::json_api::Resource::id(self);
+
+                Ok(::json_api::doc::Identifier::new(kind, id))
+            }
+
+            fn to_object(
+                &self,
+                ctx: &mut ::json_api::view::Context,
+            ) -> Result<::json_api::doc::Object, ::json_api::Error> {
+                #[allow(dead_code)]
+                fn data_kind<'a, F, I, T>(_: &F) -> ::json_api::value::Key
+                where
+                    F: FnOnce() -> I,
+                    I: IntoIterator<Item = &'a T>,
+                    T: ::json_api::Resource + 'a,
+                {
+                    T::kind()
+                }
+
+                let mut obj = {
+                    let kind = ::json_api::Resource::instance_kind(self);
+                    let id = ::json_api::Resource::id(self);
+
+                    ::json_api::doc::Object::new(kind, id)
+                };
+
+                #(#attrs)*
+                #(#rels)*
+
+                obj.validate_members()?;
+                Ok(obj)
+            }
+        }
+    })
+}
+
+fn expand_attr(name: &Ident, key: &LitStr) -> Tokens {
+    let value = quote_spanned!(name.span()=> ::json_api::to_value(&self.#name)?);
+
+    quote! {
+        if ctx.field(#key) {
+            let key = #key.parse::<::json_api::value::Key>()?;
+            obj.attributes.insert(key, #value);
+        }
+    }
+}
+
+fn expand_has_many(name: &Ident, key: &LitStr) -> Tokens {
+    let value = quote_spanned!(name.span()=> self.#name.iter());
+
+    quote! {
+        if ctx.field(#key) {
+            let key = #key.parse::<::json_api::value::Key>()?;
+            let mut rel = ::json_api::doc::Relationship::default();
+
+            rel.data = {
+                let included = ctx.fork(data_kind(&|| #value), &key).try_included()?;
+
+                if included || ctx.emits_linkage() {
+                    let iter = #value;
+                    let mut data = match iter.size_hint() {
+                        (_, Some(size)) => Vec::with_capacity(size),
+                        _ => Vec::new(),
+                    };
+
+                    for item in iter {
+                        let kind = ::json_api::Resource::instance_kind(item);
+                        let id = ::json_api::Resource::id(item);
+                        let mut ctx = ctx.fork(kind.clone(), &key);
+
+                        data.push(::json_api::Resource::to_ident(item, &mut ctx)?);
+
+                        if included && ctx.visit(kind, id) {
+                            let object = ::json_api::Resource::to_object(item, &mut ctx)?;
+
+                            ctx.leave();
+                            ctx.include(object);
+                        }
+                    }
+
+                    Some(data.into())
+                } else {
+                    None
+                }
+            };
+
+            obj.relationships.insert(key, rel);
+        }
+    }
+}
+
+fn expand_has_one(name: &Ident, key: &LitStr) -> Tokens {
+    let value = quote_spanned!(name.span()=> self.#name.as_ref());
+
+    quote! {
+        if ctx.field(#key) {
+            let key = #key.parse::<::json_api::value::Key>()?;
+            let mut rel = ::json_api::doc::Relationship::default();
+
+            rel.data = {
+                let included = ctx.fork(data_kind(&|| #value), &key).try_included()?;
+
+                if included || ctx.emits_linkage() {
+                    let mut data = None;
+
+                    if let Some(item) = #value {
+                        let kind = ::json_api::Resource::instance_kind(item);
+                        let mut ctx = ctx.fork(kind.clone(), &key);
+
+                        data = Some(::json_api::Resource::to_ident(item, &mut ctx)?);
+
+                        if included && ctx.visit(kind, ::json_api::Resource::id(item)) {
+                            let object = ::json_api::Resource::to_object(item, &mut ctx)?;
+
+                            ctx.leave();
+                            ctx.include(object);
+                        }
+                    }
+
+                    Some(data.into())
+                } else {
+                    None
+                }
+            };
+
+            obj.relationships.insert(key, rel);
+        }
+    }
+}
+
+/// Returns the value of the `#[json_api(kind = "...")]` attribute of the struct.
+fn container_kind(input: &DeriveInput) -> Result<LitStr, Error> {
+    let mut kind = None;
+
+    for attr in &input.attrs {
+        if !attr.path().is_ident("json_api") {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("kind") {
+                kind = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `kind`"))
+            }
+        })?;
+    }
+
+    match kind {
+        Some(kind) => Ok(kind),
+        None => Err(Error::new(
+            input.ident.span(),
+            r#"missing a #[json_api(kind = "...")] attribute"#,
+        )),
+    }
+}
+
+/// Returns the role of `field` as specified by its `#[json_api(...)]` attributes.
+/// Returns `None` if the field is not a part of the resource object.
+fn field_member(field: &Field) -> Result<Option<Member>, Error> {
+    let mut role: Option<(&str, Span)> = None;
+    let mut rename = None;
+
+    for attr in &field.attrs {
+        if !attr.path().is_ident("json_api") {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            let found = ["attr", "has_many", "has_one", "id"]
+                .iter()
+                .find(|name| meta.path.is_ident(name));
+
+            if let Some(name) = found {
+                if role.is_some() {
+                    return Err(meta.error("a field can only have one of `attr`, `has_many`, \
+                                           `has_one`, or `id`"));
+                }
+
+                role = Some((name, meta.path.span()));
+                Ok(())
+            } else if meta.path.is_ident("rename") {
+                rename = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else if meta.path.is_ident("kind") {
+                Err(meta.error("`kind` must be specified on the struct"))
+            } else {
+                Err(meta.error("expected `attr`, `has_many`, `has_one`, `id`, or `rename`"))
+            }
+        })?;
+    }
+
+    let (role, span) = match role {
+        Some(role) => role,
+        None => match rename {
+            Some(rename) => return Err(Error::new(rename.span(), "`rename` requires `attr`, \
+                                                                  `has_many`, or `has_one`")),
+            None => return Ok(None),
+        },
+    };
+
+    if role == "id" {
+        return match rename {
+            Some(_) => Err(Error::new(span, "`rename` cannot be used with `id`")),
+            None => Ok(Some(Member::Id)),
+        };
+    }
+
+    let key = rename.unwrap_or_else(|| {
+        let name = field.ident.as_ref().unwrap().to_string();
+        LitStr::new(name.trim_start_matches("r#"), field.span())
+    });
+
+    match role {
+        "attr" => Ok(Some(Member::Attr(key))),
+        "has_many" => Ok(Some(Member::HasMany(key))),
+        _ => Ok(Some(Member::HasOne(key))),
+    }
+}
+
+fn combine(errors: &mut Option<Error>, e: Error) {
+    match *errors {
+        Some(ref mut errors) => errors.combine(e),
+        None => *errors = Some(e),
+    }
+}
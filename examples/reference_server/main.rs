@@ -0,0 +1,28 @@
+//! A minimal JSON API server that keeps its data in memory.
+//!
+//! Run it with `cargo run --example reference_server` to print the responses of a
+//! few requests. The same server is driven by `tests/reference_server.rs`.
+
+#[macro_use]
+extern crate json_api;
+
+mod server;
+
+use server::Server;
+
+fn main() {
+    let mut server = Server::new();
+    let requests: &[(&str, &str, &str, &[u8])] = &[
+        ("GET", "/articles", "include=author&sort=-title&page%5Bsize%5D=2", b""),
+        ("GET", "/articles/1", "fields%5Barticles%5D=title", b""),
+        ("GET", "/articles/1/relationships/comments", "", b""),
+        ("GET", "/articles/42", "", b""),
+    ];
+
+    for &(method, path, query, body) in requests {
+        let resp = server.handle(method, path, query, body);
+
+        println!("{} {}?{} -> {}", method, path, query, resp.status());
+        println!("{}\n", String::from_utf8_lossy(resp.body()));
+    }
+}
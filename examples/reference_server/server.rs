@@ -0,0 +1,505 @@
+//! An in-memory JSON API server for articles, comments, and users.
+//!
+//! Requests are plain values that are routed by [`Server::handle`]. No web framework
+//! is involved, so the module can be driven directly from tests.
+
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use json_api::doc::{self, Data, Document, ErrorObject, ErrorSource, Linkage, LinkagePolicy,
+                    NewObject, Object, PrimaryData};
+use json_api::http::header::{HeaderValue, LOCATION};
+use json_api::http::{Response, StatusCode, Uri};
+use json_api::query::{self, Direction, Page, Query};
+use json_api::schema::SchemaRegistry;
+use json_api::value::{self, Key, Map};
+use json_api::{self, Error, Resource};
+
+/// The page size that is used when a request does not specify one.
+const DEFAULT_PAGE_SIZE: u64 = 2;
+
+/// The maximum number of relationships that a sort field may traverse.
+const MAX_SORT_DEPTH: usize = 1;
+
+#[derive(Clone)]
+pub struct User {
+    pub id: u64,
+    pub name: String,
+}
+
+resource!(User, |&self| {
+    kind "users";
+    id self.id;
+
+    attr name: "string";
+});
+
+#[derive(Clone)]
+pub struct Comment {
+    pub id: u64,
+    pub body: String,
+    pub author: Option<User>,
+}
+
+resource!(Comment, |&self| {
+    kind "comments";
+    id self.id;
+
+    attr body: "string";
+    has_one author: "users";
+});
+
+#[derive(Clone)]
+pub struct Article {
+    pub id: u64,
+    pub title: String,
+    pub body: String,
+    pub author: Option<User>,
+    pub comments: Vec<Comment>,
+}
+
+resource!(Article, |&self| {
+    kind "articles";
+    id self.id;
+
+    attr title: "string";
+    attr body: "string";
+    has_one author: "users";
+    has_many comments: "comments";
+});
+
+struct ArticleRow {
+    id: u64,
+    title: String,
+    body: String,
+    author: u64,
+    comments: Vec<u64>,
+}
+
+struct CommentRow {
+    id: u64,
+    body: String,
+    author: u64,
+}
+
+/// An in-memory dataset and the handlers that serve it.
+pub struct Server {
+    articles: Vec<ArticleRow>,
+    comments: Vec<CommentRow>,
+    users: Vec<User>,
+    schemas: SchemaRegistry,
+}
+
+impl Server {
+    /// Returns a server with a small seeded dataset.
+    pub fn new() -> Self {
+        let mut schemas = SchemaRegistry::new();
+
+        schemas
+            .register::<Article>()
+            .and_then(|schemas| schemas.register::<Comment>())
+            .and_then(|schemas| schemas.register::<User>())
+            .expect("valid resource schemas");
+
+        let user = |id, name: &str| User {
+            id,
+            name: name.to_owned(),
+        };
+
+        let comment = |id, body: &str, author| CommentRow {
+            id,
+            author,
+            body: body.to_owned(),
+        };
+
+        let article = |id, title: &str, author, comments| ArticleRow {
+            id,
+            author,
+            comments,
+            body: format!("The body of {}.", title),
+            title: title.to_owned(),
+        };
+
+        Server {
+            schemas,
+            articles: vec![
+                article(1, "JSON API paints my bikeshed!", 9, vec![5, 12]),
+                article(2, "Rust in production", 10, vec![]),
+                article(3, "Another article", 9, vec![13]),
+            ],
+            comments: vec![
+                comment(5, "First!", 2),
+                comment(12, "I like XML better", 9),
+                comment(13, "Nice", 10),
+                comment(14, "Unattached", 2),
+            ],
+            users: vec![user(2, "Dan"), user(9, "Yehuda"), user(10, "Steve")],
+        }
+    }
+
+    /// Handles a request and returns the response. The `query` is the percent
+    /// encoded query string of the request without the leading `?`.
+    pub fn handle(
+        &mut self,
+        method: &str,
+        path: &str,
+        query: &str,
+        body: &[u8],
+    ) -> Response<Vec<u8>> {
+        self.route(method, path, query, body)
+            .unwrap_or_else(|e| error_response(&e))
+    }
+
+    fn route(
+        &mut self,
+        method: &str,
+        path: &str,
+        query: &str,
+        body: &[u8],
+    ) -> Result<Response<Vec<u8>>, Error> {
+        let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+        let query = query::from_str(query)?;
+
+        match (method, &segments[..]) {
+            ("GET", &["articles"]) => self.list_articles(&query),
+            ("POST", &["articles"]) => self.create_article(body, &query),
+            ("GET", &["articles", id]) => self.show_article(parse_id(id)?, &query),
+            ("PATCH", &["articles", id]) => self.update_article(parse_id(id)?, body, &query),
+            ("GET", &["articles", id, "relationships", "comments"]) => {
+                self.show_comments(parse_id(id)?)
+            }
+            ("PATCH", &["articles", id, "relationships", "comments"]) => {
+                self.replace_comments(parse_id(id)?, body)
+            }
+            _ => Err(not_found(path)),
+        }
+    }
+
+    fn list_articles(&self, query: &Query) -> Result<Response<Vec<u8>>, Error> {
+        let schema = self.schemas.get("articles").expect("articles schema");
+
+        for sort in &query.sort {
+            if let Err(error) = sort.validate(schema, &self.schemas, MAX_SORT_DEPTH) {
+                return Err(Error::error_document(vec![error]));
+            }
+        }
+
+        let mut articles = self.articles
+            .iter()
+            .map(|row| self.article(row))
+            .collect::<Vec<_>>();
+
+        articles.sort_by(|a, b| {
+            query.sort.iter().fold(Ordering::Equal, |order, sort| {
+                let field = sort.field.to_string();
+                let next = match sort.direction {
+                    Direction::Asc => sort_value(a, &field).cmp(&sort_value(b, &field)),
+                    Direction::Desc => sort_value(b, &field).cmp(&sort_value(a, &field)),
+                };
+
+                order.then(next)
+            })
+        });
+
+        let mut query = query.clone();
+        let page = query.page.unwrap_or_default();
+        let page = Page::new(page.number, Some(page.size.unwrap_or(DEFAULT_PAGE_SIZE)));
+        let size = page.size.unwrap_or(DEFAULT_PAGE_SIZE) as usize;
+        let start = (page.number as usize - 1) * size;
+        let items = articles
+            .get(start..)
+            .map(|rest| &rest[..size.min(rest.len())])
+            .unwrap_or(&[]);
+
+        query.page = Some(page);
+
+        let base = Uri::from_str("/articles")?;
+        let doc = json_api::to_doc_paginated(items, &query, articles.len() as u64, &base)?;
+
+        respond(StatusCode::OK, doc)
+    }
+
+    fn show_article(&self, id: u64, query: &Query) -> Result<Response<Vec<u8>>, Error> {
+        let article = self.find_article(id)?;
+        let doc: Document<Object> = json_api::to_doc(&article, Some(query))?;
+
+        respond(StatusCode::OK, doc)
+    }
+
+    fn create_article(&mut self, body: &[u8], query: &Query) -> Result<Response<Vec<u8>>, Error> {
+        let object = match json_api::parse_slice::<NewObject>(body)? {
+            Document::Ok {
+                data: Data::Member(data),
+                ..
+            } => (*data).ok_or_else(|| unprocessable(doc::Pointer::data(), "data is null"))?,
+            _ => return Err(unprocessable(doc::Pointer::data(), "expected a resource")),
+        };
+
+        check_kind(&object.kind)?;
+
+        let id = match object.id {
+            Some(ref id) => {
+                let id = parse_id(id)?;
+
+                if self.articles.iter().any(|row| row.id == id) {
+                    return Err(conflict(
+                        doc::Pointer::from("/data/id"),
+                        &format!("an article with the id {} already exists", id),
+                    ));
+                }
+
+                id
+            }
+            None => self.articles.iter().map(|row| row.id).max().unwrap_or(0) + 1,
+        };
+
+        let title = attribute(&object.attributes, "title")?
+            .ok_or_else(|| unprocessable(doc::Pointer::attribute("title"), "title is required"))?;
+        let body = attribute(&object.attributes, "body")?.unwrap_or_default();
+
+        self.articles.push(ArticleRow {
+            id,
+            title,
+            body,
+            author: 9,
+            comments: Vec::new(),
+        });
+
+        let article = self.find_article(id)?;
+        let doc: Document<Object> = json_api::to_doc(&article, Some(query))?;
+        let mut resp = respond(StatusCode::CREATED, doc)?;
+        let location = HeaderValue::from_str(&format!("/articles/{}", id))
+            .expect("valid location header");
+
+        resp.headers_mut().insert(LOCATION, location);
+        Ok(resp)
+    }
+
+    fn update_article(
+        &mut self,
+        id: u64,
+        body: &[u8],
+        query: &Query,
+    ) -> Result<Response<Vec<u8>>, Error> {
+        let object = match json_api::parse_slice::<Object>(body)? {
+            Document::Ok {
+                data: Data::Member(data),
+                ..
+            } => (*data).ok_or_else(|| unprocessable(doc::Pointer::data(), "data is null"))?,
+            _ => return Err(unprocessable(doc::Pointer::data(), "expected a resource")),
+        };
+
+        check_kind(&object.kind)?;
+
+        if object.id != id.to_string() {
+            return Err(conflict(
+                doc::Pointer::from("/data/id"),
+                &format!("expected the id {}, found {}", id, object.id),
+            ));
+        }
+
+        // Only the attributes that are present in the request are updated.
+        let title = attribute(&object.attributes, "title")?;
+        let body = attribute(&object.attributes, "body")?;
+        let row = self.find_row(id)?;
+
+        if let Some(title) = title {
+            row.title = title;
+        }
+
+        if let Some(body) = body {
+            row.body = body;
+        }
+
+        let article = self.find_article(id)?;
+        let doc: Document<Object> = json_api::to_doc(&article, Some(query))?;
+
+        respond(StatusCode::OK, doc)
+    }
+
+    fn show_comments(&self, id: u64) -> Result<Response<Vec<u8>>, Error> {
+        let article = self.find_article(id)?;
+        let doc = json_api::to_doc_identifiers(&article.comments, None)?;
+
+        respond(StatusCode::OK, doc)
+    }
+
+    fn replace_comments(&mut self, id: u64, body: &[u8]) -> Result<Response<Vec<u8>>, Error> {
+        let linkage = Linkage::from_slice(body)?;
+        let mut policy = LinkagePolicy::new();
+
+        policy.allowed_kinds.insert(Comment::kind());
+        linkage.validate(&policy)?;
+
+        if let Data::Member(_) = *linkage.data() {
+            return Err(unprocessable(doc::Pointer::data(), "expected an array"));
+        }
+
+        let mut ids = Vec::new();
+
+        for (index, value) in linkage.into_unique_ids().into_iter().enumerate() {
+            let id = parse_id(&value)?;
+
+            if !self.comments.iter().any(|row| row.id == id) {
+                let mut pointer = doc::Pointer::data();
+
+                pointer.push_index(index).push("id");
+                return Err(Error::error_document(vec![error_object(
+                    StatusCode::NOT_FOUND,
+                    Some(pointer),
+                    &format!("comment {} does not exist", id),
+                )]));
+            }
+
+            ids.push(id);
+        }
+
+        self.find_row(id)?.comments = ids;
+
+        let mut resp = Response::new(Vec::new());
+
+        *resp.status_mut() = StatusCode::NO_CONTENT;
+        Ok(resp)
+    }
+
+    fn article(&self, row: &ArticleRow) -> Article {
+        Article {
+            id: row.id,
+            title: row.title.clone(),
+            body: row.body.clone(),
+            author: self.user(row.author),
+            comments: row.comments
+                .iter()
+                .filter_map(|id| self.comments.iter().find(|comment| comment.id == *id))
+                .map(|comment| Comment {
+                    id: comment.id,
+                    body: comment.body.clone(),
+                    author: self.user(comment.author),
+                })
+                .collect(),
+        }
+    }
+
+    fn find_article(&self, id: u64) -> Result<Article, Error> {
+        self.articles
+            .iter()
+            .find(|row| row.id == id)
+            .map(|row| self.article(row))
+            .ok_or_else(|| not_found(&format!("/articles/{}", id)))
+    }
+
+    fn find_row(&mut self, id: u64) -> Result<&mut ArticleRow, Error> {
+        self.articles
+            .iter_mut()
+            .find(|row| row.id == id)
+            .ok_or_else(|| not_found(&format!("/articles/{}", id)))
+    }
+
+    fn user(&self, id: u64) -> Option<User> {
+        self.users.iter().find(|user| user.id == id).cloned()
+    }
+}
+
+/// Returns the attribute `name` of a resource in a request as a string.
+fn attribute(attributes: &Map, name: &str) -> Result<Option<String>, Error> {
+    match attributes.get(name) {
+        Some(value) => value::from_value(value.clone()).map(Some).map_err(|_| {
+            unprocessable(doc::Pointer::attribute(name), &format!("{} must be a string", name))
+        }),
+        None => Ok(None),
+    }
+}
+
+fn check_kind(kind: &Key) -> Result<(), Error> {
+    if *kind == Article::kind() {
+        Ok(())
+    } else {
+        Err(conflict(
+            doc::Pointer::from("/data/type"),
+            &format!("expected the type articles, found {}", kind),
+        ))
+    }
+}
+
+fn parse_id(value: &str) -> Result<u64, Error> {
+    value.parse().map_err(|_| {
+        let mut error = error_object(StatusCode::BAD_REQUEST, None, "ids must be integers");
+
+        error.source = Some(ErrorSource::from(doc::Pointer::from("/data/id")));
+        Error::error_document(vec![error])
+    })
+}
+
+/// Returns the value that an article is sorted by for the sort field `field`.
+fn sort_value(article: &Article, field: &str) -> String {
+    match field {
+        "title" => article.title.clone(),
+        "body" => article.body.clone(),
+        "author.name" => article
+            .author
+            .as_ref()
+            .map(|author| author.name.clone())
+            .unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+fn error_object(status: StatusCode, pointer: Option<doc::Pointer>, detail: &str) -> ErrorObject {
+    let mut error = ErrorObject::new(Some(status));
+
+    error.detail = Some(detail.to_owned());
+    error.source = pointer.map(ErrorSource::from);
+    error
+}
+
+fn conflict(pointer: doc::Pointer, detail: &str) -> Error {
+    Error::error_document(vec![error_object(StatusCode::CONFLICT, Some(pointer), detail)])
+}
+
+fn not_found(path: &str) -> Error {
+    let detail = format!("{} does not exist", path);
+    Error::error_document(vec![error_object(StatusCode::NOT_FOUND, None, &detail)])
+}
+
+fn unprocessable(pointer: doc::Pointer, detail: &str) -> Error {
+    let status = StatusCode::UNPROCESSABLE_ENTITY;
+    Error::error_document(vec![error_object(status, Some(pointer), detail)])
+}
+
+/// Returns a response with `doc` as the body and the JSON API media type as the
+/// `Content-Type`.
+fn respond<T: PrimaryData>(
+    status: StatusCode,
+    doc: Document<T>,
+) -> Result<Response<Vec<u8>>, Error> {
+    let body = json_api::to_vec::<_, T>(doc, None)?;
+    let (name, value) = json_api::content_type_header();
+    let mut resp = Response::new(body);
+
+    *resp.status_mut() = status;
+    resp.headers_mut().insert(name, value);
+    Ok(resp)
+}
+
+/// Returns a response with an error document that describes `e`.
+fn error_response(e: &Error) -> Response<Vec<u8>> {
+    let errors = match *e.kind() {
+        json_api::error::ErrorKind::ErrorDocument(ref errors) => errors.clone(),
+        _ => vec![ErrorObject::from(e)],
+    };
+
+    let status = errors
+        .iter()
+        .filter_map(|error| error.status)
+        .next()
+        .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+    let doc: Document<Object> = Document::Err {
+        errors,
+        jsonapi: Default::default(),
+        links: Default::default(),
+        meta: Default::default(),
+    };
+
+    respond(status, doc).expect("error documents can be serialized")
+}
@@ -1,37 +1,15 @@
+use json_api::doc::{Document, ErrorObject, Object};
 use json_api::http::StatusCode;
+use rocket::catcher::BoxFuture;
 use rocket::http::Status;
-use rocket::{Catcher, Error as RocketError, Request, Response};
+use rocket::{Catcher, Request, Response};
 
-use response;
+use crate::response;
 
 macro_rules! catchers {
     ({ $($status:expr => $name:ident),* }) => {
-        $(pub fn $name(
-            _: RocketError,
-            _req: &Request,
-        ) -> Result<Response<'static>, Status> {
-            use json_api;
-            use json_api::doc::{Document, ErrorObject, Object};
-
-            let doc: Document<Object> = Document::Err {
-                errors: {
-                    let mut errors = Vec::with_capacity(1);
-
-                    errors.push(ErrorObject::new(Some($status)));
-                    errors
-                },
-                jsonapi: Default::default(),
-                links: Default::default(),
-                meta: Default::default(),
-            };
-
-            json_api::to_vec(doc, None)
-                .map(response::with_body)
-                .or_else(response::fail)
-                .map(|mut resp| {
-                    resp.set_raw_status($status.as_u16(), "");
-                    resp
-                })
+        $(pub fn $name<'r>(_: Status, _: &'r Request<'_>) -> BoxFuture<'r> {
+            Box::pin(async move { respond($status) })
         })*
 
         pub fn catchers() -> Vec<Catcher> {
@@ -40,6 +18,57 @@ macro_rules! catchers {
     }
 }
 
+/// Returns a response containing an error document with the given status.
+pub fn respond(status: StatusCode) -> Result<Response<'static>, Status> {
+    respond_with(vec![ErrorObject::new(Some(status))])
+}
+
+/// Returns a response containing an error document with the given errors. The status
+/// of the response is derived from the status of each error.
+pub fn respond_with(errors: Vec<ErrorObject>) -> Result<Response<'static>, Status> {
+    let status = status(&errors);
+    let doc: Document<Object> = Document::Err {
+        errors,
+        jsonapi: Default::default(),
+        links: Default::default(),
+        meta: Default::default(),
+    };
+
+    json_api::to_vec(doc, None)
+        .map(response::with_body)
+        .or_else(response::fail)
+        .map(|mut resp| {
+            resp.set_status(Status::new(status.as_u16()));
+            resp
+        })
+}
+
+/// Returns the most generally applicable status of `errors`.
+///
+/// If every error has the same status, that status is returned. Otherwise, the
+/// response is a `400 Bad Request` if every error is a client error, or a `500
+/// Internal Server Error` if it is not.
+fn status(errors: &[ErrorObject]) -> StatusCode {
+    let mut statuses = errors
+        .iter()
+        .map(|error| error.status.unwrap_or(StatusCode::INTERNAL_SERVER_ERROR));
+
+    let first = match statuses.next() {
+        Some(value) => value,
+        None => return StatusCode::INTERNAL_SERVER_ERROR,
+    };
+
+    statuses.fold(first, |status, next| {
+        if status == next {
+            status
+        } else if status.is_client_error() && next.is_client_error() {
+            StatusCode::BAD_REQUEST
+        } else {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    })
+}
+
 catchers!({
     StatusCode::BAD_REQUEST => handle_bad_request,
     StatusCode::UNAUTHORIZED => handle_unauthorized,
@@ -81,3 +110,34 @@ catchers!({
     StatusCode::NOT_EXTENDED => handle_not_extended,
     StatusCode::NETWORK_AUTHENTICATION_REQUIRED => handle_network_authentication_required
 });
+
+#[cfg(test)]
+mod tests {
+    use json_api::doc::ErrorObject;
+    use json_api::http::StatusCode;
+
+    use super::status;
+
+    fn errors(statuses: &[StatusCode]) -> Vec<ErrorObject> {
+        statuses
+            .iter()
+            .map(|status| ErrorObject::new(Some(*status)))
+            .collect()
+    }
+
+    #[test]
+    fn status_of_errors() {
+        let conflict = StatusCode::CONFLICT;
+        let not_found = StatusCode::NOT_FOUND;
+        let unavailable = StatusCode::SERVICE_UNAVAILABLE;
+
+        assert_eq!(status(&[]), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(status(&[ErrorObject::new(None)]), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(status(&errors(&[conflict, conflict])), conflict);
+        assert_eq!(status(&errors(&[conflict, not_found])), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            status(&errors(&[not_found, unavailable])),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
@@ -1,20 +1,244 @@
-use rocket::Rocket;
-use rocket::fairing::{Fairing, Info, Kind};
+use json_api::MEDIA_TYPE;
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::http::uri::Origin;
+use rocket::http::{Method, Status};
+use rocket::route::{BoxFuture, Outcome};
+use rocket::{Build, Data, Request, Rocket, Route};
 
-use error;
+use crate::error;
 
-pub struct JsonApiFairing;
+/// The path of the internal route that answers `406 Not Acceptable`.
+const NOT_ACCEPTABLE: &str = "/__json-api/not-acceptable";
 
+/// The path of the internal route that answers `415 Unsupported Media Type`.
+const UNSUPPORTED_MEDIA_TYPE: &str = "/__json-api/unsupported-media-type";
+
+/// Attaches catchers that render errors as JSON API documents.
+///
+/// The fairing is attached with `rocket.attach(JsonApiFairing::new())`. To enforce the
+/// JSON API media type, enable [`negotiate`].
+///
+/// [`negotiate`]: #method.negotiate
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JsonApiFairing {
+    negotiate: bool,
+}
+
+impl JsonApiFairing {
+    /// Returns the fairing with the default options.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Enforce the content negotiation requirements of the JSON API specification.
+    /// Defaults to `false`.
+    ///
+    /// When enabled, a request with a `Content-Type` of `application/vnd.api+json` with
+    /// media type parameters other than `ext` or `profile` is answered with
+    /// `415 Unsupported Media Type`. A request with an `Accept` header that contains
+    /// the JSON API media type, but only with such parameters, is answered with
+    /// `406 Not Acceptable`. Media types are compared case-insensitively.
+    ///
+    /// The request is rejected before it is dispatched, so the route that it would
+    /// have matched is never called. Rocket does not allow a fairing to respond to a
+    /// request directly, so the request is rewritten to an internal route that fails
+    /// with the status. The error document is rendered by the catchers of the fairing.
+    pub fn negotiate(mut self, value: bool) -> Self {
+        self.negotiate = value;
+        self
+    }
+}
+
+#[rocket::async_trait]
 impl Fairing for JsonApiFairing {
     fn info(&self) -> Info {
         Info {
-            kind: Kind::Attach,
+            kind: Kind::Ignite | Kind::Request,
             name: "JsonApiFairing",
         }
     }
 
-    fn on_attach(&self, rocket: Rocket) -> Result<Rocket, Rocket> {
-        let rocket = rocket.catch(error::catchers());
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let mut rocket = rocket.register("/", error::catchers());
+
+        if self.negotiate {
+            rocket = rocket.mount(
+                "/",
+                vec![
+                    Route::new(Method::Get, NOT_ACCEPTABLE, not_acceptable),
+                    Route::new(Method::Get, UNSUPPORTED_MEDIA_TYPE, unsupported_media_type),
+                ],
+            );
+        }
+
         Ok(rocket)
     }
+
+    async fn on_request(&self, request: &mut Request<'_>, _: &mut Data<'_>) {
+        if !self.negotiate {
+            return;
+        }
+
+        let path = {
+            let headers = request.headers();
+
+            if !content_type_supported(headers.get_one("Content-Type")) {
+                UNSUPPORTED_MEDIA_TYPE
+            } else if !accept_supported(headers.get("Accept")) {
+                NOT_ACCEPTABLE
+            } else {
+                return;
+            }
+        };
+
+        request.set_method(Method::Get);
+        request.set_uri(Origin::parse(path).expect("internal route path is a valid URI"));
+    }
+}
+
+fn not_acceptable<'r>(_: &'r Request<'_>, _: Data<'r>) -> BoxFuture<'r> {
+    Box::pin(async { Outcome::Error(Status::NotAcceptable) })
+}
+
+fn unsupported_media_type<'r>(_: &'r Request<'_>, _: Data<'r>) -> BoxFuture<'r> {
+    Box::pin(async { Outcome::Error(Status::UnsupportedMediaType) })
+}
+
+/// Returns `false` if `value` is the JSON API media type with unsupported media type
+/// parameters.
+fn content_type_supported(value: Option<&str>) -> bool {
+    match value.and_then(|value| parameterized(value, false)) {
+        Some(true) => false,
+        Some(false) | None => true,
+    }
+}
+
+/// Returns `false` if the JSON API media type is present in the values of an `Accept`
+/// header and each instance of it is modified with unsupported media type parameters.
+fn accept_supported<'a, I>(values: I) -> bool
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut found = false;
+
+    for range in values.into_iter().flat_map(|value| value.split(',')) {
+        match parameterized(range, true) {
+            Some(false) => return true,
+            Some(true) => found = true,
+            None => {}
+        }
+    }
+
+    !found
+}
+
+/// Returns whether or not `value` has media type parameters other than `ext` and
+/// `profile` if it is the JSON API media type. Returns `None` for any other media type.
+///
+/// When `accept` is `true`, the `q` parameter and the parameters that follow it are
+/// treated as accept parameters of an `Accept` header rather than media type
+/// parameters.
+fn parameterized(value: &str, accept: bool) -> Option<bool> {
+    let mut parts = value.split(';').map(str::trim);
+
+    if !parts.next().unwrap_or("").eq_ignore_ascii_case(MEDIA_TYPE) {
+        return None;
+    }
+
+    for param in parts.filter(|param| !param.is_empty()) {
+        let name = param.split('=').next().unwrap_or("").trim();
+
+        if accept && name.eq_ignore_ascii_case("q") {
+            break;
+        }
+
+        if !name.eq_ignore_ascii_case("ext") && !name.eq_ignore_ascii_case("profile") {
+            return Some(true);
+        }
+    }
+
+    Some(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use json_api::MEDIA_TYPE;
+    use rocket::http::{Header, Status};
+    use rocket::local::blocking::Client;
+
+    use super::{accept_supported, content_type_supported, JsonApiFairing};
+
+    #[test]
+    fn catchers() {
+        let client = Client::tracked(rocket::build().attach(JsonApiFairing::new())).unwrap();
+        let resp = client.get("/articles").dispatch();
+
+        assert_eq!(resp.status(), Status::NotFound);
+        assert_eq!(resp.headers().get_one("Content-Type"), Some(MEDIA_TYPE));
+        assert!(resp.into_string().unwrap().contains(r#""status":"404""#));
+    }
+
+    #[test]
+    fn negotiate() {
+        let fairing = JsonApiFairing::new().negotiate(true);
+        let client = Client::tracked(rocket::build().attach(fairing)).unwrap();
+
+        let resp = client
+            .post("/articles")
+            .header(Header::new("Content-Type", "application/vnd.api+json; charset=utf-8"))
+            .dispatch();
+
+        assert_eq!(resp.status(), Status::UnsupportedMediaType);
+        assert_eq!(resp.headers().get_one("Content-Type"), Some(MEDIA_TYPE));
+
+        let resp = client
+            .get("/articles")
+            .header(Header::new("Accept", "application/vnd.api+json; charset=utf-8"))
+            .dispatch();
+
+        assert_eq!(resp.status(), Status::NotAcceptable);
+
+        let resp = client
+            .get("/articles")
+            .header(Header::new("Accept", "application/vnd.api+json"))
+            .dispatch();
+
+        assert_eq!(resp.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn content_type() {
+        assert!(content_type_supported(None));
+        assert!(content_type_supported(Some("application/json; charset=utf-8")));
+        assert!(content_type_supported(Some("application/vnd.api+json")));
+        assert!(content_type_supported(Some("Application/VND.API+JSON")));
+        assert!(content_type_supported(Some("APPLICATION/vnd.api+json;ext=bulk")));
+        assert!(content_type_supported(Some(
+            r#"application/vnd.api+json; ext="https://jsonapi.org/ext/atomic"; profile="x""#
+        )));
+
+        assert!(!content_type_supported(Some("application/vnd.api+json; charset=utf-8")));
+        assert!(!content_type_supported(Some("application/vnd.api+json; ext=bulk; v=1")));
+    }
+
+    #[test]
+    fn accept() {
+        assert!(accept_supported(vec![]));
+        assert!(accept_supported(vec!["*/*"]));
+        assert!(accept_supported(vec!["text/html, application/json; version=1"]));
+        assert!(accept_supported(vec!["application/vnd.api+json"]));
+        assert!(accept_supported(vec!["application/VND.api+json; q=0.9"]));
+        assert!(accept_supported(vec!["application/vnd.api+json; ext=bulk"]));
+        assert!(accept_supported(vec!["application/vnd.api+json; profile=x; q=0.5; v=1"]));
+        assert!(accept_supported(vec![
+            "application/vnd.api+json; charset=utf-8",
+            "application/vnd.api+json",
+        ]));
+
+        assert!(!accept_supported(vec!["application/vnd.api+json; charset=utf-8"]));
+        assert!(!accept_supported(vec![
+            "application/vnd.api+json; version=1, */*; q=0.1",
+            "Application/Vnd.Api+Json; charset=utf-8; q=0.5",
+        ]));
+    }
 }
@@ -1,3 +1,4 @@
+#[cfg_attr(test, macro_use)]
 extern crate json_api;
 #[macro_use]
 extern crate lazy_static;
@@ -9,17 +10,17 @@ mod error;
 mod fairing;
 
 mod config {
-    use std::env;
-
-    use rocket::config::Environment;
+    use rocket::figment::Profile;
+    use rocket::Config;
 
     lazy_static! {
-        pub static ref ROCKET_ENV: Environment = {
-            match env::var("ROCKET_ENV").ok() {
-                Some(value) => value.parse().unwrap_or(Environment::Development),
-                None => Environment::Development,
-            }
-        };
+        pub static ref ROCKET_PROFILE: Profile =
+            Profile::from_env_or("ROCKET_PROFILE", Config::DEFAULT_PROFILE);
+    }
+
+    /// Returns `true` if the application is running with the release profile.
+    pub fn is_release() -> bool {
+        *ROCKET_PROFILE == Config::RELEASE_PROFILE
     }
 }
 
@@ -2,12 +2,13 @@ use std::ops::{Deref, DerefMut};
 
 use serde::de::DeserializeOwned;
 
-use json_api::doc::{NewObject, Object};
+use json_api::doc::{Linkage as JsonApiLinkage, LinkagePolicy, NewObject, Object};
+use json_api::error::ErrorKind;
 use json_api::query::{self, Page, Query as JsonApiQuery, Sort};
 use json_api::value::collections::{map, set, Set};
 use json_api::value::{Key, Path, Value};
-use json_api::{self, Error};
-use rocket::data::{self, Data, FromData};
+use json_api::Error;
+use rocket::data::{self, Data, FromData, Limits};
 use rocket::http::Status;
 use rocket::outcome::Outcome;
 use rocket::request::{self, FromRequest, Request};
@@ -36,13 +37,17 @@ impl<T: DeserializeOwned> DerefMut for Create<T> {
     }
 }
 
-impl<T: DeserializeOwned> FromData for Create<T> {
+#[rocket::async_trait]
+impl<'r, T: DeserializeOwned + Send> FromData<'r> for Create<T> {
     type Error = Error;
 
-    fn from_data(_: &Request, data: Data) -> data::Outcome<Self, Self::Error> {
-        let reader = data.open();
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+        let body = match read(req, data).await {
+            Ok(body) => body,
+            Err((status, e)) => return fail_with(status, e),
+        };
 
-        match json_api::from_reader::<_, NewObject, _>(reader) {
+        match json_api::from_slice::<NewObject, _>(&body) {
             Ok(value) => Outcome::Success(Create(value)),
             Err(e) => fail(e),
         }
@@ -73,19 +78,81 @@ impl<T: DeserializeOwned> DerefMut for Update<T> {
     }
 }
 
-impl<T: DeserializeOwned> FromData for Update<T> {
+#[rocket::async_trait]
+impl<'r, T: DeserializeOwned + Send> FromData<'r> for Update<T> {
     type Error = Error;
 
-    fn from_data(_: &Request, data: Data) -> data::Outcome<Self, Self::Error> {
-        let reader = data.open();
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+        let body = match read(req, data).await {
+            Ok(body) => body,
+            Err((status, e)) => return fail_with(status, e),
+        };
 
-        match json_api::from_reader::<_, Object, _>(reader) {
+        match json_api::from_slice::<Object, _>(&body) {
             Ok(value) => Outcome::Success(Update(value)),
             Err(e) => fail(e),
         }
     }
 }
 
+/// The resource linkage of a relationship, such as the body of a request to
+/// `PATCH /articles/1/relationships/tags`.
+///
+/// If a [`LinkagePolicy`] is managed by the application, the linkage is validated
+/// against it. A request that does not comply with the policy fails with the status
+/// of the first error in the resulting error document.
+///
+/// [`LinkagePolicy`]: ../../json_api/doc/struct.LinkagePolicy.html
+#[derive(Debug)]
+pub struct Linkage(pub JsonApiLinkage);
+
+impl Linkage {
+    /// Consumes the `Linkage` wrapper and returns the wrapped value.
+    pub fn into_inner(self) -> JsonApiLinkage {
+        self.0
+    }
+}
+
+impl Deref for Linkage {
+    type Target = JsonApiLinkage;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Linkage {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromData<'r> for Linkage {
+    type Error = Error;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+        let body = match read(req, data).await {
+            Ok(body) => body,
+            Err((status, e)) => return fail_with(status, e),
+        };
+
+        let linkage = match JsonApiLinkage::from_reader(&body[..]) {
+            Ok(linkage) => linkage,
+            Err(e) => return fail(e),
+        };
+
+        if let Some(policy) = req.rocket().state::<LinkagePolicy>() {
+            if let Err(e) = linkage.validate(policy) {
+                let status = error_status(&e);
+                return fail_with(status, e);
+            }
+        }
+
+        Outcome::Success(Linkage(linkage))
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Query {
     inner: JsonApiQuery,
@@ -99,15 +166,15 @@ impl Query {
         self.inner
     }
 
-    pub fn fields(&self) -> map::Iter<Key, Set> {
+    pub fn fields(&self) -> map::Iter<'_, Key, Set> {
         self.inner.fields.iter()
     }
 
-    pub fn filter(&self) -> map::Iter<Path, Value> {
+    pub fn filter(&self) -> map::Iter<'_, Path, Value> {
         self.inner.filter.iter()
     }
 
-    pub fn include(&self) -> set::Iter<Path> {
+    pub fn include(&self) -> set::Iter<'_, Path> {
         self.inner.include.iter()
     }
 
@@ -115,7 +182,7 @@ impl Query {
         self.inner.page
     }
 
-    pub fn sort(&self) -> set::Iter<Sort> {
+    pub fn sort(&self) -> set::Iter<'_, Sort> {
         self.inner.sort.iter()
     }
 }
@@ -134,24 +201,67 @@ impl DerefMut for Query {
     }
 }
 
-impl<'a, 'r> FromRequest<'a, 'r> for Query {
+impl Query {
+    /// Parses the query string of the URI of `req`.
+    pub(crate) fn from_uri(req: &Request) -> Result<Self, Error> {
+        match req.uri().query() {
+            Some(value) => query::from_str(value.as_str()).map(|inner| Query { inner }),
+            None => Ok(Default::default()),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Query {
     type Error = Error;
 
-    fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
-        match req.uri().query().map(query::from_str) {
-            Some(Ok(inner)) => Outcome::Success(Query { inner }),
-            Some(Err(e)) => fail(e),
-            None => Outcome::Success(Default::default()),
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        match Query::from_uri(req) {
+            Ok(value) => Outcome::Success(value),
+            Err(e) => fail(e),
         }
     }
 }
 
+/// Reads the body of a request up to the `json` data limit of the application.
+async fn read(req: &Request<'_>, data: Data<'_>) -> Result<Vec<u8>, (Status, Error)> {
+    let limit = req.limits().get("json").unwrap_or(Limits::JSON);
+    let body = data
+        .open(limit)
+        .into_bytes()
+        .await
+        .map_err(|e| (Status::BadRequest, Error::from(serde_json::Error::io(e))))?;
+
+    if !body.is_complete() {
+        let e = Error::from(format!("request body is larger than {}", limit));
+        return Err((Status::PayloadTooLarge, e));
+    }
+
+    Ok(body.into_inner())
+}
+
 fn fail<T, F>(e: Error) -> Outcome<T, (Status, Error), F> {
-    use config::ROCKET_ENV;
+    fail_with(Status::BadRequest, e)
+}
 
-    if !ROCKET_ENV.is_prod() {
+fn fail_with<T, F>(status: Status, e: Error) -> Outcome<T, (Status, Error), F> {
+    if !crate::config::is_release() {
         eprintln!("{:?}", e);
     }
 
-    Outcome::Failure((Status::BadRequest, e))
+    Outcome::Error((status, e))
+}
+
+/// Returns the status of the first error in an error document.
+fn error_status(e: &Error) -> Status {
+    let status = match *e.kind() {
+        ErrorKind::ErrorDocument(ref errors) => {
+            errors.iter().filter_map(|error| error.status).next()
+        }
+        _ => None,
+    };
+
+    status
+        .and_then(|status| Status::from_code(status.as_u16()))
+        .unwrap_or(Status::UnprocessableEntity)
 }
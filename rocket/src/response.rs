@@ -2,14 +2,18 @@ use std::io::Cursor;
 use std::iter::FromIterator;
 use std::ops::{Deref, DerefMut};
 
-use json_api::doc::Object;
-use json_api::{self, Error, Resource};
-use rocket::Outcome;
+use json_api::doc::{Data, Document, ErrorCatalog, ErrorObject, Object};
+use json_api::error::ErrorKind;
+use json_api::http::StatusCode;
+use json_api::query::Query as JsonApiQuery;
+use json_api::value::Map;
+use json_api::{Error, Resource};
 use rocket::http::Status;
-use rocket::request::{FromRequest, Request};
-use rocket::response::{Responder, Response};
+use rocket::request::Request;
+use rocket::response::{self, Responder, Response};
 
-use request::Query;
+use crate::error;
+use crate::request::Query;
 
 #[derive(Debug)]
 pub struct Collection<T: Resource>(pub Vec<T>);
@@ -46,12 +50,9 @@ impl<T: Resource> FromIterator<T> for Collection<T> {
     }
 }
 
-impl<T: Resource> Responder<'static> for Collection<T> {
-    fn respond_to(self, request: &Request) -> Result<Response<'static>, Status> {
-        let query = match Query::from_request(request) {
-            Outcome::Success(value) => Some(value.into_inner()),
-            Outcome::Failure(_) | Outcome::Forward(_) => None,
-        };
+impl<'r, T: Resource> Responder<'r, 'static> for Collection<T> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        let query = Query::from_uri(request).ok().map(Query::into_inner);
 
         json_api::to_vec::<_, Object>(&*self, query.as_ref())
             .map(with_body)
@@ -59,6 +60,88 @@ impl<T: Resource> Responder<'static> for Collection<T> {
     }
 }
 
+/// Renders one page of a collection that contains `total` resources.
+///
+/// The response contains the `first`, `last`, and when applicable, `prev` and `next`
+/// pagination links for the page of the request, as well as the `total` as a `meta`
+/// member. Each link is built from the path of the request URI. A query string that
+/// cannot be parsed results in a `400 Bad Request` error document.
+#[derive(Debug)]
+pub struct Paginated<T: Resource> {
+    /// The resources on the page of the request.
+    pub items: Vec<T>,
+
+    /// The total number of resources in the collection.
+    pub total: u64,
+}
+
+impl<T: Resource> Paginated<T> {
+    /// Returns a new `Paginated` for one page of a collection that contains `total`
+    /// resources.
+    pub fn new(items: Vec<T>, total: u64) -> Self {
+        Paginated { items, total }
+    }
+
+    /// Consumes the [`Paginated`] wrapper and returns the resources on the page.
+    ///
+    /// [`Paginated`]: ./struct.Paginated.html
+    pub fn into_inner(self) -> Vec<T> {
+        self.items
+    }
+}
+
+impl<T: Resource> Deref for Paginated<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.items
+    }
+}
+
+impl<T: Resource> DerefMut for Paginated<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.items
+    }
+}
+
+impl<'r, T: Resource> Responder<'r, 'static> for Paginated<T> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        let query = match Query::from_uri(request) {
+            Ok(value) => value.into_inner(),
+            Err(e) => return bad_request(&e),
+        };
+
+        paginate(&self.items, &query, self.total, request)
+            .map(with_body)
+            .or_else(fail)
+    }
+}
+
+/// Returns a `400 Bad Request` error document for a query string that could not be
+/// parsed.
+fn bad_request(e: &Error) -> response::Result<'static> {
+    let mut error = ErrorObject::from(e);
+
+    if !error.status.is_some_and(|status| status.is_client_error()) {
+        error = ErrorObject::new(Some(StatusCode::BAD_REQUEST));
+    }
+
+    log(e);
+    error::respond_with(vec![error])
+}
+
+fn paginate<T: Resource>(
+    items: &[T],
+    query: &JsonApiQuery,
+    total: u64,
+    request: &Request,
+) -> Result<Vec<u8>, Error> {
+    let base = request.uri().path().as_str().parse()?;
+    let doc = json_api::to_doc_paginated(items, query, total, &base)?;
+
+    Ok(serde_json::to_vec(&doc)?)
+}
+
 #[derive(Debug)]
 pub struct Created<T: Resource>(pub T);
 
@@ -85,12 +168,9 @@ impl<T: Resource> DerefMut for Created<T> {
     }
 }
 
-impl<T: Resource> Responder<'static> for Created<T> {
-    fn respond_to(self, request: &Request) -> Result<Response<'static>, Status> {
-        let query = match Query::from_request(request) {
-            Outcome::Success(value) => Some(value.into_inner()),
-            Outcome::Failure(_) | Outcome::Forward(_) => None,
-        };
+impl<'r, T: Resource> Responder<'r, 'static> for Created<T> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        let query = Query::from_uri(request).ok().map(Query::into_inner);
 
         json_api::to_vec::<_, Object>(&*self, query.as_ref())
             .map(with_body)
@@ -102,6 +182,109 @@ impl<T: Resource> Responder<'static> for Created<T> {
     }
 }
 
+/// Responds with `202 Accepted` and a document with `null` primary data.
+///
+/// The wrapped value is added to the `meta` of the document. It can be used to return
+/// information about the processing of the request, such as the id of a job.
+#[derive(Debug, Default)]
+pub struct Accepted(pub Option<Map>);
+
+impl Accepted {
+    /// Consumes the [`Accepted`] wrapper and returns the wrapped value.
+    ///
+    /// [`Accepted`]: ./struct.Accepted.html
+    pub fn into_inner(self) -> Option<Map> {
+        self.0
+    }
+}
+
+impl<'r> Responder<'r, 'static> for Accepted {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        let doc = Document::<Object>::Ok {
+            data: Data::Member(Box::new(None)),
+            included: Default::default(),
+            jsonapi: Default::default(),
+            links: Default::default(),
+            meta: self.0.unwrap_or_default(),
+        };
+
+        serde_json::to_vec(&doc)
+            .map(with_body)
+            .map_err(Error::from)
+            .or_else(fail)
+            .map(|mut resp| {
+                resp.set_status(Status::Accepted);
+                resp
+            })
+    }
+}
+
+/// Renders an [`Error`] as an error document.
+///
+/// A handler that returns `Result<Member<T>, ErrorResponse>` can use the `?` operator
+/// with any function that returns a `Result<_, json_api::Error>`. The status of the
+/// response is derived from the kind of the error. For example, a request document
+/// that cannot be parsed results in a `400 Bad Request`. An error that is not caused
+/// by the request results in a `500 Internal Server Error` without details.
+///
+/// If an [`ErrorCatalog`] is managed by the application, it is applied to each error
+/// object before the response is rendered.
+///
+/// [`Error`]: ../json_api/struct.Error.html
+/// [`ErrorCatalog`]: ../json_api/doc/struct.ErrorCatalog.html
+#[derive(Debug)]
+pub struct ErrorResponse(pub Error);
+
+impl ErrorResponse {
+    /// Consumes the [`ErrorResponse`] wrapper and returns the wrapped value.
+    ///
+    /// [`ErrorResponse`]: ./struct.ErrorResponse.html
+    pub fn into_inner(self) -> Error {
+        self.0
+    }
+}
+
+impl Deref for ErrorResponse {
+    type Target = Error;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ErrorResponse {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<Error> for ErrorResponse {
+    fn from(error: Error) -> Self {
+        ErrorResponse(error)
+    }
+}
+
+impl<'r> Responder<'r, 'static> for ErrorResponse {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        let mut errors = match *self.kind() {
+            ErrorKind::ErrorDocument(ref errors) => errors.clone(),
+            _ => vec![ErrorObject::from(&*self)],
+        };
+
+        if let Some(catalog) = request.rocket().state::<ErrorCatalog>() {
+            for error in &mut errors {
+                error.apply_catalog(catalog);
+            }
+        }
+
+        if errors.iter().any(|error| error.status.is_none_or(|status| status.is_server_error())) {
+            log(&self);
+        }
+
+        error::respond_with(errors)
+    }
+}
+
 #[derive(Debug)]
 pub struct Member<T>(pub T);
 
@@ -128,12 +311,9 @@ impl<T: Resource> DerefMut for Member<T> {
     }
 }
 
-impl<T: Resource> Responder<'static> for Member<T> {
-    fn respond_to(self, request: &Request) -> Result<Response<'static>, Status> {
-        let query = match Query::from_request(request) {
-            Outcome::Success(value) => Some(value.into_inner()),
-            Outcome::Failure(_) | Outcome::Forward(_) => None,
-        };
+impl<'r, T: Resource> Responder<'r, 'static> for Member<T> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        let query = Query::from_uri(request).ok().map(Query::into_inner);
 
         json_api::to_vec::<_, Object>(&*self, query.as_ref())
             .map(with_body)
@@ -141,20 +321,89 @@ impl<T: Resource> Responder<'static> for Member<T> {
     }
 }
 
+/// Responds with `204 No Content` and an empty body.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoContent;
+
+impl<'r> Responder<'r, 'static> for NoContent {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        Ok(Response::build().status(Status::NoContent).finalize())
+    }
+}
+
 pub(crate) fn with_body(body: Vec<u8>) -> Response<'static> {
     Response::build()
-        .raw_header("Content-Type", "application/vnd.api+json")
-        .sized_body(Cursor::new(body))
+        .raw_header("Content-Type", json_api::MEDIA_TYPE)
+        .sized_body(body.len(), Cursor::new(body))
         .finalize()
 }
 
-#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
-pub(crate) fn fail(e: Error) -> Result<Response<'static>, Status> {
-    use config::ROCKET_ENV;
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn fail(e: Error) -> response::Result<'static> {
+    log(&e);
+    Err(Status::InternalServerError)
+}
 
-    if !ROCKET_ENV.is_prod() {
+fn log(e: &Error) {
+    if !crate::config::is_release() {
         eprintln!("{:?}", e);
     }
+}
 
-    Err(Status::InternalServerError)
+#[cfg(test)]
+mod tests {
+    use json_api::MEDIA_TYPE;
+    use rocket::http::Status;
+    use rocket::local::blocking::Client;
+
+    use super::{with_body, NoContent, Paginated};
+
+    struct Article {
+        id: u64,
+    }
+
+    resource!(Article, |&self| {
+        kind "articles";
+        id self.id;
+    });
+
+    #[rocket::get("/articles")]
+    fn articles() -> Paginated<Article> {
+        Paginated::new(vec![Article { id: 1 }], 1)
+    }
+
+    #[rocket::delete("/articles/1")]
+    fn delete_article() -> NoContent {
+        NoContent
+    }
+
+    #[test]
+    fn no_content() {
+        let rocket = rocket::build().mount("/", rocket::routes![delete_article]);
+        let client = Client::tracked(rocket).unwrap();
+        let resp = client.delete("/articles/1").dispatch();
+
+        assert_eq!(resp.status(), Status::NoContent);
+        assert_eq!(resp.headers().get_one("Content-Type"), None);
+    }
+
+    #[test]
+    fn paginated_invalid_query() {
+        let rocket = rocket::build().mount("/", rocket::routes![articles]);
+        let client = Client::tracked(rocket).unwrap();
+
+        let resp = client.get("/articles?page[number]=first").dispatch();
+        assert_eq!(resp.status(), Status::BadRequest);
+        assert_eq!(resp.headers().get_one("Content-Type"), Some(MEDIA_TYPE));
+        assert!(resp.into_string().unwrap().contains(r#""parameter":"page[number]""#));
+
+        let resp = client.get("/articles?page[number]=1").dispatch();
+        assert_eq!(resp.status(), Status::Ok);
+    }
+
+    #[test]
+    fn with_body_content_type() {
+        let resp = with_body(Vec::new());
+        assert_eq!(resp.headers().get_one("Content-Type"), Some(MEDIA_TYPE));
+    }
 }
@@ -3,6 +3,7 @@
 set -e
 
 CARGO_BIN="$CARGO_HOME/bin"
+export CARGO_RESOLVER_INCOMPATIBLE_RUST_VERSIONS="fallback"
 OUTPUT="/tmp/output.log"
 
 EXEC="\u001b[46m\u001b[30m  EXEC  \u001b[39m\u001b[49m"
@@ -60,4 +61,6 @@ case $CIRCLE_NODE_INDEX in
   ;;
   2) DEFAULT_TOOLCHAIN=$NIGHTLY
   ;;
+  3) DEFAULT_TOOLCHAIN=$MSRV
+  ;;
 esac
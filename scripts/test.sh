@@ -5,15 +5,12 @@ source scripts/setup.sh
 
 echo ""
 
-run rustup install stable beta $NIGHTLY
+run rustup install stable beta $NIGHTLY $MSRV
 run rustup default $DEFAULT_TOOLCHAIN
 run rustup component add rustfmt-preview --toolchain stable
+run rustup component add clippy --toolchain $NIGHTLY
 
-if ! has_plugin clippy; then
-  run cargo +$NIGHTLY install clippy --vers 0.0.204
-fi
-
-run cargo update
+run cargo +stable update
 run cargo build
 
 run_plugin stable fmt -- --write-mode diff
@@ -26,6 +23,14 @@ else
   run cargo test
 fi
 
+if [ $DEFAULT_TOOLCHAIN == "stable" ]; then
+  if ! has_plugin semver-checks; then
+    run cargo install cargo-semver-checks --locked
+  fi
+
+  run_plugin stable semver-checks check-release --package json-api
+fi
+
 if [ "$CIRCLECI" == "true" ]; then
   if ! [ -f /usr/local/bin/kcov ]; then
     run scripts/install_kcov.sh
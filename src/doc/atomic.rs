@@ -0,0 +1,861 @@
+//! Types for the *[atomic operations]* extension.
+//!
+//! This module is only available when the `atomic` feature is enabled.
+//!
+//! [atomic operations]: https://jsonapi.org/ext/atomic/
+
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::Serialize;
+
+use doc::link;
+use doc::parse::{self, member};
+use doc::{deserialize_some, Data, Document, Identifier, InvalidLinks, JsonApi, Link, NewObject,
+          Object, Pointer, PrimaryData};
+use error::Error;
+use value::{self, Key, Map, Value};
+
+/// A document containing a sequence of operations that should be performed in order.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// # extern crate serde_json;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::atomic::{OperationCode, OperationsDocument};
+///
+/// let doc: OperationsDocument = serde_json::from_str(r#"{
+///     "atomic:operations": [{
+///         "op": "remove",
+///         "ref": {
+///             "type": "articles",
+///             "id": "13"
+///         }
+///     }]
+/// }"#)?;
+///
+/// assert_eq!(doc.operations.len(), 1);
+/// assert_eq!(doc.operations[0].op, OperationCode::Remove);
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct OperationsDocument {
+    /// The operations to perform, in the order they should be performed.
+    #[serde(rename = "atomic:operations")]
+    pub operations: Vec<Operation>,
+
+    /// Information about this implementation of the specification that the
+    /// document was created with. For more information, check out the *[JSON API
+    /// object]* section of the JSON API specification.
+    ///
+    /// [JSON API object]: https://goo.gl/hZUcEt
+    #[serde(default)]
+    pub jsonapi: JsonApi,
+
+    /// Contains relevant links. If this value of this field is empty, it will not be
+    /// serialized. For more information, check out the *[links]* section of the JSON
+    /// API specification.
+    ///
+    /// [links]: https://goo.gl/E4E6Vt
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub links: Map<Key, Link>,
+
+    /// Non-standard meta information. If this value of this field is empty, it will not
+    /// be serialized. For more information, check out the *[meta information]* section
+    /// of the JSON API specification.
+    ///
+    /// [meta information]: https://goo.gl/LyrGF8
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub meta: Map,
+
+    /// Private field for backwards compatibility.
+    #[serde(skip)]
+    _ext: (),
+}
+
+impl OperationsDocument {
+    /// Returns a new `OperationsDocument` with the specified `operations`.
+    pub fn new(operations: Vec<Operation>) -> Self {
+        OperationsDocument {
+            operations,
+            jsonapi: Default::default(),
+            links: Default::default(),
+            meta: Default::default(),
+            _ext: (),
+        }
+    }
+
+    /// Fails if the document contains a link with an invalid `href`.
+    fn check_links(&mut self) -> Result<(), Error> {
+        let root = Pointer::root();
+        let policy = InvalidLinks::Error;
+
+        parse::check_jsonapi(&mut self.jsonapi, &root, policy)?;
+
+        for (index, operation) in self.operations.iter_mut().enumerate() {
+            let mut pointer = member(&root, "atomic:operations");
+
+            pointer.push_index(index);
+
+            if let Some(ref mut href) = operation.href {
+                href.check(&member(&pointer, "href"), policy)?;
+            }
+
+            match operation.data {
+                Some(OperationData::NewObject(ref mut obj)) => {
+                    obj.check_links(&member(&pointer, "data"), policy)?;
+                }
+                Some(OperationData::Object(ref mut obj)) => {
+                    obj.check_links(&member(&pointer, "data"), policy)?;
+                }
+                Some(OperationData::Linkage(_)) | None => {}
+            }
+        }
+
+        link::check_links(&mut self.links, &member(&root, "links"), policy)
+    }
+}
+
+impl<'de> Deserialize<'de> for OperationsDocument {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct RawOperationsDocument {
+            #[serde(rename = "atomic:operations")]
+            operations: Vec<Operation>,
+            #[serde(default)]
+            jsonapi: JsonApi,
+            #[serde(default)]
+            links: Map<Key, Link>,
+            #[serde(default)]
+            meta: Map,
+        }
+
+        let raw = RawOperationsDocument::deserialize(deserializer)?;
+        let mut doc = OperationsDocument::new(raw.operations);
+
+        doc.jsonapi = raw.jsonapi;
+        doc.links = raw.links;
+        doc.meta = raw.meta;
+
+        doc.check_links().map_err(de::Error::custom)?;
+        Ok(doc)
+    }
+}
+
+/// A single operation in an [`OperationsDocument`].
+///
+/// [`OperationsDocument`]: ./struct.OperationsDocument.html
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Operation {
+    /// The kind of operation to perform.
+    pub op: OperationCode,
+
+    /// The target of the operation. If both `target` and `href` are `None`, the target
+    /// is derived from `data`.
+    #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+    pub target: Option<Ref>,
+
+    /// A URI reference that identifies the target of the operation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub href: Option<Link>,
+
+    /// The primary data of the operation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<OperationData>,
+
+    /// Non-standard meta information. If this value of this field is empty, it will not
+    /// be serialized. For more information, check out the *[meta information]* section
+    /// of the JSON API specification.
+    ///
+    /// [meta information]: https://goo.gl/LyrGF8
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub meta: Map,
+
+    /// Private field for backwards compatibility.
+    #[serde(skip)]
+    _ext: (),
+}
+
+impl Operation {
+    /// Returns a new `Operation` with the specified `op` code.
+    pub fn new(op: OperationCode) -> Self {
+        Operation {
+            op,
+            target: Default::default(),
+            href: Default::default(),
+            data: Default::default(),
+            meta: Default::default(),
+            _ext: (),
+        }
+    }
+
+    /// Returns `true` if the operation targets a relationship rather than a resource.
+    pub fn is_relationship(&self) -> bool {
+        let by_ref = self.target
+            .as_ref()
+            .map_or(false, |target| target.relationship.is_some());
+        let by_href = self.href
+            .as_ref()
+            .map_or(false, |href| href.reference().contains("/relationships/"));
+
+        by_ref || by_href
+    }
+}
+
+impl<'de> Deserialize<'de> for Operation {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct RawOperation {
+            op: OperationCode,
+            #[serde(default, rename = "ref")]
+            target: Option<Ref>,
+            #[serde(default)]
+            href: Option<Link>,
+            #[serde(default, deserialize_with = "deserialize_some")]
+            data: Option<Value>,
+            #[serde(default)]
+            meta: Map,
+        }
+
+        let raw = RawOperation::deserialize(deserializer)?;
+        let mut operation = Operation::new(raw.op);
+
+        operation.target = raw.target;
+        operation.href = raw.href;
+        operation.meta = raw.meta;
+
+        operation.data = match raw.data {
+            Some(data) => {
+                let result = if operation.is_relationship() || !data.is_object() {
+                    value::from_value(data).map(OperationData::Linkage)
+                } else if operation.op == OperationCode::Add {
+                    value::from_value(data).map(OperationData::NewObject)
+                } else {
+                    value::from_value(data).map(OperationData::Object)
+                };
+
+                Some(result.map_err(de::Error::custom)?)
+            }
+            None => None,
+        };
+
+        Ok(operation)
+    }
+}
+
+/// The kind of an [`Operation`].
+///
+/// [`Operation`]: ./struct.Operation.html
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OperationCode {
+    /// Create a new resource or add members to a to-many relationship.
+    Add,
+
+    /// Update a resource or replace the members of a relationship.
+    Update,
+
+    /// Delete a resource or remove members from a to-many relationship.
+    Remove,
+}
+
+/// The primary data of an [`Operation`].
+///
+/// When an operation is deserialized, the variant is chosen based on the target of the
+/// operation and the op code. Operations that target a relationship, or have data that
+/// is an array or `null`, contain [`Linkage`]. Otherwise `add` operations contain a
+/// [`NewObject`] and `update` operations contain an [`Object`].
+///
+/// An `OperationData` can be created from any of these values, as well as from one or
+/// more [`Identifier`]s, with `From`.
+///
+/// [`Identifier`]: ../struct.Identifier.html
+/// [`Operation`]: ./struct.Operation.html
+/// [`Linkage`]: #variant.Linkage
+/// [`NewObject`]: #variant.NewObject
+/// [`Object`]: #variant.Object
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum OperationData {
+    /// Resource linkage for an operation that targets a relationship.
+    Linkage(Data<Identifier>),
+
+    /// A resource that does not already exist.
+    NewObject(NewObject),
+
+    /// A preexisting resource.
+    Object(Object),
+}
+
+impl From<Data<Identifier>> for OperationData {
+    fn from(data: Data<Identifier>) -> Self {
+        OperationData::Linkage(data)
+    }
+}
+
+impl From<Identifier> for OperationData {
+    fn from(ident: Identifier) -> Self {
+        OperationData::Linkage(ident.into())
+    }
+}
+
+impl From<Vec<Identifier>> for OperationData {
+    fn from(idents: Vec<Identifier>) -> Self {
+        OperationData::Linkage(idents.into())
+    }
+}
+
+impl From<NewObject> for OperationData {
+    fn from(obj: NewObject) -> Self {
+        OperationData::NewObject(obj)
+    }
+}
+
+impl From<Object> for OperationData {
+    fn from(obj: Object) -> Self {
+        OperationData::Object(obj)
+    }
+}
+
+/// References the target of an [`Operation`].
+///
+/// [`Operation`]: ./struct.Operation.html
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Ref {
+    /// The id of the target resource.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// The type of the target resource.
+    #[serde(rename = "type")]
+    pub kind: Key,
+
+    /// A local id that identifies a resource created by a previous operation in the
+    /// same document.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lid: Option<String>,
+
+    /// The name of the relationship to target. If this value is `None`, the operation
+    /// targets the resource itself.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relationship: Option<Key>,
+
+    /// Private field for backwards compatibility.
+    #[serde(skip)]
+    _ext: (),
+}
+
+impl Ref {
+    /// Returns a new `Ref` that targets the resource with the specified `kind` and
+    /// `id`.
+    pub fn new(kind: Key, id: String) -> Self {
+        Ref {
+            kind,
+            id: Some(id),
+            lid: None,
+            relationship: None,
+            _ext: (),
+        }
+    }
+}
+
+impl From<Identifier> for Ref {
+    fn from(ident: Identifier) -> Self {
+        let Identifier { id, kind, lid, .. } = ident;
+
+        Ref {
+            id: if id.is_empty() && lid.is_some() { None } else { Some(id) },
+            kind,
+            lid,
+            relationship: None,
+            _ext: (),
+        }
+    }
+}
+
+/// A document containing the results of each operation in an [`OperationsDocument`].
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// # extern crate serde_json;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::Object;
+/// use json_api::doc::atomic::OperationsResult;
+///
+/// let article = Object::new("articles".parse()?, "1".to_owned());
+/// let doc = OperationsResult::builder()
+///     .data(article)
+///     .empty()
+///     .build()?;
+///
+/// assert_eq!(doc.results.len(), 2);
+/// assert_eq!(
+///     serde_json::to_string(&doc.results)?,
+///     r#"[{"data":{"id":"1","type":"articles"}},{}]"#
+/// );
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`OperationsDocument`]: ./struct.OperationsDocument.html
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct OperationsResult {
+    /// The result of each operation, in the order the operations were performed.
+    #[serde(rename = "atomic:results")]
+    pub results: Vec<OperationResult>,
+
+    /// Information about this implementation of the specification that the
+    /// document was created with. For more information, check out the *[JSON API
+    /// object]* section of the JSON API specification.
+    ///
+    /// [JSON API object]: https://goo.gl/hZUcEt
+    #[serde(default)]
+    pub jsonapi: JsonApi,
+
+    /// Contains relevant links. If this value of this field is empty, it will not be
+    /// serialized. For more information, check out the *[links]* section of the JSON
+    /// API specification.
+    ///
+    /// [links]: https://goo.gl/E4E6Vt
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub links: Map<Key, Link>,
+
+    /// Non-standard meta information. If this value of this field is empty, it will not
+    /// be serialized. For more information, check out the *[meta information]* section
+    /// of the JSON API specification.
+    ///
+    /// [meta information]: https://goo.gl/LyrGF8
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub meta: Map,
+
+    /// Private field for backwards compatibility.
+    #[serde(skip)]
+    _ext: (),
+}
+
+impl OperationsResult {
+    /// Returns a new `OperationsResult` with the specified `results`.
+    pub fn new(results: Vec<OperationResult>) -> Self {
+        OperationsResult {
+            results,
+            jsonapi: Default::default(),
+            links: Default::default(),
+            meta: Default::default(),
+            _ext: (),
+        }
+    }
+
+    /// Returns a builder that can be used to create a new results document.
+    pub fn builder() -> OperationsResultBuilder {
+        Default::default()
+    }
+
+    /// Fails if the document contains a link with an invalid `href`.
+    fn check_links(&mut self) -> Result<(), Error> {
+        let root = Pointer::root();
+        let policy = InvalidLinks::Error;
+
+        parse::check_jsonapi(&mut self.jsonapi, &root, policy)?;
+
+        for (index, result) in self.results.iter_mut().enumerate() {
+            let mut pointer = member(&root, "atomic:results");
+
+            pointer.push_index(index).push("data");
+
+            if let Some(ref mut data) = result.data {
+                parse::check_data(data, &pointer, policy)?;
+            }
+        }
+
+        link::check_links(&mut self.links, &member(&root, "links"), policy)
+    }
+}
+
+impl<'de> Deserialize<'de> for OperationsResult {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct RawOperationsResult {
+            #[serde(rename = "atomic:results")]
+            results: Vec<OperationResult>,
+            #[serde(default)]
+            jsonapi: JsonApi,
+            #[serde(default)]
+            links: Map<Key, Link>,
+            #[serde(default)]
+            meta: Map,
+        }
+
+        let raw = RawOperationsResult::deserialize(deserializer)?;
+        let mut doc = OperationsResult::new(raw.results);
+
+        doc.jsonapi = raw.jsonapi;
+        doc.links = raw.links;
+        doc.meta = raw.meta;
+
+        doc.check_links().map_err(de::Error::custom)?;
+        Ok(doc)
+    }
+}
+
+/// The result of a single [`Operation`].
+///
+/// [`Operation`]: ./struct.Operation.html
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct OperationResult {
+    /// The primary data of the result. If this value is `None`, the result is
+    /// serialized as an empty object.
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Data<Object>>,
+
+    /// Non-standard meta information. If this value of this field is empty, it will not
+    /// be serialized. For more information, check out the *[meta information]* section
+    /// of the JSON API specification.
+    ///
+    /// [meta information]: https://goo.gl/LyrGF8
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub meta: Map,
+
+    /// Private field for backwards compatibility.
+    #[serde(skip)]
+    _ext: (),
+}
+
+impl OperationResult {
+    /// Returns a new `OperationResult` with the specified `data`.
+    pub fn new(data: Option<Data<Object>>) -> Self {
+        OperationResult {
+            data,
+            meta: Default::default(),
+            _ext: (),
+        }
+    }
+}
+
+/// An implementation of the "builder pattern" that can be used to construct a
+/// new `OperationsResult`.
+///
+/// Results are collected in the order they are supplied. The first error that occurs
+/// is returned when [`build`] is called.
+///
+/// [`build`]: #method.build
+#[derive(Debug, Default)]
+pub struct OperationsResultBuilder {
+    meta: Vec<(String, Result<Value, Error>)>,
+    results: Vec<Result<OperationResult, Error>>,
+}
+
+impl OperationsResultBuilder {
+    /// Attempt to construct a new results document from the previously supplied
+    /// values.
+    pub fn build(&mut self) -> Result<OperationsResult, Error> {
+        let mut doc = OperationsResult::new(Vec::with_capacity(self.results.len()));
+
+        for result in self.results.drain(..) {
+            doc.results.push(result?);
+        }
+
+        for (key, value) in self.meta.drain(..) {
+            doc.meta.insert(key.parse()?, value?);
+        }
+
+        Ok(doc)
+    }
+
+    /// Adds the result of an operation that returns `data` as its primary data.
+    pub fn data<T>(&mut self, data: T) -> &mut Self
+    where
+        T: Into<Data<Object>>,
+    {
+        self.results.push(Ok(OperationResult::new(Some(data.into()))));
+        self
+    }
+
+    /// Adds the result of an operation from a rendered document. The data and meta of
+    /// the document are kept. If the document contains errors, they are returned by
+    /// [`build`] as an [`ErrorDocument`] error.
+    ///
+    /// [`build`]: #method.build
+    /// [`ErrorDocument`]: ../error/enum.ErrorKind.html#variant.ErrorDocument
+    pub fn document(&mut self, doc: Document<Object>) -> &mut Self {
+        self.results.push(match doc {
+            Document::Ok { data, meta, .. } => {
+                let mut result = OperationResult::new(Some(data));
+
+                result.meta = meta;
+                Ok(result)
+            }
+            Document::Err { errors, .. } => Err(Error::error_document(errors)),
+            Document::Meta { meta, .. } => {
+                let mut result = OperationResult::new(None);
+
+                result.meta = meta;
+                Ok(result)
+            }
+        });
+
+        self
+    }
+
+    /// Adds the result of an operation that has no data, such as a `remove`
+    /// operation. It is serialized as an empty object.
+    pub fn empty(&mut self) -> &mut Self {
+        self.results.push(Ok(Default::default()));
+        self
+    }
+
+    /// Adds a member to the top-level meta information of the document. An error is
+    /// returned by [`build`] if `key` is not a valid member name or if `value` cannot
+    /// be serialized.
+    ///
+    /// [`build`]: #method.build
+    pub fn meta<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: Into<String>,
+        V: Serialize,
+    {
+        self.meta.push((key.into(), value::to_value(value)));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{self, Value as JsonValue};
+
+    use super::{Operation, OperationCode, OperationData, OperationsDocument, OperationsResult,
+                Ref};
+    use doc::{Data, Identifier, NewObject, Object};
+    use view::Render;
+
+    const SOURCE: &str = r#"{
+        "atomic:operations": [{
+            "op": "add",
+            "href": "/blogPosts",
+            "data": {
+                "type": "articles",
+                "attributes": {
+                    "title": "JSON API paints my bikeshed!"
+                }
+            }
+        }, {
+            "op": "update",
+            "data": {
+                "type": "articles",
+                "id": "13",
+                "attributes": {
+                    "title": "To TDD or Not"
+                }
+            }
+        }, {
+            "op": "update",
+            "ref": {
+                "type": "articles",
+                "id": "13",
+                "relationship": "author"
+            },
+            "data": null
+        }, {
+            "op": "add",
+            "ref": {
+                "type": "articles",
+                "id": "1",
+                "relationship": "comments"
+            },
+            "data": [
+                { "type": "comments", "id": "123" }
+            ]
+        }, {
+            "op": "remove",
+            "ref": {
+                "type": "articles",
+                "id": "13"
+            }
+        }]
+    }"#;
+
+    #[test]
+    fn operations_from_str() {
+        let doc = serde_json::from_str::<OperationsDocument>(SOURCE).unwrap();
+        let ops = &doc.operations;
+
+        assert_eq!(ops.len(), 5);
+
+        assert_eq!(ops[0].op, OperationCode::Add);
+        assert_eq!(ops[0].href.as_ref().map(|href| href.reference()), Some("/blogPosts".into()));
+        match ops[0].data {
+            Some(OperationData::NewObject(ref obj)) => {
+                assert_eq!(obj.kind, "articles");
+                assert_eq!(obj.id, None);
+            }
+            ref data => panic!("unexpected data {:?}", data),
+        }
+
+        assert_eq!(ops[1].op, OperationCode::Update);
+        match ops[1].data {
+            Some(OperationData::Object(ref obj)) => {
+                assert_eq!(obj.id, "13");
+                assert_eq!(obj.attributes.get("title"), Some(&"To TDD or Not".into()));
+            }
+            ref data => panic!("unexpected data {:?}", data),
+        }
+
+        assert!(ops[2].is_relationship());
+        assert_eq!(
+            ops[2].data,
+            Some(OperationData::Linkage(Data::Member(Box::new(None))))
+        );
+
+        match ops[3].data {
+            Some(OperationData::Linkage(Data::Collection(ref idents))) => {
+                assert_eq!(idents.len(), 1);
+                assert_eq!(idents[0].id, "123");
+            }
+            ref data => panic!("unexpected data {:?}", data),
+        }
+
+        assert_eq!(ops[4].op, OperationCode::Remove);
+        assert_eq!(ops[4].data, None);
+        assert_eq!(
+            ops[4].target,
+            Some(Ref::new("articles".parse().unwrap(), "13".to_owned()))
+        );
+    }
+
+    #[test]
+    fn operations_round_trip() {
+        let doc = serde_json::from_str::<OperationsDocument>(SOURCE).unwrap();
+        let json = serde_json::to_value(&doc).unwrap();
+
+        assert_eq!(serde_json::from_value::<OperationsDocument>(json).unwrap(), doc);
+    }
+
+    #[test]
+    fn operations_to_value() {
+        let mut add = Operation::new(OperationCode::Add);
+        let mut remove = Operation::new(OperationCode::Remove);
+
+        let mut update = Operation::new(OperationCode::Update);
+        let author = Identifier::new("users".parse().unwrap(), "9".to_owned());
+
+        add.data = Some(OperationData::NewObject(NewObject::new(
+            "articles".parse().unwrap(),
+        )));
+        update.data = Some(OperationData::Object(Object::new(
+            author.kind.clone(),
+            author.id.clone(),
+        )));
+        remove.target = Some(author.into());
+
+        let doc = OperationsDocument::new(vec![add, update, remove]);
+        let json = serde_json::to_value(&doc).unwrap();
+        let expected = serde_json::from_str::<JsonValue>(
+            r#"{
+                "atomic:operations": [
+                    { "op": "add", "data": { "type": "articles" } },
+                    { "op": "update", "data": { "type": "users", "id": "9" } },
+                    { "op": "remove", "ref": { "type": "users", "id": "9" } }
+                ],
+                "jsonapi": { "version": "1.0" }
+            }"#,
+        ).unwrap();
+
+        assert_eq!(json, expected);
+        assert_eq!(serde_json::from_value::<OperationsDocument>(json).unwrap(), doc);
+    }
+
+    #[test]
+    fn results_to_value() {
+        let mut article = Object::new("articles".parse().unwrap(), "1".to_owned());
+
+        article
+            .attributes
+            .insert("title".parse().unwrap(), "JSON API paints my bikeshed!".into());
+        article.meta.insert("created".parse().unwrap(), true.into());
+
+        let doc = OperationsResult::builder()
+            .document(article.render(None).unwrap())
+            .data(Data::Member(Box::new(None)))
+            .empty()
+            .meta("count", 3)
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_value(&doc).unwrap();
+        let expected = serde_json::from_str::<JsonValue>(
+            r#"{
+                "atomic:results": [{
+                    "data": {
+                        "type": "articles",
+                        "id": "1",
+                        "attributes": {
+                            "title": "JSON API paints my bikeshed!"
+                        }
+                    },
+                    "meta": { "created": true }
+                }, {
+                    "data": null
+                }, {}],
+                "jsonapi": { "version": "1.0" },
+                "meta": { "count": 3 }
+            }"#,
+        ).unwrap();
+
+        assert_eq!(json, expected);
+        assert_eq!(serde_json::from_value::<OperationsResult>(json).unwrap(), doc);
+    }
+
+    #[test]
+    fn invalid_links() {
+        let error = serde_json::from_str::<OperationsDocument>(
+            r#"{
+                "atomic:operations": [{
+                    "op": "remove",
+                    "href": "/articles/1 2"
+                }]
+            }"#,
+        ).unwrap_err();
+
+        assert!(error.to_string().contains(r#"at "/atomic:operations/0/href""#));
+
+        let error = serde_json::from_str::<OperationsResult>(
+            r#"{
+                "atomic:results": [{
+                    "data": {
+                        "type": "articles",
+                        "id": "1",
+                        "links": { "self": "/articles/ 1" }
+                    }
+                }]
+            }"#,
+        ).unwrap_err();
+
+        assert!(error.to_string().contains(r#"at "/atomic:results/0/data/links/self""#));
+    }
+}
@@ -0,0 +1,261 @@
+use std::iter::FromIterator;
+
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+use doc::{ErrorObject, Link};
+use http::StatusCode;
+use value::Map;
+
+/// The defaults for errors with a given application-specific error code.
+///
+/// For more information, check out the [`ErrorCatalog`] type.
+///
+/// [`ErrorCatalog`]: ./struct.ErrorCatalog.html
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct CatalogEntry {
+    /// A link to further details about errors with the code. The link is added to the
+    /// `links` of an error object as `about`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub about: Option<Link>,
+
+    /// The HTTP status code of errors with the code. This field is serialized as
+    /// `status`.
+    #[serde(default, rename = "status", skip_serializing_if = "Option::is_none",
+            with = "super::error::serde_status")]
+    pub default_status: Option<StatusCode>,
+
+    /// A short, human-readable summary of errors with the code.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// Private field for backwards compatibility.
+    #[serde(skip)]
+    _ext: (),
+}
+
+impl CatalogEntry {
+    /// Returns a new `CatalogEntry` with the specified `title`, `about` link, and
+    /// `default_status`.
+    pub fn new(
+        title: Option<String>,
+        about: Option<Link>,
+        default_status: Option<StatusCode>,
+    ) -> Self {
+        CatalogEntry {
+            about,
+            default_status,
+            title,
+            _ext: (),
+        }
+    }
+}
+
+/// A collection of [`CatalogEntry`] values keyed by application-specific error code.
+///
+/// A catalog keeps the title, the `about` link, and the HTTP status of each error code
+/// in one place. It can be deserialized from a JSON object with an entry for each
+/// code.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// # extern crate serde_json;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::ErrorCatalog;
+///
+/// let catalog: ErrorCatalog = serde_json::from_str(r#"{
+///     "E1001": {
+///         "title": "Article Not Published",
+///         "about": "https://example.com/errors/E1001",
+///         "status": "409"
+///     }
+/// }"#)?;
+///
+/// let error = catalog.build("E1001", "The article must be published first.");
+///
+/// assert_eq!(error.title, Some("Article Not Published".to_owned()));
+/// assert_eq!(error.status.map(|status| status.as_u16()), Some(409));
+/// assert_eq!(
+///     error.links.get("about").map(|link| link.reference()),
+///     Some("https://example.com/errors/E1001".into())
+/// );
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`CatalogEntry`]: ./struct.CatalogEntry.html
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ErrorCatalog {
+    entries: Map<String, CatalogEntry>,
+}
+
+impl ErrorCatalog {
+    /// Returns a new, empty `ErrorCatalog`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns an `ErrorObject` with the specified `code` and `detail`. If the catalog
+    /// contains an entry for `code`, the defaults of the entry are applied.
+    pub fn build(&self, code: &str, detail: &str) -> ErrorObject {
+        let mut error = ErrorObject::default();
+
+        error.code = Some(code.to_owned());
+        error.detail = Some(detail.to_owned());
+        error.apply_catalog(self);
+        error
+    }
+
+    /// Returns a reference to the entry for `code`.
+    pub fn get(&self, code: &str) -> Option<&CatalogEntry> {
+        self.entries.get(code)
+    }
+
+    /// Inserts an entry for `code`. If the catalog already contained an entry for
+    /// `code`, the previous entry is returned.
+    pub fn insert(&mut self, code: String, entry: CatalogEntry) -> Option<CatalogEntry> {
+        self.entries.insert(code, entry)
+    }
+
+    /// Returns `true` if the catalog does not contain any entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of entries in the catalog.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl FromIterator<(String, CatalogEntry)> for ErrorCatalog {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (String, CatalogEntry)>,
+    {
+        let entries = Map::from_iter(iter);
+        ErrorCatalog { entries }
+    }
+}
+
+impl<'de> Deserialize<'de> for ErrorCatalog {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Map::deserialize(deserializer).map(|entries| ErrorCatalog { entries })
+    }
+}
+
+impl Serialize for ErrorCatalog {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.entries.serialize(serializer)
+    }
+}
+
+impl ErrorObject {
+    /// Fills the title, `about` link, and status of the error from the entry for its
+    /// code in `catalog`. Fields that are already present are not overwritten. The
+    /// error is left untouched if it does not have a code or the code is not in
+    /// `catalog`.
+    pub fn apply_catalog(&mut self, catalog: &ErrorCatalog) {
+        let entry = match self.code.as_ref().and_then(|code| catalog.get(code)) {
+            Some(entry) => entry,
+            None => return,
+        };
+
+        if self.title.is_none() {
+            self.title = entry.title.clone();
+        }
+
+        if self.status.is_none() {
+            self.status = entry.default_status;
+        }
+
+        if let Some(ref about) = entry.about {
+            let key = "about".parse().expect("valid member name");
+
+            if !self.links.contains_key(&key) {
+                self.links.insert(key, about.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+
+    use super::{CatalogEntry, ErrorCatalog};
+    use doc::ErrorObject;
+
+    fn catalog() -> ErrorCatalog {
+        let entry = CatalogEntry::new(
+            Some("Article Not Published".to_owned()),
+            Some("https://example.com/errors/E1001".parse().unwrap()),
+            Some(StatusCode::CONFLICT),
+        );
+
+        vec![("E1001".to_owned(), entry)].into_iter().collect()
+    }
+
+    #[test]
+    fn build() {
+        let error = catalog().build("E1001", "The article must be published first.");
+
+        assert_eq!(error.code, Some("E1001".to_owned()));
+        assert_eq!(error.detail, Some("The article must be published first.".to_owned()));
+        assert_eq!(error.title, Some("Article Not Published".to_owned()));
+        assert_eq!(error.status, Some(StatusCode::CONFLICT));
+        assert_eq!(
+            error.links.get("about").map(|link| link.reference()),
+            Some("https://example.com/errors/E1001".into())
+        );
+    }
+
+    #[test]
+    fn explicit_fields_precedence() {
+        let mut error = ErrorObject::new(Some(StatusCode::GONE));
+
+        error.code = Some("E1001".to_owned());
+        error.links.insert(
+            "about".parse().unwrap(),
+            "https://example.com/help".parse().unwrap(),
+        );
+        error.apply_catalog(&catalog());
+
+        assert_eq!(error.title, Some("Gone".to_owned()));
+        assert_eq!(error.status, Some(StatusCode::GONE));
+        assert_eq!(
+            error.links.get("about").map(|link| link.reference()),
+            Some("https://example.com/help".into())
+        );
+    }
+
+    #[test]
+    fn unknown_code() {
+        let error = catalog().build("E9999", "Something went wrong.");
+        let mut expected = ErrorObject::default();
+
+        expected.code = Some("E9999".to_owned());
+        expected.detail = Some("Something went wrong.".to_owned());
+        assert_eq!(error, expected);
+
+        let mut error = ErrorObject::default();
+
+        error.apply_catalog(&catalog());
+        assert_eq!(error, ErrorObject::default());
+    }
+}
@@ -1,16 +1,187 @@
+use std::cell::RefCell;
 use std::io::{Read, Write};
 
 use serde::de::DeserializeOwned;
-use serde_json;
+use serde::ser::{Error as SerError, Serialize, SerializeMap, SerializeSeq, Serializer};
+use serde_json::{self, ser::Formatter};
 
-use doc::{Data, Document, PrimaryData};
+use http::{StatusCode, Uri};
+
+use doc::{link, Data, Document, ErrorObject, ErrorSource, Identifier, JsonApi, Link, Object,
+          ParseOptions, Pointer, PrimaryData};
 use error::Error;
 use query::Query;
-use value::{self, Value};
-use view::Render;
+use resource::{self, Resource};
+use value::{self, Key, Map, Set, Value};
+use view::{Context, Options, Render};
 
 /// Interpret a `Document<T>` as a type `U`.
+///
+/// If the document contains errors, the error objects are returned in an
+/// [`ErrorKind::ErrorDocument`] so they can be inspected by the caller. A document
+/// that only contains meta information is interpreted as `null`.
+///
+/// [`ErrorKind::ErrorDocument`]: ../error/enum.ErrorKind.html#variant.ErrorDocument
 pub fn from_doc<T, U>(doc: Document<T>) -> Result<U, Error>
+where
+    T: PrimaryData,
+    U: DeserializeOwned,
+{
+    decode(doc, false)
+}
+
+/// Interpret a `Document<T>` as a type `U`, including the `type` of each resource.
+///
+/// Like [`from_doc`], except each resource object is flattened with a `type` member
+/// alongside its `id`. Related resources that are not included in the document are
+/// flattened as an object containing their `id` and `type`, rather than just the `id`.
+/// This makes it possible to deserialize polymorphic relationships.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// # #[macro_use]
+/// # extern crate serde_derive;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::Object;
+///
+/// #[derive(Deserialize)]
+/// struct Related {
+///     id: String,
+///     #[serde(rename = "type")]
+///     kind: String,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Article {
+///     #[serde(rename = "type")]
+///     kind: String,
+///     author: Related,
+/// }
+///
+/// let doc = json_api::parse_str::<Object>(r#"{
+///     "data": {
+///         "type": "articles",
+///         "id": "1",
+///         "relationships": {
+///             "author": {
+///                 "data": { "type": "people", "id": "9" }
+///             }
+///         }
+///     }
+/// }"#)?;
+///
+/// let article: Article = json_api::from_doc_typed(doc)?;
+///
+/// assert_eq!(article.kind, "articles");
+/// assert_eq!(article.author.kind, "people");
+/// assert_eq!(article.author.id, "9");
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`from_doc`]: ./fn.from_doc.html
+pub fn from_doc_typed<T, U>(doc: Document<T>) -> Result<U, Error>
+where
+    T: PrimaryData,
+    U: DeserializeOwned,
+{
+    decode(doc, true)
+}
+
+/// Interpret a `Document<T>` as a type `U`, requiring every related resource to be
+/// included.
+///
+/// [`from_doc`] flattens a related resource that is not included in the document as
+/// its `id`, which is usually not the shape that `U` expects for a relationship. This
+/// function returns an [`ErrorKind::UnresolvedIdentifier`] instead, with the dotted path
+/// of relationship names that lead to the missing resource. Relationships without
+/// resource linkage are ignored.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// # #[macro_use]
+/// # extern crate serde_derive;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::Object;
+/// use json_api::error::ErrorKind;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Author {
+///     name: String,
+/// }
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Post {
+///     author: Author,
+/// }
+///
+/// let doc = json_api::parse_str::<Object>(r#"{
+///     "data": {
+///         "type": "posts",
+///         "id": "1",
+///         "relationships": {
+///             "author": {
+///                 "data": { "type": "people", "id": "9" }
+///             }
+///         }
+///     }
+/// }"#)?;
+///
+/// match *json_api::from_doc_strict::<_, Post>(doc).unwrap_err().kind() {
+///     ErrorKind::UnresolvedIdentifier(ref kind, ref id, ref path) => {
+///         assert_eq!((&**kind, &**id, &**path), ("people", "9", "author"));
+///     }
+///     ref kind => panic!("unexpected error: {}", kind),
+/// }
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`from_doc`]: ./fn.from_doc.html
+/// [`ErrorKind::UnresolvedIdentifier`]: ../error/enum.ErrorKind.html#variant.UnresolvedIdentifier
+pub fn from_doc_strict<T, U>(doc: Document<T>) -> Result<U, Error>
+where
+    T: PrimaryData,
+    U: DeserializeOwned,
+{
+    if let Document::Ok {
+        ref data,
+        ref included,
+        ..
+    } = doc
+    {
+        match *data {
+            Data::Member(ref data) => if let Some(ref item) = **data {
+                item.resolve(included)?;
+            },
+            Data::Collection(ref data) => for item in data {
+                item.resolve(included)?;
+            },
+        }
+    }
+
+    decode(doc, false)
+}
+
+fn decode<T, U>(doc: Document<T>, typed: bool) -> Result<U, Error>
 where
     T: PrimaryData,
     U: DeserializeOwned,
@@ -19,23 +190,178 @@ where
         Document::Ok { data, included, .. } => {
             let value = value::convert::to_json(match data {
                 Data::Member(data) => match *data {
-                    Some(item) => item.flatten(&included),
+                    Some(item) => item.flatten(&included, typed),
                     None => Value::Null,
                 },
                 Data::Collection(data) => data.into_iter()
-                    .map(|item| item.flatten(&included))
+                    .map(|item| item.flatten(&included, typed))
                     .collect(),
             });
 
             Ok(serde_json::from_value(value)?)
         }
-        Document::Err { .. } => {
-            let e = Error::from("Document contains one or more error(s)");
-            Err(e)
-        }
+        Document::Err { errors, .. } => Err(Error::error_document(errors)),
+        Document::Meta { .. } => Ok(serde_json::from_value(serde_json::Value::Null)?),
+    }
+}
+
+/// Deserialize a `Document<T>` from an IO stream of JSON text.
+///
+/// Unlike [`from_reader`], the document is returned as-is so the top-level members
+/// remain available. Use [`Document::decode`] to interpret it as another type.
+///
+/// [`from_reader`]: ./fn.from_reader.html
+/// [`Document::decode`]: ./enum.Document.html#method.decode
+pub fn parse_reader<R, T>(data: R) -> Result<Document<T>, Error>
+where
+    R: Read,
+    T: PrimaryData,
+{
+    ParseOptions::new().parse_reader(data)
+}
+
+/// Deserialize a `Document<T>` from bytes of JSON text.
+///
+/// Unlike [`from_slice`], the document is returned as-is so the top-level members
+/// remain available. Use [`Document::decode`] to interpret it as another type.
+///
+/// [`from_slice`]: ./fn.from_slice.html
+/// [`Document::decode`]: ./enum.Document.html#method.decode
+pub fn parse_slice<T>(data: &[u8]) -> Result<Document<T>, Error>
+where
+    T: PrimaryData,
+{
+    ParseOptions::new().parse_slice(data)
+}
+
+/// Deserialize the resource linkage of a to-one relationship from bytes of JSON text.
+///
+/// The document must contain a single resource identifier or `null` as primary
+/// data. If the primary data is an array or the document does not contain any, an
+/// [`ErrorKind::ErrorDocument`] with a `422 Unprocessable Entity` error is returned
+/// that describes the expected shape.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc;
+///
+/// let author = doc::parse_linkage_member(br#"{
+///     "data": { "type": "people", "id": "12" }
+/// }"#)?;
+///
+/// assert_eq!(author.map(|ident| ident.id), Some("12".to_owned()));
+/// assert!(doc::parse_linkage_member(br#"{ "data": [] }"#).is_err());
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`ErrorKind::ErrorDocument`]: ../error/enum.ErrorKind.html#variant.ErrorDocument
+pub fn parse_linkage_member(data: &[u8]) -> Result<Option<Identifier>, Error> {
+    parse_linkage(data)?.expect_member().map_err(unprocessable)
+}
+
+/// Deserialize the resource linkage of a to-many relationship from bytes of JSON
+/// text.
+///
+/// The document must contain an array of resource identifiers as primary data.
+/// Otherwise, an [`ErrorKind::ErrorDocument`] with a `422 Unprocessable Entity` error
+/// is returned that describes the expected shape.
+///
+/// [`ErrorKind::ErrorDocument`]: ../error/enum.ErrorKind.html#variant.ErrorDocument
+pub fn parse_linkage_collection(data: &[u8]) -> Result<Vec<Identifier>, Error> {
+    parse_linkage(data)?.expect_collection().map_err(unprocessable)
+}
+
+fn parse_linkage(data: &[u8]) -> Result<Data<Identifier>, Error> {
+    linkage_data(parse_slice(data)?)
+}
+
+/// Returns the primary data of a document that contains resource linkage.
+pub(crate) fn linkage_data(doc: Document<Identifier>) -> Result<Data<Identifier>, Error> {
+    match doc {
+        Document::Ok { data, .. } => Ok(data),
+        Document::Err { errors, .. } => Err(Error::error_document(errors)),
+        Document::Meta { .. } => Err(unprocessable(Error::missing_field("data"))),
     }
 }
 
+/// Wraps `e` in an error document that points to the primary data of a request.
+fn unprocessable(e: Error) -> Error {
+    let mut error = ErrorObject::new(Some(StatusCode::UNPROCESSABLE_ENTITY));
+
+    error.detail = Some(e.to_string());
+    error.source = Some(ErrorSource::from(Pointer::data()));
+    Error::error_document(vec![error])
+}
+
+/// Deserialize a `Document<T>` from a string of JSON text.
+///
+/// Unlike [`from_str`], the document is returned as-is so the top-level members
+/// remain available. Use [`Document::decode`] to interpret it as another type.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// # #[macro_use]
+/// # extern crate serde_derive;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::Object;
+///
+/// #[derive(Deserialize)]
+/// struct Article {
+///     id: String,
+///     title: String,
+/// }
+///
+/// let doc = json_api::parse_str::<Object>(r#"{
+///     "data": {
+///         "type": "articles",
+///         "id": "1",
+///         "attributes": {
+///             "title": "Hello, world!"
+///         }
+///     },
+///     "meta": {
+///         "copyright": "Copyright 2017 Example Corp."
+///     }
+/// }"#)?;
+///
+/// let article = doc.decode::<Article>()?;
+///
+/// assert_eq!(article.id, "1");
+/// assert_eq!(article.title, "Hello, world!");
+/// assert!(doc.meta().contains_key("copyright"));
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`from_str`]: ./fn.from_str.html
+/// [`Document::decode`]: ./enum.Document.html#method.decode
+pub fn parse_str<T>(data: &str) -> Result<Document<T>, Error>
+where
+    T: PrimaryData,
+{
+    ParseOptions::new().parse_str(data)
+}
+
 /// Deserialize a `Document<T>` from an IO stream of JSON text and then
 /// iterpret it as a type `U`.
 pub fn from_reader<R, T, U>(data: R) -> Result<U, Error>
@@ -44,7 +370,7 @@ where
     T: PrimaryData,
     U: DeserializeOwned,
 {
-    from_doc::<T, _>(serde_json::from_reader(data)?)
+    from_doc::<T, _>(parse_reader(data)?)
 }
 
 /// Deserialize a `Document<T>` from bytes of JSON text and then iterpret it as
@@ -54,7 +380,7 @@ where
     T: PrimaryData,
     U: DeserializeOwned,
 {
-    from_doc::<T, _>(serde_json::from_slice(data)?)
+    from_doc::<T, _>(parse_slice(data)?)
 }
 
 /// Deserialize a `Document<T>` from a string of JSON text and then iterpret it
@@ -64,16 +390,255 @@ where
     T: PrimaryData,
     U: DeserializeOwned,
 {
-    from_doc::<T, _>(serde_json::from_str(data)?)
+    from_doc::<T, _>(parse_str(data)?)
 }
 
 /// Render type `T` as a `Document<U>`.
+///
+/// If `query` is `None`, every attribute and relationship of a resource is rendered
+/// and no resources are included, as if the query did not contain a sparse fieldset or
+/// an include path.
 pub fn to_doc<T, U>(value: T, query: Option<&Query>) -> Result<Document<U>, Error>
 where
     T: Render<U>,
     U: PrimaryData,
 {
-    value.render(query)
+    let doc = value.render(query)?;
+
+    record!(rendered(&doc));
+    Ok(doc)
+}
+
+/// Render type `T` as a `Document<U>` with the given render options.
+pub fn to_doc_with<T, U>(
+    value: T,
+    query: Option<&Query>,
+    options: &Options,
+) -> Result<Document<U>, Error>
+where
+    T: Render<U>,
+    U: PrimaryData,
+{
+    let doc = value.render_with(query, options)?;
+
+    record!(rendered(&doc));
+    Ok(doc)
+}
+
+/// Render a resource as a standalone `Object` without a document wrapper.
+///
+/// Unlike the primary data of a document returned by [`to_doc`], the `links` and
+/// `meta` of the resource remain members of the object. Related resources are not
+/// rendered unless they are included by `query`, in which case they are discarded. Use
+/// [`to_object_with_included`] if the included resources are needed as well.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate json_api;
+/// # extern crate serde_json;
+/// #
+/// # use json_api::Error;
+/// #
+/// struct Post {
+///     id: u64,
+///     title: String,
+/// }
+///
+/// resource!(Post, |&self| {
+///     kind "posts";
+///     id self.id;
+///     attrs title;
+/// });
+///
+/// # fn example() -> Result<(), Error> {
+/// let post = Post {
+///     id: 1,
+///     title: "Hello, world!".to_owned(),
+/// };
+///
+/// let object = json_api::to_object(&post, None)?;
+/// let json = serde_json::to_string(&object)?;
+///
+/// assert_eq!(
+///     json,
+///     r#"{"attributes":{"title":"Hello, world!"},"id":"1","type":"posts"}"#
+/// );
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`to_doc`]: ./fn.to_doc.html
+/// [`to_object_with_included`]: ./fn.to_object_with_included.html
+pub fn to_object<T: Resource>(value: &T, query: Option<&Query>) -> Result<Object, Error> {
+    to_object_with_included(value, query).map(|(object, _)| object)
+}
+
+/// Render a resource as a standalone `Object` along with the set of related resources
+/// that are included by `query`.
+pub fn to_object_with_included<T: Resource>(
+    value: &T,
+    query: Option<&Query>,
+) -> Result<(Object, Set<Object>), Error> {
+    let mut included = Set::new();
+    let object = {
+        let kind = value.instance_kind();
+        let mut ctx = Context::new(kind.clone(), query, &mut included);
+
+        ctx.visit(kind, value.id());
+        value.to_object(&mut ctx)?
+    };
+
+    Ok((object, included))
+}
+
+/// Render a page of a collection as a `Document<Object>` with pagination links.
+///
+/// The document contains `first`, `last`, and when applicable, `prev` and `next`
+/// top-level links for the page of `query`, as well as the `total` number of items in
+/// the collection as a `meta` member. Each link is created by appending `query` to
+/// `base` with the `page` parameter replaced, so the links preserve the sparse
+/// fieldsets, filters, and sort order of the request. If `query` does not contain a
+/// page, the entire collection is considered to be one page.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # struct Post(u64);
+/// #
+/// # resource!(Post, |&self| {
+/// #     kind "posts";
+/// #     id self.0;
+/// # });
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::query;
+///
+/// let posts = vec![Post(3), Post(4)];
+/// let query = query::from_str("page[number]=2&page[size]=2")?;
+/// let base = "/posts".parse()?;
+/// let doc = json_api::to_doc_paginated(&posts, &query, 5, &base)?;
+///
+/// assert_eq!(doc.meta().get("total"), Some(&5.into()));
+/// assert_eq!(
+///     doc.links().get("next").map(|link| link.reference()),
+///     Some("/posts?page%5Bnumber%5D=3&page%5Bsize%5D=2".into())
+/// );
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+pub fn to_doc_paginated<T: Resource>(
+    items: &[T],
+    query: &Query,
+    total: u64,
+    base: &Uri,
+) -> Result<Document<Object>, Error> {
+    let mut doc = to_doc(items, Some(query))?;
+
+    paginate(&mut doc, query, total, base)?;
+    Ok(doc)
+}
+
+/// Render a collection as a `Document<Identifier>`.
+///
+/// Each item is rendered with [`Resource::to_ident`], so the attributes and
+/// relationships of the collection are never computed. The top-level links and meta of
+/// each item are still added to the document. This is useful for lightweight responses
+/// to clients that only need to know which resources are in a collection.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate json_api;
+/// # extern crate serde_json;
+/// #
+/// # use json_api::Error;
+/// #
+/// struct Post {
+///     id: u64,
+///     title: String,
+/// }
+///
+/// resource!(Post, |&self| {
+///     kind "posts";
+///     id self.id;
+///     attrs title;
+/// });
+///
+/// # fn example() -> Result<(), Error> {
+/// let posts = vec![Post {
+///     id: 1,
+///     title: "Hello, world!".to_owned(),
+/// }];
+///
+/// let doc = json_api::to_doc_identifiers(&posts, None)?;
+/// let json = serde_json::to_string(&doc)?;
+///
+/// assert_eq!(json, r#"{"data":[{"id":"1","type":"posts"}],"jsonapi":{"version":"1.0"}}"#);
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`Resource::to_ident`]: ./trait.Resource.html#tymethod.to_ident
+pub fn to_doc_identifiers<T: Resource>(
+    items: &[T],
+    query: Option<&Query>,
+) -> Result<Document<Identifier>, Error> {
+    to_doc(items, query)
+}
+
+/// Render a page of a collection as a `Document<Identifier>` with pagination links.
+///
+/// Like [`to_doc_paginated`], except each item is rendered as a resource identifier
+/// like [`to_doc_identifiers`].
+///
+/// [`to_doc_identifiers`]: ./fn.to_doc_identifiers.html
+/// [`to_doc_paginated`]: ./fn.to_doc_paginated.html
+pub fn to_doc_identifiers_paginated<T: Resource>(
+    items: &[T],
+    query: &Query,
+    total: u64,
+    base: &Uri,
+) -> Result<Document<Identifier>, Error> {
+    let mut doc = to_doc(items, Some(query))?;
+
+    paginate(&mut doc, query, total, base)?;
+    Ok(doc)
+}
+
+/// Adds the pagination links and `total` meta member for the page of `query` to `doc`.
+fn paginate<T>(doc: &mut Document<T>, query: &Query, total: u64, base: &Uri) -> Result<(), Error>
+where
+    T: PrimaryData,
+{
+    let page = query.page.unwrap_or_default();
+
+    for (key, link) in link::paginate(&base.to_string(), query, page, total) {
+        doc.links_mut().insert(key.parse()?, link?);
+    }
+
+    doc.meta_mut().insert("total".parse()?, total.into());
+    Ok(())
 }
 
 /// Render type `T` as a `Document<U>` and then serialize it as a string of
@@ -83,7 +648,10 @@ where
     T: Render<U>,
     U: PrimaryData,
 {
-    Ok(serde_json::to_string(&to_doc(value, query)?)?)
+    let output = serde_json::to_string(&to_doc(value, query)?)?;
+
+    record!(serialized(output.len()));
+    Ok(output)
 }
 
 /// Render type `T` as a `Document<U>` and then serialize it as a
@@ -93,7 +661,10 @@ where
     T: Render<U>,
     U: PrimaryData,
 {
-    Ok(serde_json::to_string_pretty(&to_doc(value, query)?)?)
+    let output = serde_json::to_string_pretty(&to_doc(value, query)?)?;
+
+    record!(serialized(output.len()));
+    Ok(output)
 }
 
 /// Render type `T` as a `Document<U>` and then serialize it as a JSON byte
@@ -103,7 +674,10 @@ where
     T: Render<U>,
     U: PrimaryData,
 {
-    Ok(serde_json::to_vec(&to_doc(value, query)?)?)
+    let output = serde_json::to_vec(&to_doc(value, query)?)?;
+
+    record!(serialized(output.len()));
+    Ok(output)
 }
 
 /// Render type `T` as a `Document<U>` and then serialize it as a
@@ -113,7 +687,10 @@ where
     T: Render<U>,
     U: PrimaryData,
 {
-    Ok(serde_json::to_vec_pretty(&to_doc(value, query)?)?)
+    let output = serde_json::to_vec_pretty(&to_doc(value, query)?)?;
+
+    record!(serialized(output.len()));
+    Ok(output)
 }
 
 /// Render type `T` as a `Document<U>` and then serialize it as JSON into the
@@ -124,6 +701,9 @@ where
     T: Render<U>,
     U: PrimaryData,
 {
+    #[cfg(feature = "metrics")]
+    let writer = ::metrics::Counted(writer);
+
     serde_json::to_writer(writer, &to_doc(value, query)?)?;
     Ok(())
 }
@@ -136,6 +716,543 @@ where
     T: Render<U>,
     U: PrimaryData,
 {
+    #[cfg(feature = "metrics")]
+    let writer = ::metrics::Counted(writer);
+
     serde_json::to_writer_pretty(writer, &to_doc(value, query)?)?;
     Ok(())
 }
+
+/// Render each resource of `items` as the primary data of a collection document and
+/// serialize it as JSON into the IO stream.
+///
+/// Unlike [`to_writer`], the resources are rendered and written one at a time as they
+/// are pulled from the iterator, so the primary data is never held in memory all at
+/// once. The output is the same as rendering the entire collection with [`to_writer`].
+///
+/// Related resources that are included by `query` must be retained until every item has
+/// been rendered so they can be deduplicated. They are written after the primary data,
+/// along with the top-level links and meta of the document. Use a query without an
+/// `include` parameter to bound memory usage by the size of a single resource.
+///
+/// If a resource fails to render, the error is returned and the document that has been
+/// written to the stream is incomplete.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// struct Post(u64);
+///
+/// resource!(Post, |&self| {
+///     kind "posts";
+///     id self.0;
+/// });
+///
+/// # fn example() -> Result<(), Error> {
+/// let mut buf = Vec::new();
+///
+/// json_api::to_writer_streamed(&mut buf, (1..3).map(Post), None)?;
+///
+/// assert_eq!(
+///     String::from_utf8(buf).unwrap(),
+///     concat!(
+///         r#"{"data":[{"id":"1","type":"posts"},{"id":"2","type":"posts"}],"#,
+///         r#""jsonapi":{"version":"1.0"}}"#,
+///     )
+/// );
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`to_writer`]: ./fn.to_writer.html
+pub fn to_writer_streamed<W, I>(writer: W, items: I, query: Option<&Query>) -> Result<(), Error>
+where
+    W: Write,
+    I: IntoIterator,
+    I::Item: Resource,
+{
+    #[cfg(feature = "metrics")]
+    let writer = ::metrics::Counted(writer);
+
+    stream(&mut serde_json::Serializer::new(writer), items, query)
+}
+
+/// Render each resource of `items` as the primary data of a collection document and
+/// serialize it as pretty-printed JSON into the IO stream.
+///
+/// For more information, check out [`to_writer_streamed`].
+///
+/// [`to_writer_streamed`]: ./fn.to_writer_streamed.html
+pub fn to_writer_streamed_pretty<W, I>(
+    writer: W,
+    items: I,
+    query: Option<&Query>,
+) -> Result<(), Error>
+where
+    W: Write,
+    I: IntoIterator,
+    I::Item: Resource,
+{
+    #[cfg(feature = "metrics")]
+    let writer = ::metrics::Counted(writer);
+
+    stream(&mut serde_json::Serializer::pretty(writer), items, query)
+}
+
+fn stream<W, F, I>(
+    serializer: &mut serde_json::Serializer<W, F>,
+    items: I,
+    query: Option<&Query>,
+) -> Result<(), Error>
+where
+    W: Write,
+    F: Formatter,
+    I: IntoIterator,
+    I::Item: Resource,
+{
+    let data = Stream {
+        items: RefCell::new(Some(items.into_iter())),
+        query,
+        error: Default::default(),
+        included: Default::default(),
+        links: Default::default(),
+        meta: Default::default(),
+    };
+
+    let result = data.write(serializer);
+
+    if let Some(e) = data.error.into_inner() {
+        return Err(e);
+    }
+
+    result?;
+    record!(streamed(data.included.borrow().len()));
+
+    Ok(())
+}
+
+/// The primary data of a streamed document. Serializing it renders each resource of the
+/// iterator and collects the included resources and top-level members of the document.
+struct Stream<'q, I> {
+    items: RefCell<Option<I>>,
+    query: Option<&'q Query>,
+    error: RefCell<Option<Error>>,
+    included: RefCell<Set<Object>>,
+    links: RefCell<Map<Key, Link>>,
+    meta: RefCell<Map>,
+}
+
+impl<'q, I> Stream<'q, I>
+where
+    I: Iterator,
+    I::Item: Resource,
+{
+    /// Writes the document in the same shape as a serialized `Document::Ok`.
+    fn write<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_map(None)?;
+
+        state.serialize_entry("data", self)?;
+
+        let included = self.included.borrow();
+        let links = self.links.borrow();
+        let meta = self.meta.borrow();
+
+        if !included.is_empty() {
+            state.serialize_entry("included", &*included)?;
+        }
+
+        state.serialize_entry("jsonapi", &JsonApi::default())?;
+
+        if !links.is_empty() {
+            state.serialize_entry("links", &*links)?;
+        }
+
+        if !meta.is_empty() {
+            state.serialize_entry("meta", &*meta)?;
+        }
+
+        state.end()
+    }
+
+    fn render(&self, item: &I::Item) -> Result<Object, Error> {
+        let object = {
+            let mut included = self.included.borrow_mut();
+            resource::to_object(item, self.query, &mut included, None)?
+        };
+
+        self.links.borrow_mut().extend(item.doc_links()?);
+        self.meta.borrow_mut().extend(item.doc_meta()?);
+
+        Ok(object)
+    }
+}
+
+impl<'q, I> Serialize for Stream<'q, I>
+where
+    I: Iterator,
+    I::Item: Resource,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_seq(None)?;
+
+        for item in self.items.borrow_mut().take().into_iter().flatten() {
+            let object = self.render(&item).map_err(|e| {
+                let msg = e.to_string();
+
+                // Keep the original error so it can be returned to the caller.
+                *self.error.borrow_mut() = Some(e);
+                S::Error::custom(msg)
+            })?;
+
+            state.serialize_element(&object)?;
+        }
+
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+    use serde_json;
+
+    use doc::{Data, Document, Object, Pointer};
+    use error::{Error, ErrorKind};
+    use value::Value;
+
+    const POLYMORPHIC: &str = r#"{
+        "data": {
+            "type": "posts",
+            "id": "1",
+            "relationships": {
+                "attachments": {
+                    "data": [
+                        { "type": "images", "id": "1" },
+                        { "type": "videos", "id": "2" }
+                    ]
+                }
+            }
+        },
+        "included": [{
+            "type": "images",
+            "id": "1",
+            "attributes": {
+                "url": "/images/1.png"
+            }
+        }]
+    }"#;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(tag = "type")]
+    enum Attachment {
+        #[serde(rename = "images")]
+        Image { id: String, url: String },
+        #[serde(rename = "videos")]
+        Video { id: String },
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Post {
+        id: String,
+        #[serde(rename = "type")]
+        kind: String,
+        attachments: Vec<Attachment>,
+    }
+
+    const ARTICLE: &str = concat!(
+        r#"{"data":{"attributes":{"title":"Hello"},"id":"1","type":"articles"},"#,
+        r#""jsonapi":{"version":"1.0"},"#,
+        r#""links":{"self":"/articles/1"},"#,
+        r#""meta":{"copyright":"Example Corp."}}"#,
+    );
+
+    #[test]
+    fn parse_str_preserves_document() {
+        let doc = super::parse_str::<Object>(ARTICLE).unwrap();
+
+        assert_eq!(doc.self_link().unwrap().reference(), "/articles/1");
+        assert_eq!(doc.meta().get("copyright"), Some(&"Example Corp.".into()));
+        assert_eq!(serde_json::to_string(&doc).unwrap(), ARTICLE);
+
+        let value = doc.decode::<Value>().unwrap();
+
+        assert_eq!(value.pointer("/title"), Some(&"Hello".into()));
+        assert_eq!(doc.meta().len(), 1);
+    }
+
+    #[test]
+    fn parse_slice_and_reader() {
+        let from_slice = super::parse_slice::<Object>(ARTICLE.as_bytes()).unwrap();
+        let from_reader = super::parse_reader::<_, Object>(ARTICLE.as_bytes()).unwrap();
+
+        assert_eq!(from_slice, from_reader);
+        assert_eq!(from_slice.links().len(), 1);
+    }
+
+    fn linkage_error(e: &Error) -> (Option<StatusCode>, String) {
+        match *e.kind() {
+            ErrorKind::ErrorDocument(ref errors) => {
+                let error = &errors[0];
+                (error.status, error.detail.clone().unwrap())
+            }
+            ref kind => panic!("unexpected error kind: {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn parse_linkage_member() {
+        let ident = super::parse_linkage_member(br#"{"data":{"type":"users","id":"1"}}"#);
+        assert_eq!(ident.unwrap().map(|ident| ident.id), Some("1".to_owned()));
+        assert_eq!(super::parse_linkage_member(br#"{"data":null}"#).unwrap(), None);
+
+        let e = super::parse_linkage_member(br#"{"data":[]}"#).unwrap_err();
+        assert_eq!(
+            linkage_error(&e),
+            (
+                Some(StatusCode::UNPROCESSABLE_ENTITY),
+                "expected a single resource or null but found an array of 0 resource(s)"
+                    .to_owned(),
+            )
+        );
+
+        let e = super::parse_linkage_member(br#"{"meta":{}}"#).unwrap_err();
+        assert_eq!(
+            linkage_error(&e).1,
+            r#"missing required field "data""#.to_owned()
+        );
+    }
+
+    #[test]
+    fn parse_linkage_collection() {
+        let data = br#"{"data":[{"type":"users","id":"1"},{"type":"users","id":"2"}]}"#;
+        assert_eq!(super::parse_linkage_collection(data).unwrap().len(), 2);
+        assert!(super::parse_linkage_collection(br#"{"data":[]}"#).unwrap().is_empty());
+
+        let e = super::parse_linkage_collection(br#"{"data":null}"#).unwrap_err();
+        assert_eq!(
+            linkage_error(&e).1,
+            "expected an array of resources but found null".to_owned()
+        );
+
+        let e = super::parse_linkage_collection(br#"{"data":{"type":"users","id":"1"}}"#);
+        assert_eq!(
+            linkage_error(&e.unwrap_err()).1,
+            "expected an array of resources but found a single resource".to_owned()
+        );
+    }
+
+    #[test]
+    fn parse_str_error_document() {
+        let source = r#"{"errors":[{"status":"404"}],"meta":{"request-id":"abc"}}"#;
+        let doc: Document<Object> = super::parse_str(source).unwrap();
+
+        assert!(!doc.is_ok());
+        assert_eq!(doc.meta().get("request-id"), Some(&"abc".into()));
+        assert!(doc.decode::<Value>().is_err());
+    }
+
+    #[test]
+    fn from_str_error_document() {
+        let source = r#"{
+            "errors": [{
+                "status": "422",
+                "code": "invalid-title",
+                "title": "Invalid Attribute",
+                "detail": "Title must contain at least three characters.",
+                "source": { "pointer": "/data/attributes/title" }
+            }, {
+                "status": "403",
+                "title": "Forbidden"
+            }]
+        }"#;
+
+        let e = super::from_str::<Object, Value>(source).unwrap_err();
+        let errors = match *e.kind() {
+            ErrorKind::ErrorDocument(ref errors) => errors,
+            ref kind => panic!("unexpected error kind: {:?}", kind),
+        };
+
+        assert_eq!(e.to_string(), "Document contains 2 error(s)");
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].status, Some(StatusCode::UNPROCESSABLE_ENTITY));
+        assert_eq!(errors[0].code, Some("invalid-title".to_owned()));
+        assert_eq!(errors[0].title, Some("Invalid Attribute".to_owned()));
+        assert_eq!(
+            errors[0].detail,
+            Some("Title must contain at least three characters.".to_owned())
+        );
+        assert_eq!(
+            errors[0].source.as_ref().and_then(|s| s.pointer.as_ref()),
+            Some(&Pointer::attribute("title"))
+        );
+        assert_eq!(errors[1].status, Some(StatusCode::FORBIDDEN));
+    }
+
+    #[test]
+    fn from_doc_typed_polymorphic() {
+        let doc = super::parse_str::<Object>(POLYMORPHIC).unwrap();
+        let post: Post = super::from_doc_typed(doc).unwrap();
+
+        assert_eq!(post.id, "1");
+        assert_eq!(post.kind, "posts");
+        assert_eq!(
+            post.attachments,
+            vec![
+                Attachment::Image {
+                    id: "1".to_owned(),
+                    url: "/images/1.png".to_owned(),
+                },
+                Attachment::Video { id: "2".to_owned() },
+            ]
+        );
+    }
+
+    #[test]
+    fn from_doc_untyped() {
+        let doc = super::parse_str::<Object>(POLYMORPHIC).unwrap();
+        let value: Value = super::from_doc(doc).unwrap();
+
+        assert_eq!(value.pointer("/type"), None);
+        assert_eq!(value.pointer("/attachments/0/type"), None);
+        assert_eq!(value.pointer("/attachments/1"), Some(&"2".into()));
+    }
+
+    const BLOG: &str = r#"{
+        "data": {
+            "type": "posts",
+            "id": "1",
+            "relationships": {
+                "author": {
+                    "data": { "type": "people", "id": "9" }
+                },
+                "comments": {
+                    "data": [
+                        { "type": "comments", "id": "1" },
+                        { "type": "comments", "id": "2" }
+                    ]
+                },
+                "tags": {
+                    "links": { "related": "/posts/1/tags" }
+                }
+            }
+        },
+        "included": [{
+            "type": "people",
+            "id": "9",
+            "attributes": { "name": "Alfred" }
+        }, {
+            "type": "comments",
+            "id": "1",
+            "attributes": { "body": "First" },
+            "relationships": {
+                "author": { "data": { "type": "people", "id": "9" } }
+            }
+        }, {
+            "type": "comments",
+            "id": "2",
+            "attributes": { "body": "Second" },
+            "relationships": {
+                "author": { "data": { "type": "people", "id": "10" } }
+            }
+        }]
+    }"#;
+
+    #[derive(Debug, Deserialize)]
+    struct Person {
+        name: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Comment {
+        body: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Blog {
+        author: Person,
+        comments: Vec<Comment>,
+    }
+
+    fn unresolved(e: &Error) -> (&str, &str, &str) {
+        match *e.kind() {
+            ErrorKind::UnresolvedIdentifier(ref kind, ref id, ref path) => (kind, id, path),
+            ref kind => panic!("unexpected error: {}", kind),
+        }
+    }
+
+    #[test]
+    fn from_doc_strict_to_one() {
+        let mut doc = super::parse_str::<Object>(BLOG).unwrap();
+
+        if let Document::Ok {
+            data: Data::Member(ref mut data),
+            ..
+        } = doc
+        {
+            if let Some(ref mut post) = **data {
+                post.relationships.remove("comments");
+            }
+        }
+
+        let blog = super::from_doc_strict::<_, Value>(doc.clone()).unwrap();
+        assert_eq!(blog.pointer("/author/name"), Some(&"Alfred".into()));
+
+        if let Document::Ok { ref mut included, .. } = doc {
+            included.clear();
+        }
+
+        let e = super::from_doc_strict::<_, Value>(doc.clone()).unwrap_err();
+        assert_eq!(unresolved(&e), ("people", "9", "author"));
+
+        // The lenient variant falls back to the id of the related resource.
+        let blog = super::from_doc::<_, Value>(doc).unwrap();
+        assert_eq!(blog.pointer("/author"), Some(&"9".into()));
+    }
+
+    #[test]
+    fn from_doc_strict_to_many() {
+        let doc = super::parse_str::<Object>(BLOG).unwrap();
+        let e = super::from_doc_strict::<_, Blog>(doc).unwrap_err();
+
+        assert_eq!(unresolved(&e), ("people", "10", "comments.author"));
+
+        let source = BLOG.replace(r#""id": "10""#, r#""id": "9""#);
+        let doc = super::parse_str::<Object>(&source).unwrap();
+        let blog = super::from_doc_strict::<_, Blog>(doc).unwrap();
+        let bodies = blog.comments.iter().map(|c| c.body.as_str()).collect::<Vec<_>>();
+
+        assert_eq!(blog.author.name, "Alfred");
+        assert_eq!(bodies, ["First", "Second"]);
+    }
+
+    #[test]
+    fn from_doc_strict_unresolved() {
+        let source = BLOG.replace(
+            r#"{ "type": "comments", "id": "2" }"#,
+            r#"{ "type": "comments", "id": "3" }"#,
+        );
+        let doc = super::parse_str::<Object>(&source).unwrap();
+
+        // The lenient variant fails with a type error instead.
+        assert!(super::from_doc::<_, Blog>(doc.clone()).is_err());
+
+        let e = super::from_doc_strict::<_, Blog>(doc).unwrap_err();
+        assert_eq!(unresolved(&e), ("comments", "3", "comments"));
+        assert_eq!(
+            e.to_string(),
+            concat!(
+                r#"relationship "comments" references a resource that is not included: "#,
+                r#"type "comments", id "3""#,
+            )
+        );
+    }
+}
@@ -1,5 +1,7 @@
-use doc::Link;
+use doc::{Link, Pointer};
+use error::{Error, ErrorKind};
 use http::StatusCode;
+use value::size::{EstimateSize, StructSize};
 use value::{Key, Map};
 
 /// Contains information about problems encountered while performing an
@@ -71,6 +73,80 @@ impl ErrorObject {
     }
 }
 
+impl<'a> From<&'a Error> for ErrorObject {
+    /// Returns an `ErrorObject` that describes `error` to a client.
+    ///
+    /// A query parameter that could not be decoded results in a `400 Bad Request`
+    /// with the name of the parameter as the source of the error when it is known. A
+    /// document that could not be parsed or does not match the expected shape, or that
+    /// requests an unsupported version, also results in a `400 Bad Request` with the
+    /// error message as the detail. Any other error results in a `500 Internal Server
+    /// Error` without details.
+    ///
+    /// An [`ErrorKind::ErrorDocument`] may contain more than one error. Use its errors
+    /// directly to preserve each of them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// # extern crate http;
+    /// #
+    /// # fn main() {
+    /// use http::StatusCode;
+    /// use json_api::doc::ErrorObject;
+    /// use json_api::query;
+    ///
+    /// let e = query::from_str("page[number]=first").unwrap_err();
+    /// let error = ErrorObject::from(&e);
+    ///
+    /// assert_eq!(error.status, Some(StatusCode::BAD_REQUEST));
+    /// assert_eq!(error.title, Some("Invalid Query Parameter".to_owned()));
+    /// assert_eq!(error.source.unwrap().parameter, Some("page[number]".to_owned()));
+    /// # }
+    /// ```
+    fn from(error: &'a Error) -> Self {
+        match *error.kind() {
+            ErrorKind::QueryParameter(ref name, ref reason) => ErrorObject {
+                detail: Some(reason.clone()),
+                source: name.clone().map(|name| ErrorSource::from_parts(Some(name), None)),
+                status: Some(StatusCode::BAD_REQUEST),
+                title: Some("Invalid Query Parameter".to_owned()),
+                ..Default::default()
+            },
+            ErrorKind::Utf8(_) => ErrorObject {
+                detail: Some("The query string is not valid UTF-8.".to_owned()),
+                status: Some(StatusCode::BAD_REQUEST),
+                title: Some("Invalid Query String".to_owned()),
+                ..Default::default()
+            },
+            ErrorKind::Json(ref e) if !e.is_io() => invalid_document(error),
+            ErrorKind::InvalidLink(..)
+            | ErrorKind::InvalidMemberName(..)
+            | ErrorKind::MissingField(..)
+            | ErrorKind::ReservedMember(..)
+            | ErrorKind::UnexpectedData(..)
+            | ErrorKind::UnresolvedIdentifier(..) => invalid_document(error),
+            ErrorKind::UnsupportedVersion(..) => ErrorObject {
+                detail: Some(error.to_string()),
+                status: Some(StatusCode::BAD_REQUEST),
+                title: Some("Unsupported Version".to_owned()),
+                ..Default::default()
+            },
+            _ => ErrorObject::new(Some(StatusCode::INTERNAL_SERVER_ERROR)),
+        }
+    }
+}
+
+fn invalid_document(error: &Error) -> ErrorObject {
+    ErrorObject {
+        detail: Some(error.to_string()),
+        status: Some(StatusCode::BAD_REQUEST),
+        title: Some("Invalid Document".to_owned()),
+        ..Default::default()
+    }
+}
+
 /// References to the source of the error.
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub struct ErrorSource {
@@ -80,7 +156,7 @@ pub struct ErrorSource {
 
     /// A JSON pointer to the associated entity in the request document.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub pointer: Option<String>,
+    pub pointer: Option<Pointer>,
 
     /// Private field for backwards compatibility.
     #[serde(skip)]
@@ -90,7 +166,14 @@ pub struct ErrorSource {
 impl ErrorSource {
     /// Returns a new `ErrorSource` with the specified `parameter` and
     /// `pointer` values.
+    #[deprecated(since = "0.5.0", note = "use `ErrorSource::from_parts` instead")]
     pub fn new(parameter: Option<String>, pointer: Option<String>) -> Self {
+        ErrorSource::from_parts(parameter, pointer.map(Pointer::from))
+    }
+
+    /// Returns a new `ErrorSource` with the specified `parameter` and
+    /// `pointer` values.
+    pub fn from_parts(parameter: Option<String>, pointer: Option<Pointer>) -> Self {
         ErrorSource {
             parameter,
             pointer,
@@ -99,7 +182,71 @@ impl ErrorSource {
     }
 }
 
-mod serde_status {
+impl From<Pointer> for ErrorSource {
+    /// Returns a new `ErrorSource` that references the member of the request document
+    /// at `pointer`.
+    fn from(pointer: Pointer) -> Self {
+        ErrorSource::from_parts(None, Some(pointer))
+    }
+}
+
+impl EstimateSize for ErrorObject {
+    fn estimated_size(&self) -> usize {
+        let mut state = StructSize::new();
+
+        if let Some(ref code) = self.code {
+            state.field("code", code);
+        }
+
+        if let Some(ref detail) = self.detail {
+            state.field("detail", detail);
+        }
+
+        if let Some(ref id) = self.id {
+            state.field("id", id);
+        }
+
+        if !self.links.is_empty() {
+            state.field("links", &self.links);
+        }
+
+        if !self.meta.is_empty() {
+            state.field("meta", &self.meta);
+        }
+
+        if let Some(ref source) = self.source {
+            state.field("source", source);
+        }
+
+        if let Some(status) = self.status {
+            state.field("status", status.as_str());
+        }
+
+        if let Some(ref title) = self.title {
+            state.field("title", title);
+        }
+
+        state.end()
+    }
+}
+
+impl EstimateSize for ErrorSource {
+    fn estimated_size(&self) -> usize {
+        let mut state = StructSize::new();
+
+        if let Some(ref parameter) = self.parameter {
+            state.field("parameter", parameter);
+        }
+
+        if let Some(ref pointer) = self.pointer {
+            state.field("pointer", pointer);
+        }
+
+        state.end()
+    }
+}
+
+pub(crate) mod serde_status {
     use std::fmt::{self, Formatter};
 
     use serde::de::{Deserializer, Error, Visitor};
@@ -149,3 +296,74 @@ mod serde_status {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+
+    use super::{ErrorObject, ErrorSource};
+    use doc::Object;
+    use error::{Error, ErrorKind};
+    use query;
+
+    #[test]
+    fn from_query_parameter_error() {
+        let e = query::from_str("include=author&page[size]=abc").unwrap_err();
+        let error = ErrorObject::from(&e);
+
+        assert_eq!(error.status, Some(StatusCode::BAD_REQUEST));
+        assert_eq!(error.title, Some("Invalid Query Parameter".to_owned()));
+        assert_eq!(error.detail, Some("invalid digit found in string".to_owned()));
+        assert_eq!(
+            error.source,
+            Some(ErrorSource::from_parts(Some("page[size]".to_owned()), None))
+        );
+        assert_eq!(
+            e.to_string(),
+            r#"invalid query parameter "page[size]": invalid digit found in string"#
+        );
+    }
+
+    #[test]
+    fn from_query_fields_error() {
+        let e = query::from_str("fields[articles]=title,-body").unwrap_err();
+        let error = ErrorObject::from(&e);
+
+        assert_eq!(
+            error.source.and_then(|source| source.parameter),
+            Some("fields[articles]".to_owned())
+        );
+    }
+
+    #[test]
+    fn from_document_error() {
+        let e = ::parse_str::<Object>(r#"{ "data": { "type": "posts" } }"#).unwrap_err();
+        let error = ErrorObject::from(&e);
+
+        assert_eq!(error.status, Some(StatusCode::BAD_REQUEST));
+        assert_eq!(error.title, Some("Invalid Document".to_owned()));
+        assert_eq!(error.detail, Some(e.to_string()));
+
+        let e = Error::from(ErrorKind::UnsupportedVersion("2.0".to_owned()));
+        let error = ErrorObject::from(&e);
+
+        assert_eq!(error.status, Some(StatusCode::BAD_REQUEST));
+        assert_eq!(error.title, Some("Unsupported Version".to_owned()));
+
+        let e = Error::from("unexpected");
+        let error = ErrorObject::from(&e);
+
+        assert_eq!(error.status, Some(StatusCode::INTERNAL_SERVER_ERROR));
+        assert_eq!(error.detail, None);
+    }
+
+    #[test]
+    fn from_utf8_error() {
+        let e = query::from_str("filter[name]=%FF").unwrap_err();
+        let error = ErrorObject::from(&e);
+
+        assert_eq!(error.status, Some(StatusCode::BAD_REQUEST));
+        assert_eq!(error.title, Some("Invalid Query String".to_owned()));
+        assert_eq!(error.source, None);
+    }
+}
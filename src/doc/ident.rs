@@ -2,10 +2,15 @@ use std::cmp::{Eq, PartialEq};
 use std::hash::{Hash, Hasher};
 use std::mem;
 
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
 use doc::{Data, Document, Object, PrimaryData};
 use error::Error;
 use query::Query;
 use sealed::Sealed;
+use value::collections::Equivalent;
+use value::size::{EstimateSize, StructSize};
 use value::{Key, Map, Set, Value};
 use view::Render;
 
@@ -19,12 +24,15 @@ use view::Render;
 /// [equality]: ./struct.Object.html#equality
 /// [hashing]: ./struct.Object.html#hashing
 /// [resource identifier objects]: https://goo.gl/vgfzru
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug)]
 pub struct Identifier {
     /// A string that contains a unique identfier for this resource type (`kind`). For
     /// more information, check out the *[identification]* section of the JSON API
     /// specification.
     ///
+    /// The value of this field is empty if the identifier only has a `lid`. An
+    /// identifier that is deserialized must contain an `id`, a `lid`, or both.
+    ///
     /// [identification]: https://goo.gl/3s681i
     pub id: String,
 
@@ -34,19 +42,23 @@ pub struct Identifier {
     /// specification.
     ///
     /// [identification]: https://goo.gl/3s681i
-    #[serde(rename = "type")]
     pub kind: Key,
 
+    /// A local id that refers to a resource created by a previous operation in the same
+    /// [atomic operations] document. Local ids were added in version 1.1 of the
+    /// JSON API specification.
+    ///
+    /// [atomic operations]: https://jsonapi.org/ext/atomic/
+    pub lid: Option<String>,
+
     /// Non-standard meta information. If this value of this field is empty, it will not
     /// be serialized. For more information, check out the *[meta information]* section
     /// of the JSON API specification.
     ///
     /// [meta information]: https://goo.gl/LyrGF8
-    #[serde(default, skip_serializing_if = "Map::is_empty")]
     pub meta: Map,
 
     /// Private field for backwards compatibility.
-    #[serde(skip)]
     _ext: (),
 }
 
@@ -74,10 +86,45 @@ impl Identifier {
         Identifier {
             id,
             kind,
+            lid: None,
             meta: Default::default(),
             _ext: (),
         }
     }
+
+    /// Returns a new `Identifier` that refers to a resource by its local id.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::Identifier;
+    ///
+    /// let ident = Identifier::local("authors".parse()?, "a".to_owned());
+    /// assert_eq!(ident.lid, Some("a".to_owned()));
+    /// assert!(ident.id.is_empty());
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn local(kind: Key, lid: String) -> Self {
+        let mut ident = Identifier::new(kind, String::new());
+
+        ident.lid = Some(lid);
+        ident
+    }
+
+    /// Returns `false` if the `id` of the identifier is omitted in favor of its `lid`.
+    fn has_id(&self) -> bool {
+        !self.id.is_empty() || self.lid.is_none()
+    }
 }
 
 impl Eq for Identifier {}
@@ -94,7 +141,10 @@ impl From<Object> for Identifier {
 
 impl<'a> From<&'a Object> for Identifier {
     fn from(object: &'a Object) -> Self {
-        object.clone().into()
+        let mut ident = Identifier::new(object.kind.clone(), object.id.clone());
+
+        ident.meta = object.meta.clone();
+        ident
     }
 }
 
@@ -107,13 +157,98 @@ impl Hash for Identifier {
 
 impl PartialEq for Identifier {
     fn eq(&self, rhs: &Identifier) -> bool {
-        self.id == rhs.id && self.kind == rhs.kind
+        self.id == rhs.id && self.kind == rhs.kind && self.lid == rhs.lid
     }
 }
 
 impl PartialEq<Object> for Identifier {
     fn eq(&self, rhs: &Object) -> bool {
-        self.id == rhs.id && self.kind == rhs.kind
+        self.id == rhs.id && self.kind == rhs.kind && self.lid.is_none()
+    }
+}
+
+impl Equivalent<Object> for Identifier {
+    fn equivalent(&self, key: &Object) -> bool {
+        self == key
+    }
+}
+
+impl<'de> Deserialize<'de> for Identifier {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct RawIdentifier {
+            id: Option<String>,
+            #[serde(rename = "type")]
+            kind: Key,
+            lid: Option<String>,
+            #[serde(default)]
+            meta: Map,
+        }
+
+        let raw = RawIdentifier::deserialize(deserializer)?;
+        let id = match (raw.id, raw.lid.is_some()) {
+            (Some(id), _) => id,
+            (None, true) => String::new(),
+            (None, false) => return Err(de::Error::missing_field("id")),
+        };
+
+        Ok(Identifier {
+            id,
+            kind: raw.kind,
+            lid: raw.lid,
+            meta: raw.meta,
+            _ext: (),
+        })
+    }
+}
+
+impl EstimateSize for Identifier {
+    fn estimated_size(&self) -> usize {
+        let mut state = StructSize::new();
+
+        if self.has_id() {
+            state.field("id", &self.id);
+        }
+
+        state.field("type", &self.kind);
+
+        if let Some(ref lid) = self.lid {
+            state.field("lid", lid);
+        }
+
+        if !self.meta.is_empty() {
+            state.field("meta", &self.meta);
+        }
+
+        state.end()
+    }
+}
+
+impl Serialize for Identifier {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Identifier", 4)?;
+
+        if self.has_id() {
+            state.serialize_field("id", &self.id)?;
+        }
+
+        state.serialize_field("type", &self.kind)?;
+
+        if let Some(ref lid) = self.lid {
+            state.serialize_field("lid", lid)?;
+        }
+
+        if !self.meta.is_empty() {
+            state.serialize_field("meta", &self.meta)?;
+        }
+
+        state.end()
     }
 }
 
@@ -143,13 +278,221 @@ impl Render<Identifier> for Vec<Identifier> {
     }
 }
 
+impl Identifier {
+    /// Returns the flattened representation of an identifier that could not be
+    /// resolved against the included resources of a document.
+    pub(crate) fn into_value(self, typed: bool) -> Value {
+        if !typed {
+            return match self.lid {
+                Some(lid) if self.id.is_empty() => Value::String(lid),
+                _ => Value::String(self.id),
+            };
+        }
+
+        let mut map = Map::with_capacity(3);
+
+        if !self.id.is_empty() {
+            map.insert(Key::from_raw("id".to_owned()), Value::String(self.id));
+        }
+
+        map.insert(Key::from_raw("type".to_owned()), Value::String(self.kind.into()));
+
+        if let Some(lid) = self.lid {
+            map.insert(Key::from_raw("lid".to_owned()), Value::String(lid));
+        }
+
+        Value::Object(map)
+    }
+}
+
 impl PrimaryData for Identifier {
-    fn flatten(self, incl: &Set<Object>) -> Value {
-        incl.into_iter()
-            .find(|item| self == **item)
-            .map(|item| item.clone().flatten(incl))
-            .unwrap_or_else(|| self.id.clone().into())
+    fn flatten(self, incl: &Set<Object>, typed: bool) -> Value {
+        match incl.get(&self) {
+            Some(item) => item.clone().flatten(incl, typed),
+            None => self.into_value(typed),
+        }
+    }
+
+    fn resolve(&self, incl: &Set<Object>) -> Result<(), Error> {
+        match incl.get(self) {
+            Some(item) => item.resolve(incl),
+            None => Ok(()),
+        }
     }
 }
 
 impl Sealed for Identifier {}
+
+/// A borrowed resource identifier that can be used to look up an [`Object`] or an
+/// [`Identifier`] in a [`Set`] without allocating.
+///
+/// `IdentRef` shares its [equality] and [hashing] behavior with [`Object`].
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::{IdentRef, Object};
+/// use json_api::value::{Key, Set};
+///
+/// let kind = "users".parse::<Key>()?;
+/// let mut included = Set::new();
+///
+/// included.insert(Object::new(kind.clone(), "1".to_owned()));
+///
+/// assert!(included.get(&IdentRef::new(&kind, "1")).is_some());
+/// assert!(included.get(&IdentRef::new(&kind, "2")).is_none());
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`Identifier`]: ./struct.Identifier.html
+/// [`Object`]: ./struct.Object.html
+/// [`Set`]: ../value/struct.Set.html
+/// [equality]: ./struct.Object.html#equality
+/// [hashing]: ./struct.Object.html#hashing
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct IdentRef<'a> {
+    /// The type of the referenced resource.
+    pub kind: &'a Key,
+
+    /// The id of the referenced resource.
+    pub id: &'a str,
+
+    /// Private field for backwards compatibility.
+    _ext: (),
+}
+
+impl<'a> IdentRef<'a> {
+    /// Returns a new `IdentRef`.
+    pub fn new(kind: &'a Key, id: &'a str) -> Self {
+        IdentRef { kind, id, _ext: () }
+    }
+}
+
+impl<'a> From<&'a Identifier> for IdentRef<'a> {
+    fn from(ident: &'a Identifier) -> Self {
+        IdentRef::new(&ident.kind, &ident.id)
+    }
+}
+
+impl<'a> From<&'a Object> for IdentRef<'a> {
+    fn from(object: &'a Object) -> Self {
+        IdentRef::new(&object.kind, &object.id)
+    }
+}
+
+impl<'a> Hash for IdentRef<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.kind.hash(state);
+    }
+}
+
+impl<'a> Equivalent<Identifier> for IdentRef<'a> {
+    fn equivalent(&self, key: &Identifier) -> bool {
+        self.id == key.id && *self.kind == key.kind
+    }
+}
+
+impl<'a> Equivalent<Object> for IdentRef<'a> {
+    fn equivalent(&self, key: &Object) -> bool {
+        self.id == key.id && *self.kind == key.kind
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use doc::{self, Data, Document, Identifier, Object, Relationship};
+    use value::{Key, Set, Value};
+
+    use super::IdentRef;
+
+    #[test]
+    fn ident_id_or_lid() {
+        let e = serde_json::from_str::<Identifier>(r#"{"type":"people"}"#).unwrap_err();
+        assert!(e.to_string().starts_with("missing field `id`"));
+
+        let source = r#"{"type":"people","lid":"a"}"#;
+        let ident = serde_json::from_str::<Identifier>(source).unwrap();
+
+        assert!(ident.id.is_empty());
+        assert_eq!(ident.lid, Some("a".to_owned()));
+        assert_eq!(serde_json::to_string(&ident).unwrap(), source);
+
+        let source = r#"{"id":"1","type":"people","lid":"a"}"#;
+        let ident = serde_json::from_str::<Identifier>(source).unwrap();
+        assert_eq!(serde_json::to_string(&ident).unwrap(), source);
+
+        let ident = Identifier::new("people".parse().unwrap(), String::new());
+        assert_eq!(serde_json::to_string(&ident).unwrap(), r#"{"id":"","type":"people"}"#);
+    }
+
+    #[test]
+    fn ident_ref_lookup() {
+        let kind = "users".parse::<Key>().unwrap();
+        let ident = Identifier::new(kind.clone(), "1".to_owned());
+        let mut idents = Set::new();
+        let mut objects = Set::new();
+
+        idents.insert(ident.clone());
+        objects.insert(Object::new(kind.clone(), "1".to_owned()));
+
+        assert_eq!(idents.get(&IdentRef::new(&kind, "1")), Some(&ident));
+        assert_eq!(idents.get(&IdentRef::new(&kind, "2")), None);
+        assert_eq!(objects.get(&IdentRef::from(&ident)).map(|o| &o.id), Some(&ident.id));
+        assert_eq!(objects.get(&ident).map(|o| &o.id), Some(&ident.id));
+
+        let other = "posts".parse::<Key>().unwrap();
+        assert!(objects.get(&IdentRef::new(&other, "1")).is_none());
+    }
+
+    #[test]
+    fn flatten_large_included() {
+        let kind = "comments".parse::<Key>().unwrap();
+        let count = 10_000;
+
+        let included = (0..count)
+            .map(|n| {
+                let mut object = Object::new(kind.clone(), n.to_string());
+                object.attributes.insert("body".parse().unwrap(), format!("#{}", n).into());
+                object
+            })
+            .collect::<Set<Object>>();
+
+        let data = (0..count)
+            .rev()
+            .map(|n| Identifier::new(kind.clone(), n.to_string()))
+            .collect();
+
+        let mut object = Object::new("posts".parse().unwrap(), "1".to_owned());
+        let comments = Relationship::new(Data::Collection(data));
+
+        object.relationships.insert("comments".parse().unwrap(), comments);
+
+        let doc = Document::Ok {
+            data: Data::Member(Box::new(Some(object))),
+            included,
+            jsonapi: Default::default(),
+            links: Default::default(),
+            meta: Default::default(),
+        };
+
+        let value = doc::from_doc::<_, Value>(doc).unwrap();
+        let comments = value.pointer("/comments").and_then(Value::as_array).unwrap();
+
+        assert_eq!(comments.len(), count);
+        assert_eq!(comments[0].pointer("/body"), Some(&"#9999".into()));
+        assert_eq!(comments[count - 1].pointer("/body"), Some(&"#0".into()));
+    }
+}
@@ -1,19 +1,36 @@
+use std::borrow::Cow;
 use std::cmp::{Eq, PartialEq};
 use std::fmt::{self, Display, Formatter};
 use std::hash::{Hash, Hasher};
-use std::ops::Deref;
 use std::str::FromStr;
 
 use http::Uri;
+use percent_encoding::{utf8_percent_encode, QUERY_ENCODE_SET};
 use serde::de::{self, Deserialize, Deserializer, MapAccess, Visitor};
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 
-use error::Error;
-use value::Map;
+use doc::{InvalidLinks, Pointer};
+use error::{Error, ErrorKind};
+use query::{self, Page, Query};
+use value::size::{EstimateSize, StructSize};
+use value::{self, Key, Map, Value};
+
+define_encode_set! {
+    /// Characters that must be encoded in the key or value of a query parameter.
+    pub PARAM_ENCODE_SET = [QUERY_ENCODE_SET] | {'%', '&', '+', '=', '[', ']'}
+}
 
 /// A data structure containing a URL. Can be deserialized from either a string or link
 /// object.
 ///
+/// Parsing a link with `FromStr` requires the `href` to be a valid [`Uri`]. Links that
+/// contain a relative reference (i.e `../comments`) or a URI template (i.e
+/// `/articles/{id}`) can be created with [`parse_relaxed`]. Deserialization uses
+/// [`parse_relaxed`]. A link with an invalid `href` can be deserialized on its own,
+/// but deserializing a [`Document`] that contains one applies [`InvalidLinks`]. The
+/// URI reference of a link is serialized exactly as it was supplied until `href` is
+/// changed and can be accessed with [`reference`].
+///
 /// For more information, check out the *[links]* section of the JSON API specification.
 ///
 /// # Example
@@ -34,35 +51,419 @@ use value::Map;
 /// # }
 /// ```
 ///
+/// [`Document`]: ./enum.Document.html
+/// [`InvalidLinks`]: ./enum.InvalidLinks.html
+/// [`Uri`]: ../http/uri/struct.Uri.html
+/// [`parse_relaxed`]: #method.parse_relaxed
+/// [`reference`]: #method.reference
 /// [links]: https://goo.gl/E4E6Vt
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct Link {
-    /// The link’s URI.
-    pub href: Uri,
+    /// The link’s URI reference.
+    ///
+    /// Prior to version 0.5, the type of this field was `Uri`. Links that only contain
+    /// a `Uri` can be matched with [`Href::Uri`] or accessed with [`uri`].
+    ///
+    /// [`Href::Uri`]: ./enum.Href.html#variant.Uri
+    /// [`uri`]: #method.uri
+    pub href: Href,
 
-    /// Non-standard meta information. If this value of this field is empty, the link
-    /// will be serialized as a string containing the contents of `href`. For more
-    /// information, check out the *[meta information]* section of the JSON API
+    /// A link to a description document (i.e OpenAPI or JSON Schema) for the link’s
+    /// target. Introduced in version 1.1 of the JSON API specification.
+    pub describedby: Option<Box<Link>>,
+
+    /// The language(s) of the link’s target. A single language tag is serialized as a
+    /// string. Introduced in version 1.1 of the JSON API specification.
+    pub hreflang: Vec<String>,
+
+    /// The media type of the link’s target. This field is derived from the `type`
+    /// member if the link is deserialized. Introduced in version 1.1 of the JSON API
     /// specification.
+    pub kind: Option<String>,
+
+    /// Non-standard meta information. If this value of this field and all of the other
+    /// optional members of the link are empty, the link will be serialized as a string
+    /// containing the contents of `href`. For more information, check out the *[meta
+    /// information]* section of the JSON API specification.
     ///
     /// [meta information]: https://goo.gl/LyrGF8
     pub meta: Map,
 
+    /// The link’s relation type. Introduced in version 1.1 of the JSON API
+    /// specification.
+    pub rel: Option<String>,
+
+    /// A human-readable label for the link’s target. Introduced in version 1.1 of the
+    /// JSON API specification.
+    pub title: Option<String>,
+
+    /// The `Uri` that `href` was parsed to and the string it was parsed from. Used to
+    /// serialize `href` exactly as it was supplied until it is changed.
+    verbatim: Option<(Uri, String)>,
+
+    /// The reason that `href` is invalid if the link was deserialized while invalid
+    /// links were being recorded.
+    invalid: Option<String>,
+
     /// Private field for backwards compatibility.
     _ext: (),
 }
 
-impl Deref for Link {
-    type Target = Uri;
+impl Link {
+    /// Returns a builder that can be used to create a new link with the given `href`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::Link;
+    ///
+    /// let link = Link::builder("/articles")
+    ///     .param("page[number]", "2")
+    ///     .meta("count", 42)
+    ///     .build()?;
+    ///
+    /// assert_eq!(link, "/articles?page%5Bnumber%5D=2");
+    /// assert_eq!(link.meta.get("count"), Some(&42.into()));
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn builder<T>(href: T) -> LinkBuilder
+    where
+        T: Into<String>,
+    {
+        LinkBuilder::new(href.into())
+    }
+
+    /// Attempts to parse a link that may contain a relative reference or a URI
+    /// template. Returns an error if `value` contains characters that are not allowed
+    /// in a URI reference or if a template expression is not closed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::Link;
+    ///
+    /// let link = Link::parse_relaxed("/articles/{id}")?;
+    ///
+    /// assert!(link.is_template());
+    /// assert!(link.uri().is_none());
+    /// assert_eq!(link, "/articles/{id}");
+    ///
+    /// assert!("/articles/{id}".parse::<Link>().is_err());
+    /// assert!(Link::parse_relaxed("/articles/{id").is_err());
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn parse_relaxed(value: &str) -> Result<Self, Error> {
+        match Href::parse(value) {
+            Ok(href) => Ok(Link::from_parts(value, href)),
+            Err(reason) => Err(Error::invalid_link(value, None, &reason)),
+        }
+    }
+
+    /// Parses a link with [`parse_relaxed`] for deserialization. If `href` is invalid,
+    /// the link is returned as-is along with the reason that it is invalid. The
+    /// reason is reported when the links of the document that contains the link are
+    /// checked.
+    ///
+    /// [`parse_relaxed`]: #method.parse_relaxed
+    fn deserialize_href(href: String) -> Self {
+        match Href::parse(&href) {
+            Ok(value) => Link::from_parts(&href, value),
+            Err(reason) => {
+                let mut link = Link::from_parts(&href, Href::Reference(href.clone()));
+
+                link.invalid = Some(reason);
+                link
+            }
+        }
+    }
+
+    /// Applies `policy` to the link if it was invalid when it was deserialized.
+    /// Returns `false` if the link should be removed.
+    pub(crate) fn check(
+        &mut self,
+        pointer: &Pointer,
+        policy: InvalidLinks,
+    ) -> Result<bool, Error> {
+        if let Some(mut describedby) = self.describedby.take() {
+            let mut pointer = pointer.clone();
+
+            pointer.push("describedby");
 
-    fn deref(&self) -> &Self::Target {
-        &self.href
+            if describedby.check(&pointer, policy)? {
+                self.describedby = Some(describedby);
+            }
+        }
+
+        let reason = match self.invalid.take() {
+            Some(reason) => reason,
+            None => return Ok(true),
+        };
+
+        match policy {
+            InvalidLinks::Error => {
+                Err(Error::invalid_link(&self.reference(), Some(pointer), &reason))
+            }
+            InvalidLinks::Drop => Ok(false),
+            InvalidLinks::Raw => Ok(true),
+        }
+    }
+
+    fn from_parts(raw: &str, href: Href) -> Self {
+        let verbatim = match href {
+            Href::Uri(ref uri) => Some((uri.clone(), raw.to_owned())),
+            Href::Reference(_) | Href::Template(_) => None,
+        };
+
+        Link {
+            href,
+            verbatim,
+            invalid: None,
+            describedby: Default::default(),
+            hreflang: Default::default(),
+            kind: Default::default(),
+            meta: Default::default(),
+            rel: Default::default(),
+            title: Default::default(),
+            _ext: (),
+        }
+    }
+
+    /// Returns `true` if the link can be serialized as a string containing the
+    /// contents of `href`.
+    fn is_bare(&self) -> bool {
+        self.describedby.is_none() && self.hreflang.is_empty() && self.kind.is_none()
+            && self.meta.is_empty() && self.rel.is_none() && self.title.is_none()
+    }
+
+    /// Returns the link’s URI reference. A reference that was parsed as a `Uri` is
+    /// returned exactly as it was supplied unless the `href` field has been changed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::Link;
+    ///
+    /// let mut link = Link::parse_relaxed("https://example.com")?;
+    /// assert_eq!(link.reference(), "https://example.com");
+    ///
+    /// link.href = "../comments".parse()?;
+    /// assert_eq!(link.reference(), "../comments");
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn reference(&self) -> Cow<'_, str> {
+        match (&self.href, &self.verbatim) {
+            (&Href::Uri(ref uri), &Some((ref parsed, ref raw))) if uri == parsed => {
+                Cow::Borrowed(raw)
+            }
+            (&Href::Uri(ref uri), _) => Cow::Owned(uri.to_string()),
+            (&Href::Reference(ref value), _) | (&Href::Template(ref value), _) => {
+                Cow::Borrowed(value)
+            }
+        }
+    }
+
+    /// Returns `true` if the link’s URI reference contains a URI template expression.
+    pub fn is_template(&self) -> bool {
+        self.href.is_template()
+    }
+
+    /// Optionally returns the `Uri` of the link. Returns `None` if `href` is a relative
+    /// reference or a URI template that cannot be represented as a `Uri`.
+    pub fn uri(&self) -> Option<&Uri> {
+        self.href.uri()
+    }
+}
+
+/// The URI reference of a [`Link`]. Parsing an `Href` with `FromStr` accepts relative
+/// references and URI templates in addition to a `Uri`.
+///
+/// [`Link`]: ./struct.Link.html
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Href {
+    /// A reference that can be represented as a `Uri` (i.e `https://example.com` or
+    /// `/articles/1`).
+    Uri(Uri),
+
+    /// A reference that cannot be represented as a `Uri` (i.e `../comments`). Links
+    /// that are kept with [`InvalidLinks::Raw`] also use this variant.
+    ///
+    /// [`InvalidLinks::Raw`]: ./enum.InvalidLinks.html#variant.Raw
+    Reference(String),
+
+    /// A reference that contains a URI template expression (i.e `/articles/{id}`).
+    Template(String),
+}
+
+impl Href {
+    /// Returns `true` if the reference contains a URI template expression.
+    pub fn is_template(&self) -> bool {
+        match *self {
+            Href::Template(_) => true,
+            Href::Uri(_) | Href::Reference(_) => false,
+        }
+    }
+
+    /// Optionally returns the `Uri` of the reference. Returns `None` if the reference
+    /// cannot be represented as a `Uri`.
+    pub fn uri(&self) -> Option<&Uri> {
+        match *self {
+            Href::Uri(ref uri) => Some(uri),
+            Href::Reference(_) | Href::Template(_) => None,
+        }
+    }
+
+    /// Parses `value` as a `Uri`, relative reference, or URI template. Returns the
+    /// reason that `value` is invalid if it is none of them.
+    fn parse(value: &str) -> Result<Self, String> {
+        check_href(value)?;
+
+        if value.contains('{') {
+            return Ok(Href::Template(value.to_owned()));
+        }
+
+        Ok(match value.parse() {
+            Ok(uri) => Href::Uri(uri),
+            Err(_) => Href::Reference(value.to_owned()),
+        })
+    }
+}
+
+impl Display for Href {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Href::Uri(ref uri) => Display::fmt(uri, f),
+            Href::Reference(ref value) | Href::Template(ref value) => f.write_str(value),
+        }
+    }
+}
+
+impl From<Uri> for Href {
+    fn from(uri: Uri) -> Self {
+        Href::Uri(uri)
+    }
+}
+
+impl FromStr for Href {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Href::parse(value).map_err(|reason| Error::invalid_link(value, None, &reason))
+    }
+}
+
+/// Applies `policy` to each link in `links` that was invalid when it was deserialized.
+/// The `pointer` references `links` in the document.
+pub(crate) fn check_links(
+    links: &mut Map<Key, Link>,
+    pointer: &Pointer,
+    policy: InvalidLinks,
+) -> Result<(), Error> {
+    let mut result = Ok(());
+
+    links.retain(|key, link| {
+        if result.is_err() {
+            return true;
+        }
+
+        let mut pointer = pointer.clone();
+
+        pointer.push(key);
+        link.check(&pointer, policy).unwrap_or_else(|e| {
+            result = Err(e);
+            true
+        })
+    });
+
+    result
+}
+
+/// Applies `policy` to each link in an array of links, such as the `ext` member of a
+/// JSON API object. The `pointer` references `links` in the document.
+pub(crate) fn check_list(
+    links: &mut Vec<Link>,
+    pointer: &Pointer,
+    policy: InvalidLinks,
+) -> Result<(), Error> {
+    let items = links.drain(..).collect::<Vec<_>>();
+
+    for (index, mut link) in items.into_iter().enumerate() {
+        let mut pointer = pointer.clone();
+
+        pointer.push_index(index);
+
+        if link.check(&pointer, policy)? {
+            links.push(link);
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the reason that `value` is not a valid URI reference or URI template.
+fn check_href(value: &str) -> Result<(), String> {
+    let mut in_expr = false;
+
+    for c in value.chars() {
+        match c {
+            '{' if in_expr => return Err("nested template expression".to_owned()),
+            '}' if !in_expr => return Err("unopened template expression".to_owned()),
+            '{' | '}' => in_expr = !in_expr,
+            '"' | '<' | '>' | '\\' | '^' | '`' | '|' => {
+                return Err(format!("invalid uri character '{}'", c))
+            }
+            _ if c.is_whitespace() || c.is_control() => {
+                return Err(format!("invalid uri character {:?}", c))
+            }
+            _ => {}
+        }
+    }
+
+    if in_expr {
+        return Err("unclosed template expression".to_owned());
+    }
+
+    Ok(())
+}
+
+impl Default for Link {
+    fn default() -> Self {
+        Link::from_parts("/", Href::Uri(Default::default()))
     }
 }
 
 impl Display for Link {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        self.href.fmt(f)
+        f.write_str(&self.reference())
     }
 }
 
@@ -72,11 +473,13 @@ impl FromStr for Link {
     type Err = Error;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
-        Ok(Link {
-            href: value.parse()?,
-            meta: Default::default(),
-            _ext: (),
-        })
+        match value.parse() {
+            Ok(uri) => Ok(Link::from_parts(value, Href::Uri(uri))),
+            Err(e) => {
+                let reason = e.to_string();
+                Err(Error::with_chain(e, ErrorKind::invalid_link(value, None, &reason)))
+            }
+        }
     }
 }
 
@@ -94,13 +497,13 @@ impl PartialEq for Link {
 
 impl PartialEq<Uri> for Link {
     fn eq(&self, rhs: &Uri) -> bool {
-        self.href == *rhs
+        self.uri().map_or(false, |uri| uri == rhs)
     }
 }
 
 impl<'a> PartialEq<&'a str> for Link {
     fn eq(&self, other: &&'a str) -> bool {
-        self.href == *other
+        self.reference() == *other
     }
 }
 
@@ -118,8 +521,22 @@ impl<'de> Deserialize<'de> for Link {
         #[derive(Deserialize)]
         #[serde(field_identifier, rename_all = "lowercase")]
         enum Field {
+            Describedby,
             Href,
+            Hreflang,
             Meta,
+            Rel,
+            Title,
+            Type,
+            #[serde(other)]
+            Other,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Hreflang {
+            One(String),
+            Many(Vec<String>),
         }
 
         struct LinkVisitor;
@@ -135,39 +552,85 @@ impl<'de> Deserialize<'de> for Link {
             where
                 E: de::Error,
             {
-                value.parse().map_err(de::Error::custom)
+                Ok(Link::deserialize_href(value.to_owned()))
             }
 
             fn visit_map<V>(self, mut map: V) -> Result<Link, V::Error>
             where
                 V: MapAccess<'de>,
             {
-                let mut href = None;
+                let mut describedby = None;
+                let mut href = None::<String>;
+                let mut hreflang = None;
+                let mut kind = None;
                 let mut meta = None;
+                let mut rel = None;
+                let mut title = None;
 
                 while let Some(key) = map.next_key()? {
                     match key {
+                        Field::Describedby if describedby.is_some() => {
+                            return Err(de::Error::duplicate_field("describedby"))
+                        }
                         Field::Href if href.is_some() => {
                             return Err(de::Error::duplicate_field("href"))
                         }
+                        Field::Hreflang if hreflang.is_some() => {
+                            return Err(de::Error::duplicate_field("hreflang"))
+                        }
                         Field::Meta if meta.is_some() => {
                             return Err(de::Error::duplicate_field("meta"))
                         }
+                        Field::Rel if rel.is_some() => {
+                            return Err(de::Error::duplicate_field("rel"))
+                        }
+                        Field::Title if title.is_some() => {
+                            return Err(de::Error::duplicate_field("title"))
+                        }
+                        Field::Type if kind.is_some() => {
+                            return Err(de::Error::duplicate_field("type"))
+                        }
+                        Field::Describedby => {
+                            describedby = Some(Box::new(map.next_value()?));
+                        }
                         Field::Href => {
-                            let next = map.next_value::<String>()?;
-                            href = Some(next.parse().map_err(de::Error::custom)?);
+                            href = Some(map.next_value()?);
+                        }
+                        Field::Hreflang => {
+                            hreflang = Some(match map.next_value()? {
+                                Hreflang::One(value) => vec![value],
+                                Hreflang::Many(value) => value,
+                            });
                         }
                         Field::Meta => {
                             meta = Some(map.next_value()?);
                         }
+                        Field::Rel => {
+                            rel = Some(map.next_value()?);
+                        }
+                        Field::Title => {
+                            title = Some(map.next_value()?);
+                        }
+                        Field::Type => {
+                            kind = Some(map.next_value()?);
+                        }
+                        Field::Other => {
+                            map.next_value::<de::IgnoredAny>()?;
+                        }
                     }
                 }
 
-                Ok(Link {
-                    href: href.ok_or_else(|| de::Error::missing_field("href"))?,
-                    meta: meta.unwrap_or_default(),
-                    _ext: (),
-                })
+                let href = href.ok_or_else(|| de::Error::missing_field("href"))?;
+                let mut link = Link::deserialize_href(href);
+
+                link.describedby = describedby;
+                link.hreflang = hreflang.unwrap_or_default();
+                link.kind = kind;
+                link.meta = meta.unwrap_or_default();
+                link.rel = rel;
+                link.title = title;
+
+                Ok(link)
             }
         }
 
@@ -175,23 +638,437 @@ impl<'de> Deserialize<'de> for Link {
     }
 }
 
+impl EstimateSize for Link {
+    fn estimated_size(&self) -> usize {
+        let href = self.reference();
+
+        if self.is_bare() {
+            return href.estimated_size();
+        }
+
+        let mut state = StructSize::new();
+
+        state.field("href", &*href);
+
+        if let Some(ref rel) = self.rel {
+            state.field("rel", rel);
+        }
+
+        if let Some(ref describedby) = self.describedby {
+            state.field("describedby", describedby);
+        }
+
+        if let Some(ref title) = self.title {
+            state.field("title", title);
+        }
+
+        if let Some(ref kind) = self.kind {
+            state.field("type", kind);
+        }
+
+        match self.hreflang.len() {
+            0 => {}
+            1 => {
+                state.field("hreflang", &self.hreflang[0]);
+            }
+            _ => {
+                state.field("hreflang", &self.hreflang);
+            }
+        }
+
+        if !self.meta.is_empty() {
+            state.field("meta", &self.meta);
+        }
+
+        state.end()
+    }
+}
+
 impl Serialize for Link {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let href = self.href.to_string();
-        let meta = &self.meta;
+        let href = self.reference();
+
+        if self.is_bare() {
+            return serializer.serialize_str(&href);
+        }
+
+        let mut state = serializer.serialize_struct("Link", 7)?;
+
+        state.serialize_field("href", &href)?;
+
+        if let Some(ref rel) = self.rel {
+            state.serialize_field("rel", rel)?;
+        }
+
+        if let Some(ref describedby) = self.describedby {
+            state.serialize_field("describedby", describedby)?;
+        }
+
+        if let Some(ref title) = self.title {
+            state.serialize_field("title", title)?;
+        }
+
+        if let Some(ref kind) = self.kind {
+            state.serialize_field("type", kind)?;
+        }
+
+        match self.hreflang.len() {
+            0 => {}
+            1 => state.serialize_field("hreflang", &self.hreflang[0])?,
+            _ => state.serialize_field("hreflang", &self.hreflang)?,
+        }
+
+        if !self.meta.is_empty() {
+            state.serialize_field("meta", &self.meta)?;
+        }
+
+        state.end()
+    }
+}
+
+/// An implementation of the "builder pattern" that can be used to construct a
+/// new `Link`.
+///
+/// Query parameters are appended to the `href` in the order they are supplied. The
+/// first error that occurs is returned when [`build`] is called.
+///
+/// [`build`]: #method.build
+#[derive(Debug)]
+pub struct LinkBuilder {
+    href: String,
+    meta: Vec<(String, Result<Value, Error>)>,
+    params: Vec<Result<String, Error>>,
+}
+
+impl LinkBuilder {
+    fn new(href: String) -> Self {
+        LinkBuilder {
+            href,
+            meta: Default::default(),
+            params: Default::default(),
+        }
+    }
+
+    /// Attempt to construct a new link from the previously supplied values.
+    pub fn build(&mut self) -> Result<Link, Error> {
+        let mut href = self.href.clone();
+        let fragment = href.find('#').map(|index| href.split_off(index));
+
+        for param in self.params.drain(..) {
+            let param = param?;
+
+            if param.is_empty() {
+                continue;
+            }
+
+            if !href.ends_with('?') && !href.ends_with('&') {
+                href.push(if href.contains('?') { '&' } else { '?' });
+            }
+
+            href.push_str(&param);
+        }
+
+        if let Some(fragment) = fragment {
+            href.push_str(&fragment);
+        }
+
+        let mut link = href.parse::<Link>()?;
+
+        for (key, value) in self.meta.drain(..) {
+            link.meta.insert(key.parse()?, value?);
+        }
+
+        Ok(link)
+    }
+
+    /// Adds a member to the meta information of the link. An error is returned by
+    /// [`build`] if `key` is not a valid member name or if `value` cannot be
+    /// serialized.
+    ///
+    /// [`build`]: #method.build
+    pub fn meta<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: Into<String>,
+        V: Serialize,
+    {
+        self.meta.push((key.into(), value::to_value(value)));
+        self
+    }
+
+    /// Appends a query parameter to the `href` of the link. The `key` and `value` are
+    /// percent encoded, and parameters are appended in the order they are supplied.
+    pub fn param<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let key = utf8_percent_encode(key.as_ref(), PARAM_ENCODE_SET);
+        let value = utf8_percent_encode(value.as_ref(), PARAM_ENCODE_SET);
+
+        self.params.push(Ok(format!("{}={}", key, value)));
+        self
+    }
+
+    /// Appends the parameters of a [`Query`] to the `href` of the link. An error is
+    /// returned by [`build`] if the query cannot be encoded.
+    ///
+    /// [`build`]: #method.build
+    /// [`Query`]: ../query/struct.Query.html
+    pub fn query(&mut self, value: &Query) -> &mut Self {
+        self.params.push(query::to_string(value));
+        self
+    }
+}
+
+/// Returns `first`, `last`, and when applicable, `prev` and `next` links for the given
+/// `page` of a collection that contains `total` items.
+///
+/// Each link is created by appending `query` to `base` with the pagination parameters
+/// of `query` replaced. If the size of `page` is `None`, the entire collection is
+/// considered to be one page.
+pub(crate) fn paginate(
+    base: &str,
+    query: &Query,
+    page: Page,
+    total: u64,
+) -> Vec<(&'static str, Result<Link, Error>)> {
+    let size = page.size.unwrap_or(total);
+    let last = match size {
+        0 => 1,
+        _ => (total / size + u64::from(total % size != 0)).max(1),
+    };
+
+    let link = |number| {
+        let mut query = query.clone();
+
+        query.page = Some(Page::new(number, page.size));
+        Link::builder(base).query(&query).build()
+    };
+
+    let mut links = vec![("first", link(1))];
+
+    if page.number > 1 {
+        links.push(("prev", link((page.number - 1).min(last))));
+    }
+
+    if page.number < last {
+        links.push(("next", link(page.number + 1)));
+    }
+
+    links.push(("last", link(last)));
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::{Href, Link};
+    use query::Query;
+
+    const HREFS: &[&str] = &[
+        "https://rust-lang.org",
+        "https://example.com/articles/1",
+        "/articles/1/comments",
+        "../comments",
+        "/articles?page[number]=2&page[size]=10",
+        "?page[number]=2",
+        "/articles/{id}",
+        "https://example.com/articles{?page,sort}",
+    ];
+
+    #[test]
+    fn link_absolute() {
+        let link = "https://rust-lang.org".parse::<Link>().unwrap();
+
+        assert!(link.uri().is_some());
+        assert!(!link.is_template());
+        assert_eq!(link.to_string(), "https://rust-lang.org");
+        assert_eq!(link, "https://rust-lang.org");
+    }
 
-        if meta.is_empty() {
-            serializer.serialize_str(&href)
-        } else {
-            let mut state = serializer.serialize_struct("Link", 2)?;
+    #[test]
+    fn link_path_relative() {
+        let link = Link::parse_relaxed("../comments").unwrap();
 
-            state.serialize_field("href", &href)?;
-            state.serialize_field("meta", meta)?;
+        assert!(link.uri().is_none());
+        assert_eq!(link.reference(), "../comments");
+        assert_eq!(link.href, Href::Reference("../comments".to_owned()));
+        assert!("../comments".parse::<Link>().is_err());
+
+        let link = "/articles/1/comments".parse::<Link>().unwrap();
+
+        assert!(link.uri().is_some());
+        assert_eq!(link, "/articles/1/comments");
+    }
+
+    #[test]
+    fn link_href_changed() {
+        let mut link = Link::parse_relaxed("/articles/{id}").unwrap();
+
+        link.href = "/articles/1".parse().unwrap();
+
+        assert!(!link.is_template());
+        assert_eq!(link.href, Href::Uri("/articles/1".parse().unwrap()));
+        assert_eq!(link, "/articles/1");
+        assert_eq!(serde_json::to_string(&link).unwrap(), r#""/articles/1""#);
+
+        let mut link = "https://rust-lang.org".parse::<Link>().unwrap();
+
+        link.href = Href::Uri("/".parse().unwrap());
+        assert_eq!(link, "/");
+        assert_eq!(link.reference(), "/");
+    }
 
-            state.end()
+    #[test]
+    fn link_query() {
+        let link = Link::parse_relaxed("/articles?page[number]=2").unwrap();
+
+        assert_eq!(link.uri().and_then(|uri| uri.query()), Some("page[number]=2"));
+        assert_eq!(link.to_string(), "/articles?page[number]=2");
+    }
+
+    #[test]
+    fn link_template() {
+        let link = Link::parse_relaxed("/articles/{id}").unwrap();
+
+        assert!(link.is_template());
+        assert!(link.uri().is_none());
+        assert_eq!(link, "/articles/{id}");
+
+        assert!(Link::parse_relaxed("/articles/{id").is_err());
+        assert!(Link::parse_relaxed("/articles/id}").is_err());
+        assert!(Link::parse_relaxed("/articles/{{id}}").is_err());
+        assert!(Link::parse_relaxed("/articles/ 1").is_err());
+    }
+
+    #[test]
+    fn link_round_trip() {
+        for href in HREFS {
+            let source = serde_json::to_string(href).unwrap();
+            let link = serde_json::from_str::<Link>(&source).unwrap();
+
+            assert_eq!(link, *href);
+            assert_eq!(link.to_string(), *href);
+            assert_eq!(serde_json::to_string(&link).unwrap(), source);
         }
     }
+
+    #[test]
+    fn link_builder_bare() {
+        let link = Link::builder("/articles/1").build().unwrap();
+
+        assert_eq!(link, "/articles/1");
+        assert!(link.uri().is_some());
+        assert_eq!(serde_json::to_string(&link).unwrap(), r#""/articles/1""#);
+    }
+
+    #[test]
+    fn link_builder_params() {
+        let query = Query::builder().page(2, Some(10)).build().unwrap();
+        let link = Link::builder("/articles?filter[author]=1#top")
+            .param("q", "a&b = c%")
+            .query(&query)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            link,
+            concat!(
+                "/articles?filter[author]=1",
+                "&q=a%26b%20%3D%20c%25",
+                "&page%5Bnumber%5D=2&page%5Bsize%5D=10",
+                "#top",
+            )
+        );
+
+        let link = Link::builder("https://example.com/articles")
+            .query(&Query::new())
+            .param("include", "author")
+            .build()
+            .unwrap();
+
+        assert_eq!(link, "https://example.com/articles?include=author");
+        assert!(Link::builder("/articles/{id}").build().is_err());
+    }
+
+    #[test]
+    fn link_builder_meta() {
+        let link = Link::builder("/articles/1/comments")
+            .meta("count", 42)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&link).unwrap(),
+            r#"{"href":"/articles/1/comments","meta":{"count":42}}"#
+        );
+    }
+
+    #[test]
+    fn link_object_v1() {
+        let source = r#"{"href":"/articles/1","meta":{"count":1}}"#;
+        let link = serde_json::from_str::<Link>(source).unwrap();
+
+        assert_eq!(link, "/articles/1");
+        assert_eq!(link.rel, None);
+        assert!(link.hreflang.is_empty());
+        assert_eq!(serde_json::to_string(&link).unwrap(), source);
+
+        let source = r#"{"href":"/articles/1"}"#;
+        let link = serde_json::from_str::<Link>(source).unwrap();
+
+        assert_eq!(serde_json::to_string(&link).unwrap(), r#""/articles/1""#);
+    }
+
+    #[test]
+    fn link_object_v1_1() {
+        let source = concat!(
+            r#"{"href":"/articles/1","#,
+            r#""rel":"canonical","#,
+            r#""describedby":"/schemas/articles.json","#,
+            r#""title":"An article","#,
+            r#""type":"application/vnd.api+json","#,
+            r#""hreflang":["en","fr"],"#,
+            r#""meta":{"count":1}}"#,
+        );
+        let link = serde_json::from_str::<Link>(source).unwrap();
+
+        assert_eq!(link.rel, Some("canonical".to_owned()));
+        assert_eq!(
+            link.describedby.as_ref().map(|link| link.reference()),
+            Some("/schemas/articles.json".into())
+        );
+        assert_eq!(link.title, Some("An article".to_owned()));
+        assert_eq!(link.kind, Some("application/vnd.api+json".to_owned()));
+        assert_eq!(link.hreflang, vec!["en".to_owned(), "fr".to_owned()]);
+        assert_eq!(serde_json::to_string(&link).unwrap(), source);
+
+        let source = r#"{"href":"/articles/1","hreflang":"en"}"#;
+        let link = serde_json::from_str::<Link>(source).unwrap();
+
+        assert_eq!(link.hreflang, vec!["en".to_owned()]);
+        assert_eq!(serde_json::to_string(&link).unwrap(), source);
+    }
+
+    #[test]
+    fn link_object_unknown_member() {
+        let source = r#"{"href":"/articles/1","anchor":"/articles"}"#;
+        let link = serde_json::from_str::<Link>(source).unwrap();
+
+        assert_eq!(link, "/articles/1");
+    }
+
+    #[test]
+    fn link_object_round_trip() {
+        let source = r#"{"href":"/articles/{id}","meta":{"count":1}}"#;
+        let link = serde_json::from_str::<Link>(source).unwrap();
+
+        assert_eq!(link, "/articles/{id}");
+        assert_eq!(link.meta.len(), 1);
+        assert_eq!(serde_json::to_string(&link).unwrap(), source);
+    }
 }
@@ -0,0 +1,304 @@
+use std::io::Read;
+
+use http::StatusCode;
+
+use doc::{convert, Data, ErrorObject, ErrorSource, Identifier, Pointer};
+use error::Error;
+use value::{Key, Set};
+
+/// The resource linkage of a relationship, such as the body of a request to
+/// `PATCH /articles/1/relationships/tags`.
+///
+/// Use [`validate`] to check the linkage against the [`LinkagePolicy`] of a
+/// relationship before it is applied.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::{Linkage, LinkagePolicy};
+///
+/// let linkage = Linkage::from_slice(br#"{
+///     "data": [
+///         { "type": "tags", "id": "2" },
+///         { "type": "tags", "id": "3" },
+///         { "type": "tags", "id": "2" }
+///     ]
+/// }"#)?;
+///
+/// let mut policy = LinkagePolicy::new();
+///
+/// assert!(linkage.validate(&policy).is_err());
+///
+/// policy.allow_duplicates = true;
+/// linkage.validate(&policy)?;
+///
+/// assert_eq!(linkage.into_unique_ids(), ["2", "3"]);
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`validate`]: #method.validate
+/// [`LinkagePolicy`]: ./struct.LinkagePolicy.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Linkage(Data<Identifier>);
+
+impl Linkage {
+    /// Deserialize the resource linkage of a relationship from bytes of JSON text.
+    ///
+    /// The primary data of the document may be a single resource identifier, `null`,
+    /// or an array of resource identifiers.
+    pub fn from_slice(data: &[u8]) -> Result<Self, Error> {
+        convert::linkage_data(convert::parse_slice(data)?).map(Linkage)
+    }
+
+    /// Deserialize the resource linkage of a relationship from an IO stream of JSON
+    /// text.
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, Error> {
+        convert::linkage_data(convert::parse_reader(reader)?).map(Linkage)
+    }
+
+    /// Returns a reference to the underlying resource linkage.
+    pub fn data(&self) -> &Data<Identifier> {
+        &self.0
+    }
+
+    /// Consumes the `Linkage` and returns the underlying resource linkage.
+    pub fn into_inner(self) -> Data<Identifier> {
+        self.0
+    }
+
+    /// Consumes the `Linkage` and returns the id of each resource identifier. Ids
+    /// that occur more than once are only returned the first time they occur.
+    pub fn into_unique_ids(self) -> Vec<String> {
+        let ids: Set<String> = match self.0 {
+            Data::Collection(idents) => idents.into_iter().map(|ident| ident.id).collect(),
+            Data::Member(ident) => ident.into_iter().map(|ident| ident.id).collect(),
+        };
+
+        ids.into_iter().collect()
+    }
+
+    /// Checks the linkage against `policy`.
+    ///
+    /// If the linkage does not comply with `policy`, an [`ErrorKind::ErrorDocument`]
+    /// is returned with an error for each problem. Each error points to the member of
+    /// the request that caused it:
+    ///
+    /// - A resource identifier with a type that is not allowed results in a `409
+    ///   Conflict` that points to its `type` (e.g. `/data/3/type`).
+    /// - A resource identifier that occurs more than once results in a `422
+    ///   Unprocessable Entity` that points to each repeated occurrence.
+    /// - Linkage that contains more than `max_items` resource identifiers results in
+    ///   a `422 Unprocessable Entity` that points to `/data`.
+    ///
+    /// [`ErrorKind::ErrorDocument`]: ../error/enum.ErrorKind.html#variant.ErrorDocument
+    pub fn validate(&self, policy: &LinkagePolicy) -> Result<(), Error> {
+        let mut errors = Vec::new();
+
+        let idents = match self.0 {
+            Data::Collection(ref idents) => idents
+                .iter()
+                .enumerate()
+                .map(|(index, ident)| {
+                    let mut pointer = Pointer::data();
+
+                    pointer.push_index(index);
+                    (pointer, ident)
+                })
+                .collect(),
+            Data::Member(ref ident) => (**ident)
+                .iter()
+                .map(|ident| (Pointer::data(), ident))
+                .collect::<Vec<_>>(),
+        };
+
+        if let Some(max) = policy.max_items {
+            if idents.len() > max {
+                let detail = format!(
+                    "expected at most {} resource identifier(s), found {}",
+                    max,
+                    idents.len()
+                );
+
+                errors.push(linkage_error(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    Pointer::data(),
+                    detail,
+                ));
+            }
+        }
+
+        let mut seen = Set::with_capacity(idents.len());
+
+        for (pointer, ident) in idents {
+            let allowed = policy.allowed_kinds.is_empty()
+                || policy.allowed_kinds.contains(&ident.kind);
+
+            if !allowed {
+                let mut pointer = pointer.clone();
+                let detail = format!("resources of type '{}' are not allowed", ident.kind);
+
+                pointer.push("type");
+                errors.push(linkage_error(StatusCode::CONFLICT, pointer, detail));
+            }
+
+            if !seen.insert(ident) && !policy.allow_duplicates {
+                let detail = format!(
+                    "duplicate resource identifier {}:{}",
+                    ident.kind, ident.id
+                );
+
+                errors.push(linkage_error(StatusCode::UNPROCESSABLE_ENTITY, pointer, detail));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::error_document(errors))
+        }
+    }
+}
+
+impl From<Data<Identifier>> for Linkage {
+    fn from(data: Data<Identifier>) -> Self {
+        Linkage(data)
+    }
+}
+
+/// Describes the resource linkage that a relationship accepts.
+///
+/// For more information, check out the [`Linkage::validate`] method.
+///
+/// [`Linkage::validate`]: ./struct.Linkage.html#method.validate
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LinkagePolicy {
+    /// Accept linkage that contains the same resource identifier more than once.
+    /// Defaults to `false`.
+    pub allow_duplicates: bool,
+
+    /// The resource types that the relationship accepts. If the set is empty,
+    /// resources of any type are accepted.
+    pub allowed_kinds: Set<Key>,
+
+    /// The maximum number of resource identifiers that the relationship accepts.
+    pub max_items: Option<usize>,
+
+    /// Private field for backwards compatibility.
+    _ext: (),
+}
+
+impl LinkagePolicy {
+    /// Returns a new `LinkagePolicy` that accepts any resource linkage without
+    /// duplicates.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+fn linkage_error(status: StatusCode, pointer: Pointer, detail: String) -> ErrorObject {
+    let mut error = ErrorObject::new(Some(status));
+
+    error.detail = Some(detail);
+    error.source = Some(ErrorSource::from(pointer));
+    error
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Linkage, LinkagePolicy};
+    use doc::ErrorObject;
+    use error::ErrorKind;
+
+    fn linkage(data: &str) -> Linkage {
+        Linkage::from_slice(format!(r#"{{"data":{}}}"#, data).as_bytes()).unwrap()
+    }
+
+    fn errors(linkage: &Linkage, policy: &LinkagePolicy) -> Vec<ErrorObject> {
+        match *linkage.validate(policy).unwrap_err().kind() {
+            ErrorKind::ErrorDocument(ref errors) => errors.clone(),
+            ref kind => panic!("unexpected error {:?}", kind),
+        }
+    }
+
+    fn summary(errors: &[ErrorObject]) -> Vec<(u16, String)> {
+        errors
+            .iter()
+            .map(|error| {
+                let status = error.status.unwrap().as_u16();
+                let pointer = error.source.as_ref().unwrap().pointer.clone().unwrap();
+
+                (status, pointer.to_string())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn linkage_duplicates() {
+        let linkage = linkage(
+            r#"[
+                {"type": "tags", "id": "1"},
+                {"type": "tags", "id": "2"},
+                {"type": "tags", "id": "1"},
+                {"type": "tags", "id": "1"}
+            ]"#,
+        );
+        let mut policy = LinkagePolicy::new();
+
+        assert_eq!(
+            summary(&errors(&linkage, &policy)),
+            [(422, "/data/2".to_owned()), (422, "/data/3".to_owned())]
+        );
+
+        policy.allow_duplicates = true;
+        assert!(linkage.validate(&policy).is_ok());
+        assert_eq!(linkage.into_unique_ids(), ["1", "2"]);
+    }
+
+    #[test]
+    fn linkage_kinds() {
+        let linkage = linkage(
+            r#"[
+                {"type": "tags", "id": "1"},
+                {"type": "tags", "id": "2"},
+                {"type": "tags", "id": "3"},
+                {"type": "people", "id": "4"}
+            ]"#,
+        );
+        let mut policy = LinkagePolicy::new();
+
+        policy.allowed_kinds.insert("tags".parse().unwrap());
+        assert_eq!(
+            summary(&errors(&linkage, &policy)),
+            [(409, "/data/3/type".to_owned())]
+        );
+
+        let member = self::linkage(r#"{"type": "people", "id": "4"}"#);
+        assert_eq!(
+            summary(&errors(&member, &policy)),
+            [(409, "/data/type".to_owned())]
+        );
+        assert!(self::linkage("null").validate(&policy).is_ok());
+    }
+
+    #[test]
+    fn linkage_max_items() {
+        let linkage = linkage(r#"[{"type": "tags", "id": "1"}, {"type": "tags", "id": "2"}]"#);
+        let mut policy = LinkagePolicy::new();
+
+        policy.max_items = Some(1);
+        assert_eq!(summary(&errors(&linkage, &policy)), [(422, "/data".to_owned())]);
+
+        policy.max_items = Some(2);
+        assert!(linkage.validate(&policy).is_ok());
+    }
+}
@@ -1,38 +1,66 @@
 //! Components of a JSON API document.
 
+mod catalog;
 mod convert;
 mod ident;
 mod link;
+mod linkage;
 mod object;
+mod parse;
+mod pointer;
+mod registry;
 mod relationship;
 mod specification;
+mod validate;
 
 mod error;
 
+#[cfg(feature = "atomic")]
+pub mod atomic;
+
 use std::iter::FromIterator;
+use std::slice;
 
-use serde::de::DeserializeOwned;
+use serde::de::{self, Deserialize, DeserializeOwned, Deserializer};
 use serde::ser::Serialize;
+use serde_json;
 
 use error::Error;
 use query::Query;
 use sealed::Sealed;
+use value::size::{EstimateSize, StructSize};
 use value::{Key, Map, Set, Value};
 use view::Render;
 
+pub use self::catalog::{CatalogEntry, ErrorCatalog};
 pub use self::convert::*;
 pub use self::error::{ErrorObject, ErrorSource};
-pub use self::ident::Identifier;
-pub use self::link::Link;
-pub use self::object::{NewObject, Object};
-pub use self::relationship::Relationship;
+pub use self::ident::{IdentRef, Identifier};
+pub use self::link::{Href, Link, LinkBuilder};
+pub use self::linkage::{Linkage, LinkagePolicy};
+pub use self::object::{NewObject, Object, ObjectBuilder};
+pub use self::parse::{InvalidLinks, ParseOptions};
+pub use self::pointer::Pointer;
+pub use self::registry::TypeRegistry;
+pub use self::relationship::{Relationship, RelationshipBuilder};
 pub use self::specification::{JsonApi, Version};
+pub use self::validate::{validate, Severity, Violation};
 
 /// A marker trait used to indicate that a type can be the primary data for a
 /// document.
-pub trait PrimaryData: DeserializeOwned + Sealed + Serialize {
+pub trait PrimaryData: DeserializeOwned + EstimateSize + Sealed + Serialize {
+    #[doc(hidden)]
+    fn flatten(self, incl: &Set<Object>, typed: bool) -> Value;
+
     #[doc(hidden)]
-    fn flatten(self, &Set<Object>) -> Value;
+    fn resolve(&self, _: &Set<Object>) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[doc(hidden)]
+    fn check_links(&mut self, _: &Pointer, _: InvalidLinks) -> Result<(), Error> {
+        Ok(())
+    }
 }
 
 /// Represents a compound JSON API document.
@@ -41,7 +69,7 @@ pub trait PrimaryData: DeserializeOwned + Sealed + Serialize {
 /// specification.
 ///
 /// [document structure]: https://goo.gl/CXTNmt
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 #[serde(bound = "T: PrimaryData", untagged)]
 pub enum Document<T: PrimaryData> {
     /// Does not contain errors.
@@ -95,23 +123,458 @@ pub enum Document<T: PrimaryData> {
         #[serde(default, skip_serializing_if = "Map::is_empty")]
         meta: Map,
     },
+
+    /// Does not contain primary data or errors. Only contains meta information.
+    ///
+    /// Useful for responses that do not have a resource to return, such as a health
+    /// check or the acknowledgement of an asynchronous job.
+    Meta {
+        #[serde(default)]
+        jsonapi: JsonApi,
+
+        #[serde(default, skip_serializing_if = "Map::is_empty")]
+        links: Map<Key, Link>,
+
+        meta: Map,
+    },
 }
 
 impl<T: PrimaryData> Document<T> {
-    /// Returns `true` if the document does not contain any errors.
-    pub fn is_ok(&self) -> bool {
+    /// Returns a new document that only contains meta information.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::{Document, Object};
+    /// use json_api::value::Map;
+    ///
+    /// let mut meta = Map::new();
+    /// meta.insert("status".parse()?, "healthy".into());
+    ///
+    /// let doc = Document::<Object>::meta_only(meta);
+    /// let json = json_api::to_string(doc, None)?;
+    ///
+    /// assert_eq!(json, r#"{"jsonapi":{"version":"1.0"},"meta":{"status":"healthy"}}"#);
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn meta_only(meta: Map) -> Self {
+        Document::Meta {
+            meta,
+            jsonapi: Default::default(),
+            links: Default::default(),
+        }
+    }
+
+    /// Returns an estimate of the length of the document when it is serialized as
+    /// compact JSON, without serializing it.
+    ///
+    /// The estimate is exact unless the document contains a floating point number, in
+    /// which case the estimate is an upper bound.
+    pub fn estimated_size(&self) -> usize {
+        let mut state = StructSize::new();
+
         match *self {
-            Document::Ok { .. } => true,
-            Document::Err { .. } => false,
+            Document::Ok {
+                ref data,
+                ref included,
+                ref jsonapi,
+                ref links,
+                ref meta,
+            } => {
+                state.field("data", data);
+
+                if !included.is_empty() {
+                    state.field("included", included);
+                }
+
+                state.field("jsonapi", jsonapi);
+
+                if !links.is_empty() {
+                    state.field("links", links);
+                }
+
+                if !meta.is_empty() {
+                    state.field("meta", meta);
+                }
+            }
+            Document::Err {
+                ref errors,
+                ref jsonapi,
+                ref links,
+                ref meta,
+            } => {
+                state.field("errors", errors).field("jsonapi", jsonapi);
+
+                if !links.is_empty() {
+                    state.field("links", links);
+                }
+
+                if !meta.is_empty() {
+                    state.field("meta", meta);
+                }
+            }
+            Document::Meta {
+                ref jsonapi,
+                ref links,
+                ref meta,
+            } => {
+                state.field("jsonapi", jsonapi);
+
+                if !links.is_empty() {
+                    state.field("links", links);
+                }
+
+                state.field("meta", meta);
+            }
+        }
+
+        state.end()
+    }
+
+    /// Removes included resources until the [estimated size] of the document is less
+    /// than or equal to `max_bytes`.
+    ///
+    /// Included resources are removed in the reverse order that they were included.
+    /// Since resources are included as relationships are traversed, the resources
+    /// that are furthest from the primary data are removed first. The identifier of each
+    /// removed resource is recorded in the `trimmed-included` member of the document's
+    /// meta. The size of this record is accounted for in the budget.
+    ///
+    /// The primary data of the document is never removed. If the document is still over
+    /// budget once every included resource has been removed, it will remain over
+    /// budget. Documents that do not contain primary data are left as is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::{Data, Document, Object};
+    ///
+    /// let article = Object::new("articles".parse()?, "1".to_owned());
+    /// let author = Object::builder("people".parse()?, "9".to_owned())
+    ///     .attr("name", "Dan Gebhardt")
+    ///     .build()?;
+    /// let comment = Object::builder("comments".parse()?, "5".to_owned())
+    ///     .attr("body", "🦀".repeat(50))
+    ///     .build()?;
+    ///
+    /// let mut doc = Document::Ok {
+    ///     data: Data::from(article),
+    ///     included: vec![author, comment].into_iter().collect(),
+    ///     jsonapi: Default::default(),
+    ///     links: Default::default(),
+    ///     meta: Default::default(),
+    /// };
+    ///
+    /// doc.trim_included_to_budget(250);
+    /// assert!(doc.estimated_size() <= 250);
+    ///
+    /// if let Document::Ok { included, meta, .. } = doc {
+    ///     assert_eq!(included.len(), 1);
+    ///     assert!(meta.contains_key("trimmed-included"));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [estimated size]: #method.estimated_size
+    pub fn trim_included_to_budget(&mut self, max_bytes: usize) {
+        let key = Key::from_raw("trimmed-included".to_owned());
+
+        loop {
+            let mut size = self.estimated_size();
+
+            if size <= max_bytes {
+                return;
+            }
+
+            let (included, meta) = match *self {
+                Document::Ok {
+                    ref mut included,
+                    ref mut meta,
+                    ..
+                } => (included, meta),
+                Document::Err { .. } | Document::Meta { .. } => return,
+            };
+
+            if included.is_empty() {
+                return;
+            }
+
+            // The size is tracked as each resource is removed rather than estimated
+            // again. Every adjustment errs on the side of a larger document, so the
+            // budget is checked once more after the record is written to the meta.
+            if !meta.contains_key(&key) {
+                size += key.estimated_size() + 4;
+
+                if meta.is_empty() {
+                    size += "meta".estimated_size() + 4;
+                }
+            }
+
+            let mut trimmed = Vec::new();
+
+            while size > max_bytes {
+                let item = match included.pop() {
+                    Some(item) => item,
+                    None => break,
+                };
+
+                size -= item.estimated_size() + 1;
+
+                let ident = Value::from_iter(vec![
+                    (Key::from_raw("type".to_owned()), Value::String(item.kind.to_string())),
+                    (Key::from_raw("id".to_owned()), Value::String(item.id)),
+                ]);
+
+                size += ident.estimated_size() + 1;
+                trimmed.push(ident);
+            }
+
+            if let Some(&mut Value::Array(ref mut data)) = meta.get_mut(&key) {
+                data.extend(trimmed);
+                continue;
+            }
+
+            meta.insert(key.clone(), Value::Array(trimmed));
         }
     }
 
+    /// Interpret the document as a type `U` without consuming it.
+    ///
+    /// This is equivalent to calling [`from_doc`] with a clone of the document.
+    ///
+    /// [`from_doc`]: ./fn.from_doc.html
+    pub fn decode<U>(&self) -> Result<U, Error>
+    where
+        T: Clone,
+        U: DeserializeOwned,
+    {
+        from_doc(self.clone())
+    }
+
+    /// Serializes the document to a `serde_json::Value`.
+    ///
+    /// This is useful when a document is embedded in a larger JSON structure, since
+    /// it avoids serializing the document to a string and parsing it again.
+    pub fn to_json_value(&self) -> Result<serde_json::Value, Error> {
+        Ok(serde_json::to_value(self)?)
+    }
+
+    /// Returns `true` if the document does not contain any errors. A document that
+    /// only contains meta information is considered to be ok.
+    pub fn is_ok(&self) -> bool {
+        !self.is_err()
+    }
+
     /// Returns `true` if the document contains 1 or more error(s).
     pub fn is_err(&self) -> bool {
         match *self {
-            Document::Ok { .. } => true,
-            Document::Err { .. } => false,
+            Document::Err { .. } => true,
+            Document::Ok { .. } | Document::Meta { .. } => false,
+        }
+    }
+
+    /// Returns `true` if the document only contains meta information.
+    pub fn is_meta(&self) -> bool {
+        match *self {
+            Document::Meta { .. } => true,
+            Document::Ok { .. } | Document::Err { .. } => false,
+        }
+    }
+
+    /// Returns a reference to the top-level [JSON API object] of the document.
+    ///
+    /// [JSON API object]: ./struct.JsonApi.html
+    pub fn jsonapi(&self) -> &JsonApi {
+        match *self {
+            Document::Ok { ref jsonapi, .. }
+            | Document::Err { ref jsonapi, .. }
+            | Document::Meta { ref jsonapi, .. } => jsonapi,
+        }
+    }
+
+    /// Returns a mutable reference to the top-level [JSON API object] of the document.
+    /// Use this method to carry extensions or profiles through a rendered document.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::{Document, Object, Version};
+    ///
+    /// let article = Object::new("articles".parse()?, "1".to_owned());
+    /// let mut doc: Document<Object> = json_api::to_doc(article, None)?;
+    ///
+    /// doc.jsonapi_mut().version = Version::V1_1;
+    /// doc.jsonapi_mut().add_ext("https://jsonapi.org/ext/atomic".parse()?);
+    ///
+    /// assert_eq!(doc.jsonapi().ext.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [JSON API object]: ./struct.JsonApi.html
+    pub fn jsonapi_mut(&mut self) -> &mut JsonApi {
+        match *self {
+            Document::Ok { ref mut jsonapi, .. }
+            | Document::Err { ref mut jsonapi, .. }
+            | Document::Meta { ref mut jsonapi, .. } => jsonapi,
+        }
+    }
+
+    /// Returns a reference to the top-level links of the document.
+    pub fn links(&self) -> &Map<Key, Link> {
+        match *self {
+            Document::Ok { ref links, .. }
+            | Document::Err { ref links, .. }
+            | Document::Meta { ref links, .. } => links,
+        }
+    }
+
+    /// Returns a mutable reference to the top-level links of the document.
+    pub fn links_mut(&mut self) -> &mut Map<Key, Link> {
+        match *self {
+            Document::Ok { ref mut links, .. }
+            | Document::Err { ref mut links, .. }
+            | Document::Meta { ref mut links, .. } => links,
+        }
+    }
+
+    /// Returns a reference to the top-level meta information of the document.
+    pub fn meta(&self) -> &Map {
+        match *self {
+            Document::Ok { ref meta, .. }
+            | Document::Err { ref meta, .. }
+            | Document::Meta { ref meta, .. } => meta,
+        }
+    }
+
+    /// Returns a mutable reference to the top-level meta information of the document.
+    pub fn meta_mut(&mut self) -> &mut Map {
+        match *self {
+            Document::Ok { ref mut meta, .. }
+            | Document::Err { ref mut meta, .. }
+            | Document::Meta { ref mut meta, .. } => meta,
+        }
+    }
+
+    /// Returns a reference to the top-level `self` link if it is present.
+    pub fn self_link(&self) -> Option<&Link> {
+        self.links().get("self")
+    }
+
+    /// Sets the top-level `self` link. If a `self` link already existed, the old value
+    /// is returned in `Some`.
+    pub fn set_self_link(&mut self, link: Link) -> Option<Link> {
+        self.links_mut().insert(Key::from_raw("self".to_owned()), link)
+    }
+}
+
+impl<'de, T: PrimaryData> Deserialize<'de> for Document<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let Unchecked(mut doc) = Unchecked::deserialize(deserializer)?;
+
+        parse::check_document(&mut doc, InvalidLinks::Error).map_err(de::Error::custom)?;
+        Ok(doc)
+    }
+}
+
+/// A document that is deserialized without applying an [`InvalidLinks`] policy to
+/// the links that it contains.
+///
+/// [`InvalidLinks`]: ./enum.InvalidLinks.html
+pub(crate) struct Unchecked<T: PrimaryData>(pub(crate) Document<T>);
+
+impl<'de, T: PrimaryData> Deserialize<'de> for Unchecked<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(bound = "T: PrimaryData")]
+        struct RawDocument<T: PrimaryData> {
+            #[serde(default, deserialize_with = "deserialize_some")]
+            data: Option<Data<T>>,
+            #[serde(default)]
+            errors: Option<Vec<ErrorObject>>,
+            #[serde(default)]
+            included: Option<Set<Object>>,
+            #[serde(default)]
+            jsonapi: JsonApi,
+            #[serde(default)]
+            links: Map<Key, Link>,
+            #[serde(default)]
+            meta: Option<Map>,
         }
+
+        let raw = RawDocument::<T>::deserialize(deserializer)?;
+
+        let doc = match (raw.data, raw.errors) {
+            (Some(_), Some(_)) => Err(de::Error::custom(
+                "a document must not contain both `data` and `errors`",
+            )),
+            (None, _) if raw.included.is_some() => Err(de::Error::custom(
+                "a document must not contain `included` without `data`",
+            )),
+            (Some(data), None) => Ok(Document::Ok {
+                data,
+                included: raw.included.unwrap_or_default(),
+                jsonapi: raw.jsonapi,
+                links: raw.links,
+                meta: raw.meta.unwrap_or_default(),
+            }),
+            (None, Some(ref errors)) if errors.is_empty() => Err(de::Error::custom(
+                "the `errors` of a document must contain at least one error object",
+            )),
+            (None, Some(errors)) => Ok(Document::Err {
+                errors,
+                jsonapi: raw.jsonapi,
+                links: raw.links,
+                meta: raw.meta.unwrap_or_default(),
+            }),
+            (None, None) => match raw.meta {
+                Some(meta) => Ok(Document::Meta {
+                    meta,
+                    jsonapi: raw.jsonapi,
+                    links: raw.links,
+                }),
+                None => Err(de::Error::custom(
+                    "a document must contain at least one of `data`, `errors`, or `meta`",
+                )),
+            },
+        };
+
+        doc.map(Unchecked)
     }
 }
 
@@ -137,6 +600,163 @@ pub enum Data<T: PrimaryData> {
     Member(Box<Option<T>>),
 }
 
+impl<T: PrimaryData> Data<T> {
+    /// Returns the resources of the data as a slice. A member is returned as a slice
+    /// with one element, or an empty slice if it is `null`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::{Data, Identifier};
+    ///
+    /// let ident = Identifier::new("users".parse()?, "1".to_owned());
+    ///
+    /// assert_eq!(Data::from(ident.clone()).as_slice(), [ident.clone()]);
+    /// assert_eq!(Data::Collection(vec![ident.clone()]).as_slice(), [ident]);
+    /// assert!(Data::<Identifier>::from(None).as_slice().is_empty());
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn as_slice(&self) -> &[T] {
+        match *self {
+            Data::Collection(ref data) => data,
+            Data::Member(ref data) => match **data {
+                Some(ref item) => slice::from_ref(item),
+                None => &[],
+            },
+        }
+    }
+
+    /// Returns an iterator over the resources of the data.
+    pub fn iter(&self) -> slice::Iter<T> {
+        self.as_slice().iter()
+    }
+
+    /// Returns the number of resources in the data.
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    /// Returns `true` if the data is an empty collection or `null`.
+    pub fn is_empty(&self) -> bool {
+        self.as_slice().is_empty()
+    }
+
+    /// Consumes the data and returns new data of the same shape with `f` applied to
+    /// each resource.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::{Data, Identifier, Object};
+    ///
+    /// let data = Data::from(Object::new("users".parse()?, "1".to_owned()));
+    /// let idents = data.map(Identifier::from);
+    ///
+    /// assert_eq!(idents.iter().next().map(|ident| &*ident.id), Some("1"));
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn map<U, F>(self, f: F) -> Data<U>
+    where
+        U: PrimaryData,
+        F: FnMut(T) -> U,
+    {
+        match self {
+            Data::Collection(data) => Data::Collection(data.into_iter().map(f).collect()),
+            Data::Member(data) => Data::Member(Box::new((*data).map(f))),
+        }
+    }
+
+    /// Consumes the data and returns the optional member. Returns an
+    /// [`ErrorKind::UnexpectedData`] if the data is a collection.
+    ///
+    /// Since `Data` is deserialized from either an object, `null`, or an array, this
+    /// method can be used to reject a collection where a single resource is expected,
+    /// such as the resource linkage of a to-one relationship.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::{Data, Identifier};
+    ///
+    /// let ident = Identifier::new("users".parse()?, "1".to_owned());
+    /// let data = Data::from(ident.clone());
+    ///
+    /// assert_eq!(data.expect_member()?, Some(ident.clone()));
+    ///
+    /// let data = Data::Collection(vec![ident]);
+    /// assert!(data.expect_member().is_err());
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`ErrorKind::UnexpectedData`]: ../error/enum.ErrorKind.html#variant.UnexpectedData
+    pub fn expect_member(self) -> Result<Option<T>, Error> {
+        match self {
+            Data::Member(data) => Ok(*data),
+            Data::Collection(data) => Err(Error::unexpected_data(
+                "a single resource or null",
+                &format!("an array of {} resource(s)", data.len()),
+            )),
+        }
+    }
+
+    /// Consumes the data and returns the collection. Returns an
+    /// [`ErrorKind::UnexpectedData`] if the data is a single resource or `null`.
+    ///
+    /// [`ErrorKind::UnexpectedData`]: ../error/enum.ErrorKind.html#variant.UnexpectedData
+    pub fn expect_collection(self) -> Result<Vec<T>, Error> {
+        match self {
+            Data::Collection(data) => Ok(data),
+            Data::Member(data) => Err(Error::unexpected_data(
+                "an array of resources",
+                if data.is_some() {
+                    "a single resource"
+                } else {
+                    "null"
+                },
+            )),
+        }
+    }
+}
+
+impl<T: PrimaryData> EstimateSize for Data<T> {
+    fn estimated_size(&self) -> usize {
+        match *self {
+            Data::Collection(ref data) => data.estimated_size(),
+            Data::Member(ref data) => data.estimated_size(),
+        }
+    }
+}
+
 impl<T: PrimaryData> From<Option<T>> for Data<T> {
     fn from(value: Option<T>) -> Self {
         Data::Member(Box::new(value))
@@ -163,3 +783,288 @@ impl<T: PrimaryData> FromIterator<T> for Data<T> {
         Data::Collection(Vec::from_iter(iter))
     }
 }
+
+/// Deserializes a member that is present as `Some`, even if its value is `null`.
+pub(crate) fn deserialize_some<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::{Data, Document, Identifier, Link, Object, Relationship};
+    use value::Set;
+
+    fn object(kind: &str, id: &str, body: &str) -> Object {
+        Object::builder(kind.parse().unwrap(), id.to_owned())
+            .attr("body", body)
+            .attr("rating", 4.5)
+            .build()
+            .unwrap()
+    }
+
+    fn document(included: Vec<Object>) -> Document<Object> {
+        let mut article = Object::builder("articles".parse().unwrap(), "1".to_owned())
+            .attr("title", "JSON API paints my \"bikeshed\"!")
+            .attr("tags", vec!["api", "json"])
+            .attr("published", true)
+            .attr("views", -1)
+            .link("self", "/articles/1".parse().unwrap())
+            .meta("draft", None::<bool>)
+            .build()
+            .unwrap();
+        let comments = included
+            .iter()
+            .map(|item| Identifier::new(item.kind.clone(), item.id.clone()))
+            .collect::<Relationship>();
+        let mut links = ::value::Map::new();
+
+        links.insert(
+            "self".parse().unwrap(),
+            Link::builder("/articles/1/comments")
+                .meta("count", included.len())
+                .build()
+                .unwrap(),
+        );
+
+        article
+            .relationships
+            .insert("comments".parse().unwrap(), comments);
+
+        Document::Ok {
+            links,
+            data: Data::from(article),
+            included: included.into_iter().collect::<Set<_>>(),
+            jsonapi: Default::default(),
+            meta: Default::default(),
+        }
+    }
+
+    /// Asserts that the estimated size of `doc` is an upper bound that is within the
+    /// maximum length of a float for each of the `floats` in the document.
+    fn assert_estimate(doc: &Document<Object>, floats: usize) {
+        let actual = serde_json::to_string(doc).unwrap().len();
+        let estimate = doc.estimated_size();
+
+        assert!(estimate >= actual, "{} < {}", estimate, actual);
+        assert!(estimate <= actual + floats * 24, "{} > {}", estimate, actual);
+    }
+
+    #[test]
+    fn document_estimated_size() {
+        assert_estimate(&document(Vec::new()), 0);
+        assert_estimate(
+            &document(vec![
+                object("comments", "1", "First!"),
+                object("comments", "2", "tab\tnewline\n🦀"),
+            ]),
+            2,
+        );
+
+        let err = Document::Err::<Object> {
+            errors: vec![Default::default()],
+            jsonapi: Default::default(),
+            links: Default::default(),
+            meta: Default::default(),
+        };
+
+        assert_eq!(
+            err.estimated_size(),
+            serde_json::to_string(&err).unwrap().len()
+        );
+    }
+
+    #[test]
+    fn document_is_ok_is_err() {
+        let doc = parse(r#"{"data":null}"#).unwrap();
+        assert!(doc.is_ok());
+        assert!(!doc.is_err());
+
+        let doc = parse(r#"{"errors":[{"status":"404"}]}"#).unwrap();
+        assert!(!doc.is_ok());
+        assert!(doc.is_err());
+    }
+
+    #[test]
+    fn document_to_json_value() {
+        let doc = document(vec![object("comments", "1", "First!")]);
+        let value = doc.to_json_value().unwrap();
+
+        assert_eq!(value["data"]["type"], "articles");
+        assert_eq!(value["data"]["id"], "1");
+        assert_eq!(value["included"][0]["type"], "comments");
+
+        let json = serde_json::to_string(&doc).unwrap();
+        assert_eq!(value, serde_json::from_str::<serde_json::Value>(&json).unwrap());
+    }
+
+    #[test]
+    fn document_trim_included() {
+        let body = "x".repeat(100);
+        let included = vec![
+            object("comments", "1", &body),
+            object("comments", "2", &body),
+            object("comments", "3", &body),
+        ];
+
+        let mut doc = document(included.clone());
+        let size = doc.estimated_size();
+
+        doc.trim_included_to_budget(size);
+        assert_eq!(doc, document(included.clone()));
+
+        doc.trim_included_to_budget(size - 100);
+        assert!(doc.estimated_size() <= size - 100);
+
+        match doc {
+            Document::Ok {
+                ref included,
+                ref meta,
+                ..
+            } => {
+                let ids = included.iter().map(|item| &*item.id).collect::<Vec<_>>();
+                let trimmed = serde_json::to_string(&meta.get("trimmed-included")).unwrap();
+
+                assert_eq!(ids, vec!["1", "2"]);
+                assert_eq!(trimmed, r#"[{"type":"comments","id":"3"}]"#);
+            }
+            Document::Err { .. } | Document::Meta { .. } => unreachable!(),
+        }
+
+        doc.trim_included_to_budget(0);
+
+        match doc {
+            Document::Ok {
+                ref included,
+                ref meta,
+                ..
+            } => {
+                let trimmed = serde_json::to_string(&meta.get("trimmed-included")).unwrap();
+
+                assert!(included.is_empty());
+                assert_eq!(
+                    trimmed,
+                    concat!(
+                        r#"[{"type":"comments","id":"3"},"#,
+                        r#"{"type":"comments","id":"2"},"#,
+                        r#"{"type":"comments","id":"1"}]"#,
+                    )
+                );
+            }
+            Document::Err { .. } | Document::Meta { .. } => unreachable!(),
+        }
+    }
+
+    fn parse(source: &str) -> Result<Document<Object>, String> {
+        serde_json::from_str(source).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn document_deserialize() {
+        let doc = parse(r#"{"data":null,"meta":{"total":0}}"#).unwrap();
+        assert_eq!(doc, Document::Ok {
+            data: Data::Member(Box::new(None)),
+            included: Default::default(),
+            jsonapi: Default::default(),
+            links: Default::default(),
+            meta: vec![("total".parse().unwrap(), 0.into())].into_iter().collect(),
+        });
+
+        let doc = parse(r#"{"data":[],"included":[]}"#).unwrap();
+        assert_eq!(doc, Document::Ok {
+            data: Data::Collection(Vec::new()),
+            included: Default::default(),
+            jsonapi: Default::default(),
+            links: Default::default(),
+            meta: Default::default(),
+        });
+
+        let doc = parse(r#"{"errors":[{"status":"404"}]}"#).unwrap();
+        assert!(match doc {
+            Document::Err { ref errors, .. } => errors.len() == 1,
+            Document::Ok { .. } | Document::Meta { .. } => false,
+        });
+    }
+
+    #[test]
+    fn document_meta_only() {
+        let source = r#"{"jsonapi":{"version":"1.0"},"meta":{"status":"healthy"}}"#;
+        let doc = parse(source).unwrap();
+
+        assert!(doc.is_ok());
+        assert!(doc.is_meta());
+        assert!(!doc.is_err());
+        assert_eq!(doc.meta().get("status"), Some(&"healthy".into()));
+        assert_eq!(serde_json::to_string(&doc).unwrap(), source);
+        assert_eq!(doc.estimated_size(), source.len());
+        assert_eq!(doc.decode::<Option<Object>>().unwrap(), None);
+
+        let doc = parse(r#"{"data":null,"meta":{"status":"healthy"}}"#).unwrap();
+        assert!(!doc.is_meta());
+    }
+
+    #[test]
+    fn document_deserialize_invalid() {
+        let cases = [
+            (r#"{"data":null,"errors":[{"status":"500"}]}"#, "both `data` and `errors`"),
+            (r#"{"errors":[{"status":"500"}],"included":[]}"#, "`included` without `data`"),
+            (r#"{"included":[]}"#, "`included` without `data`"),
+            (r#"{"errors":[]}"#, "at least one error object"),
+            (r#"{"links":{}}"#, "at least one of `data`, `errors`, or `meta`"),
+            (r#"{}"#, "at least one of `data`, `errors`, or `meta`"),
+        ];
+
+        for &(source, message) in &cases {
+            let error = parse(source).unwrap_err();
+            assert!(error.contains(message), "{}: {}", source, error);
+        }
+    }
+
+    #[test]
+    fn data_accessors() {
+        let items = vec![object("users", "1", "a"), object("users", "2", "b")];
+        let collection = Data::Collection(items.clone());
+        let member = Data::from(items[0].clone());
+        let null = Data::<Object>::from(None);
+
+        assert_eq!(collection.as_slice(), &items[..]);
+        assert_eq!(collection.len(), 2);
+        assert!(!collection.is_empty());
+        assert_eq!(Data::<Object>::Collection(Vec::new()).len(), 0);
+
+        assert_eq!(member.as_slice(), &items[..1]);
+        assert_eq!(member.iter().map(|item| &*item.id).collect::<Vec<_>>(), ["1"]);
+        assert_eq!(member.len(), 1);
+
+        assert!(null.as_slice().is_empty());
+        assert_eq!(null.iter().next(), None);
+        assert!(null.is_empty());
+    }
+
+    #[test]
+    fn data_map() {
+        let items = vec![object("users", "1", "a"), object("users", "2", "b")];
+        let idents = Data::Collection(items.clone()).map(Identifier::from);
+
+        match idents {
+            Data::Collection(ref data) => {
+                assert_eq!(data.iter().map(|item| &*item.id).collect::<Vec<_>>(), ["1", "2"]);
+            }
+            Data::Member(_) => panic!("expected a collection"),
+        }
+
+        assert_eq!(
+            Data::from(items[1].clone()).map(Identifier::from),
+            Data::from(Identifier::new("users".parse().unwrap(), "2".to_owned()))
+        );
+        assert_eq!(
+            Data::<Object>::from(None).map(Identifier::from),
+            Data::Member(Box::new(None))
+        );
+    }
+}
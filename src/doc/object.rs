@@ -1,14 +1,23 @@
 use std::cmp::{Eq, PartialEq};
 use std::hash::{Hash, Hasher};
 use std::mem;
+use std::slice;
 
-use doc::{Data, Document, Identifier, Link, PrimaryData, Relationship};
+use serde::ser::Serialize;
+
+use doc::registry::{self, TypeRegistry};
+use doc::{parse, Data, Document, IdentRef, Identifier, InvalidLinks, Link, Pointer, PrimaryData,
+          Relationship};
 use error::Error;
 use query::Query;
 use sealed::Sealed;
-use value::{Key, Map, Set, Value};
+use value::size::{EstimateSize, StructSize};
+use value::{self, Key, Map, Set, Value};
 use view::Render;
 
+/// Member names that cannot be used as the name of an attribute or relationship.
+const RESERVED: &[&str] = &["id", "links", "meta", "relationships", "type"];
+
 /// A preexisting resource. Commonly found in the document of a response or `PATCH`
 /// request.
 ///
@@ -132,8 +141,16 @@ use view::Render;
 /// # }
 /// ```
 ///
+/// # Serialization
+///
+/// An `Object` serializes to a standalone *resource object* without a document
+/// wrapper. Members that are empty, such as `attributes` or `links`, are omitted. This
+/// makes it possible to embed a rendered resource in a payload that is not a JSON API
+/// document with [`json_api::to_object`] and a serializer such as `serde_json`.
+///
 /// [`Identifier`]: ./struct.Identifier.html
 /// [`NewObject`]: ./struct.NewObject.html
+/// [`json_api::to_object`]: ../fn.to_object.html
 /// [`Resource`]: ../trait.Resource.html
 /// [`resource!`]: ../macro.resource.html
 /// [`json_api::to_doc`]: ../fn.to_doc.html
@@ -226,6 +243,207 @@ impl Object {
             _ext: (),
         }
     }
+
+    /// Returns a builder that can be used to construct a new `Object`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::{Identifier, Object};
+    ///
+    /// let author = Identifier::new("users".parse()?, "1".to_owned());
+    /// let obj = Object::builder("articles".parse()?, "1".to_owned())
+    ///     .attr("title", "Hello, World!")
+    ///     .attr("publishedAt", "2017-11-22")
+    ///     .relationship("author", author.into())
+    ///     .build()?;
+    ///
+    /// assert_eq!(obj.attributes.len(), 2);
+    /// assert_eq!(obj.attributes.get("title"), Some(&"Hello, World!".into()));
+    /// assert_eq!(obj.attributes.get("published-at"), Some(&"2017-11-22".into()));
+    /// assert!(obj.relationships.contains_key("author"));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn builder(kind: Key, id: String) -> ObjectBuilder {
+        ObjectBuilder::new(kind, id)
+    }
+
+    /// Returns an estimate of the length of the object when it is serialized as
+    /// compact JSON, without serializing it.
+    ///
+    /// The estimate is exact unless the object contains a floating point number, in
+    /// which case the estimate is an upper bound.
+    pub fn estimated_size(&self) -> usize {
+        let mut state = StructSize::new();
+
+        if !self.attributes.is_empty() {
+            state.field("attributes", &self.attributes);
+        }
+
+        state.field("id", &self.id).field("type", &self.kind);
+
+        if !self.links.is_empty() {
+            state.field("links", &self.links);
+        }
+
+        if !self.meta.is_empty() {
+            state.field("meta", &self.meta);
+        }
+
+        if !self.relationships.is_empty() {
+            state.field("relationships", &self.relationships);
+        }
+
+        state.end()
+    }
+
+    /// Returns a reference to the `self` link if it is present.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::Object;
+    ///
+    /// let mut obj = Object::new("users".parse()?, "1".to_owned());
+    /// assert!(obj.self_link().is_none());
+    ///
+    /// obj.set_self_link("/users/1".parse()?);
+    /// assert_eq!(obj.self_link().map(|link| link.to_string()), Some("/users/1".to_owned()));
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn self_link(&self) -> Option<&Link> {
+        self.links.get("self")
+    }
+
+    /// Sets the `self` link. If a `self` link already existed, the old value is
+    /// returned in `Some`.
+    pub fn set_self_link(&mut self, link: Link) -> Option<Link> {
+        self.links.insert(Key::from_raw("self".to_owned()), link)
+    }
+
+    /// Removes each attribute with a name that starts with an underscore.
+    ///
+    /// Names that start with an underscore are not valid member names, but they can be
+    /// added to a map with `Key::from_raw` by code that mirrors the fields of an
+    /// internal struct. Call this method before rendering an object that may contain
+    /// such attributes. To remove attributes that match another predicate, use
+    /// [`Map::retain`] on the `attributes` of the object.
+    ///
+    /// [`Map::retain`]: ../value/collections/map/struct.Map.html#method.retain
+    pub fn strip_private(&mut self) {
+        self.attributes.retain(|key, _| !key.starts_with('_'));
+    }
+
+    /// Returns an error if the name of an attribute or relationship is reserved.
+    ///
+    /// The names `id`, `type`, `links`, `meta`, and `relationships` are reserved by
+    /// the specification. For more information, check out the *[fields]* section of
+    /// the JSON API specification.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::Object;
+    ///
+    /// let mut obj = Object::new("users".parse()?, "1".to_owned());
+    ///
+    /// obj.attributes.insert("name".parse()?, "Alfred".into());
+    /// assert!(obj.validate_members().is_ok());
+    ///
+    /// obj.attributes.insert("type".parse()?, "butler".into());
+    /// assert!(obj.validate_members().is_err());
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [fields]: http://jsonapi.org/format/#document-resource-object-fields
+    pub fn validate_members(&self) -> Result<(), Error> {
+        validate_members(&self.attributes, &self.relationships)
+    }
+
+    /// Returns an error if the resource linkage of a relationship references a type
+    /// that is not in `registry`.
+    ///
+    /// The error is an [`ErrorKind::ErrorDocument`] that contains a `422 Unprocessable
+    /// Entity` error for each unknown identifier. The source of each error is a pointer
+    /// to the `type` member of the identifier, relative to a request document with the
+    /// object as its primary data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::{Identifier, Object, Pointer, Relationship, TypeRegistry};
+    /// use json_api::error::ErrorKind;
+    ///
+    /// let mut registry = TypeRegistry::new();
+    /// let mut obj = Object::new("articles".parse()?, "1".to_owned());
+    /// let author = Identifier::new("uzers".parse()?, "1".to_owned());
+    ///
+    /// registry.insert("users".parse()?);
+    /// obj.relationships
+    ///     .insert("author".parse()?, Relationship::new(author.into()));
+    ///
+    /// let e = obj.validate_linkage(&registry).unwrap_err();
+    ///
+    /// if let ErrorKind::ErrorDocument(ref errors) = *e.kind() {
+    ///     let source = errors[0].source.as_ref().unwrap();
+    ///     let pointer = source.pointer.as_ref().map(Pointer::as_str);
+    ///
+    ///     assert_eq!(pointer, Some("/data/relationships/author/data/type"));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`ErrorKind::ErrorDocument`]: ../error/enum.ErrorKind.html#variant.ErrorDocument
+    pub fn validate_linkage(&self, registry: &TypeRegistry) -> Result<(), Error> {
+        registry::validate_linkage(&self.relationships, registry)
+    }
+}
+
+impl EstimateSize for Object {
+    fn estimated_size(&self) -> usize {
+        Object::estimated_size(self)
+    }
 }
 
 impl Eq for Object {}
@@ -297,27 +515,37 @@ impl Render<Object> for Vec<Object> {
 }
 
 impl PrimaryData for Object {
-    fn flatten(self, incl: &Set<Object>) -> Value {
+    fn check_links(&mut self, pointer: &Pointer, policy: InvalidLinks) -> Result<(), Error> {
+        parse::check_object(&mut self.links, &mut self.relationships, pointer, policy)
+    }
+
+    fn flatten(self, incl: &Set<Object>, typed: bool) -> Value {
         #[cfg_attr(rustfmt, rustfmt_skip)]
-        let Object { id, attributes, relationships, .. } = self;
+        let Object { id, kind, attributes, relationships, .. } = self;
         let mut map = {
-            let size = attributes.len() + relationships.len() + 1;
+            let size = attributes.len() + relationships.len() + 2;
             Map::with_capacity(size)
         };
 
         map.insert(Key::from_raw("id".to_owned()), Value::String(id));
+
+        if typed {
+            map.insert(Key::from_raw("type".to_owned()), Value::String(kind.into()));
+        }
+
         map.extend(attributes);
 
         for (key, value) in relationships {
             let value = match value.data {
-                Data::Member(data) => match *data {
-                    Some(item) => item.flatten(incl),
+                Some(Data::Member(data)) => match *data {
+                    Some(item) => item.flatten(incl, typed),
                     None => Value::Null,
                 },
-                Data::Collection(data) => {
-                    let iter = data.into_iter().map(|item| item.flatten(incl));
+                Some(Data::Collection(data)) => {
+                    let iter = data.into_iter().map(|item| item.flatten(incl, typed));
                     Value::Array(iter.collect())
                 }
+                None => continue,
             };
 
             map.insert(key, value);
@@ -325,18 +553,182 @@ impl PrimaryData for Object {
 
         Value::Object(map)
     }
+
+    fn resolve(&self, incl: &Set<Object>) -> Result<(), Error> {
+        let mut visited = Set::new();
+
+        visited.insert(IdentRef::from(self));
+        resolve(&self.relationships, incl, &mut Vec::new(), &mut visited)
+    }
 }
 
 impl Sealed for Object {}
 
+/// Returns an error if an identifier in `relationships`, or in the relationships of an
+/// included resource that one of them resolves to, is not in `incl`. Each included
+/// resource is only checked once.
+fn resolve<'a>(
+    relationships: &'a Map<Key, Relationship>,
+    incl: &'a Set<Object>,
+    path: &mut Vec<&'a str>,
+    visited: &mut Set<IdentRef<'a>>,
+) -> Result<(), Error> {
+    for (key, value) in relationships {
+        let idents = match value.data {
+            Some(Data::Member(ref data)) => match **data {
+                Some(ref ident) => slice::from_ref(ident),
+                None => &[],
+            },
+            Some(Data::Collection(ref data)) => &data[..],
+            None => continue,
+        };
+
+        path.push(key);
+
+        for ident in idents {
+            let item = match incl.get(ident) {
+                Some(item) => item,
+                None => {
+                    let path = path.join(".");
+                    return Err(Error::unresolved_identifier(&ident.kind, &ident.id, &path));
+                }
+            };
+
+            if visited.insert(IdentRef::from(item)) {
+                resolve(&item.relationships, incl, path, visited)?;
+            }
+        }
+
+        path.pop();
+    }
+
+    Ok(())
+}
+
+/// An implementation of the "builder pattern" that can be used to construct a
+/// new `Object`.
+///
+/// Keys are parsed and values are serialized when they are supplied. The first error
+/// that occurs is returned when [`build`] is called.
+///
+/// [`build`]: #method.build
+#[derive(Debug)]
+pub struct ObjectBuilder {
+    attributes: Vec<(String, Result<Value, Error>)>,
+    id: String,
+    kind: Key,
+    links: Vec<(String, Link)>,
+    meta: Vec<(String, Result<Value, Error>)>,
+    relationships: Vec<(String, Relationship)>,
+}
+
+impl ObjectBuilder {
+    fn new(kind: Key, id: String) -> Self {
+        ObjectBuilder {
+            id,
+            kind,
+            attributes: Default::default(),
+            links: Default::default(),
+            meta: Default::default(),
+            relationships: Default::default(),
+        }
+    }
+
+    /// Attempt to construct a new object from the previously supplied values.
+    pub fn build(&mut self) -> Result<Object, Error> {
+        let mut obj = Object::new(self.kind.clone(), self.id.clone());
+
+        for (key, value) in self.attributes.drain(..) {
+            obj.attributes.insert(key.parse()?, value?);
+        }
+
+        for (key, value) in self.links.drain(..) {
+            obj.links.insert(key.parse()?, value);
+        }
+
+        for (key, value) in self.meta.drain(..) {
+            obj.meta.insert(key.parse()?, value?);
+        }
+
+        for (key, value) in self.relationships.drain(..) {
+            obj.relationships.insert(key.parse()?, value);
+        }
+
+        obj.validate_members()?;
+        Ok(obj)
+    }
+
+    /// Adds an attribute with the given `key` and `value`. An error is returned by
+    /// [`build`] if `key` is not a valid member name, if it is reserved, or if `value`
+    /// cannot be serialized.
+    ///
+    /// [`build`]: #method.build
+    pub fn attr<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: Into<String>,
+        V: Serialize,
+    {
+        self.attributes.push((key.into(), value::to_value(value)));
+        self
+    }
+
+    /// Adds a link with the given `key`. An error is returned by [`build`] if `key` is
+    /// not a valid member name.
+    ///
+    /// [`build`]: #method.build
+    pub fn link<K>(&mut self, key: K, value: Link) -> &mut Self
+    where
+        K: Into<String>,
+    {
+        self.links.push((key.into(), value));
+        self
+    }
+
+    /// Adds a member to the meta information of the object. An error is returned by
+    /// [`build`] if `key` is not a valid member name or if `value` cannot be
+    /// serialized.
+    ///
+    /// [`build`]: #method.build
+    pub fn meta<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: Into<String>,
+        V: Serialize,
+    {
+        self.meta.push((key.into(), value::to_value(value)));
+        self
+    }
+
+    /// Adds a relationship with the given `key`. An error is returned by [`build`] if
+    /// `key` is not a valid member name or if it is reserved.
+    ///
+    /// [`build`]: #method.build
+    pub fn relationship<K>(&mut self, key: K, value: Relationship) -> &mut Self
+    where
+        K: Into<String>,
+    {
+        self.relationships.push((key.into(), value));
+        self
+    }
+}
+
 /// A resource that does not already exist. Commonly found in the document of a
 /// `POST` request.
 ///
 /// For more information, check out the *[creating resources]* section of the JSON API
 /// specification.
 ///
+/// When a document of new objects is interpreted with [`from_doc`] or one of its
+/// variants, relationships are flattened the same way as those of an [`Object`]. A
+/// related resource that is included is flattened as an object. Otherwise it is
+/// flattened as its `id`, or as an object with an `id` and `type` with
+/// [`from_doc_typed`]. The `id` member is only present if the object has a
+/// client-generated id.
+///
 /// [creating resources]: https://goo.gl/KoLQgh
-#[derive(Clone, Debug, Deserialize, Serialize)]
+/// [`from_doc`]: ../fn.from_doc.html
+/// [`from_doc_typed`]: ../fn.from_doc_typed.html
+/// [`Object`]: ./struct.Object.html
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct NewObject {
     /// Contains some of the object's data. If this value of this field is empty, it will
     /// not be serialized. For more information, check out the *[attributes]* section of
@@ -353,6 +745,7 @@ pub struct NewObject {
     ///
     /// [client-generated id]: https://goo.gl/W16smj
     /// [identification]: https://goo.gl/3s681i
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
 
     /// Describes resources that share common attributes and relationships. This field
@@ -364,6 +757,14 @@ pub struct NewObject {
     #[serde(rename = "type")]
     pub kind: Key,
 
+    /// A local id that later operations in the same [atomic operations] document can
+    /// use to refer to this object before it has an `id`. Local ids were added in
+    /// version 1.1 of the JSON API specification.
+    ///
+    /// [atomic operations]: https://jsonapi.org/ext/atomic/
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lid: Option<String>,
+
     /// Contains relevant links. If this value of this field is empty, it will not be
     /// serialized. For more information, check out the *[links]* section of the JSON
     /// API specification.
@@ -417,6 +818,7 @@ impl NewObject {
         NewObject {
             kind,
             id: Default::default(),
+            lid: Default::default(),
             attributes: Default::default(),
             links: Default::default(),
             meta: Default::default(),
@@ -424,14 +826,73 @@ impl NewObject {
             _ext: (),
         }
     }
+
+    /// Returns an error if the name of an attribute or relationship is reserved.
+    ///
+    /// For more information, check out the documentation of
+    /// [`Object::validate_members`].
+    ///
+    /// [`Object::validate_members`]: ./struct.Object.html#method.validate_members
+    pub fn validate_members(&self) -> Result<(), Error> {
+        validate_members(&self.attributes, &self.relationships)
+    }
+
+    /// Returns an error if the resource linkage of a relationship references a type
+    /// that is not in `registry`.
+    ///
+    /// For more information, check out the documentation of
+    /// [`Object::validate_linkage`].
+    ///
+    /// [`Object::validate_linkage`]: ./struct.Object.html#method.validate_linkage
+    pub fn validate_linkage(&self, registry: &TypeRegistry) -> Result<(), Error> {
+        registry::validate_linkage(&self.relationships, registry)
+    }
+}
+
+impl EstimateSize for NewObject {
+    fn estimated_size(&self) -> usize {
+        let mut state = StructSize::new();
+
+        if !self.attributes.is_empty() {
+            state.field("attributes", &self.attributes);
+        }
+
+        if let Some(ref id) = self.id {
+            state.field("id", id);
+        }
+
+        state.field("type", &self.kind);
+
+        if let Some(ref lid) = self.lid {
+            state.field("lid", lid);
+        }
+
+        if !self.links.is_empty() {
+            state.field("links", &self.links);
+        }
+
+        if !self.meta.is_empty() {
+            state.field("meta", &self.meta);
+        }
+
+        if !self.relationships.is_empty() {
+            state.field("relationships", &self.relationships);
+        }
+
+        state.end()
+    }
 }
 
 impl PrimaryData for NewObject {
-    fn flatten(self, _: &Set<Object>) -> Value {
+    fn check_links(&mut self, pointer: &Pointer, policy: InvalidLinks) -> Result<(), Error> {
+        parse::check_object(&mut self.links, &mut self.relationships, pointer, policy)
+    }
+
+    fn flatten(self, incl: &Set<Object>, typed: bool) -> Value {
         #[cfg_attr(rustfmt, rustfmt_skip)]
-        let NewObject { id, attributes, relationships, .. } = self;
+        let NewObject { id, kind, attributes, relationships, .. } = self;
         let mut map = {
-            let size = attributes.len() + relationships.len() + 1;
+            let size = attributes.len() + relationships.len() + 2;
             Map::with_capacity(size)
         };
 
@@ -439,15 +900,23 @@ impl PrimaryData for NewObject {
             map.insert(Key::from_raw("id".to_owned()), Value::String(value));
         }
 
+        if typed {
+            map.insert(Key::from_raw("type".to_owned()), Value::String(kind.into()));
+        }
+
         map.extend(attributes);
 
         for (key, value) in relationships {
             let value = match value.data {
-                Data::Member(data) => match *data {
-                    Some(Identifier { id, .. }) => Value::String(id),
+                Some(Data::Member(data)) => match *data {
+                    Some(item) => item.flatten(incl, typed),
                     None => Value::Null,
                 },
-                Data::Collection(data) => data.into_iter().map(|ident| ident.id).collect(),
+                Some(Data::Collection(data)) => {
+                    let iter = data.into_iter().map(|item| item.flatten(incl, typed));
+                    Value::Array(iter.collect())
+                }
+                None => continue,
             };
 
             map.insert(key, value);
@@ -455,6 +924,25 @@ impl PrimaryData for NewObject {
 
         Value::Object(map)
     }
+
+    fn resolve(&self, incl: &Set<Object>) -> Result<(), Error> {
+        resolve(&self.relationships, incl, &mut Vec::new(), &mut Set::new())
+    }
+}
+
+impl From<Object> for NewObject {
+    fn from(obj: Object) -> Self {
+        NewObject {
+            attributes: obj.attributes,
+            id: None,
+            kind: obj.kind,
+            lid: None,
+            links: obj.links,
+            meta: obj.meta,
+            relationships: obj.relationships,
+            _ext: (),
+        }
+    }
 }
 
 impl Render<NewObject> for NewObject {
@@ -469,4 +957,217 @@ impl Render<NewObject> for NewObject {
     }
 }
 
+impl Render<NewObject> for Vec<NewObject> {
+    fn render(self, _: Option<&Query>) -> Result<Document<NewObject>, Error> {
+        Ok(Document::Ok {
+            data: Data::Collection(self),
+            included: Default::default(),
+            jsonapi: Default::default(),
+            links: Default::default(),
+            meta: Default::default(),
+        })
+    }
+}
+
 impl Sealed for NewObject {}
+
+fn validate_members(attributes: &Map, relationships: &Map<Key, Relationship>) -> Result<(), Error> {
+    let keys = attributes.keys().chain(relationships.keys());
+
+    for key in keys {
+        if RESERVED.contains(&&**key) {
+            return Err(Error::reserved_member(key));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{self, Value as JsonValue};
+
+    use super::{NewObject, Object};
+    use doc::{self, Data, Document, Identifier};
+    use error::ErrorKind;
+    use resource::Resource;
+    use value::{Key, Set, Value};
+    use view::Context;
+
+    struct User {
+        id: u64,
+        kind: String,
+    }
+
+    resource!(User, |&self| {
+        kind "users";
+        id self.id;
+
+        attr "type", &self.kind;
+    });
+
+    #[test]
+    fn object_reserved_attribute() {
+        let err = Object::builder("users".parse().unwrap(), "1".to_owned())
+            .attr("name", "Alfred")
+            .attr("type", "butler")
+            .build()
+            .unwrap_err();
+
+        match *err.kind() {
+            ErrorKind::ReservedMember(ref name) => assert_eq!(name, "type"),
+            ref kind => panic!("unexpected error: {}", kind),
+        }
+    }
+
+    #[test]
+    fn object_reserved_relationship() {
+        let ident = Identifier::new("users".parse().unwrap(), "2".to_owned());
+        let result = Object::builder("users".parse().unwrap(), "1".to_owned())
+            .relationship("meta", ident.into())
+            .build();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn new_object_reserved_attribute() {
+        let mut obj = NewObject::new("users".parse().unwrap());
+
+        obj.attributes.insert("name".parse().unwrap(), "Alfred".into());
+        assert!(obj.validate_members().is_ok());
+
+        obj.attributes.insert("id".parse().unwrap(), 1.into());
+        assert!(obj.validate_members().is_err());
+    }
+
+    #[test]
+    fn resource_reserved_attribute() {
+        let user = User {
+            id: 1,
+            kind: "butler".to_owned(),
+        };
+        let mut included = Set::new();
+        let mut ctx = Context::new(User::kind(), None, &mut included);
+
+        assert!(user.to_object(&mut ctx).is_err());
+    }
+
+    fn json(source: &str) -> JsonValue {
+        serde_json::from_str(source).unwrap()
+    }
+
+    #[test]
+    fn new_object_flatten() {
+        let source = r#"{
+            "data": {
+                "type": "articles",
+                "relationships": {
+                    "author": { "data": { "type": "users", "id": "9" } },
+                    "tags": { "data": [{ "type": "tags", "id": "1" }] }
+                }
+            },
+            "included": [
+                { "type": "tags", "id": "1", "attributes": { "name": "rust" } }
+            ]
+        }"#;
+
+        let doc = doc::parse_str::<NewObject>(source).unwrap();
+        let value = doc::from_doc::<_, JsonValue>(doc.clone()).unwrap();
+
+        // Related resources are resolved against `included` like they are for an
+        // `Object`, and the id is omitted since the object does not have one.
+        assert_eq!(
+            value,
+            json(r#"{ "author": "9", "tags": [{ "id": "1", "name": "rust" }] }"#)
+        );
+        assert_eq!(
+            doc::from_doc_typed::<_, JsonValue>(doc.clone()).unwrap()["author"],
+            json(r#"{ "id": "9", "type": "users" }"#)
+        );
+
+        match *doc::from_doc_strict::<_, JsonValue>(doc).unwrap_err().kind() {
+            ErrorKind::UnresolvedIdentifier(ref kind, ref id, ref path) => {
+                assert_eq!((&**kind, &**id, &**path), ("users", "9", "author"));
+            }
+            ref kind => panic!("unexpected error: {}", kind),
+        }
+
+        let mut object = NewObject::new("articles".parse().unwrap());
+
+        object.id = Some("1".to_owned());
+
+        let doc = Document::Ok {
+            data: Data::Member(Box::new(Some(object))),
+            included: Default::default(),
+            jsonapi: Default::default(),
+            links: Default::default(),
+            meta: Default::default(),
+        };
+
+        assert_eq!(doc::from_doc::<_, JsonValue>(doc).unwrap(), json(r#"{ "id": "1" }"#));
+    }
+
+    #[test]
+    fn nested_attributes() {
+        let source = r#"{
+            "type": "events",
+            "id": "1",
+            "attributes": {
+                "title": "Launch",
+                "geo": {
+                    "lat": 40.7,
+                    "lng": -74.0,
+                    "bounds": {
+                        "north": { "lat": 41.0, "lng": -73.0 },
+                        "south": { "lat": 40.0, "lng": -75.0 }
+                    }
+                },
+                "venue": { "type": "places", "id": "2" }
+            }
+        }"#;
+
+        let object = serde_json::from_str::<Object>(source).unwrap();
+
+        assert!(object.relationships.is_empty());
+        assert_eq!(object.attributes.len(), 3);
+        assert_eq!(object.attributes.get("venue").map(Value::is_object), Some(true));
+        assert_eq!(
+            object.attributes.get("geo").and_then(|geo| geo.pointer("/bounds/north/lat")),
+            Some(&41.0.into())
+        );
+
+        let expected = serde_json::from_str::<JsonValue>(source).unwrap();
+        let actual = serde_json::to_value(&object).unwrap();
+
+        assert_eq!(actual, expected);
+
+        let doc = Document::Ok {
+            data: Data::Member(Box::new(Some(object))),
+            included: Default::default(),
+            jsonapi: Default::default(),
+            links: Default::default(),
+            meta: Default::default(),
+        };
+        let value = doc::from_doc::<_, Value>(doc).unwrap();
+
+        assert_eq!(value.pointer("/venue/type"), Some(&"places".into()));
+        assert_eq!(value.pointer("/geo/bounds/south/lng"), Some(&(-75.0).into()));
+    }
+
+    #[test]
+    fn strip_private() {
+        let mut object = Object::new("users".parse().unwrap(), "1".to_owned());
+
+        object.attributes.insert("name".parse().unwrap(), "Alfred".into());
+        object.attributes.insert(Key::from_raw("_internal".to_owned()), true.into());
+        object.attributes.insert("email".parse().unwrap(), "alfred@example.com".into());
+        object.meta.insert(Key::from_raw("_internal".to_owned()), true.into());
+        object.strip_private();
+
+        let keys = object.attributes.keys().map(|key| &**key).collect::<Vec<_>>();
+
+        assert_eq!(keys, ["name", "email"]);
+        assert!(object.meta.contains_key("_internal"));
+    }
+}
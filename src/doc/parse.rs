@@ -0,0 +1,271 @@
+use std::io::Read;
+use std::mem;
+
+use serde_json;
+
+use doc::link;
+use doc::{Data, Document, JsonApi, Link, Pointer, PrimaryData, Relationship, Unchecked};
+use error::Error;
+use value::{Key, Map, Set};
+
+/// Describes how a link with an `href` that is not a valid URI reference is handled
+/// when a document is parsed.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum InvalidLinks {
+    /// Fail with an [`ErrorKind::InvalidLink`] that contains the `href` and a JSON
+    /// pointer to the link.
+    ///
+    /// [`ErrorKind::InvalidLink`]: ../error/enum.ErrorKind.html#variant.InvalidLink
+    Error,
+
+    /// Remove the link from the document.
+    Drop,
+
+    /// Keep the link. The `href` is available as-is and the [`uri`] of the link is
+    /// `None`.
+    ///
+    /// [`uri`]: ./struct.Link.html#method.uri
+    Raw,
+}
+
+impl Default for InvalidLinks {
+    fn default() -> Self {
+        InvalidLinks::Error
+    }
+}
+
+/// Options that control how a document is parsed.
+///
+/// The default value of each option preserves the behavior of [`parse_slice`].
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::{Document, InvalidLinks, Object, ParseOptions};
+///
+/// let json = r#"{
+///     "data": null,
+///     "links": {
+///         "self": "/articles",
+///         "related": "http://exa mple.com"
+///     }
+/// }"#;
+///
+/// let result = json_api::parse_str::<Object>(json);
+/// let message = result.unwrap_err().to_string();
+///
+/// assert!(message.contains(r#""http://exa mple.com" at "/links/related""#));
+///
+/// let mut options = ParseOptions::new();
+/// options.invalid_links = InvalidLinks::Drop;
+///
+/// let doc: Document<Object> = options.parse_str(json)?;
+///
+/// assert!(doc.links().contains_key("self"));
+/// assert!(!doc.links().contains_key("related"));
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`parse_slice`]: ./fn.parse_slice.html
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ParseOptions {
+    /// How a link with an invalid `href` is handled. Defaults to
+    /// [`InvalidLinks::Error`].
+    ///
+    /// [`InvalidLinks::Error`]: ./enum.InvalidLinks.html#variant.Error
+    pub invalid_links: InvalidLinks,
+
+    /// Private field for backwards compatibility.
+    _ext: (),
+}
+
+impl ParseOptions {
+    /// Returns the default parse options.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Deserialize a `Document<T>` from an IO stream of JSON text.
+    pub fn parse_reader<R, T>(&self, data: R) -> Result<Document<T>, Error>
+    where
+        R: Read,
+        T: PrimaryData,
+    {
+        let result = self.parse(|| serde_json::from_reader(data));
+
+        record!(parsed(&result));
+        result
+    }
+
+    /// Deserialize a `Document<T>` from bytes of JSON text.
+    pub fn parse_slice<T>(&self, data: &[u8]) -> Result<Document<T>, Error>
+    where
+        T: PrimaryData,
+    {
+        let result = self.parse(|| serde_json::from_slice(data));
+
+        record!(parsed(&result));
+        result
+    }
+
+    /// Deserialize a `Document<T>` from a string of JSON text.
+    pub fn parse_str<T>(&self, data: &str) -> Result<Document<T>, Error>
+    where
+        T: PrimaryData,
+    {
+        let result = self.parse(|| serde_json::from_str(data));
+
+        record!(parsed(&result));
+        result
+    }
+
+    fn parse<F, T>(&self, f: F) -> Result<Document<T>, Error>
+    where
+        F: FnOnce() -> Result<Unchecked<T>, serde_json::Error>,
+        T: PrimaryData,
+    {
+        let Unchecked(mut doc) = f()?;
+
+        check_document(&mut doc, self.invalid_links)?;
+        Ok(doc)
+    }
+}
+
+/// Applies `policy` to the links of each member of an object.
+pub(crate) fn check_object(
+    links: &mut Map<Key, Link>,
+    relationships: &mut Map<Key, Relationship>,
+    pointer: &Pointer,
+    policy: InvalidLinks,
+) -> Result<(), Error> {
+    link::check_links(links, &member(pointer, "links"), policy)?;
+
+    for (key, rel) in relationships {
+        let mut pointer = member(pointer, "relationships");
+
+        pointer.push(key).push("links");
+        link::check_links(&mut rel.links, &pointer, policy)?;
+    }
+
+    Ok(())
+}
+
+/// Applies `policy` to every link in `doc`.
+pub(crate) fn check_document<T>(
+    doc: &mut Document<T>,
+    policy: InvalidLinks,
+) -> Result<(), Error>
+where
+    T: PrimaryData,
+{
+    let root = Pointer::root();
+
+    match *doc {
+        Document::Ok {
+            ref mut data,
+            ref mut included,
+            ref mut jsonapi,
+            ref mut links,
+            ..
+        } => {
+            check_jsonapi(jsonapi, &root, policy)?;
+            check_data(data, &Pointer::data(), policy)?;
+
+            // The items of a set cannot be borrowed mutably, so the included resources
+            // are moved into a new set in the same order.
+            let items = mem::replace(included, Set::new());
+
+            for (index, mut item) in items.into_iter().enumerate() {
+                let mut pointer = member(&root, "included");
+
+                pointer.push_index(index);
+                item.check_links(&pointer, policy)?;
+                included.insert(item);
+            }
+
+            link::check_links(links, &member(&root, "links"), policy)
+        }
+        Document::Err {
+            ref mut errors,
+            ref mut jsonapi,
+            ref mut links,
+            ..
+        } => {
+            check_jsonapi(jsonapi, &root, policy)?;
+
+            for (index, error) in errors.iter_mut().enumerate() {
+                let mut pointer = member(&root, "errors");
+
+                pointer.push_index(index).push("links");
+                link::check_links(&mut error.links, &pointer, policy)?;
+            }
+
+            link::check_links(links, &member(&root, "links"), policy)
+        }
+        Document::Meta {
+            ref mut jsonapi,
+            ref mut links,
+            ..
+        } => {
+            check_jsonapi(jsonapi, &root, policy)?;
+            link::check_links(links, &member(&root, "links"), policy)
+        }
+    }
+}
+
+/// Applies `policy` to the links of each resource in `data`. The `pointer`
+/// references `data` in the document.
+pub(crate) fn check_data<T>(
+    data: &mut Data<T>,
+    pointer: &Pointer,
+    policy: InvalidLinks,
+) -> Result<(), Error>
+where
+    T: PrimaryData,
+{
+    match *data {
+        Data::Member(ref mut item) => if let Some(ref mut item) = **item {
+            item.check_links(pointer, policy)?;
+        },
+        Data::Collection(ref mut items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                let mut pointer = pointer.clone();
+
+                pointer.push_index(index);
+                item.check_links(&pointer, policy)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Applies `policy` to the `ext` and `profile` links of a JSON API object that is a
+/// member of `root`.
+pub(crate) fn check_jsonapi(
+    jsonapi: &mut JsonApi,
+    root: &Pointer,
+    policy: InvalidLinks,
+) -> Result<(), Error> {
+    let pointer = member(root, "jsonapi");
+
+    link::check_list(&mut jsonapi.ext, &member(&pointer, "ext"), policy)?;
+    link::check_list(&mut jsonapi.profile, &member(&pointer, "profile"), policy)
+}
+
+pub(crate) fn member(pointer: &Pointer, token: &str) -> Pointer {
+    let mut pointer = pointer.clone();
+
+    pointer.push(token);
+    pointer
+}
@@ -0,0 +1,258 @@
+use std::fmt::{self, Display, Formatter};
+use std::ops::Deref;
+use std::str::FromStr;
+
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+use error::Error;
+use value::size::EstimateSize;
+
+/// A [JSON Pointer] to a value in a document.
+///
+/// Pointers are most commonly used as the `pointer` of an [`ErrorSource`] to
+/// reference the member of a request document that caused an error. Constructors are
+/// provided for the locations that are referenced most often so the pointers produced
+/// by different handlers remain consistent.
+///
+/// Reference tokens are escaped as they are added, so a member name that contains `~`
+/// or `/` is always referenced correctly.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::Pointer;
+///
+/// assert_eq!(Pointer::attribute("title"), "/data/attributes/title");
+/// assert_eq!(Pointer::relationship("author"), "/data/relationships/author");
+/// assert_eq!(
+///     Pointer::relationship_data("comments", 2),
+///     "/data/relationships/comments/data/2"
+/// );
+///
+/// let mut pointer = Pointer::data();
+///
+/// pointer.push("meta").push("a/b");
+/// assert_eq!(pointer, "/data/meta/a~1b");
+///
+/// assert!("/data/meta/a~2b".parse::<Pointer>().is_err());
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [JSON Pointer]: https://tools.ietf.org/html/rfc6901
+/// [`ErrorSource`]: ./struct.ErrorSource.html
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Pointer(String);
+
+impl Pointer {
+    /// Returns a pointer to the entire document.
+    pub fn root() -> Self {
+        Default::default()
+    }
+
+    /// Returns a pointer to the primary data of a document.
+    pub fn data() -> Self {
+        Pointer("/data".to_owned())
+    }
+
+    /// Returns a pointer to the attribute `name` of the primary data.
+    pub fn attribute(name: &str) -> Self {
+        let mut pointer = Pointer::data();
+
+        pointer.push("attributes").push(name);
+        pointer
+    }
+
+    /// Returns a pointer to the relationship `name` of the primary data.
+    pub fn relationship(name: &str) -> Self {
+        let mut pointer = Pointer::data();
+
+        pointer.push("relationships").push(name);
+        pointer
+    }
+
+    /// Returns a pointer to the resource identifier at `index` in the linkage of the
+    /// to-many relationship `name` of the primary data.
+    pub fn relationship_data(name: &str, index: usize) -> Self {
+        let mut pointer = Pointer::relationship(name);
+
+        pointer.push("data").push_index(index);
+        pointer
+    }
+
+    /// Appends `token` to the end of the pointer, escaping `~` and `/`.
+    pub fn push(&mut self, token: &str) -> &mut Self {
+        self.0.reserve(token.len() + 1);
+        self.0.push('/');
+
+        for value in token.chars() {
+            match value {
+                '~' => self.0.push_str("~0"),
+                '/' => self.0.push_str("~1"),
+                _ => self.0.push(value),
+            }
+        }
+
+        self
+    }
+
+    /// Appends an array `index` to the end of the pointer.
+    pub fn push_index(&mut self, index: usize) -> &mut Self {
+        self.push(&index.to_string())
+    }
+
+    /// Returns a string slice containing the escaped pointer.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for Pointer {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl Display for Pointer {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(self)
+    }
+}
+
+impl From<Pointer> for String {
+    fn from(pointer: Pointer) -> Self {
+        let Pointer(value) = pointer;
+        value
+    }
+}
+
+impl From<String> for Pointer {
+    /// Returns a pointer that contains `value` as-is.
+    ///
+    /// The value is not validated. Use [`str::parse`] to reject values that are not a
+    /// valid JSON Pointer.
+    ///
+    /// [`str::parse`]: https://doc.rust-lang.org/std/primitive.str.html#method.parse
+    fn from(value: String) -> Self {
+        Pointer(value)
+    }
+}
+
+impl<'a> From<&'a str> for Pointer {
+    fn from(value: &'a str) -> Self {
+        Pointer(value.to_owned())
+    }
+}
+
+impl FromStr for Pointer {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if !value.is_empty() && !value.starts_with('/') {
+            bail!(r#"json pointer "{}" must start with '/'"#, value);
+        }
+
+        let mut chars = value.chars();
+
+        while let Some(next) = chars.next() {
+            if next != '~' {
+                continue;
+            }
+
+            match chars.next() {
+                Some('0') | Some('1') => {}
+                _ => bail!(r#"json pointer "{}" contains an invalid escape sequence"#, value),
+            }
+        }
+
+        Ok(Pointer(value.to_owned()))
+    }
+}
+
+impl PartialEq<str> for Pointer {
+    fn eq(&self, rhs: &str) -> bool {
+        self.as_str() == rhs
+    }
+}
+
+impl<'a> PartialEq<&'a str> for Pointer {
+    fn eq(&self, rhs: &&str) -> bool {
+        self.as_str() == *rhs
+    }
+}
+
+impl<'de> Deserialize<'de> for Pointer {
+    /// Deserializes a pointer without validating it so documents that were produced
+    /// by other implementations can always be read.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(Pointer)
+    }
+}
+
+impl EstimateSize for Pointer {
+    fn estimated_size(&self) -> usize {
+        self.as_str().estimated_size()
+    }
+}
+
+impl Serialize for Pointer {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::Pointer;
+
+    #[test]
+    fn escape() {
+        assert_eq!(Pointer::attribute("a~b/c"), "/data/attributes/a~0b~1c");
+        assert_eq!(Pointer::relationship("~1"), "/data/relationships/~01");
+
+        let mut pointer = Pointer::root();
+
+        pointer.push("");
+        assert_eq!(pointer, "/");
+    }
+
+    #[test]
+    fn parse() {
+        for value in &["", "/", "/data", "/data/attributes/a~0b~1c", "/~01"] {
+            assert_eq!(value.parse::<Pointer>().unwrap(), *value);
+        }
+
+        for value in &["data", "/data~", "/data/~2", "data/attributes"] {
+            assert!(value.parse::<Pointer>().is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn serialize() {
+        let pointer = Pointer::relationship_data("comments", 1);
+        let json = serde_json::to_string(&pointer).unwrap();
+
+        assert_eq!(json, r#""/data/relationships/comments/data/1""#);
+        assert_eq!(serde_json::from_str::<Pointer>(&json).unwrap(), pointer);
+    }
+}
@@ -0,0 +1,235 @@
+use http::StatusCode;
+
+use doc::{Data, ErrorObject, ErrorSource, Identifier, Pointer, Relationship};
+use error::Error;
+use resource::Resource;
+use value::collections::set;
+use value::{Key, Map, Set};
+
+/// A set of resource types that are known to an application.
+///
+/// A registry can be used to validate the resource linkage of a client supplied
+/// resource object before it is persisted. For more information, check out the
+/// documentation of [`Object::validate_linkage`].
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # struct User {
+/// #     id: u64,
+/// # }
+/// #
+/// # resource!(User, |&self| {
+/// #     kind "users";
+/// #     id self.id;
+/// # });
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::TypeRegistry;
+///
+/// let mut registry = TypeRegistry::new();
+///
+/// registry.register::<User>().insert("articles".parse()?);
+///
+/// assert!(registry.contains("users"));
+/// assert!(registry.contains("articles"));
+/// assert!(!registry.contains("uzers"));
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`Object::validate_linkage`]: ./struct.Object.html#method.validate_linkage
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TypeRegistry {
+    kinds: Set<Key>,
+}
+
+impl TypeRegistry {
+    /// Returns a new, empty registry.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds the [`kind`] of the resource `T` to the registry.
+    ///
+    /// [`kind`]: ../trait.Resource.html#tymethod.kind
+    pub fn register<T: Resource>(&mut self) -> &mut Self {
+        self.kinds.insert(T::kind());
+        self
+    }
+
+    /// Adds `kind` to the registry. Returns `true` if the registry did not already
+    /// contain `kind`.
+    pub fn insert(&mut self, kind: Key) -> bool {
+        self.kinds.insert(kind)
+    }
+
+    /// Returns `true` if `kind` is a known resource type.
+    pub fn contains(&self, kind: &str) -> bool {
+        self.kinds.contains(kind)
+    }
+
+    /// Returns the number of resource types in the registry.
+    pub fn len(&self) -> usize {
+        self.kinds.len()
+    }
+
+    /// Returns `true` if the registry does not contain any resource types.
+    pub fn is_empty(&self) -> bool {
+        self.kinds.is_empty()
+    }
+
+    /// Returns an iterator visiting each resource type in the order that they were
+    /// added.
+    pub fn iter(&self) -> set::Iter<Key> {
+        self.kinds.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a TypeRegistry {
+    type Item = &'a Key;
+    type IntoIter = set::Iter<'a, Key>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Returns an [`ErrorKind::ErrorDocument`] with an error for each identifier in
+/// `relationships` that has a type which is not in `registry`.
+///
+/// [`ErrorKind::ErrorDocument`]: ../error/enum.ErrorKind.html#variant.ErrorDocument
+pub(crate) fn validate_linkage(
+    relationships: &Map<Key, Relationship>,
+    registry: &TypeRegistry,
+) -> Result<(), Error> {
+    let mut errors = Vec::new();
+
+    for (key, value) in relationships {
+        match value.data {
+            Some(Data::Member(ref data)) => if let Some(ref ident) = **data {
+                let mut pointer = Pointer::relationship(key);
+
+                pointer.push("data");
+                check(ident, pointer, registry, &mut errors);
+            },
+            Some(Data::Collection(ref data)) => for (index, ident) in data.iter().enumerate() {
+                let pointer = Pointer::relationship_data(key, index);
+                check(ident, pointer, registry, &mut errors);
+            },
+            None => {}
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::error_document(errors))
+    }
+}
+
+fn check(
+    ident: &Identifier,
+    mut pointer: Pointer,
+    registry: &TypeRegistry,
+    errors: &mut Vec<ErrorObject>,
+) {
+    if registry.contains(&ident.kind) {
+        return;
+    }
+
+    let mut error = ErrorObject::new(Some(StatusCode::UNPROCESSABLE_ENTITY));
+
+    error.detail = Some(format!(r#"Unknown resource type "{}"."#, ident.kind));
+    pointer.push("type");
+    error.source = Some(ErrorSource::from(pointer));
+    errors.push(error);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TypeRegistry;
+    use doc::{Data, Identifier, NewObject, Object, Relationship};
+    use error::{Error, ErrorKind};
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::new();
+
+        registry.insert("articles".parse().unwrap());
+        registry.insert("users".parse().unwrap());
+        registry
+    }
+
+    fn ident(kind: &str, id: &str) -> Identifier {
+        Identifier::new(kind.parse().unwrap(), id.to_owned())
+    }
+
+    fn pointers(err: &Error) -> Vec<&str> {
+        match *err.kind() {
+            ErrorKind::ErrorDocument(ref errors) => errors
+                .iter()
+                .filter_map(|error| error.source.as_ref())
+                .filter_map(|source| source.pointer.as_ref())
+                .map(|pointer| pointer.as_str())
+                .collect(),
+            ref kind => panic!("unexpected error: {}", kind),
+        }
+    }
+
+    #[test]
+    fn validate_linkage_member() {
+        let mut obj = NewObject::new("articles".parse().unwrap());
+
+        obj.relationships.insert(
+            "author".parse().unwrap(),
+            Relationship::new(ident("users", "1").into()),
+        );
+        assert!(obj.validate_linkage(&registry()).is_ok());
+
+        obj.relationships.insert(
+            "author".parse().unwrap(),
+            Relationship::new(ident("uzers", "1").into()),
+        );
+
+        let err = obj.validate_linkage(&registry()).unwrap_err();
+        assert_eq!(pointers(&err), vec!["/data/relationships/author/data/type"]);
+    }
+
+    #[test]
+    fn validate_linkage_collection() {
+        let mut obj = Object::new("users".parse().unwrap(), "1".to_owned());
+        let data = vec![
+            ident("articles", "1"),
+            ident("artciles", "2"),
+            ident("articles", "3"),
+            ident("comments", "4"),
+        ];
+
+        obj.relationships.insert(
+            "articles".parse().unwrap(),
+            Relationship::new(Data::Collection(data)),
+        );
+        obj.relationships.insert(
+            "editor".parse().unwrap(),
+            Relationship::new(Data::Member(Box::new(None))),
+        );
+
+        let err = obj.validate_linkage(&registry()).unwrap_err();
+        assert_eq!(
+            pointers(&err),
+            vec![
+                "/data/relationships/articles/data/1/type",
+                "/data/relationships/articles/data/3/type",
+            ]
+        );
+    }
+}
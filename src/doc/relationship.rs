@@ -1,7 +1,14 @@
 use std::iter::FromIterator;
 
-use doc::{Data, Identifier, Link};
-use value::{Key, Map};
+use serde::ser::Serialize;
+
+use doc::{deserialize_some, link, Data, Identifier, Link};
+use error::Error;
+use query::{Page, Query};
+use resource::Resource;
+use value::size::{EstimateSize, StructSize};
+use value::{self, Key, Map, Set, Value};
+use view::Context;
 
 /// Represents a resource's relationship to another.
 ///
@@ -9,13 +16,16 @@ use value::{Key, Map};
 /// specification.
 ///
 /// [relationships]: https://goo.gl/ZQw9Xr
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct Relationship {
-    /// Contains resource linkage. For more information, checkout the
-    /// *[resource linkage]* section of the JSON API specification.
+    /// Contains resource linkage. If this value of this field is `None`, it will not
+    /// be serialized. For more information, checkout the *[resource linkage]* section
+    /// of the JSON API specification.
     ///
     /// [resource linkage]: https://goo.gl/evZF8m
-    pub data: Data<Identifier>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Data<Identifier>>,
 
     /// Contains relevant links. If this value of this field is empty, it will not be
     /// serialized. For more information, check out the *[links]* section of the JSON
@@ -63,12 +73,234 @@ impl Relationship {
     /// ```
     pub fn new(data: Data<Identifier>) -> Self {
         Relationship {
-            data,
+            data: Some(data),
             links: Default::default(),
             meta: Default::default(),
             _ext: (),
         }
     }
+
+    /// Returns a new to-one `Relationship` that links to `resource`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[macro_use]
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// struct User(u64);
+    ///
+    /// resource!(User, |&self| {
+    ///     kind "users";
+    ///     id self.0;
+    /// });
+    ///
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::{Identifier, Relationship};
+    ///
+    /// let author = Relationship::of(&User(9))?;
+    ///
+    /// assert_eq!(author, Identifier::new("users".parse()?, "9".to_owned()).into());
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn of<T: Resource>(resource: &T) -> Result<Self, Error> {
+        let mut incl = Set::new();
+        let mut ctx = Context::new(resource.instance_kind(), None, &mut incl);
+
+        resource.to_ident(&mut ctx).map(Relationship::from)
+    }
+
+    /// Returns a new to-many `Relationship` that links to each resource in
+    /// `resources`.
+    pub fn of_many<T: Resource>(resources: &[T]) -> Result<Self, Error> {
+        let mut incl = Set::new();
+        let mut ctx = Context::new(T::kind(), None, &mut incl);
+
+        resources
+            .iter()
+            .map(|item| item.to_ident(&mut ctx))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Relationship::from)
+    }
+
+    /// Returns a builder that can be used to create a new relationship.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::{Data, Identifier, Relationship};
+    /// use json_api::query::Page;
+    ///
+    /// let comments = vec![
+    ///     Identifier::new("comments".parse()?, "1".to_owned()),
+    ///     Identifier::new("comments".parse()?, "2".to_owned()),
+    /// ];
+    ///
+    /// let relationship = Relationship::builder()
+    ///     .data(Data::Collection(comments))
+    ///     .link("self", "/articles/1/relationships/comments".parse()?)
+    ///     .paginate("/articles/1/relationships/comments", Page::new(1, Some(2)), 5)
+    ///     .meta("total", 5)
+    ///     .build()?;
+    ///
+    /// assert_eq!(relationship.links.len(), 4);
+    /// assert_eq!(
+    ///     relationship.links.get("next").map(|link| link.reference()),
+    ///     Some("/articles/1/relationships/comments?page%5Bnumber%5D=2&page%5Bsize%5D=2".into())
+    /// );
+    /// assert_eq!(relationship.meta.get("total"), Some(&5.into()));
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn builder() -> RelationshipBuilder {
+        Default::default()
+    }
+
+    /// Returns a new `Relationship` with a `self` and `related` link. Returns an error
+    /// if either `self_href` or `related_href` is not a valid URI.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::{Data, Relationship};
+    ///
+    /// let relationship = Relationship::with_links(
+    ///     Data::Collection(Vec::new()),
+    ///     "/articles/1/relationships/comments",
+    ///     "/articles/1/comments",
+    /// )?;
+    ///
+    /// assert_eq!(
+    ///     relationship.self_link().map(|link| link.to_string()),
+    ///     Some("/articles/1/relationships/comments".to_owned())
+    /// );
+    ///
+    /// assert_eq!(
+    ///     relationship.related_link().map(|link| link.to_string()),
+    ///     Some("/articles/1/comments".to_owned())
+    /// );
+    ///
+    /// let invalid = Relationship::with_links(
+    ///     Data::Collection(Vec::new()),
+    ///     "/articles/1/relationships/comments",
+    ///     "not a uri",
+    /// );
+    ///
+    /// assert!(invalid.is_err());
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn with_links(
+        data: Data<Identifier>,
+        self_href: &str,
+        related_href: &str,
+    ) -> Result<Self, Error> {
+        let mut relationship = Relationship::new(data);
+
+        relationship.set_self_link(self_href.parse()?);
+        relationship.set_related_link(related_href.parse()?);
+
+        Ok(relationship)
+    }
+
+    /// Returns the relationship with a `count` member added to its meta. A `count`
+    /// member that already existed is replaced.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::{Identifier, Relationship};
+    ///
+    /// let comments = vec![
+    ///     Identifier::new("comments".parse()?, "1".to_owned()),
+    ///     Identifier::new("comments".parse()?, "2".to_owned()),
+    /// ];
+    ///
+    /// let relationship = Relationship::from(comments).with_count(2);
+    /// assert_eq!(relationship.meta.get("count"), Some(&2.into()));
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn with_count(mut self, n: usize) -> Self {
+        self.meta.insert(Key::from_raw("count".to_owned()), Value::from(n as u64));
+        self
+    }
+
+    /// Returns a reference to the `related` link if it is present.
+    pub fn related_link(&self) -> Option<&Link> {
+        self.links.get("related")
+    }
+
+    /// Returns a reference to the `self` link if it is present.
+    pub fn self_link(&self) -> Option<&Link> {
+        self.links.get("self")
+    }
+
+    /// Sets the `related` link. If a `related` link already existed, the old value is
+    /// returned in `Some`.
+    pub fn set_related_link(&mut self, link: Link) -> Option<Link> {
+        self.links.insert(Key::from_raw("related".to_owned()), link)
+    }
+
+    /// Sets the `self` link. If a `self` link already existed, the old value is
+    /// returned in `Some`.
+    pub fn set_self_link(&mut self, link: Link) -> Option<Link> {
+        self.links.insert(Key::from_raw("self".to_owned()), link)
+    }
+}
+
+impl EstimateSize for Relationship {
+    fn estimated_size(&self) -> usize {
+        let mut state = StructSize::new();
+
+        if let Some(ref data) = self.data {
+            state.field("data", data);
+        }
+
+        if !self.links.is_empty() {
+            state.field("links", &self.links);
+        }
+
+        if !self.meta.is_empty() {
+            state.field("meta", &self.meta);
+        }
+
+        state.end()
+    }
 }
 
 impl From<Option<Identifier>> for Relationship {
@@ -100,3 +332,198 @@ impl FromIterator<Identifier> for Relationship {
         Relationship::new(data)
     }
 }
+
+/// An implementation of the "builder pattern" that can be used to construct a
+/// new `Relationship`.
+///
+/// Keys are parsed and values are serialized when they are supplied. The first error
+/// that occurs is returned when [`build`] is called.
+///
+/// [`build`]: #method.build
+#[derive(Debug, Default)]
+pub struct RelationshipBuilder {
+    data: Option<Data<Identifier>>,
+    links: Vec<(String, Result<Link, Error>)>,
+    meta: Vec<(String, Result<Value, Error>)>,
+}
+
+impl RelationshipBuilder {
+    /// Attempt to construct a new relationship from the previously supplied values.
+    /// Returns an error if `data` was not supplied.
+    pub fn build(&mut self) -> Result<Relationship, Error> {
+        let data = match self.data.take() {
+            Some(data) => data,
+            None => return Err(Error::missing_field("data")),
+        };
+
+        let mut relationship = Relationship::new(data);
+
+        for (key, value) in self.links.drain(..) {
+            relationship.links.insert(key.parse()?, value?);
+        }
+
+        for (key, value) in self.meta.drain(..) {
+            relationship.meta.insert(key.parse()?, value?);
+        }
+
+        Ok(relationship)
+    }
+
+    pub fn data(&mut self, value: Data<Identifier>) -> &mut Self {
+        self.data = Some(value);
+        self
+    }
+
+    pub fn link<K>(&mut self, key: K, value: Link) -> &mut Self
+    where
+        K: Into<String>,
+    {
+        self.links.push((key.into(), Ok(value)));
+        self
+    }
+
+    pub fn meta<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: Into<String>,
+        V: Serialize,
+    {
+        self.meta.push((key.into(), value::to_value(value)));
+        self
+    }
+
+    /// Adds `first`, `last`, and when applicable, `prev` and `next` links for the
+    /// given `page` of a collection that contains `total` items.
+    ///
+    /// Each link is created by appending the `page` query parameters to `base`. If the
+    /// size of `page` is `None`, the entire collection is considered to be one page.
+    pub fn paginate(&mut self, base: &str, page: Page, total: u64) -> &mut Self {
+        let links = link::paginate(base, &Query::default(), page, total);
+
+        for (key, link) in links {
+            self.links.push((key.to_owned(), link));
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Relationship;
+    use doc::{Data, Identifier};
+    use query::Page;
+
+    struct User(u64);
+
+    resource!(User, |&self| {
+        kind "users";
+        id self.0;
+
+        ident_meta "admin", self.0 == 1;
+    });
+
+    fn links(page: Page, total: u64) -> Vec<(String, String)> {
+        Relationship::builder()
+            .data(Data::Collection(Vec::new()))
+            .paginate("/articles/1/comments", page, total)
+            .build()
+            .unwrap()
+            .links
+            .iter()
+            .map(|(key, link)| (key.to_string(), link.to_string()))
+            .collect()
+    }
+
+    fn href(number: u64) -> String {
+        match number {
+            1 => "/articles/1/comments?page%5Bsize%5D=10".to_owned(),
+            _ => format!(
+                "/articles/1/comments?page%5Bnumber%5D={}&page%5Bsize%5D=10",
+                number
+            ),
+        }
+    }
+
+    #[test]
+    fn relationship_builder() {
+        let ident = Identifier::new("users".parse().unwrap(), "1".to_owned());
+        let relationship = Relationship::builder()
+            .data(Data::Member(Box::new(Some(ident))))
+            .link("related", "/articles/1/author".parse().unwrap())
+            .meta("verified", true)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            relationship.related_link().map(|link| link.reference()),
+            Some("/articles/1/author".into())
+        );
+        assert_eq!(relationship.meta.get("verified"), Some(&true.into()));
+
+        assert!(Relationship::builder().build().is_err());
+        assert!(Relationship::builder()
+            .data(Data::Collection(Vec::new()))
+            .meta("-invalid", true)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn relationship_of() {
+        let author = Relationship::of(&User(9)).unwrap();
+
+        match author.data {
+            Some(Data::Member(ref data)) => {
+                let ident = data.as_ref().as_ref().unwrap();
+
+                assert_eq!(ident.kind, "users");
+                assert_eq!(ident.id, "9");
+                assert_eq!(ident.meta.get("admin"), Some(&false.into()));
+            }
+            ref data => panic!("unexpected data {:?}", data),
+        }
+
+        let editors = Relationship::of_many(&[User(1), User(2)]).unwrap();
+        let ids = editors
+            .data
+            .as_ref()
+            .map(|data| data.iter().map(|ident| &*ident.id).collect::<Vec<_>>());
+
+        assert_eq!(ids, Some(vec!["1", "2"]));
+        assert_eq!(
+            Relationship::of_many::<User>(&[]).unwrap().data,
+            Some(Data::Collection(Vec::new()))
+        );
+    }
+
+    #[test]
+    fn relationship_paginate() {
+        assert_eq!(
+            links(Page::new(1, Some(10)), 25),
+            vec![
+                ("first".to_owned(), href(1)),
+                ("next".to_owned(), href(2)),
+                ("last".to_owned(), href(3)),
+            ]
+        );
+
+        assert_eq!(
+            links(Page::new(2, Some(10)), 25),
+            vec![
+                ("first".to_owned(), href(1)),
+                ("prev".to_owned(), href(1)),
+                ("next".to_owned(), href(3)),
+                ("last".to_owned(), href(3)),
+            ]
+        );
+
+        assert_eq!(
+            links(Page::new(3, Some(10)), 0),
+            vec![
+                ("first".to_owned(), href(1)),
+                ("prev".to_owned(), href(1)),
+                ("last".to_owned(), href(1)),
+            ]
+        );
+    }
+}
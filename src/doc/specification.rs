@@ -4,8 +4,10 @@ use std::str::FromStr;
 use serde::de::{Deserialize, Deserializer, Error as DeError};
 use serde::ser::{Serialize, Serializer};
 
+use doc::Link;
 use error::Error;
 use value::Map;
+use value::size::{EstimateSize, StructSize};
 
 /// Information about this implementation of the specification.
 ///
@@ -15,6 +17,12 @@ use value::Map;
 /// [JSON API object]: https://goo.gl/hZUcEt
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct JsonApi {
+    /// The URIs of the extensions that are applied to the document. If this value of
+    /// this field is empty, it will not be included if the object is serialized.
+    /// Introduced in version 1.1 of the JSON API specification.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ext: Vec<Link>,
+
     /// Non-standard meta information. If this value of this field is empty, it will not
     /// be included if the object is serialized. For more information, check out the
     /// *[meta information]* section of the JSON API specification.
@@ -23,6 +31,12 @@ pub struct JsonApi {
     #[serde(default, skip_serializing_if = "Map::is_empty")]
     pub meta: Map,
 
+    /// The URIs of the profiles that are applied to the document. If this value of this
+    /// field is empty, it will not be included if the object is serialized. Introduced
+    /// in version 1.1 of the JSON API specification.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profile: Vec<Link>,
+
     /// The latest version of the JSON API specification that is supported by
     /// this implementation. Defaults to the latest available version.
     pub version: Version,
@@ -48,17 +62,120 @@ impl JsonApi {
     pub fn new(version: Version) -> Self {
         JsonApi {
             version,
+            ext: Default::default(),
             meta: Default::default(),
+            profile: Default::default(),
             _ext: (),
         }
     }
+
+    /// Returns `self` with `ext` added to the applied extensions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::{JsonApi, Version};
+    ///
+    /// let jsonapi = JsonApi::new(Version::V1_1)
+    ///     .with_ext("https://jsonapi.org/ext/atomic".parse()?)
+    ///     .with_profile("http://example.com/profiles/flexible-pagination".parse()?);
+    ///
+    /// assert_eq!(jsonapi.ext.len(), 1);
+    /// assert_eq!(jsonapi.profile.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn with_ext(mut self, ext: Link) -> Self {
+        self.add_ext(ext);
+        self
+    }
+
+    /// Returns `self` with `profile` added to the applied profiles.
+    pub fn with_profile(mut self, profile: Link) -> Self {
+        self.add_profile(profile);
+        self
+    }
+
+    /// Adds `ext` to the applied extensions if it is not already present.
+    pub fn add_ext(&mut self, ext: Link) {
+        if !self.ext.contains(&ext) {
+            self.ext.push(ext);
+        }
+    }
+
+    /// Adds `profile` to the applied profiles if it is not already present.
+    pub fn add_profile(&mut self, profile: Link) {
+        if !self.profile.contains(&profile) {
+            self.profile.push(profile);
+        }
+    }
+}
+
+impl EstimateSize for JsonApi {
+    fn estimated_size(&self) -> usize {
+        let mut state = StructSize::new();
+
+        if !self.ext.is_empty() {
+            state.field("ext", &self.ext);
+        }
+
+        if !self.meta.is_empty() {
+            state.field("meta", &self.meta);
+        }
+
+        if !self.profile.is_empty() {
+            state.field("profile", &self.profile);
+        }
+
+        state.field("version", &self.version).end()
+    }
 }
 
 /// The version of the specification.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::Version;
+///
+/// assert_eq!("1.0".parse::<Version>()?, Version::V1);
+/// assert_eq!("1.1".parse::<Version>()?, Version::V1_1);
+/// assert_eq!(Version::V1_1.to_string(), "1.1");
+/// assert!("2.0".parse::<Version>().is_err());
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Version {
     /// Version 1.0
     V1,
+
+    /// Version 1.1
+    V1_1,
+}
+
+impl EstimateSize for Version {
+    fn estimated_size(&self) -> usize {
+        5
+    }
 }
 
 impl Default for Version {
@@ -71,6 +188,7 @@ impl Display for Version {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         f.write_str(match *self {
             Version::V1 => "1.0",
+            Version::V1_1 => "1.1",
         })
     }
 }
@@ -81,6 +199,7 @@ impl FromStr for Version {
     fn from_str(value: &str) -> Result<Self, Self::Err> {
         match value {
             "1.0" => Ok(Version::V1),
+            "1.1" => Ok(Version::V1_1),
             v => Err(Error::unsupported_version(v)),
         }
     }
@@ -103,6 +222,59 @@ impl Serialize for Version {
     {
         serializer.serialize_str(match *self {
             Version::V1 => "1.0",
+            Version::V1_1 => "1.1",
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::{JsonApi, Version};
+
+    #[test]
+    fn jsonapi_v1() {
+        let source = r#"{"version":"1.0"}"#;
+        let jsonapi = serde_json::from_str::<JsonApi>(source).unwrap();
+
+        assert_eq!(jsonapi, JsonApi::default());
+        assert_eq!(serde_json::to_string(&jsonapi).unwrap(), source);
+    }
+
+    #[test]
+    fn jsonapi_ext_profile() {
+        let source = concat!(
+            r#"{"ext":["https://jsonapi.org/ext/atomic"],"#,
+            r#""profile":["http://example.com/profiles/flexible-pagination","#,
+            r#""http://example.com/profiles/resource-versioning"],"#,
+            r#""version":"1.1"}"#,
+        );
+        let jsonapi = serde_json::from_str::<JsonApi>(source).unwrap();
+        let expected = JsonApi::new(Version::V1_1)
+            .with_ext("https://jsonapi.org/ext/atomic".parse().unwrap())
+            .with_profile(
+                "http://example.com/profiles/flexible-pagination"
+                    .parse()
+                    .unwrap(),
+            )
+            .with_profile(
+                "http://example.com/profiles/resource-versioning"
+                    .parse()
+                    .unwrap(),
+            );
+
+        assert_eq!(jsonapi, expected);
+        assert_eq!(serde_json::to_string(&jsonapi).unwrap(), source);
+    }
+
+    #[test]
+    fn jsonapi_add_ext() {
+        let mut jsonapi = JsonApi::new(Version::V1_1);
+
+        jsonapi.add_ext("https://jsonapi.org/ext/atomic".parse().unwrap());
+        jsonapi.add_ext("https://jsonapi.org/ext/atomic".parse().unwrap());
+
+        assert_eq!(jsonapi.ext.len(), 1);
+    }
+}
@@ -0,0 +1,279 @@
+use doc::{Data, Document, IdentRef, Identifier, Link, Object, Pointer, Relationship, Version};
+use value::{Key, Map, Set};
+
+/// Describes how serious a [`Violation`] is.
+///
+/// [`Violation`]: ./struct.Violation.html
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Severity {
+    /// The document breaks a rule that the JSON API specification requires.
+    Error,
+
+    /// The document is likely to be misunderstood by clients, but it does not break a
+    /// required rule of the specification.
+    Warning,
+}
+
+/// A spec conformance problem found by [`validate`].
+///
+/// [`validate`]: ./fn.validate.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Violation {
+    /// A human-readable explanation of the problem.
+    pub message: String,
+
+    /// A JSON Pointer to the member of the document that caused the problem.
+    pub pointer: Pointer,
+
+    /// How serious the problem is.
+    pub severity: Severity,
+
+    /// Private field for backwards compatibility.
+    _ext: (),
+}
+
+impl Violation {
+    /// Returns a new `Violation`.
+    pub fn new(severity: Severity, pointer: Pointer, message: String) -> Self {
+        Violation {
+            message,
+            pointer,
+            severity,
+            _ext: (),
+        }
+    }
+}
+
+/// Checks `doc` against the conformance rules of the JSON API specification and
+/// returns every violation that was found.
+///
+/// The following rules are checked:
+///
+/// - Every resource in `included` is reachable from the primary data through
+///   resource linkage (*[full linkage]*).
+/// - A resource in `included` does not duplicate a resource of the primary data.
+/// - Every relationship object contains at least one of `data`, `links`, or `meta`.
+/// - Every resource object has an `id`.
+/// - Link objects of a JSON API 1.0 document only contain `href` and `meta`.
+///
+/// Since `included` is a [`Set`], resources with the same type and id are already
+/// collapsed into one when a document is parsed.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::{self, Object, Relationship, Severity};
+///
+/// let mut article = Object::new("articles".parse()?, "1".to_owned());
+///
+/// article.relationships.insert("author".parse()?, Relationship::default());
+///
+/// let violations = doc::validate(&json_api::to_doc(article, None)?);
+///
+/// assert_eq!(violations.len(), 1);
+/// assert_eq!(violations[0].severity, Severity::Error);
+/// assert_eq!(violations[0].pointer, "/data/relationships/author");
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [full linkage]: http://jsonapi.org/format/#document-compound-documents
+/// [`Set`]: ../value/struct.Set.html
+pub fn validate(doc: &Document<Object>) -> Vec<Violation> {
+    let mut validator = Validator {
+        version: doc.jsonapi().version,
+        violations: Vec::new(),
+    };
+
+    validator.links(&child(&Pointer::root(), "links"), doc.links());
+
+    match *doc {
+        Document::Ok {
+            ref data,
+            ref included,
+            ..
+        } => {
+            let primary: Vec<_> = match *data {
+                Data::Member(ref item) => (**item)
+                    .iter()
+                    .map(|object| (Pointer::data(), object))
+                    .collect(),
+                Data::Collection(ref items) => items
+                    .iter()
+                    .enumerate()
+                    .map(|(index, object)| {
+                        let mut pointer = Pointer::data();
+
+                        pointer.push_index(index);
+                        (pointer, object)
+                    })
+                    .collect(),
+            };
+
+            for &(ref pointer, object) in &primary {
+                validator.object(pointer, object);
+            }
+
+            for (index, object) in included.iter().enumerate() {
+                validator.object(&included_pointer(index), object);
+            }
+
+            validator.duplicates(&primary, included);
+            validator.linkage(&primary, included);
+        }
+        Document::Err { ref errors, .. } => for (index, error) in errors.iter().enumerate() {
+            let mut pointer = Pointer::root();
+
+            pointer.push("errors").push_index(index).push("links");
+            validator.links(&pointer, &error.links);
+        },
+        Document::Meta { .. } => {}
+    }
+
+    validator.violations
+}
+
+struct Validator {
+    version: Version,
+    violations: Vec<Violation>,
+}
+
+impl Validator {
+    fn report(&mut self, severity: Severity, pointer: Pointer, message: String) {
+        self.violations.push(Violation::new(severity, pointer, message));
+    }
+
+    fn duplicates(&mut self, primary: &[(Pointer, &Object)], included: &Set<Object>) {
+        for &(_, object) in primary {
+            if let Some((index, _)) = included.get_full(&IdentRef::from(object)) {
+                let message = format!(
+                    "included resource duplicates the primary resource {}:{}",
+                    object.kind, object.id
+                );
+
+                self.report(Severity::Error, included_pointer(index), message);
+            }
+        }
+    }
+
+    fn linkage(&mut self, primary: &[(Pointer, &Object)], included: &Set<Object>) {
+        let mut reached = vec![false; included.len()];
+        let mut queue = primary.iter().map(|&(_, object)| object).collect::<Vec<_>>();
+
+        while let Some(object) = queue.pop() {
+            for ident in object.relationships.values().flat_map(linkage) {
+                if let Some((index, item)) = included.get_full(&IdentRef::from(ident)) {
+                    if !reached[index] {
+                        reached[index] = true;
+                        queue.push(item);
+                    }
+                }
+            }
+        }
+
+        for (index, object) in included.iter().enumerate() {
+            if !reached[index] {
+                let message = format!(
+                    "included resource {}:{} is not linked to the primary data",
+                    object.kind, object.id
+                );
+
+                self.report(Severity::Error, included_pointer(index), message);
+            }
+        }
+    }
+
+    fn links(&mut self, pointer: &Pointer, links: &Map<Key, Link>) {
+        if self.version != Version::V1 {
+            return;
+        }
+
+        for (key, link) in links {
+            let mut members = Vec::new();
+
+            if link.describedby.is_some() {
+                members.push("describedby");
+            }
+
+            if !link.hreflang.is_empty() {
+                members.push("hreflang");
+            }
+
+            if link.rel.is_some() {
+                members.push("rel");
+            }
+
+            if link.title.is_some() {
+                members.push("title");
+            }
+
+            if link.kind.is_some() {
+                members.push("type");
+            }
+
+            if !members.is_empty() {
+                let message = format!(
+                    "link object contains members that are not part of JSON API 1.0: {}",
+                    members.join(", ")
+                );
+
+                self.report(Severity::Warning, child(pointer, key), message);
+            }
+        }
+    }
+
+    fn object(&mut self, pointer: &Pointer, object: &Object) {
+        if object.id.is_empty() {
+            let message = "resource object is missing an id".to_owned();
+            self.report(Severity::Error, child(pointer, "id"), message);
+        }
+
+        self.links(&child(pointer, "links"), &object.links);
+
+        for (name, rel) in &object.relationships {
+            let mut rel_pointer = child(pointer, "relationships");
+
+            rel_pointer.push(name);
+
+            if rel.data.is_none() && rel.links.is_empty() && rel.meta.is_empty() {
+                let message =
+                    "relationship object must contain data, links, or meta".to_owned();
+
+                self.report(Severity::Error, rel_pointer.clone(), message);
+            }
+
+            self.links(&child(&rel_pointer, "links"), &rel.links);
+        }
+    }
+}
+
+fn child(pointer: &Pointer, token: &str) -> Pointer {
+    let mut pointer = pointer.clone();
+
+    pointer.push(token);
+    pointer
+}
+
+fn included_pointer(index: usize) -> Pointer {
+    let mut pointer = Pointer::root();
+
+    pointer.push("included").push_index(index);
+    pointer
+}
+
+fn linkage(rel: &Relationship) -> Vec<&Identifier> {
+    match rel.data {
+        Some(Data::Member(ref ident)) => (**ident).iter().collect(),
+        Some(Data::Collection(ref idents)) => idents.iter().collect(),
+        None => Vec::new(),
+    }
+}
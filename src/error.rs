@@ -1,12 +1,18 @@
 //! The `Error` struct, the `Result` alias, and other tools to handle failure.
 
+use std::fmt::Display;
 use std::str::Utf8Error;
 
+use doc::ErrorObject;
 use http::status::InvalidStatusCode as InvalidStatusCodeError;
 use http::uri::InvalidUri as InvalidUriError;
+use serde::{de, ser};
 use serde_json::Error as JsonError;
 use serde_qs::Error as QueryError;
 
+/// The maximum number of characters of an `href` that are included in an error.
+const MAX_HREF_LEN: usize = 64;
+
 error_chain!{
     foreign_links {
         InvalidStatusCode(InvalidStatusCodeError);
@@ -17,9 +23,24 @@ error_chain!{
     }
 
     errors {
-        InvalidMemberName(name: String) {
-            description("TODO")
-            display("TODO")
+        ErrorDocument(errors: Vec<ErrorObject>) {
+            description("A document contains one or more error(s).")
+            display("Document contains {} error(s)", errors.len())
+        }
+
+        InvalidLink(href: String, pointer: Option<String>, reason: String) {
+            description("A link contains an invalid URI reference.")
+            display("{}", match *pointer {
+                Some(ref pointer) => {
+                    format!(r#"invalid link "{}" at "{}": {}"#, href, pointer, reason)
+                }
+                None => format!(r#"invalid link "{}": {}"#, href, reason),
+            })
+        }
+
+        InvalidMemberName(path: String, reason: String) {
+            description("An object contains a key that is not a valid member name.")
+            display(r#"invalid member name at "{}": {}"#, path, reason)
         }
 
         MissingField(name: String) {
@@ -27,6 +48,30 @@ error_chain!{
             display(r#"missing required field "{}""#, name)
         }
 
+        QueryParameter(name: Option<String>, reason: String) {
+            description("A query parameter could not be decoded.")
+            display("{}", match *name {
+                Some(ref name) => format!(r#"invalid query parameter "{}": {}"#, name, reason),
+                None => format!("invalid query string: {}", reason),
+            })
+        }
+
+        ReservedMember(name: String) {
+            description("A resource object contains a field with a reserved name.")
+            display(r#"cannot use reserved member name "{}" as a field"#, name)
+        }
+
+        UnexpectedData(expected: String, found: String) {
+            description("The primary data or resource linkage has an unexpected shape.")
+            display("expected {} but found {}", expected, found)
+        }
+
+        UnresolvedIdentifier(kind: String, id: String, path: String) {
+            description("A relationship references a resource that is not included.")
+            display("relationship \"{}\" references a resource that is not included: \
+                     type \"{}\", id \"{}\"", path, kind, id)
+        }
+
         UnsupportedVersion(version: String) {
             description("The specified version of is not \
                          supported by this implementation.")
@@ -37,11 +82,62 @@ error_chain!{
 }
 
 impl Error {
+    pub fn error_document(errors: Vec<ErrorObject>) -> Self {
+        Self::from(ErrorKind::ErrorDocument(errors))
+    }
+
+    /// Returns an [`ErrorKind::InvalidLink`]. The `href` is truncated if it is longer
+    /// than 64 characters.
+    ///
+    /// [`ErrorKind::InvalidLink`]: ./enum.ErrorKind.html#variant.InvalidLink
+    pub fn invalid_link(href: &str, pointer: Option<&str>, reason: &str) -> Self {
+        Self::from(ErrorKind::invalid_link(href, pointer, reason))
+    }
+
     pub fn missing_field(name: &str) -> Self {
         Self::from(ErrorKind::MissingField(name.to_owned()))
     }
 
+    pub fn reserved_member(name: &str) -> Self {
+        Self::from(ErrorKind::ReservedMember(name.to_owned()))
+    }
+
+    pub fn unexpected_data(expected: &str, found: &str) -> Self {
+        Self::from(ErrorKind::UnexpectedData(expected.to_owned(), found.to_owned()))
+    }
+
+    pub fn unresolved_identifier(kind: &str, id: &str, path: &str) -> Self {
+        Self::from(ErrorKind::UnresolvedIdentifier(
+            kind.to_owned(),
+            id.to_owned(),
+            path.to_owned(),
+        ))
+    }
+
     pub fn unsupported_version(version: &str) -> Self {
         Self::from(ErrorKind::UnsupportedVersion(version.to_owned()))
     }
 }
+
+impl ErrorKind {
+    pub(crate) fn invalid_link(href: &str, pointer: Option<&str>, reason: &str) -> Self {
+        let href = match href.char_indices().nth(MAX_HREF_LEN) {
+            Some((index, _)) => format!("{}...", &href[..index]),
+            None => href.to_owned(),
+        };
+
+        ErrorKind::InvalidLink(href, pointer.map(|value| value.to_owned()), reason.to_owned())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::from(msg.to_string())
+    }
+}
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::from(msg.to_string())
+    }
+}
@@ -2,8 +2,12 @@
 
 #[macro_use]
 extern crate error_chain;
-extern crate ordermap;
+#[cfg(feature = "derive")]
+extern crate json_api_derive;
+extern crate indexmap;
+#[macro_use]
 extern crate percent_encoding;
+#[macro_use]
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
@@ -12,6 +16,16 @@ extern crate serde_qs;
 
 pub extern crate http;
 
+/// Records an event with the `metrics` module. Expands to nothing when the `metrics`
+/// feature is disabled.
+macro_rules! record {
+    ($($tt:tt)*) => {
+        #[cfg(feature = "metrics")]
+        ::metrics::$($tt)*;
+    };
+}
+
+#[macro_use]
 mod resource;
 
 mod sealed {
@@ -22,19 +36,57 @@ mod sealed {
 
 pub mod doc;
 pub mod error;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod query;
+pub mod schema;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 pub mod value;
 pub mod view;
 
 #[doc(inline)]
 pub use doc::Document;
 #[doc(inline)]
-pub use doc::{from_doc, from_reader, from_slice, from_str};
+pub use doc::{from_doc, from_doc_strict, from_doc_typed, from_reader, from_slice, from_str,
+              parse_reader, parse_slice, parse_str};
 #[doc(inline)]
-pub use doc::{to_doc, to_string, to_string_pretty, to_vec, to_vec_pretty, to_writer,
-              to_writer_pretty};
+pub use doc::{to_doc, to_doc_identifiers, to_doc_identifiers_paginated, to_doc_paginated,
+              to_doc_with, to_object, to_object_with_included, to_string, to_string_pretty,
+              to_vec, to_vec_pretty, to_writer, to_writer_pretty, to_writer_streamed,
+              to_writer_streamed_pretty};
 #[doc(inline)]
 pub use error::Error;
 pub use resource::Resource;
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub use json_api_derive::Resource;
 #[doc(inline)]
 pub use value::{from_value, to_value, Value};
+
+/// The media type of a JSON API document.
+pub const MEDIA_TYPE: &str = "application/vnd.api+json";
+
+/// Returns a `Content-Type` header with the value of [`MEDIA_TYPE`].
+///
+/// [`MEDIA_TYPE`]: ./constant.MEDIA_TYPE.html
+pub fn content_type_header() -> (http::header::HeaderName, http::header::HeaderValue) {
+    let value = http::header::HeaderValue::from_static(MEDIA_TYPE);
+    (http::header::CONTENT_TYPE, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use http::header::CONTENT_TYPE;
+
+    use super::{content_type_header, MEDIA_TYPE};
+
+    #[test]
+    fn media_type() {
+        let (name, value) = content_type_header();
+
+        assert_eq!(MEDIA_TYPE, "application/vnd.api+json");
+        assert_eq!(name, CONTENT_TYPE);
+        assert_eq!(value, MEDIA_TYPE);
+    }
+}
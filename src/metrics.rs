@@ -0,0 +1,146 @@
+//! Process-wide counters for documents that are rendered, parsed, and serialized.
+//!
+//! Counters are maintained with relaxed atomic operations and are only compiled when
+//! the `metrics` feature is enabled. When the feature is disabled, none of the
+//! bookkeeping below is present in the functions of this crate.
+//!
+//! # Example
+//!
+//! ```
+//! # extern crate json_api;
+//! #
+//! # use json_api::Error;
+//! #
+//! # fn example() -> Result<(), Error> {
+//! use json_api::{metrics, Document};
+//! use json_api::doc::Object;
+//!
+//! let before = metrics::snapshot();
+//! let _: Document<Object> = json_api::parse_str(r#"{ "data": null }"#)?;
+//! let after = metrics::snapshot();
+//!
+//! assert!(after.documents_parsed > before.documents_parsed);
+//! # Ok(())
+//! # }
+//! #
+//! # fn main() {
+//! # example().unwrap();
+//! # }
+//! ```
+
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use doc::{Document, PrimaryData};
+use error::Error;
+
+static DOCUMENTS_RENDERED: AtomicUsize = AtomicUsize::new(0);
+static DOCUMENTS_PARSED: AtomicUsize = AtomicUsize::new(0);
+static PARSE_FAILURES: AtomicUsize = AtomicUsize::new(0);
+static BYTES_SERIALIZED: AtomicUsize = AtomicUsize::new(0);
+static INCLUDED_RESOURCES_EMITTED: AtomicUsize = AtomicUsize::new(0);
+
+/// A point-in-time copy of the counters maintained by this module.
+///
+/// Counters are read individually, so a snapshot taken while other threads are
+/// rendering or parsing documents is not guaranteed to be consistent across fields.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct MetricsSnapshot {
+    /// The number of documents returned by `to_doc` and the functions built on top of
+    /// it, including the documents written by `to_writer_streamed`.
+    pub documents_rendered: u64,
+
+    /// The number of documents successfully deserialized by `parse_slice` and the
+    /// functions built on top of it.
+    pub documents_parsed: u64,
+
+    /// The number of documents that failed to deserialize.
+    pub parse_failures: u64,
+
+    /// The number of bytes of JSON written by `to_vec`, `to_string`, `to_writer`, and
+    /// their pretty-printed counterparts.
+    pub bytes_serialized: u64,
+
+    /// The number of resources in the `included` member of rendered documents.
+    pub included_resources_emitted: u64,
+
+    /// Private field for backwards compatibility.
+    _ext: (),
+}
+
+/// Returns the current value of each counter.
+pub fn snapshot() -> MetricsSnapshot {
+    MetricsSnapshot {
+        documents_rendered: load(&DOCUMENTS_RENDERED),
+        documents_parsed: load(&DOCUMENTS_PARSED),
+        parse_failures: load(&PARSE_FAILURES),
+        bytes_serialized: load(&BYTES_SERIALIZED),
+        included_resources_emitted: load(&INCLUDED_RESOURCES_EMITTED),
+        _ext: (),
+    }
+}
+
+/// Sets every counter back to zero.
+///
+/// This is primarily useful in tests. Since counters are shared by every thread in
+/// the process, prefer comparing two snapshots when other threads may be using this
+/// crate concurrently.
+pub fn reset() {
+    for counter in &[
+        &DOCUMENTS_RENDERED,
+        &DOCUMENTS_PARSED,
+        &PARSE_FAILURES,
+        &BYTES_SERIALIZED,
+        &INCLUDED_RESOURCES_EMITTED,
+    ] {
+        counter.store(0, Ordering::Relaxed);
+    }
+}
+
+fn load(counter: &AtomicUsize) -> u64 {
+    counter.load(Ordering::Relaxed) as u64
+}
+
+fn add(counter: &AtomicUsize, n: usize) {
+    counter.fetch_add(n, Ordering::Relaxed);
+}
+
+pub(crate) fn rendered<T: PrimaryData>(doc: &Document<T>) {
+    add(&DOCUMENTS_RENDERED, 1);
+
+    if let Document::Ok { ref included, .. } = *doc {
+        add(&INCLUDED_RESOURCES_EMITTED, included.len());
+    }
+}
+
+pub(crate) fn streamed(included: usize) {
+    add(&DOCUMENTS_RENDERED, 1);
+    add(&INCLUDED_RESOURCES_EMITTED, included);
+}
+
+pub(crate) fn parsed<T>(result: &Result<T, Error>) {
+    match *result {
+        Ok(_) => add(&DOCUMENTS_PARSED, 1),
+        Err(_) => add(&PARSE_FAILURES, 1),
+    }
+}
+
+pub(crate) fn serialized(n: usize) {
+    add(&BYTES_SERIALIZED, n);
+}
+
+/// Wraps an IO stream to count the bytes that are written to it.
+pub(crate) struct Counted<W>(pub W);
+
+impl<W: Write> Write for Counted<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.0.write(buf)?;
+
+        serialized(n);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
@@ -1,14 +1,14 @@
 use std::mem;
 
 use error::Error;
-use query::{Direction, Page, Query, Sort};
+use query::{fieldset, Direction, Page, Query, Sort};
 use value::{Key, Map, Path, Set, Value};
 
 /// An implementation of the "builder pattern" that can be used to construct a
 /// new query.
 #[derive(Default)]
 pub struct Builder {
-    fields: Vec<(String, Vec<String>)>,
+    fields: Vec<Fields>,
     filter: Vec<(String, Value)>,
     include: Vec<String>,
     page: Option<Page>,
@@ -18,6 +18,9 @@ pub struct Builder {
 impl Builder {
     /// Attempt to construct a new query from the previously supplied values.
     pub fn build(&mut self) -> Result<Query, Error> {
+        let mut fields = Map::new();
+        let mut excluded = Map::new();
+
         Ok(Query {
             sort: {
                 self.sort
@@ -35,19 +38,31 @@ impl Builder {
                     .collect::<Result<Map<Path, Value>, Error>>()?
             },
             fields: {
-                self.fields
-                    .drain(..)
-                    .map(|(key, mut value)| {
-                        let key = key.parse::<Key>()?;
-                        let value = value
-                            .drain(..)
-                            .map(|item| item.parse())
-                            .collect::<Result<Set, Error>>()?;
-
-                        Ok((key, value))
-                    })
-                    .collect::<Result<Map<Key, Set>, Error>>()?
+                for item in self.fields.drain(..) {
+                    match item {
+                        Fields::Raw(key, value) => {
+                            let key = key.parse::<Key>()?;
+                            let iter = value.iter().map(String::as_str);
+                            let (value, exclude) = fieldset::parse(iter)?;
+
+                            if exclude {
+                                fields.remove(&key);
+                                excluded.insert(key, value);
+                            } else {
+                                excluded.remove(&key);
+                                fields.insert(key, value);
+                            }
+                        }
+                        Fields::Set(key, value) => {
+                            excluded.remove(&key);
+                            fields.insert(key, value);
+                        }
+                    }
+                }
+
+                fields
             },
+            excluded,
             include: {
                 self.include
                     .drain(..)
@@ -68,7 +83,14 @@ impl Builder {
         let key = key.into();
         let value = iter.into_iter().map(|i| i.into()).collect();
 
-        self.fields.push((key, value));
+        self.fields.push(Fields::Raw(key, value));
+        self
+    }
+
+    /// Sets the fields of `kind` to a set of member names that have already been
+    /// parsed. The set replaces any fields of `kind` that were supplied before it.
+    pub fn fields_set(&mut self, kind: Key, fields: Set) -> &mut Self {
+        self.fields.push(Fields::Set(kind, fields));
         self
     }
 
@@ -97,6 +119,11 @@ impl Builder {
         self
     }
 
+    pub fn page_offset(&mut self, offset: u64, limit: Option<u64>) -> &mut Self {
+        self.page = Some(Page::with_offset(offset, limit));
+        self
+    }
+
     pub fn sort<F>(&mut self, field: F, direction: Direction) -> &mut Self
     where
         F: Into<String>,
@@ -105,3 +132,9 @@ impl Builder {
         self
     }
 }
+
+/// The fields of a resource type that are supplied to a `Builder`.
+enum Fields {
+    Raw(String, Vec<String>),
+    Set(Key, Set),
+}
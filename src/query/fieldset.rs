@@ -0,0 +1,211 @@
+use error::Error;
+use value::collections::set;
+use value::{Key, Set};
+
+/// The effective sparse fieldset of a resource type.
+///
+/// Returned from [`Query::fieldset`].
+///
+/// [`Query::fieldset`]: ./struct.Query.html#method.fieldset
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Fieldset<'a> {
+    /// The client did not specify a fieldset for the type. Every field should be
+    /// included.
+    All,
+
+    /// The client specified a fieldset for the type. Only the fields in the set should
+    /// be included. The set may be empty, in which case no fields should be included.
+    Only(&'a Set<Key>),
+
+    /// The client specified a negated fieldset for the type (i.e `fields[articles]=-body`).
+    /// Every field except the fields in the set should be included.
+    Except(&'a Set<Key>),
+}
+
+impl<'a> Fieldset<'a> {
+    /// Returns `true` if the field should be included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::query::{self, Fieldset};
+    ///
+    /// let query = query::from_str("fields[articles]=title&fields[people]=&fields[tags]=-color")?;
+    ///
+    /// assert!(query.fieldset(&"articles".parse()?).contains("title"));
+    /// assert!(!query.fieldset(&"articles".parse()?).contains("body"));
+    /// assert!(!query.fieldset(&"people".parse()?).contains("name"));
+    /// assert!(query.fieldset(&"tags".parse()?).contains("name"));
+    /// assert!(!query.fieldset(&"tags".parse()?).contains("color"));
+    /// assert_eq!(query.fieldset(&"comments".parse()?), Fieldset::All);
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn contains(&self, field: &str) -> bool {
+        match *self {
+            Fieldset::All => true,
+            Fieldset::Only(set) => set.contains(field),
+            Fieldset::Except(set) => !set.contains(field),
+        }
+    }
+
+    /// Returns `true` if the fieldset does not include every field.
+    pub fn is_restricted(&self) -> bool {
+        match *self {
+            Fieldset::All => false,
+            Fieldset::Only(_) | Fieldset::Except(_) => true,
+        }
+    }
+
+    /// Returns an iterator over the fields in the fieldset.
+    ///
+    /// The fields of a resource type are not known to a query, so the iterator of
+    /// `Fieldset::All` and `Fieldset::Except` is always empty. Use [`is_restricted`]
+    /// to distinguish `Fieldset::All` from an explicitly empty fieldset.
+    ///
+    /// [`is_restricted`]: #method.is_restricted
+    pub fn iter(&self) -> FieldsetIter<'a> {
+        let iter = match *self {
+            Fieldset::All | Fieldset::Except(_) => None,
+            Fieldset::Only(set) => Some(set.iter()),
+        };
+
+        FieldsetIter { iter }
+    }
+}
+
+impl<'a> IntoIterator for Fieldset<'a> {
+    type Item = &'a Key;
+    type IntoIter = FieldsetIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the fields of a [`Fieldset`].
+///
+/// [`Fieldset`]: ./enum.Fieldset.html
+pub struct FieldsetIter<'a> {
+    iter: Option<set::Iter<'a, Key>>,
+}
+
+impl<'a> Iterator for FieldsetIter<'a> {
+    type Item = &'a Key;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.as_mut().and_then(Iterator::next)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter
+            .as_ref()
+            .map_or((0, Some(0)), Iterator::size_hint)
+    }
+}
+
+/// Parses the fields of a fieldset. Returns `true` along with the fields if each field
+/// is prefixed with `'-'`, in which case the fields should be excluded.
+pub(crate) fn parse<'a, I>(iter: I) -> Result<(Set, bool), Error>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut fields = Set::new();
+    let mut negated = None;
+
+    for item in iter {
+        let (field, exclude) = match item.starts_with('-') {
+            true => (&item[1..], true),
+            false => (item, false),
+        };
+
+        if *negated.get_or_insert(exclude) != exclude {
+            bail!("a fieldset cannot contain both included and excluded fields");
+        }
+
+        fields.insert(field.parse()?);
+    }
+
+    Ok((fields, negated.unwrap_or(false)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Fieldset;
+
+    use query::{self, Query};
+    use value::Key;
+
+    #[test]
+    fn fieldset_absent() {
+        let query = Query::new();
+        let fieldset = query.fieldset(&"articles".parse().unwrap());
+
+        assert_eq!(fieldset, Fieldset::All);
+        assert!(fieldset.contains("title"));
+        assert!(!fieldset.is_restricted());
+        assert_eq!(fieldset.iter().count(), 0);
+    }
+
+    #[test]
+    fn fieldset_empty() {
+        let query = query::from_str("fields[articles]=").unwrap();
+        let fieldset = query.fieldset(&"articles".parse().unwrap());
+
+        assert!(fieldset.is_restricted());
+        assert!(!fieldset.contains("title"));
+        assert!(!fieldset.contains("body"));
+        assert_eq!(fieldset.iter().count(), 0);
+        assert_eq!(query::to_string(&query).unwrap(), "fields%5Barticles%5D=");
+    }
+
+    #[test]
+    fn fieldset_only() {
+        let query = query::from_str("fields[articles]=title,body").unwrap();
+        let fieldset = query.fieldset(&"articles".parse().unwrap());
+        let fields = fieldset.iter().map(|key| &**key).collect::<Vec<_>>();
+
+        assert!(fieldset.is_restricted());
+        assert!(fieldset.contains("title"));
+        assert!(!fieldset.contains("published-at"));
+        assert_eq!(fields, vec!["title", "body"]);
+
+        for key in fieldset {
+            let _: &Key = key;
+        }
+    }
+
+    #[test]
+    fn fieldset_except() {
+        let query = query::from_str("fields[articles]=-body,-published-at").unwrap();
+        let fieldset = query.fieldset(&"articles".parse().unwrap());
+
+        assert!(fieldset.is_restricted());
+        assert!(fieldset.contains("title"));
+        assert!(!fieldset.contains("body"));
+        assert!(!fieldset.contains("published-at"));
+        assert_eq!(fieldset.iter().count(), 0);
+        assert_eq!(
+            query::to_string(&query).unwrap(),
+            "fields%5Barticles%5D=-body%2C-published-at"
+        );
+    }
+
+    #[test]
+    fn fieldset_mixed() {
+        assert!(query::from_str("fields[articles]=title,-body").is_err());
+        assert!(Query::builder()
+            .fields("articles", vec!["-title", "body"])
+            .build()
+            .is_err());
+    }
+}
@@ -1,21 +1,28 @@
 //! An API for working with well-known query parameters.
 
 mod builder;
+mod fieldset;
 mod page;
+mod parse;
 mod sort;
 
-use std::fmt::{self, Formatter};
+use std::cell::RefCell;
+use std::fmt::{self, Display, Formatter};
 
 use percent_encoding::percent_decode;
-use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+use serde::de::{self, Deserialize, DeserializeSeed, Deserializer, MapAccess, Visitor};
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 use serde_qs;
 
-use error::Error;
+use error::{Error, ErrorKind};
 use value::{Key, Map, Path, Set, Value};
 
+use self::page::PageSeed;
+
 pub use self::builder::Builder;
+pub use self::fieldset::{Fieldset, FieldsetIter};
 pub use self::page::Page;
+pub use self::parse::ParseOptions;
 pub use self::sort::{Direction, Sort};
 
 /// Represents well-known query parameters.
@@ -38,9 +45,26 @@ pub struct Query {
     /// [sparse fieldsets]: http://jsonapi.org/format/#fetching-sparse-fieldsets
     pub fields: Map<Key, Set>,
 
+    /// A map where each key is a type name and the value is a set of field names that
+    /// the client does *not* wish to receive for the given type. Every other field of
+    /// the type is included.
+    ///
+    /// This is an extension of the specification. When decoding a query string, a
+    /// fieldset in which each field name is prefixed with `'-'` (i.e
+    /// `fields[articles]=-body`) is interpreted to mean "every field except 'body'".
+    /// Included and excluded field names cannot be mixed in a single fieldset. If a type
+    /// is present in both [`fields`] and this map, [`fields`] takes precedence.
+    ///
+    /// [`fields`]: #structfield.fields
+    pub excluded: Map<Key, Set>,
+
     /// A map where each key is a field path and the value is the value the client
     /// would like each item in the return document to have for the given field.
     ///
+    /// When decoding a query string, each value is a string. An empty value (i.e
+    /// `filter[deleted-at]=`) or the literal string `null` is decoded as `Value::Null`.
+    /// When encoding a query string, `Value::Null` is encoded as an empty value.
+    ///
     /// For more information, check out the *[filter]* section of the JSON API
     /// specification.
     ///
@@ -93,6 +117,103 @@ impl Query {
     pub fn builder() -> Builder {
         Default::default()
     }
+
+    /// Returns the effective sparse fieldset of the resource type `kind`.
+    ///
+    /// If the client did not specify a fieldset for `kind`, `Fieldset::All` is
+    /// returned. An explicitly empty fieldset (i.e `fields[articles]=`) is returned
+    /// as `Fieldset::Only` with an empty set. A negated fieldset (i.e
+    /// `fields[articles]=-body`) is returned as `Fieldset::Except`.
+    pub fn fieldset(&self, kind: &Key) -> Fieldset {
+        if let Some(set) = self.fields.get(kind) {
+            return Fieldset::Only(set);
+        }
+
+        match self.excluded.get(kind) {
+            Some(set) => Fieldset::Except(set),
+            None => Fieldset::All,
+        }
+    }
+
+    /// Returns `true` if `self` and `other` describe the same request.
+    ///
+    /// Fieldsets, filters, and include paths are compared without regard to the order
+    /// in which they were added. Sort instructions are compared in order, since the
+    /// first instruction takes precedence over the ones that follow it. A query without
+    /// pagination parameters is equal to a query that requests the first page without
+    /// a page size.
+    ///
+    /// The `PartialEq` implementation of `Query` compares each member as is. The
+    /// order of sort instructions is ignored and `page` must match exactly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::query;
+    ///
+    /// let a = query::from_str("include=author,comments&page[number]=1")?;
+    /// let b = query::from_str("include=comments,author")?;
+    ///
+    /// assert!(a.semantic_eq(&b));
+    ///
+    /// let a = query::from_str("sort=title,-published-at")?;
+    /// let b = query::from_str("sort=-published-at,title")?;
+    ///
+    /// assert!(!a.semantic_eq(&b));
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn semantic_eq(&self, other: &Query) -> bool {
+        self.fields == other.fields && self.excluded == other.excluded
+            && self.filter == other.filter && self.include == other.include
+            && self.page.unwrap_or_default() == other.page.unwrap_or_default()
+            && self.sort.iter().eq(other.sort.iter())
+    }
+}
+
+impl Display for Query {
+    /// Formats the query as a query string.
+    ///
+    /// Encoding a query does not fail in practice. If it does, the `Debug`
+    /// representation of the query is written instead so the `Display` impl can be
+    /// used for logging. Use [`to_string`] if the error should be handled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::query::Query;
+    ///
+    /// let query = Query::builder().include("author").page(2, None).build()?;
+    /// assert_eq!(format!("{}", query), "include=author&page%5Bnumber%5D=2");
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`to_string`]: ./fn.to_string.html
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match to_string(self) {
+            Ok(value) => f.write_str(&value),
+            Err(_) => write!(f, "{:?}", self),
+        }
+    }
 }
 
 impl<'de> Deserialize<'de> for Query {
@@ -100,21 +221,26 @@ impl<'de> Deserialize<'de> for Query {
     where
         D: Deserializer<'de>,
     {
-        const FIELDS: &[&str] = &["fields", "filter", "include", "page", "sort"];
+        QuerySeed(&InvalidParameter::default()).deserialize(deserializer)
+    }
+}
 
-        #[derive(Debug, Deserialize)]
-        #[serde(field_identifier, rename_all = "lowercase")]
-        enum Field {
-            Fields,
-            Filter,
-            Include,
-            Page,
-            Sort,
-        }
+/// Deserializes a `Query` while recording the query parameter that caused an error in
+/// the contained `InvalidParameter`.
+struct QuerySeed<'a>(&'a InvalidParameter);
 
-        struct QueryVisitor;
+impl<'a, 'de> DeserializeSeed<'de> for QuerySeed<'a> {
+    type Value = Query;
 
-        impl<'de> Visitor<'de> for QueryVisitor {
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["fields", "filter", "include", "page", "sort"];
+
+        struct QueryVisitor<'a>(&'a InvalidParameter);
+
+        impl<'a, 'de> Visitor<'de> for QueryVisitor<'a> {
             type Value = Query;
 
             fn expecting(&self, f: &mut Formatter) -> fmt::Result {
@@ -125,40 +251,86 @@ impl<'de> Deserialize<'de> for Query {
             where
                 A: MapAccess<'de>,
             {
-                use serde::de::Error;
-
+                let invalid = self.0;
                 let mut fields = None;
+                let mut excluded = None;
                 let mut filter = None;
                 let mut include = None;
                 let mut page = None;
                 let mut sort = None;
 
-                while let Some(key) = access.next_key()? {
-                    match key {
-                        Field::Fields => {
-                            let data = access.next_value::<Map<_, String>>()?;
+                while let Some(key) = access.next_key::<String>()? {
+                    match key.as_str() {
+                        "fields" => {
+                            let data = access
+                                .next_value::<Map<String, String>>()
+                                .map_err(|e| invalid.parameter("fields", e))?;
                             let mut map = Map::with_capacity(data.len());
+                            let mut negated = Map::new();
 
                             for (field, value) in data {
-                                let value = value.parse().map_err(Error::custom)?;
-                                map.insert(field, value);
+                                let name = format!("fields[{}]", field);
+                                let field = field
+                                    .parse()
+                                    .map_err(|e| invalid.custom(&name, e))?;
+                                let (value, exclude) = if value.is_empty() {
+                                    (Set::new(), false)
+                                } else {
+                                    fieldset::parse(value.split(','))
+                                        .map_err(|e| invalid.custom(&name, e))?
+                                };
+
+                                if exclude {
+                                    negated.insert(field, value);
+                                } else {
+                                    map.insert(field, value);
+                                }
                             }
 
                             fields = Some(map);
+                            excluded = Some(negated);
                         }
-                        Field::Filter => {
-                            filter = Some(access.next_value()?);
+                        "filter" => {
+                            let data = access
+                                .next_value::<Map<String, Value>>()
+                                .map_err(|e| invalid.parameter("filter", e))?;
+                            let mut map = Map::with_capacity(data.len());
+
+                            for (field, value) in data {
+                                let field = field.parse().map_err(|e| {
+                                    invalid.custom(&format!("filter[{}]", field), e)
+                                })?;
+
+                                map.insert(field, filter_value(value));
+                            }
+
+                            filter = Some(map);
                         }
-                        Field::Include => {
-                            let data = access.next_value::<String>()?;
-                            include = Some(data.parse().map_err(Error::custom)?);
+                        "include" => {
+                            let data = access
+                                .next_value::<String>()
+                                .map_err(|e| invalid.parameter("include", e))?;
+
+                            include = Some(data.parse()
+                                .map_err(|e| invalid.custom("include", e))?);
                         }
-                        Field::Page => {
-                            page = Some(access.next_value()?);
+                        "page" => {
+                            let data = access
+                                .next_value_seed(PageSeed(invalid))
+                                .map_err(|e| invalid.parameter("page", e))?;
+
+                            page = Some(data);
+                        }
+                        "sort" => {
+                            let data = access
+                                .next_value::<String>()
+                                .map_err(|e| invalid.parameter("sort", e))?;
+
+                            sort = Some(data.parse()
+                                .map_err(|e| invalid.custom("sort", e))?);
                         }
-                        Field::Sort => {
-                            let data = access.next_value::<String>()?;
-                            sort = Some(data.parse().map_err(Error::custom)?);
+                        _ => {
+                            return Err(invalid.custom(&key, "unknown query parameter"));
                         }
                     }
                 }
@@ -166,6 +338,7 @@ impl<'de> Deserialize<'de> for Query {
                 Ok(Query {
                     page,
                     fields: fields.unwrap_or_default(),
+                    excluded: excluded.unwrap_or_default(),
                     filter: filter.unwrap_or_default(),
                     include: include.unwrap_or_default(),
                     sort: sort.unwrap_or_default(),
@@ -174,7 +347,7 @@ impl<'de> Deserialize<'de> for Query {
             }
         }
 
-        deserializer.deserialize_struct("Query", FIELDS, QueryVisitor)
+        deserializer.deserialize_struct("Query", FIELDS, QueryVisitor(self.0))
     }
 }
 
@@ -185,13 +358,20 @@ impl Serialize for Query {
     {
         let mut state = serializer.serialize_struct("Query", 5)?;
 
-        if !self.fields.is_empty() {
-            let mut fields = Map::with_capacity(self.fields.len());
+        if !self.fields.is_empty() || !self.excluded.is_empty() {
+            let mut fields = Map::with_capacity(self.fields.len() + self.excluded.len());
 
             for (key, value) in &self.fields {
                 fields.insert(key, value.to_string());
             }
 
+            for (key, value) in &self.excluded {
+                if !fields.contains_key(key) {
+                    let value = value.iter().map(|field| format!("-{}", field));
+                    fields.insert(key, value.collect::<Vec<_>>().join(","));
+                }
+            }
+
             state.serialize_field("fields", &fields)?;
         }
 
@@ -216,12 +396,35 @@ impl Serialize for Query {
 }
 
 /// Deserialize a `Query` from the bytes of a percent encoded query string.
+///
+/// If a query parameter can not be decoded, the returned error is an
+/// [`ErrorKind::QueryParameter`] that contains the name of the parameter when it is
+/// known.
+///
+/// [`ErrorKind::QueryParameter`]: ../error/enum.ErrorKind.html#variant.QueryParameter
 pub fn from_slice(data: &[u8]) -> Result<Query, Error> {
     let value = percent_decode(data).decode_utf8()?;
-    Ok(serde_qs::from_bytes(value.as_bytes())?)
+    from_decoded(&value)
+}
+
+/// Deserialize a `Query` from a query string that is already percent decoded.
+fn from_decoded(value: &str) -> Result<Query, Error> {
+    let invalid = InvalidParameter::default();
+    let config = serde_qs::Config::default();
+    let result = serde_qs::QsDeserializer::with_config(&config, value.as_bytes())
+        .and_then(|deserializer| QuerySeed(&invalid).deserialize(deserializer));
+
+    result.map_err(|e| {
+        let (name, reason) = invalid.0.into_inner().unwrap_or_else(|| (None, e.to_string()));
+        Error::with_chain(e, ErrorKind::QueryParameter(name, reason))
+    })
 }
 
 /// Deserialize a `Query` from a percent encoded query string.
+///
+/// See [`from_slice`] for more information about the errors that can occur.
+///
+/// [`from_slice`]: ./fn.from_slice.html
 pub fn from_str(data: &str) -> Result<Query, Error> {
     from_slice(data.as_bytes())
 }
@@ -241,3 +444,65 @@ pub fn to_string(query: &Query) -> Result<String, Error> {
 pub fn to_vec(query: &Query) -> Result<Vec<u8>, Error> {
     to_string(query).map(Vec::from)
 }
+
+/// Decodes an empty value or the literal string `null` in a filter as `Value::Null`.
+fn filter_value(value: Value) -> Value {
+    match value {
+        Value::String(ref data) if data.is_empty() || data == "null" => Value::Null,
+        Value::Array(data) => data.into_iter().map(filter_value).collect(),
+        Value::Object(data) => data.into_iter()
+            .map(|(key, value)| (key, filter_value(value)))
+            .collect(),
+        value => value,
+    }
+}
+
+/// The name of the query parameter that caused a deserialization error and the reason
+/// that it is invalid. The error type of `serde_qs` does not retain the message of a
+/// custom error, so it is recorded here when the error is created.
+#[derive(Default)]
+struct InvalidParameter(RefCell<Option<(Option<String>, String)>>);
+
+impl InvalidParameter {
+    /// Records `name` as the query parameter that caused `error` unless a more specific
+    /// parameter has already been recorded. The message of `error` is not used since it
+    /// may have been discarded by `serde_qs`.
+    fn parameter<E>(&self, name: &str, error: E) -> E {
+        self.record(Some(name), &"invalid value");
+        error
+    }
+
+    /// Returns a deserialization error for the query parameter `name`.
+    fn custom<E, T>(&self, name: &str, reason: T) -> E
+    where
+        E: de::Error,
+        T: Display,
+    {
+        self.record(Some(name), &reason);
+        E::custom(reason)
+    }
+
+    /// Returns a deserialization error for a value of a query parameter that is not yet
+    /// known. The name of the parameter is recorded by the caller.
+    fn value<E, T>(&self, reason: T) -> E
+    where
+        E: de::Error,
+        T: Display,
+    {
+        self.record(None, &reason);
+        E::custom(reason)
+    }
+
+    fn record<T: Display>(&self, name: Option<&str>, reason: &T) {
+        let mut invalid = self.0.borrow_mut();
+
+        match *invalid {
+            Some((ref mut prev, _)) => if prev.is_none() {
+                *prev = name.map(|value| value.to_owned());
+            },
+            None => {
+                *invalid = Some((name.map(|value| value.to_owned()), reason.to_string()));
+            }
+        }
+    }
+}
@@ -1,8 +1,10 @@
 use std::fmt::{self, Formatter};
 
-use serde::de::{Deserialize, Deserializer};
+use serde::de::{Deserialize, DeserializeSeed, Deserializer};
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 
+use super::InvalidParameter;
+
 /// Limit and offset based pagination parameters.
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Page {
@@ -14,6 +16,15 @@ pub struct Page {
     /// Optionally specifies the maximum number of items to include per page.
     pub size: Option<u64>,
 
+    /// The number of items to skip, decoded from `page[offset]`. This is an
+    /// alternative to `number` for offset based pagination. The pagination links that
+    /// this crate generates only use `number` and `size`.
+    pub offset: Option<u64>,
+
+    /// Optionally specifies the maximum number of items to include when `offset` is
+    /// used. Decoded from `page[limit]`.
+    pub limit: Option<u64>,
+
     /// Private field for backwards compatibility.
     _ext: (),
 }
@@ -37,9 +48,43 @@ impl Page {
         Page {
             number,
             size,
+            offset: None,
+            limit: None,
             _ext: (),
         }
     }
+
+    /// Returns a new `Page` that skips the first `offset` items and includes at most
+    /// `limit` items.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::query::{self, Page, Query};
+    ///
+    /// let query = Query::builder().page_offset(40, Some(20)).build()?;
+    ///
+    /// assert_eq!(query.page, Some(Page::with_offset(40, Some(20))));
+    /// assert_eq!(query::to_string(&query)?, "page%5Boffset%5D=40&page%5Blimit%5D=20");
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn with_offset(offset: u64, limit: Option<u64>) -> Self {
+        let mut page = Page::new(1, None);
+
+        page.offset = Some(offset);
+        page.limit = limit;
+        page
+    }
 }
 
 impl Default for Page {
@@ -53,20 +98,28 @@ impl<'de> Deserialize<'de> for Page {
     where
         D: Deserializer<'de>,
     {
-        use serde::de::{MapAccess, Visitor};
+        PageSeed(&InvalidParameter::default()).deserialize(deserializer)
+    }
+}
 
-        const FIELDS: &[&str] = &["number", "size"];
+/// Deserializes a `Page` while recording the query parameter that caused an error in
+/// the contained `InvalidParameter`.
+pub(super) struct PageSeed<'a>(pub(super) &'a InvalidParameter);
 
-        #[derive(Debug, Deserialize)]
-        #[serde(field_identifier, rename_all = "lowercase")]
-        enum Field {
-            Number,
-            Size,
-        }
+impl<'a, 'de> DeserializeSeed<'de> for PageSeed<'a> {
+    type Value = Page;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::{MapAccess, Visitor};
+
+        const FIELDS: &[&str] = &["number", "size", "offset", "limit"];
 
-        struct PageVisitor;
+        struct PageVisitor<'a>(&'a InvalidParameter);
 
-        impl<'de> Visitor<'de> for PageVisitor {
+        impl<'a, 'de> Visitor<'de> for PageVisitor<'a> {
             type Value = Page;
 
             fn expecting(&self, f: &mut Formatter) -> fmt::Result {
@@ -77,25 +130,86 @@ impl<'de> Deserialize<'de> for Page {
             where
                 A: MapAccess<'de>,
             {
+                let invalid = self.0;
                 let mut number = None;
                 let mut size = None;
+                let mut offset = None;
+                let mut limit = None;
 
-                while let Some(key) = access.next_key()? {
-                    match key {
-                        Field::Number => {
-                            number = access.next_value()?;
-                        }
-                        Field::Size => {
-                            size = access.next_value()?;
-                        }
-                    }
+                while let Some(key) = access.next_key::<String>()? {
+                    let name = format!("page[{}]", key);
+                    let value = match key.as_str() {
+                        "number" => &mut number,
+                        "size" => &mut size,
+                        "offset" => &mut offset,
+                        "limit" => &mut limit,
+                        _ => return Err(invalid.custom(&name, "unknown query parameter")),
+                    };
+
+                    *value = access
+                        .next_value_seed(Integer(invalid))
+                        .map_err(|e| invalid.parameter(&name, e))?;
                 }
 
-                Ok(Page::new(number.unwrap_or(1), size))
+                let mut page = Page::new(number.unwrap_or(1), size);
+
+                page.offset = offset;
+                page.limit = limit;
+                Ok(page)
+            }
+        }
+
+        deserializer.deserialize_struct("Page", FIELDS, PageVisitor(self.0))
+    }
+}
+
+/// Deserializes an optional pagination parameter from an integer or a string of
+/// digits.
+struct Integer<'a>(&'a InvalidParameter);
+
+impl<'a, 'de> DeserializeSeed<'de> for Integer<'a> {
+    type Value = Option<u64>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::{Error, Visitor};
+
+        struct IntegerVisitor<'a>(&'a InvalidParameter);
+
+        impl<'a, 'de> Visitor<'de> for IntegerVisitor<'a> {
+            type Value = Option<u64>;
+
+            fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+                write!(f, "a non-negative integer")
+            }
+
+            fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                deserializer.deserialize_any(self)
+            }
+
+            fn visit_u64<E: Error>(self, value: u64) -> Result<Self::Value, E> {
+                Ok(Some(value))
+            }
+
+            fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+                value.parse().map(Some).map_err(|e| self.0.value(e))
             }
         }
 
-        deserializer.deserialize_struct("Page", FIELDS, PageVisitor)
+        deserializer.deserialize_option(IntegerVisitor(self.0))
     }
 }
 
@@ -104,7 +218,7 @@ impl Serialize for Page {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("Page", 2)?;
+        let mut state = serializer.serialize_struct("Page", 4)?;
         let number = &self.number;
         let size = &self.size;
 
@@ -116,12 +230,22 @@ impl Serialize for Page {
             state.serialize_field("size", value)?;
         }
 
+        if let Some(ref value) = self.offset {
+            state.serialize_field("offset", value)?;
+        }
+
+        if let Some(ref value) = self.limit {
+            state.serialize_field("limit", value)?;
+        }
+
         state.end()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use serde_json;
+
     use super::Page;
 
     #[test]
@@ -147,4 +271,23 @@ mod tests {
             assert_eq!(page.size, size);
         }
     }
+
+    #[test]
+    fn page_with_offset() {
+        let page = Page::with_offset(40, Some(20));
+
+        assert_eq!(page.number, 1);
+        assert_eq!(page.size, None);
+        assert_eq!(page.offset, Some(40));
+        assert_eq!(page.limit, Some(20));
+
+        let json = serde_json::to_string(&page).unwrap();
+
+        assert_eq!(json, r#"{"offset":40,"limit":20}"#);
+        assert_eq!(serde_json::from_str::<Page>(&json).unwrap(), page);
+        assert_eq!(
+            serde_json::from_str::<Page>(r#"{"limit":"5"}"#).unwrap().limit,
+            Some(5)
+        );
+    }
 }
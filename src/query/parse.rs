@@ -0,0 +1,114 @@
+use std::borrow::Cow;
+
+use percent_encoding::percent_decode;
+
+use error::Error;
+use query::{self, Query};
+
+/// Options that control how a query string is parsed.
+///
+/// The default value of each option preserves the behavior of [`from_slice`].
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::query::{self, ParseOptions};
+///
+/// assert!(query::from_str("fields.articles=title").is_err());
+///
+/// let mut options = ParseOptions::new();
+/// options.dotted_fields = true;
+///
+/// let dotted = options.parse_str("fields.articles=title,body")?;
+/// let bracketed = query::from_str("fields%5Barticles%5D=title,body")?;
+///
+/// assert_eq!(dotted, bracketed);
+/// assert_eq!(query::to_string(&dotted)?, "fields%5Barticles%5D=title%2Cbody");
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`from_slice`]: ./fn.from_slice.html
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ParseOptions {
+    /// Accept sparse fieldsets in the form `fields.articles=title` as well as the
+    /// bracketed form `fields[articles]=title` that the specification requires. Both
+    /// forms decode to the same [`fields`]. A query is always serialized in the
+    /// bracketed form, so only the bracketed form round-trips. Defaults to `false`.
+    ///
+    /// [`fields`]: ./struct.Query.html#structfield.fields
+    pub dotted_fields: bool,
+
+    /// Private field for backwards compatibility.
+    _ext: (),
+}
+
+impl ParseOptions {
+    /// Returns the default parse options.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Deserialize a `Query` from the bytes of a percent encoded query string.
+    pub fn parse_slice(&self, data: &[u8]) -> Result<Query, Error> {
+        let value = percent_decode(data).decode_utf8()?;
+
+        if self.dotted_fields {
+            query::from_decoded(&bracket_fields(&value))
+        } else {
+            query::from_decoded(&value)
+        }
+    }
+
+    /// Deserialize a `Query` from a percent encoded query string.
+    pub fn parse_str(&self, data: &str) -> Result<Query, Error> {
+        self.parse_slice(data.as_bytes())
+    }
+}
+
+/// Rewrites each `fields.<type>` parameter in `value` as `fields[<type>]`.
+fn bracket_fields(value: &str) -> Cow<str> {
+    if !value.starts_with("fields.") && !value.contains("&fields.") {
+        return Cow::Borrowed(value);
+    }
+
+    let params = value.split('&').map(|param| {
+        if !param.starts_with("fields.") {
+            return Cow::Borrowed(param);
+        }
+
+        let (name, rest) = match param.find('=') {
+            Some(idx) => param.split_at(idx),
+            None => (param, ""),
+        };
+
+        Cow::Owned(format!("fields[{}]{}", &name["fields.".len()..], rest))
+    });
+
+    Cow::Owned(params.collect::<Vec<_>>().join("&"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bracket_fields;
+
+    #[test]
+    fn bracket_fields_params() {
+        assert_eq!(bracket_fields("include=author"), "include=author");
+        assert_eq!(bracket_fields("fields.articles=title"), "fields[articles]=title");
+        assert_eq!(
+            bracket_fields("include=author&fields.people=name&fields.articles"),
+            "include=author&fields[people]=name&fields[articles]"
+        );
+        assert_eq!(bracket_fields("filter[fields.x]=1"), "filter[fields.x]=1");
+    }
+}
@@ -2,11 +2,14 @@ use std::fmt::{self, Display, Formatter, Write};
 use std::ops::Neg;
 use std::str::FromStr;
 
+use http::StatusCode;
 use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
 
+use doc::{ErrorObject, ErrorSource};
 use error::Error;
 use query::Path;
+use schema::{ResourceSchema, SchemaRegistry};
 use sealed::Sealed;
 
 /// A single sort instruction containing a direction and field path.
@@ -61,6 +64,128 @@ impl Sort {
     pub fn reverse(&self) -> Self {
         -self.clone()
     }
+
+    /// Validates the field of the sort instruction against the schema of the primary
+    /// resource type.
+    ///
+    /// Each segment of the field except the last must be a relationship with a
+    /// declared type that is in `registry`. The last segment must be an attribute of
+    /// the related resource type. The field may not follow more than `max_depth`
+    /// relationships. Otherwise, a `400 Bad Request` error that names the offending
+    /// segment is returned with the `sort` parameter as its source.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[macro_use]
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # struct Post {
+    /// #     id: u64,
+    /// #     author: Option<User>,
+    /// # }
+    /// #
+    /// # resource!(Post, |&self| {
+    /// #     kind "posts";
+    /// #     id self.id;
+    /// #     attr "title", { "" }
+    /// #     has_one author: "users";
+    /// # });
+    /// #
+    /// # struct User(u64);
+    /// #
+    /// # resource!(User, |&self| {
+    /// #     kind "users";
+    /// #     id self.0;
+    /// #     attr "name", { "" }
+    /// # });
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::Resource;
+    /// use json_api::query::Sort;
+    /// use json_api::schema::SchemaRegistry;
+    ///
+    /// let mut registry = SchemaRegistry::new();
+    /// let schema = Post::schema()?;
+    ///
+    /// registry.register::<User>()?;
+    ///
+    /// let sort = "-author.name".parse::<Sort>()?;
+    /// assert!(sort.validate(&schema, &registry, 1).is_ok());
+    /// assert!(sort.validate(&schema, &registry, 0).is_err());
+    ///
+    /// let sort = "author.email".parse::<Sort>()?;
+    /// let error = sort.validate(&schema, &registry, 1).unwrap_err();
+    ///
+    /// assert_eq!(error.source.and_then(|source| source.parameter), Some("sort".to_owned()));
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn validate(
+        &self,
+        schema_root: &ResourceSchema,
+        registry: &SchemaRegistry,
+        max_depth: usize,
+    ) -> Result<(), ErrorObject> {
+        let (last, rels) = match self.field.split_last() {
+            Some(value) => value,
+            None => return Err(sort_error("The sort field cannot be blank.".to_owned())),
+        };
+
+        if rels.len() > max_depth {
+            return Err(sort_error(format!(
+                r#"The sort field "{}" follows more than {} relationship(s)."#,
+                self.field, max_depth
+            )));
+        }
+
+        let mut schema = schema_root;
+
+        for rel in rels {
+            let kind = match schema.relationship(rel) {
+                Some(value) => value.kind.as_ref(),
+                None => {
+                    return Err(sort_error(format!(
+                        r#"Unknown relationship "{}" of type "{}" in sort field "{}"."#,
+                        rel, schema.kind, self.field
+                    )))
+                }
+            };
+
+            schema = match kind.and_then(|kind| registry.get(kind)) {
+                Some(value) => value,
+                None => {
+                    return Err(sort_error(format!(
+                        r#"Cannot sort by relationship "{}" in sort field "{}"."#,
+                        rel, self.field
+                    )))
+                }
+            };
+        }
+
+        match schema.attribute(last) {
+            Some(_) => Ok(()),
+            None => Err(sort_error(format!(
+                r#"Unknown attribute "{}" of type "{}" in sort field "{}"."#,
+                last, schema.kind, self.field
+            ))),
+        }
+    }
+}
+
+/// Returns a `400 Bad Request` error with the `sort` parameter as its source.
+fn sort_error(detail: String) -> ErrorObject {
+    let mut error = ErrorObject::new(Some(StatusCode::BAD_REQUEST));
+
+    error.detail = Some(detail);
+    error.source = Some(ErrorSource::from_parts(Some("sort".to_owned()), None));
+    error
 }
 
 impl Display for Sort {
@@ -224,7 +349,10 @@ impl Neg for Direction {
 
 #[cfg(test)]
 mod tests {
+    use http::StatusCode;
+
     use super::{Direction, Sort};
+    use schema::{AttributeSchema, RelationshipSchema, ResourceSchema, SchemaRegistry};
     use value::Path;
 
     #[test]
@@ -273,6 +401,80 @@ mod tests {
         assert_eq!(latest.direction, Direction::Desc);
     }
 
+    fn schemas() -> (ResourceSchema, SchemaRegistry) {
+        let mut registry = SchemaRegistry::new();
+        let mut posts = ResourceSchema::new("posts".parse().unwrap());
+        let mut users = ResourceSchema::new("users".parse().unwrap());
+        let mut author = RelationshipSchema::new("author".parse().unwrap(), false);
+
+        author.kind = Some("users".parse().unwrap());
+        posts.relationships.push(author);
+        posts
+            .relationships
+            .push(RelationshipSchema::new("tags".parse().unwrap(), true));
+        posts
+            .attributes
+            .push(AttributeSchema::new("title".parse().unwrap()));
+        users
+            .attributes
+            .push(AttributeSchema::new("name".parse().unwrap()));
+        users.relationships.push(posts.relationships[0].clone());
+
+        registry.insert(posts.clone());
+        registry.insert(users);
+        (posts, registry)
+    }
+
+    fn validate(sort: &str, max_depth: usize) -> Result<(), String> {
+        let (schema, registry) = schemas();
+        let sort = sort.parse::<Sort>().unwrap();
+
+        sort.validate(&schema, &registry, max_depth).map_err(|error| {
+            let source = error.source.unwrap();
+
+            assert_eq!(error.status, Some(StatusCode::BAD_REQUEST));
+            assert_eq!(source.parameter, Some("sort".to_owned()));
+            error.detail.unwrap()
+        })
+    }
+
+    #[test]
+    fn sort_validate() {
+        assert_eq!(validate("title", 0), Ok(()));
+        assert_eq!(validate("-author.name", 1), Ok(()));
+        assert_eq!(validate("author.author.name", 2), Ok(()));
+    }
+
+    #[test]
+    fn sort_validate_unknown_relationship() {
+        let detail = validate("editor.name", 1).unwrap_err();
+        assert!(detail.contains(r#"relationship "editor""#), "{}", detail);
+
+        // A relationship without a declared type cannot be followed.
+        let detail = validate("tags.name", 1).unwrap_err();
+        assert!(detail.contains(r#"relationship "tags""#), "{}", detail);
+    }
+
+    #[test]
+    fn sort_validate_unknown_attribute() {
+        let detail = validate("body", 0).unwrap_err();
+        assert!(detail.contains(r#"attribute "body""#), "{}", detail);
+
+        let detail = validate("author.title", 1).unwrap_err();
+        assert!(detail.contains(r#"attribute "title" of type "users""#), "{}", detail);
+
+        // The last segment must be an attribute, not a relationship.
+        assert!(validate("author", 0).is_err());
+    }
+
+    #[test]
+    fn sort_validate_max_depth() {
+        let detail = validate("author.name", 0).unwrap_err();
+        assert!(detail.contains("more than 0 relationship(s)"), "{}", detail);
+
+        assert!(validate("author.author.name", 1).is_err());
+    }
+
     #[test]
     fn sort_to_string() {
         let sort = Sort::new("created-at".parse().unwrap(), Direction::Asc);
@@ -1,11 +1,12 @@
 use std::mem;
 
-use doc::{Data, Document, Identifier, Object};
+use doc::{Data, Document, Identifier, Link, NewObject, Object, PrimaryData};
 use error::Error;
 use query::Query;
-use value::Set;
+use schema::ResourceSchema;
+use value::{Map, Set, Value};
 use value::fields::Key;
-use view::{Context, Render};
+use view::{Context, Deprecations, Options, Render};
 
 /// A trait indicating that the given type can be represented as a resource.
 ///
@@ -28,6 +29,42 @@ use view::{Context, Render};
 /// # fn main() {}
 /// ```
 ///
+/// # Deriving
+///
+/// With the `derive` feature enabled, the trait can also be implemented with
+/// `#[derive(Resource)]`. The derived implementation renders attributes and
+/// relationships the same way as the [`resource!`] macro. Fields without a `json_api`
+/// attribute are not rendered.
+///
+/// - `#[json_api(kind = "...")]` on the struct specifies the type of the resource.
+/// - `#[json_api(id)]` marks the field that is rendered as the `id` of the resource.
+/// - `#[json_api(attr)]` renders the field as an attribute.
+/// - `#[json_api(has_one)]` renders an `Option<T>` field as a to-one relationship.
+/// - `#[json_api(has_many)]` renders a field with an `iter` method, such as a `Vec<T>`,
+///   as a to-many relationship.
+/// - `rename = "..."` can be combined with `attr`, `has_one`, or `has_many` to render
+///   the field under a different member name.
+///
+/// ```ignore
+/// #[macro_use]
+/// extern crate json_api;
+///
+/// use json_api::Resource;
+///
+/// #[derive(Resource)]
+/// #[json_api(kind = "posts")]
+/// struct Post {
+///     #[json_api(id)]
+///     id: u64,
+///     #[json_api(attr)]
+///     title: String,
+///     #[json_api(attr, rename = "publishedAt")]
+///     published_at: Option<String>,
+///     #[json_api(has_many)]
+///     comments: Vec<Comment>,
+/// }
+/// ```
+///
 /// [`resource!`]: ./macro.resource.html
 pub trait Resource {
     /// Returns a key containing the type of resource.
@@ -54,6 +91,20 @@ pub trait Resource {
     /// ```
     fn kind() -> Key;
 
+    /// Returns a key containing the type of a given resource. The default
+    /// implementation returns [`kind`].
+    ///
+    /// Override this method if the type of a resource depends on its value, such as an
+    /// enum with a variant for each type. The type returned by this method is used to
+    /// select the sparse fieldset of a resource when it is rendered. The [`resource!`]
+    /// macro implements this method with the `kind |&self| { ... }` syntax.
+    ///
+    /// [`kind`]: #tymethod.kind
+    /// [`resource!`]: ./macro.resource.html
+    fn instance_kind(&self) -> Key {
+        Self::kind()
+    }
+
     /// Returns a given resource's id as a string.
     ///
     /// # Example
@@ -94,71 +145,304 @@ pub trait Resource {
     ///
     /// [`json_api::to_doc`]: ./fn.to_doc.html
     fn to_object(&self, ctx: &mut Context) -> Result<Object, Error>;
+
+    /// Renders a given resource as a resource object that does not have an id, such as
+    /// the body of a request that creates the resource. The default implementation
+    /// calls [`to_object`] and discards the id. Enable the [`client_id`] render option
+    /// to keep the id as a client-generated id.
+    ///
+    /// Calling this function directly is not recommended. It is much more ergonomic to
+    /// use the [`json_api::to_doc`] function.
+    ///
+    /// [`to_object`]: #tymethod.to_object
+    /// [`client_id`]: ./view/struct.Options.html#structfield.client_id
+    /// [`json_api::to_doc`]: ./fn.to_doc.html
+    fn to_new_object(&self, ctx: &mut Context) -> Result<NewObject, Error> {
+        self.to_object(ctx).map(NewObject::from)
+    }
+
+    /// Returns the links that are added to the top-level of a document when a given
+    /// resource is rendered as primary data. The default implementation returns an
+    /// empty map.
+    ///
+    /// The [`resource!`] macro implements this method with the `doc_link` keyword.
+    ///
+    /// [`resource!`]: ./macro.resource.html
+    fn doc_links(&self) -> Result<Map<Key, Link>, Error> {
+        Ok(Default::default())
+    }
+
+    /// Returns the meta information that is added to the top-level of a document when
+    /// a given resource is rendered as primary data. The default implementation
+    /// returns an empty map.
+    ///
+    /// The [`resource!`] macro implements this method with the `doc_meta` keyword.
+    ///
+    /// [`resource!`]: ./macro.resource.html
+    fn doc_meta(&self) -> Result<Map, Error> {
+        Ok(Default::default())
+    }
+
+    /// Returns the meta information that is only added to the identifiers of a given
+    /// resource. The default implementation returns an empty map.
+    ///
+    /// The [`resource!`] macro implements this method with the `ident_meta` keyword.
+    ///
+    /// [`resource!`]: ./macro.resource.html
+    fn ident_meta(&self, _ctx: &Context) -> Result<Map, Error> {
+        Ok(Default::default())
+    }
+
+    /// Returns a note for each deprecated attribute of the resource type, keyed by
+    /// member name. The default implementation returns an empty map.
+    ///
+    /// The [`resource!`] macro implements this method with the `deprecated` keyword.
+    ///
+    /// [`resource!`]: ./macro.resource.html
+    fn deprecations() -> Result<Map<Key, String>, Error> {
+        Ok(Default::default())
+    }
+
+    /// Returns the attributes and relationships of the resource type. The default
+    /// implementation returns a schema without any members.
+    ///
+    /// The [`resource!`] macro implements this method with the attributes and
+    /// relationships that are declared in its body.
+    ///
+    /// [`resource!`]: ./macro.resource.html
+    fn schema() -> Result<ResourceSchema, Error> {
+        Ok(ResourceSchema::new(Self::kind()))
+    }
+}
+
+/// Renders `item` as the primary data of a document. Deprecated fields that are rendered
+/// are recorded in `deprecated` when it is provided.
+pub(crate) fn to_object<T: Resource>(
+    item: &T,
+    query: Option<&Query>,
+    incl: &mut Set<Object>,
+    deprecated: Option<&mut Deprecations>,
+) -> Result<Object, Error> {
+    let kind = item.instance_kind();
+    let mut ctx = Context::new(kind.clone(), query, incl);
+
+    if let Some(deprecated) = deprecated {
+        ctx.set_deprecations(deprecated);
+    }
+
+    ctx.visit(kind, item.id());
+    item.to_object(&mut ctx)
+}
+
+/// Adds an entry for each deprecated field in `deprecated` to the top-level meta of
+/// `doc`. The meta of `doc` is left untouched if no deprecated fields were rendered.
+fn advertise<T: PrimaryData>(doc: &mut Document<T>, deprecated: Deprecations) {
+    if deprecated.is_empty() {
+        return;
+    }
+
+    let entries = deprecated
+        .into_iter()
+        .map(|((kind, field), note)| {
+            let mut entry = Map::new();
+
+            entry.insert(Key::from_raw("type".to_owned()), String::from(kind).into());
+            entry.insert(Key::from_raw("field".to_owned()), String::from(field).into());
+            entry.insert(Key::from_raw("note".to_owned()), note.into());
+            Value::Object(entry)
+        })
+        .collect();
+
+    doc.meta_mut()
+        .insert(Key::from_raw("deprecations".to_owned()), Value::Array(entries));
+}
+
+/// Adds the top-level links and meta of `item` to `doc`.
+fn lift<T: Resource, U: PrimaryData>(item: &T, doc: &mut Document<U>) -> Result<(), Error> {
+    doc.links_mut().extend(item.doc_links()?);
+    doc.meta_mut().extend(item.doc_meta()?);
+    Ok(())
 }
 
 impl<'a, T: Resource> Render<Identifier> for &'a T {
     fn render(self, query: Option<&Query>) -> Result<Document<Identifier>, Error> {
         let mut incl = Set::new();
-        let mut ctx = Context::new(T::kind(), query, &mut incl);
+        let mut doc = {
+            let mut ctx = Context::new(self.instance_kind(), query, &mut incl);
+            self.to_ident(&mut ctx)?.render(query)?
+        };
 
-        self.to_ident(&mut ctx)?.render(query)
+        lift(self, &mut doc)?;
+        Ok(doc)
     }
 }
 
 impl<'a, T: Resource> Render<Identifier> for &'a [T] {
     fn render(self, query: Option<&Query>) -> Result<Document<Identifier>, Error> {
         let mut incl = Set::new();
-        let mut ctx = Context::new(T::kind(), query, &mut incl);
+        let mut doc = {
+            let mut ctx = Context::new(T::kind(), query, &mut incl);
+
+            self.into_iter()
+                .map(|item| item.to_ident(&mut ctx))
+                .collect::<Result<Vec<_>, _>>()?
+                .render(query)?
+        };
+
+        for item in self {
+            lift(item, &mut doc)?;
+        }
+
+        Ok(doc)
+    }
+}
+
+/// Renders `item` as a new resource object. The id of `item` is kept if the
+/// `client_id` option is enabled.
+fn to_new_object<T: Resource>(
+    item: &T,
+    ctx: &mut Context,
+    options: &Options,
+) -> Result<NewObject, Error> {
+    let mut obj = item.to_new_object(ctx)?;
+
+    if options.client_id {
+        obj.id = Some(item.id());
+    }
+
+    Ok(obj)
+}
+
+impl<'a, T: Resource> Render<NewObject> for &'a T {
+    fn render(self, query: Option<&Query>) -> Result<Document<NewObject>, Error> {
+        self.render_with(query, &Options::new())
+    }
+
+    fn render_with(
+        self,
+        query: Option<&Query>,
+        options: &Options,
+    ) -> Result<Document<NewObject>, Error> {
+        let mut incl = Set::new();
+        let mut doc = {
+            let mut ctx = Context::new(self.instance_kind(), query, &mut incl);
+            to_new_object(self, &mut ctx, options)?.render(query)?
+        };
+
+        lift(self, &mut doc)?;
+        options.apply(&mut doc, query)?;
+        Ok(doc)
+    }
+}
+
+impl<'a, T: Resource> Render<NewObject> for &'a [T] {
+    fn render(self, query: Option<&Query>) -> Result<Document<NewObject>, Error> {
+        self.render_with(query, &Options::new())
+    }
+
+    fn render_with(
+        self,
+        query: Option<&Query>,
+        options: &Options,
+    ) -> Result<Document<NewObject>, Error> {
+        let mut incl = Set::new();
+        let mut doc = {
+            let mut ctx = Context::new(T::kind(), query, &mut incl);
+
+            self.iter()
+                .map(|item| to_new_object(item, &mut ctx, options))
+                .collect::<Result<Vec<_>, _>>()?
+                .render(query)?
+        };
+
+        for item in self {
+            lift(item, &mut doc)?;
+        }
 
-        self.into_iter()
-            .map(|item| item.to_ident(&mut ctx))
-            .collect::<Result<Vec<_>, _>>()?
-            .render(query)
+        options.apply(&mut doc, query)?;
+        Ok(doc)
     }
 }
 
 impl<'a, T: Resource> Render<Object> for &'a T {
     fn render(self, query: Option<&Query>) -> Result<Document<Object>, Error> {
+        self.render_with(query, &Options::new())
+    }
+
+    fn render_with(
+        self,
+        query: Option<&Query>,
+        options: &Options,
+    ) -> Result<Document<Object>, Error> {
+        let mut deprecated = Deprecations::new();
         let mut incl = Set::new();
-        let (data, links, meta) = {
-            let mut ctx = Context::new(T::kind(), query, &mut incl);
-            let mut obj = self.to_object(&mut ctx)?;
-            let links = mem::replace(&mut obj.links, Default::default());
-            let meta = mem::replace(&mut obj.meta, Default::default());
+        let mut obj = {
+            let advertise = if options.advertise_deprecations {
+                Some(&mut deprecated)
+            } else {
+                None
+            };
 
-            (obj.into(), links, meta)
+            to_object(self, query, &mut incl, advertise)?
         };
+        let links = mem::replace(&mut obj.links, Default::default());
+        let meta = mem::replace(&mut obj.meta, Default::default());
+        let data = obj.into();
 
-        Ok(Document::Ok {
+        let mut doc = Document::Ok {
             data,
             links,
             meta,
             included: incl,
             jsonapi: Default::default(),
-        })
+        };
+
+        lift(self, &mut doc)?;
+        advertise(&mut doc, deprecated);
+        options.apply(&mut doc, query)?;
+        Ok(doc)
     }
 }
 
 impl<'a, T: Resource> Render<Object> for &'a [T] {
     fn render(self, query: Option<&Query>) -> Result<Document<Object>, Error> {
+        self.render_with(query, &Options::new())
+    }
+
+    fn render_with(
+        self,
+        query: Option<&Query>,
+        options: &Options,
+    ) -> Result<Document<Object>, Error> {
+        let mut deprecated = Deprecations::new();
         let mut incl = Set::new();
         let mut data = Vec::with_capacity(self.len());
 
-        {
-            let mut ctx = Context::new(T::kind(), query, &mut incl);
+        for item in self {
+            let advertise = if options.advertise_deprecations {
+                Some(&mut deprecated)
+            } else {
+                None
+            };
 
-            for item in self {
-                data.push(item.to_object(&mut ctx)?);
-            }
+            data.push(to_object(item, query, &mut incl, advertise)?);
         }
 
-        Ok(Document::Ok {
+        let mut doc = Document::Ok {
             data: Data::Collection(data),
             links: Default::default(),
             meta: Default::default(),
             included: incl,
             jsonapi: Default::default(),
-        })
+        };
+
+        for item in self {
+            lift(item, &mut doc)?;
+        }
+
+        advertise(&mut doc, deprecated);
+        options.apply(&mut doc, query)?;
+        Ok(doc)
     }
 }
 
@@ -306,320 +590,1773 @@ impl<'a, T: Resource> Render<Object> for &'a [T] {
 /// #
 /// # fn main() {}
 /// ```
-#[macro_export]
-macro_rules! resource {
-    ($target:ident, |&$this:ident| { $($rest:tt)* }) => {
-        impl $crate::Resource for $target {
-            fn kind() -> $crate::value::Key {
-                let raw = extract_resource_kind!({ $($rest)* }).to_owned();
-                $crate::value::Key::from_raw(raw)
-            }
-
-            fn id(&$this) -> String {
-                extract_resource_id!({ $($rest)* }).to_string()
-            }
-
-            fn to_ident(
-                &$this,
-                _: &mut $crate::view::Context,
-            ) -> Result<$crate::doc::Identifier, $crate::Error> {
-                let mut ident = {
-                    let kind = <$target as $crate::Resource>::kind();
-                    let id = $crate::Resource::id($this);
-
-                    $crate::doc::Identifier::new(kind, id)
-                };
-
-                {
-                    let _meta = &mut ident.meta;
-                    expand_resource_impl!(@meta $this, _meta, {
-                        $($rest)*
-                    });
-                }
-
-                Ok(ident)
-            }
-
-            fn to_object(
-                &$this,
-                ctx: &mut $crate::view::Context,
-            ) -> Result<$crate::doc::Object, $crate::error::Error> {
-                #[allow(dead_code)]
-                fn item_kind<T: $crate::Resource>(_: &T) -> $crate::value::Key {
-                    T::kind()
-                }
-
-                #[allow(dead_code)]
-                fn iter_kind<'a, I, T>(_: &I) -> $crate::value::Key
-                where
-                    I: Iterator<Item = &'a T>,
-                    T: $crate::Resource + 'a,
-                {
-                    T::kind()
-                }
-
-                let mut obj = {
-                    let kind = <$target as $crate::Resource>::kind();
-                    let id = $crate::Resource::id($this);
-
-                    $crate::doc::Object::new(kind, id)
-                };
-
-                {
-                    let _attrs = &mut obj.attributes;
-                    expand_resource_impl!(@attrs $this, _attrs, ctx, {
-                        $($rest)*
-                    });
-                }
-
-                {
-                    let _links = &mut obj.links;
-                    expand_resource_impl!(@links $this, _links, {
-                        $($rest)*
-                    });
-                }
-
-                {
-                    let _meta = &mut obj.meta;
-                    expand_resource_impl!(@meta $this, _meta, {
-                        $($rest)*
-                    });
-                }
-
-                {
-                    let _related = &mut obj.relationships;
-                    expand_resource_impl!(@rel $this, _related, ctx, {
-                        $($rest)*
-                    });
-                }
-
-                Ok(obj)
-            }
-        }
-    };
-}
-
-#[doc(hidden)]
-#[macro_export]
-macro_rules! expand_resource_impl {
-    (@attrs $this:ident, $attrs:ident, $ctx:ident, {
-        attr $key:expr, $value:block
-        $($rest:tt)*
-    }) => {
-        if $ctx.field($key) {
-            let key = $key.parse::<$crate::value::Key>()?;
-            let value = $crate::to_value($value)?;
-
-            $attrs.insert(key, value);
-        }
-
-        expand_resource_impl!(@attrs $this, $attrs, $ctx, {
-            $($rest)*
-        });
-    };
-
-    (@attrs $this:ident, $($arg:ident),*, { attr $field:ident; $($rest:tt)* }) => {
-        expand_resource_impl!(@attrs $this, $($arg),*, {
-            attr stringify!($field), &$this.$field;
-            $($rest)*
-        });
-    };
-
-    (@attrs $($arg:ident),*, { attrs $($field:ident),+; $($rest:tt)* }) => {
-        expand_resource_impl!(@attrs $($arg),*, {
-            $(attr $field;)+
-            $($rest)*
-        });
-    };
-
-    (@rel $this:ident, $related:ident, $ctx:ident, {
-        has_many $key:expr, { $($body:tt)* }
-        $($rest:tt)*
-    }) => {
-        if $ctx.field($key) {
-            let key = $key.parse::<$crate::value::Key>()?;
-            expand_resource_impl!(@has_many $this, $related, key, $ctx, {
-                $($body)*
-            });
-        }
-
-        expand_resource_impl!(@rel $this, $related, $ctx, {
-            $($rest)*
-        });
-    };
-
-    (@rel $this:ident, $related:ident, $ctx:ident, {
-        has_one $key:expr, { $($body:tt)* }
-        $($rest:tt)*
-    }) => {
-        if $ctx.field($key) {
-            let key = $key.parse::<$crate::value::Key>()?;
-            expand_resource_impl!(@has_one $this, $related, key, $ctx, {
-                $($body)*
-            });
-        }
-
-        expand_resource_impl!(@rel $this, $related, $ctx, {
-            $($rest)*
-        });
+///
+/// # Optional Attributes
+///
+/// An attribute defined with `attr` is always present in the rendered object, even if
+/// its value is `None` (rendered as `null`). The `attr_some` and `attrs_some` keywords
+/// have the same syntax as `attr` and `attrs`, but omit the attribute entirely if its
+/// value is serialized as `null`. This is useful when an absent attribute means "not
+/// provided" rather than "explicitly empty".
+///
+/// ```
+/// #[macro_use]
+/// extern crate json_api;
+///
+/// struct User {
+///     id: u64,
+///     name: String,
+///     bio: Option<String>,
+///     website: Option<String>,
+///     nickname: Option<String>,
+/// }
+///
+/// resource!(User, |&self| {
+///     kind "users";
+///     id self.id;
+///
+///     attr name;
+///     attrs_some bio, website;
+///
+///     // Define an optional virtual attribute with an expression
+///     attr_some "initial", {
+///         self.nickname.as_ref().and_then(|value| value.chars().next())
+///     }
+/// });
+///
+/// # fn example() -> Result<(), json_api::Error> {
+/// use json_api::doc::{Data, Document, Object};
+///
+/// let user = User {
+///     id: 1,
+///     name: "Alfred Pennyworth".to_owned(),
+///     bio: Some("Butler".to_owned()),
+///     website: None,
+///     nickname: None,
+/// };
+///
+/// let doc: Document<Object> = json_api::to_doc(&user, None)?;
+///
+/// if let Document::Ok { data: Data::Member(data), .. } = doc {
+///     let attrs = data.unwrap().attributes;
+///
+///     assert_eq!(attrs.get("bio"), Some(&"Butler".into()));
+///     assert!(!attrs.contains_key("website"));
+///     assert!(!attrs.contains_key("initial"));
+/// }
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// # Renamed Attributes
+///
+/// The member name of an attribute defined with `attr` or `attrs` is derived from the
+/// name of the field. To render a field under a different member name, separate the
+/// member name and the value with `=>`. The same syntax is supported by `attr_some`.
+///
+/// ```
+/// #[macro_use]
+/// extern crate json_api;
+///
+/// struct Article {
+///     id: u64,
+///     db_internal_name: String,
+///     published_at: Option<String>,
+/// }
+///
+/// resource!(Article, |&self| {
+///     kind "articles";
+///     id self.id;
+///
+///     attr "name" => self.db_internal_name;
+///     attr_some "published" => self.published_at;
+/// });
+///
+/// # fn example() -> Result<(), json_api::Error> {
+/// use json_api::doc::{Data, Document, Object};
+///
+/// let article = Article {
+///     id: 1,
+///     db_internal_name: "Hello, world!".to_owned(),
+///     published_at: Some("2017-12-01".to_owned()),
+/// };
+///
+/// let doc: Document<Object> = json_api::to_doc(&article, None)?;
+///
+/// if let Document::Ok { data: Data::Member(data), .. } = doc {
+///     let attrs = data.unwrap().attributes;
+///
+///     assert_eq!(attrs.get("name"), Some(&"Hello, world!".into()));
+///     assert_eq!(attrs.get("published"), Some(&"2017-12-01".into()));
+///     assert!(!attrs.contains_key("db_internal_name"));
+/// }
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// # Deprecated Attributes
+///
+/// An attribute can be marked as deprecated by following the name of the field with
+/// the `deprecated` keyword and a note that describes the deprecation. The attribute
+/// is rendered as usual. When a document is rendered with the
+/// [`advertise_deprecations`] option, each deprecated attribute that is present in
+/// the document is listed once in the `deprecations` member of the top-level `meta`.
+///
+/// ```
+/// #[macro_use]
+/// extern crate json_api;
+///
+/// struct User {
+///     id: u64,
+///     full_name: String,
+///     legacy_name: String,
+/// }
+///
+/// resource!(User, |&self| {
+///     kind "users";
+///     id self.id;
+///
+///     attrs full_name;
+///     attr legacy_name, deprecated "use full-name instead";
+/// });
+///
+/// # fn example() -> Result<(), json_api::Error> {
+/// use json_api::Resource;
+/// use json_api::doc::{Document, Object};
+/// use json_api::view::Options;
+///
+/// let user = User {
+///     id: 1,
+///     full_name: "Alfred Pennyworth".to_owned(),
+///     legacy_name: "Alfred".to_owned(),
+/// };
+///
+/// let mut options = Options::new();
+/// options.advertise_deprecations = true;
+///
+/// let doc: Document<Object> = json_api::to_doc_with(&user, None, &options)?;
+/// let entries = doc.meta().get("deprecations").and_then(|v| v.as_array()).unwrap();
+///
+/// assert_eq!(entries.len(), 1);
+/// assert_eq!(entries[0].pointer("/field"), Some(&"legacy-name".into()));
+/// assert_eq!(entries[0].pointer("/note"), Some(&"use full-name instead".into()));
+///
+/// let notes = User::deprecations()?;
+/// assert_eq!(notes.get("legacy-name").map(String::as_str), Some("use full-name instead"));
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`advertise_deprecations`]: ./view/struct.Options.html#structfield.advertise_deprecations
+///
+/// # Type Hints
+///
+/// An attribute defined with `attr` or `attr_some` can be followed by a colon and a
+/// type hint. The hint does not change how the attribute is rendered. It is included
+/// in the [`schema`] of the resource, where it is used as the type of the attribute
+/// when generating TypeScript definitions.
+///
+/// A relationship defined with `has_one` or `has_many` can be followed by a colon and
+/// the type of the related resources, such as `has_one author: "users";`. The type is
+/// included in the schema of the relationship.
+///
+/// ```
+/// #[macro_use]
+/// extern crate json_api;
+///
+/// struct Article {
+///     id: u64,
+///     title: String,
+///     tags: Vec<String>,
+/// }
+///
+/// resource!(Article, |&self| {
+///     kind "articles";
+///     id self.id;
+///
+///     attr title: "string";
+///     attr tags: "string[]";
+/// });
+///
+/// # fn example() -> Result<(), json_api::Error> {
+/// use json_api::Resource;
+///
+/// let schema = Article::schema()?;
+/// let hints = schema
+///     .attributes
+///     .iter()
+///     .map(|attr| attr.hint.as_ref().map(String::as_str))
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(hints, [Some("string"), Some("string[]")]);
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`schema`]: ./trait.Resource.html#method.schema
+///
+/// # Document Links and Meta
+///
+/// Links and meta defined with `link` and `meta` belong to the resource object. When a
+/// single resource is rendered as primary data they are moved to the top-level of the
+/// document. The `doc_link` and `doc_meta` keywords have the same syntax, but always
+/// belong to the top-level of the document, even when a collection of resources is
+/// rendered. If more than one resource in a collection defines the same member, the
+/// value of the last resource is used.
+///
+/// ```
+/// #[macro_use]
+/// extern crate json_api;
+///
+/// struct Article {
+///     id: u64,
+///     title: String,
+/// }
+///
+/// resource!(Article, |&self| {
+///     kind "articles";
+///     id self.id;
+///
+///     attr title;
+///
+///     doc_link "describedby", "/schemas/articles";
+///     doc_meta "api-version", "2.1";
+/// });
+///
+/// # fn example() -> Result<(), json_api::Error> {
+/// use json_api::doc::{Document, Object};
+///
+/// let articles = vec![
+///     Article { id: 1, title: "Hello, world!".to_owned() },
+///     Article { id: 2, title: "Hello, JSON API!".to_owned() },
+/// ];
+///
+/// let doc: Document<Object> = json_api::to_doc(&articles[..], None)?;
+///
+/// assert!(doc.links().contains_key("describedby"));
+/// assert_eq!(doc.meta().get("api-version"), Some(&"2.1".into()));
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// Meta defined with `meta` is rendered on the resource object and on each resource
+/// identifier of a resource, such as the linkage of a relationship that is not
+/// included. The `object_meta` keyword has the same syntax as `meta`, but the block is
+/// only evaluated when the resource is rendered as a resource object. This is useful
+/// for meta that is expensive to compute, such as the result of a count query. The
+/// `ident_meta` keyword also has the same syntax, but only applies to resource
+/// identifiers, which is useful for small hints such as the weight of a linkage. The
+/// identifier of an included resource has the same meta as its resource object, in
+/// addition to the meta defined with `ident_meta`.
+///
+/// # Render Context
+///
+/// An attribute or meta block can accept the render [`Context`] by writing it as a
+/// closure with a single argument. The argument is a shared reference to the context
+/// of the resource being rendered. It can be used to read the [`query`] of the request
+/// or the include [`path`] of the resource. Meta blocks of a relationship receive the
+/// context of the resource that defines the relationship.
+///
+/// An attribute block is only evaluated if the attribute is present in the sparse
+/// fieldset of the resource, so an expensive attribute that was not requested is never
+/// computed.
+///
+/// ```
+/// #[macro_use]
+/// extern crate json_api;
+///
+/// struct Article {
+///     id: u64,
+///     body: String,
+/// }
+///
+/// resource!(Article, |&self| {
+///     kind "articles";
+///     id self.id;
+///
+///     attr "preview", |ctx| {
+///         // Render a longer preview when the article is the primary data.
+///         let size = if ctx.path().is_empty() { 280 } else { 140 };
+///         self.body.chars().take(size).collect::<String>()
+///     }
+///
+///     meta "page", |ctx| {
+///         ctx.query().and_then(|query| query.page).map(|page| page.number)
+///     }
+/// });
+///
+/// # fn example() -> Result<(), json_api::Error> {
+/// use json_api::doc::{Data, Document, Object};
+/// use json_api::query;
+///
+/// let article = Article {
+///     id: 1,
+///     body: "Hello, world!".to_owned(),
+/// };
+///
+/// let query = query::from_str("page[number]=2")?;
+/// let doc: Document<Object> = json_api::to_doc(&article, Some(&query))?;
+///
+/// assert_eq!(doc.meta().get("page"), Some(&2.into()));
+///
+/// if let Document::Ok { data: Data::Member(data), .. } = doc {
+///     let attrs = data.unwrap().attributes;
+///     assert_eq!(attrs.get("preview"), Some(&"Hello, world!".into()));
+/// }
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`Context`]: ./view/struct.Context.html
+/// [`query`]: ./view/struct.Context.html#method.query
+/// [`path`]: ./view/struct.Context.html#method.path
+///
+/// # Relationship Data
+///
+/// The `data` of a relationship is only evaluated if the relationship is rendered
+/// with resource linkage or its resources are included. A data block can return early
+/// with the `?` operator. If it does, the error is chained with an error that names
+/// the relationship, so it is easy to tell which data block failed.
+///
+/// A `has_many` relationship can use `data_some` instead of `data` if its resources
+/// may not be loaded. The block should return an `Option` of an iterator. If it
+/// returns `None`, the relationship is rendered without a `data` member, rather than
+/// with an empty array. This distinguishes a relationship that was not loaded from a
+/// relationship that is empty.
+///
+/// ```
+/// #[macro_use]
+/// extern crate json_api;
+///
+/// struct Post {
+///     id: u64,
+///     comments: Option<Vec<Comment>>,
+/// }
+///
+/// resource!(Post, |&self| {
+///     kind "posts";
+///     id self.id;
+///
+///     has_many "comments", {
+///         data_some self.comments.as_ref().map(|comments| comments.iter());
+///         link "related", format!("/posts/{}/comments", self.id);
+///     }
+/// });
+///
+/// struct Comment {
+///     id: u64,
+///     post: Option<Post>,
+/// }
+///
+/// resource!(Comment, |&self| {
+///     kind "comments";
+///     id self.id;
+///
+///     has_one "post", {
+///         data {
+///             if self.id == 0 {
+///                 Err(json_api::Error::from("comment 0 does not have a post"))?;
+///             }
+///
+///             self.post.as_ref()
+///         }
+///     }
+/// });
+///
+/// # fn example() -> Result<(), json_api::Error> {
+/// use json_api::doc::Object;
+///
+/// let post = Post { id: 1, comments: None };
+/// let object: Object = json_api::to_object(&post, None)?;
+/// let comments = object.relationships.get("comments").unwrap();
+///
+/// assert!(comments.data.is_none());
+/// assert!(comments.links.contains_key("related"));
+///
+/// let comment = Comment { id: 0, post: None };
+/// let e = json_api::to_object(&comment, None).unwrap_err();
+///
+/// assert_eq!(e.to_string(), r#"failed to evaluate the data of relationship "post""#);
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// A `has_many` relationship can add `count;` to its body to render the number of
+/// related resources as a `count` member of the relationship's meta. If the
+/// relationship is rendered without resource linkage, the data block is evaluated to
+/// count the resources. A relationship that uses `data_some` only renders a count
+/// along with its resource linkage.
+///
+/// ```
+/// #[macro_use]
+/// extern crate json_api;
+///
+/// struct Post {
+///     id: u64,
+///     comments: Vec<Comment>,
+/// }
+///
+/// resource!(Post, |&self| {
+///     kind "posts";
+///     id self.id;
+///
+///     has_many "comments", {
+///         data self.comments.iter();
+///         count;
+///     }
+/// });
+///
+/// struct Comment {
+///     id: u64,
+/// }
+///
+/// resource!(Comment, |&self| {
+///     kind "comments";
+///     id self.id;
+/// });
+///
+/// # fn example() -> Result<(), json_api::Error> {
+/// use json_api::doc::Object;
+///
+/// let post = Post {
+///     id: 1,
+///     comments: vec![Comment { id: 1 }, Comment { id: 2 }],
+/// };
+///
+/// let object: Object = json_api::to_object(&post, None)?;
+/// let comments = object.relationships.get("comments").unwrap();
+///
+/// assert_eq!(comments.meta.get("count"), Some(&2.into()));
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// # Polymorphic Relationships
+///
+/// A relationship defined with `has_many` renders each member with the type returned
+/// by [`Resource::instance_kind`]. The type is used to select the sparse fieldset of
+/// the member and to detect cycles, so a relationship can contain resources of more
+/// than one type. A common approach is to wrap the related types in an enum that
+/// delegates to the resource of each variant. The `has_many_poly` keyword is an alias
+/// of `has_many` that is kept for compatibility.
+///
+/// ```
+/// #[macro_use]
+/// extern crate json_api;
+///
+/// use json_api::Error;
+/// use json_api::doc::{Identifier, Object};
+/// use json_api::value::Key;
+/// use json_api::view::Context;
+///
+/// struct Post {
+///     id: u64,
+/// }
+///
+/// resource!(Post, |&self| {
+///     kind "posts";
+///     id self.id;
+/// });
+///
+/// struct Comment {
+///     id: u64,
+/// }
+///
+/// resource!(Comment, |&self| {
+///     kind "comments";
+///     id self.id;
+/// });
+///
+/// enum Activity {
+///     Post(Post),
+///     Comment(Comment),
+/// }
+///
+/// impl json_api::Resource for Activity {
+///     fn kind() -> Key {
+///         "activities".parse().unwrap()
+///     }
+///
+///     fn instance_kind(&self) -> Key {
+///         match *self {
+///             Activity::Post(ref post) => post.instance_kind(),
+///             Activity::Comment(ref comment) => comment.instance_kind(),
+///         }
+///     }
+///
+///     fn id(&self) -> String {
+///         match *self {
+///             Activity::Post(ref post) => post.id(),
+///             Activity::Comment(ref comment) => comment.id(),
+///         }
+///     }
+///
+///     fn to_ident(&self, ctx: &mut Context) -> Result<Identifier, Error> {
+///         match *self {
+///             Activity::Post(ref post) => post.to_ident(ctx),
+///             Activity::Comment(ref comment) => comment.to_ident(ctx),
+///         }
+///     }
+///
+///     fn to_object(&self, ctx: &mut Context) -> Result<Object, Error> {
+///         match *self {
+///             Activity::Post(ref post) => post.to_object(ctx),
+///             Activity::Comment(ref comment) => comment.to_object(ctx),
+///         }
+///     }
+/// }
+///
+/// struct User {
+///     id: u64,
+///     activity: Vec<Activity>,
+/// }
+///
+/// resource!(User, |&self| {
+///     kind "users";
+///     id self.id;
+///
+///     has_many activity;
+/// });
+///
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::Document;
+/// use json_api::query;
+///
+/// let user = User {
+///     id: 1,
+///     activity: vec![
+///         Activity::Post(Post { id: 1 }),
+///         Activity::Comment(Comment { id: 1 }),
+///     ],
+/// };
+///
+/// let query = query::from_str("include=activity")?;
+/// let doc: Document<Object> = json_api::to_doc(&user, Some(&query))?;
+///
+/// if let Document::Ok { included, .. } = doc {
+///     let kinds: Vec<String> = included.iter().map(|obj| obj.kind.to_string()).collect();
+///     assert_eq!(kinds, vec!["posts", "comments"]);
+/// }
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// # Per-Instance Kinds
+///
+/// The `kind` of a resource is static by default. If the type of a resource depends
+/// on its value, such as an enum that is stored in a single table, the `kind |&self|`
+/// syntax defines a block that returns the type of each instance. It implements
+/// [`Resource::instance_kind`], which is used to render the `type` member and to
+/// select the sparse fieldset of each resource. A static `kind` is still required.
+///
+/// ```
+/// #[macro_use]
+/// extern crate json_api;
+///
+/// enum Animal {
+///     Cat { id: u64, name: String },
+///     Dog { id: u64, name: String },
+/// }
+///
+/// resource!(Animal, |&self| {
+///     kind "animals";
+///
+///     kind |&self| {
+///         match *self {
+///             Animal::Cat { .. } => "cats",
+///             Animal::Dog { .. } => "dogs",
+///         }
+///     }
+///
+///     id match *self {
+///         Animal::Cat { id, .. } | Animal::Dog { id, .. } => id,
+///     };
+///
+///     attr "name", match *self {
+///         Animal::Cat { ref name, .. } | Animal::Dog { ref name, .. } => name,
+///     };
+/// });
+///
+/// # fn example() -> Result<(), json_api::Error> {
+/// use json_api::doc::{Data, Document, Object};
+/// use json_api::query;
+///
+/// let animals = vec![
+///     Animal::Cat { id: 1, name: "Tom".to_owned() },
+///     Animal::Dog { id: 1, name: "Spike".to_owned() },
+/// ];
+///
+/// let query = query::from_str("fields[cats]=name&fields[dogs]=")?;
+/// let doc: Document<Object> = json_api::to_doc(&animals[..], Some(&query))?;
+///
+/// if let Document::Ok { data: Data::Collection(data), .. } = doc {
+///     assert_eq!(data[0].kind, "cats");
+///     assert_eq!(data[0].attributes.get("name"), Some(&"Tom".into()));
+///     assert_eq!(data[1].kind, "dogs");
+///     assert!(data[1].attributes.is_empty());
+/// }
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`Resource::instance_kind`]: ./trait.Resource.html#method.instance_kind
+///
+/// # Composite Keys
+///
+/// Resources that are identified by more than one field can render a derived `id` with
+/// an expression. The optional `parse_id` keyword defines the inverse operation. It
+/// generates an associated function `parse_id(&str) -> Result<T, Error>` on the target
+/// type where `T` is the return type specified in the DSL.
+///
+/// Since the components of a composite key may contain the separator, they should be
+/// escaped when the `id` is rendered and unescaped when it is parsed.
+///
+/// ```
+/// #[macro_use]
+/// extern crate json_api;
+///
+/// use json_api::Error;
+/// use json_api::doc::Identifier;
+///
+/// struct Post {
+///     tenant: String,
+///     local: u64,
+/// }
+///
+/// fn escape(value: &str) -> String {
+///     value.replace('%', "%25").replace(':', "%3A")
+/// }
+///
+/// fn unescape(value: &str) -> String {
+///     value.replace("%3A", ":").replace("%25", "%")
+/// }
+///
+/// resource!(Post, |&self| {
+///     kind "posts";
+///     id format!("{}:{}", escape(&self.tenant), self.local);
+///
+///     parse_id |value| -> (String, u64) {
+///         let mut parts = value.splitn(2, ':');
+///         let tenant = parts.next().map(unescape);
+///         let local = parts.next().map(|part| part.parse::<u64>());
+///
+///         match (tenant, local) {
+///             (Some(tenant), Some(Ok(local))) => Ok((tenant, local)),
+///             _ => Err(Error::from(format!(r#"invalid post id "{}""#, value))),
+///         }
+///     }
+/// });
+///
+/// # fn example() -> Result<(), Error> {
+/// use json_api::Resource;
+///
+/// let post = Post {
+///     tenant: "acme:eu".to_owned(),
+///     local: 1,
+/// };
+///
+/// // Render the id.
+/// let id = post.id();
+/// assert_eq!(id, "acme%3Aeu:1");
+///
+/// // Parse the id from a route parameter.
+/// let (tenant, local) = Post::parse_id(&id)?;
+/// assert_eq!(tenant, "acme:eu");
+/// assert_eq!(local, 1);
+/// assert!(Post::parse_id("acme").is_err());
+///
+/// // Construct a resource identifier for a relationship.
+/// let ident = Identifier::new(Post::kind(), id);
+/// assert_eq!(ident.id, "acme%3Aeu:1");
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+#[macro_export]
+macro_rules! resource {
+    ($target:ident, |&$this:ident| { $($rest:tt)* }) => {
+        impl $crate::Resource for $target {
+            fn kind() -> $crate::value::Key {
+                let raw = extract_resource_kind!({ $($rest)* }).to_owned();
+                $crate::value::Key::from_raw(raw)
+            }
+
+            fn instance_kind(&$this) -> $crate::value::Key {
+                extract_resource_instance_kind!($target, { $($rest)* })
+            }
+
+            fn id(&$this) -> String {
+                extract_resource_id!({ $($rest)* }).to_string()
+            }
+
+            #[allow(unused_variables)]
+            fn to_ident(
+                &$this,
+                ctx: &mut $crate::view::Context,
+            ) -> Result<$crate::doc::Identifier, $crate::Error> {
+                let mut ident = {
+                    let kind = $crate::Resource::instance_kind($this);
+                    let id = $crate::Resource::id($this);
+
+                    $crate::doc::Identifier::new(kind, id)
+                };
+
+                {
+                    let _meta = &mut ident.meta;
+                    expand_resource_impl!(@meta $this, _meta, ctx, {
+                        $($rest)*
+                    });
+                }
+
+                ident.meta.extend($crate::Resource::ident_meta($this, ctx)?);
+                Ok(ident)
+            }
+
+            fn to_object(
+                &$this,
+                ctx: &mut $crate::view::Context,
+            ) -> Result<$crate::doc::Object, $crate::error::Error> {
+                #[allow(dead_code)]
+                fn data_kind<'a, F, I, T>(_: &F) -> $crate::value::Key
+                where
+                    F: FnOnce() -> Result<I, $crate::Error>,
+                    I: IntoIterator<Item = &'a T>,
+                    T: $crate::Resource + 'a,
+                {
+                    T::kind()
+                }
+
+                #[allow(dead_code)]
+                fn data_some_kind<'a, F, I, T>(_: &F) -> $crate::value::Key
+                where
+                    F: FnOnce() -> Result<Option<I>, $crate::Error>,
+                    I: IntoIterator<Item = &'a T>,
+                    T: $crate::Resource + 'a,
+                {
+                    T::kind()
+                }
+
+                let mut obj = {
+                    let kind = $crate::Resource::instance_kind($this);
+                    let id = $crate::Resource::id($this);
+
+                    $crate::doc::Object::new(kind, id)
+                };
+
+                {
+                    let _attrs = &mut obj.attributes;
+                    expand_resource_impl!(@attrs $this, _attrs, ctx, {
+                        $($rest)*
+                    });
+                }
+
+                {
+                    let _links = &mut obj.links;
+                    expand_resource_impl!(@links $this, _links, {
+                        $($rest)*
+                    });
+                }
+
+                {
+                    let _meta = &mut obj.meta;
+                    expand_resource_impl!(@meta $this, _meta, ctx, {
+                        $($rest)*
+                    });
+                    expand_resource_impl!(@object_meta $this, _meta, ctx, {
+                        $($rest)*
+                    });
+                }
+
+                {
+                    let _related = &mut obj.relationships;
+                    expand_resource_impl!(@rel $this, _related, ctx, {
+                        $($rest)*
+                    });
+                }
+
+                obj.validate_members()?;
+                Ok(obj)
+            }
+
+            fn doc_links(
+                &$this,
+            ) -> Result<$crate::value::Map<$crate::value::Key, $crate::doc::Link>, $crate::Error> {
+                #[allow(unused_mut)]
+                let mut _links = $crate::value::Map::new();
+
+                expand_resource_impl!(@doc_links $this, _links, {
+                    $($rest)*
+                });
+
+                Ok(_links)
+            }
+
+            fn doc_meta(&$this) -> Result<$crate::value::Map, $crate::Error> {
+                #[allow(unused_mut)]
+                let mut _meta = $crate::value::Map::new();
+
+                expand_resource_impl!(@doc_meta $this, _meta, {
+                    $($rest)*
+                });
+
+                Ok(_meta)
+            }
+
+            #[allow(unused_variables)]
+            fn ident_meta(
+                &$this,
+                ctx: &$crate::view::Context,
+            ) -> Result<$crate::value::Map, $crate::Error> {
+                #[allow(unused_mut)]
+                let mut _meta = $crate::value::Map::new();
+
+                expand_resource_impl!(@ident_meta $this, _meta, ctx, {
+                    $($rest)*
+                });
+
+                Ok(_meta)
+            }
+
+            fn deprecations() -> Result<
+                $crate::value::Map<$crate::value::Key, String>,
+                $crate::Error,
+            > {
+                #[allow(unused_mut)]
+                let mut _notes = $crate::value::Map::new();
+
+                expand_resource_impl!(@deprecations _notes, {
+                    $($rest)*
+                });
+
+                Ok(_notes)
+            }
+
+            fn schema() -> Result<$crate::schema::ResourceSchema, $crate::Error> {
+                let kind = <$target as $crate::Resource>::kind();
+                let mut _schema = $crate::schema::ResourceSchema::new(kind);
+
+                expand_resource_impl!(@schema _schema, {
+                    $($rest)*
+                });
+
+                Ok(_schema)
+            }
+        }
+
+        expand_resource_parse_id!($target, { $($rest)* });
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! expand_resource_impl {
+    (@schema $schema:ident, {
+        attr $field:ident: $hint:expr;
+        $($rest:tt)*
+    }) => {
+        let mut attr = $crate::schema::AttributeSchema::new(stringify!($field).parse()?);
+
+        attr.hint = Some(String::from($hint));
+        $schema.attributes.push(attr);
+
+        expand_resource_impl!(@schema $schema, {
+            $($rest)*
+        });
+    };
+
+    (@schema $schema:ident, {
+        attr_some $field:ident: $hint:expr;
+        $($rest:tt)*
+    }) => {
+        let mut attr = $crate::schema::AttributeSchema::new(stringify!($field).parse()?);
+
+        attr.hint = Some(String::from($hint));
+        attr.optional = true;
+        $schema.attributes.push(attr);
+
+        expand_resource_impl!(@schema $schema, {
+            $($rest)*
+        });
+    };
+
+    (@schema $schema:ident, {
+        has_one $field:ident: $kind:expr;
+        $($rest:tt)*
+    }) => {
+        let mut rel = $crate::schema::RelationshipSchema::new(
+            stringify!($field).parse()?,
+            false,
+        );
+
+        rel.kind = Some($kind.parse()?);
+        $schema.relationships.push(rel);
+
+        expand_resource_impl!(@schema $schema, {
+            $($rest)*
+        });
+    };
+
+    (@schema $schema:ident, {
+        has_many $field:ident: $kind:expr;
+        $($rest:tt)*
+    }) => {
+        let mut rel = $crate::schema::RelationshipSchema::new(
+            stringify!($field).parse()?,
+            true,
+        );
+
+        rel.kind = Some($kind.parse()?);
+        $schema.relationships.push(rel);
+
+        expand_resource_impl!(@schema $schema, {
+            $($rest)*
+        });
+    };
+
+    // Type hints only apply to the schema of a resource.
+    (@$scope:tt $($args:ident),+, {
+        $kwd:ident $field:ident: $hint:expr;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@$scope $($args),+, {
+            $kwd $field;
+            $($rest)*
+        });
+    };
+
+    (@attrs $this:ident, $attrs:ident, $ctx:ident, {
+        attr $field:ident, deprecated $note:expr;
+        $($rest:tt)*
+    }) => {
+        if $ctx.field(stringify!($field)) {
+            let key = stringify!($field).parse::<$crate::value::Key>()?;
+            $ctx.deprecate(&key, $note);
+        }
+
+        expand_resource_impl!(@attrs $this, $attrs, $ctx, {
+            attr $field;
+            $($rest)*
+        });
+    };
+
+    (@attrs $this:ident, $attrs:ident, $ctx:ident, {
+        attr $key:expr, |$arg:ident| $value:block
+        $($rest:tt)*
+    }) => {
+        if $ctx.field($key) {
+            let key = $key.parse::<$crate::value::Key>()?;
+            let value = {
+                let $arg: &$crate::view::Context = &*$ctx;
+                $crate::to_value($value)?
+            };
+
+            $attrs.insert(key, value);
+        }
+
+        expand_resource_impl!(@attrs $this, $attrs, $ctx, {
+            $($rest)*
+        });
+    };
+
+    (@attrs $this:ident, $attrs:ident, $ctx:ident, {
+        attr_some $key:expr, |$arg:ident| $value:block
+        $($rest:tt)*
+    }) => {
+        if $ctx.field($key) {
+            let value = {
+                let $arg: &$crate::view::Context = &*$ctx;
+                $crate::to_value($value)?
+            };
+
+            if !value.is_null() {
+                let key = $key.parse::<$crate::value::Key>()?;
+                $attrs.insert(key, value);
+            }
+        }
+
+        expand_resource_impl!(@attrs $this, $attrs, $ctx, {
+            $($rest)*
+        });
+    };
+
+    (@attrs $this:ident, $attrs:ident, $ctx:ident, {
+        attr $key:expr, $value:block
+        $($rest:tt)*
+    }) => {
+        if $ctx.field($key) {
+            let key = $key.parse::<$crate::value::Key>()?;
+            let value = $crate::to_value($value)?;
+
+            $attrs.insert(key, value);
+        }
+
+        expand_resource_impl!(@attrs $this, $attrs, $ctx, {
+            $($rest)*
+        });
+    };
+
+    (@attrs $this:ident, $attrs:ident, $ctx:ident, {
+        attr_some $key:expr, $value:block
+        $($rest:tt)*
+    }) => {
+        if $ctx.field($key) {
+            let value = $crate::to_value($value)?;
+
+            if !value.is_null() {
+                let key = $key.parse::<$crate::value::Key>()?;
+                $attrs.insert(key, value);
+            }
+        }
+
+        expand_resource_impl!(@attrs $this, $attrs, $ctx, {
+            $($rest)*
+        });
+    };
+
+    (@attrs $this:ident, $($arg:ident),*, {
+        attr $key:expr => $value:expr;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@attrs $this, $($arg),*, {
+            attr $key, { &$value }
+            $($rest)*
+        });
+    };
+
+    (@attrs $this:ident, $($arg:ident),*, {
+        attr_some $key:expr => $value:expr;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@attrs $this, $($arg),*, {
+            attr_some $key, { &$value }
+            $($rest)*
+        });
+    };
+
+    (@attrs $this:ident, $($arg:ident),*, { attr $field:ident; $($rest:tt)* }) => {
+        expand_resource_impl!(@attrs $this, $($arg),*, {
+            attr stringify!($field), &$this.$field;
+            $($rest)*
+        });
+    };
+
+    (@attrs $($arg:ident),*, { attrs $($field:ident),+; $($rest:tt)* }) => {
+        expand_resource_impl!(@attrs $($arg),*, {
+            $(attr $field;)+
+            $($rest)*
+        });
+    };
+
+    (@attrs $this:ident, $($arg:ident),*, { attr_some $field:ident; $($rest:tt)* }) => {
+        expand_resource_impl!(@attrs $this, $($arg),*, {
+            attr_some stringify!($field), &$this.$field;
+            $($rest)*
+        });
+    };
+
+    (@attrs $($arg:ident),*, { attrs_some $($field:ident),+; $($rest:tt)* }) => {
+        expand_resource_impl!(@attrs $($arg),*, {
+            $(attr_some $field;)+
+            $($rest)*
+        });
+    };
+
+    (@rel $this:ident, $related:ident, $ctx:ident, {
+        has_many $key:expr, { $($body:tt)* }
+        $($rest:tt)*
+    }) => {
+        if $ctx.field($key) {
+            let key = $key.parse::<$crate::value::Key>()?;
+            expand_resource_impl!(@has_many $this, $related, key, $ctx, {
+                $($body)*
+            });
+        }
+
+        expand_resource_impl!(@rel $this, $related, $ctx, {
+            $($rest)*
+        });
+    };
+
+    (@rel $this:ident, $related:ident, $ctx:ident, {
+        has_many_poly $key:expr, { $($body:tt)* }
+        $($rest:tt)*
+    }) => {
+        if $ctx.field($key) {
+            let key = $key.parse::<$crate::value::Key>()?;
+            expand_resource_impl!(@has_many $this, $related, key, $ctx, {
+                $($body)*
+            });
+        }
+
+        expand_resource_impl!(@rel $this, $related, $ctx, {
+            $($rest)*
+        });
+    };
+
+    (@rel $this:ident, $related:ident, $ctx:ident, {
+        has_one $key:expr, { $($body:tt)* }
+        $($rest:tt)*
+    }) => {
+        if $ctx.field($key) {
+            let key = $key.parse::<$crate::value::Key>()?;
+            expand_resource_impl!(@has_one $this, $related, key, $ctx, {
+                $($body)*
+            });
+        }
+
+        expand_resource_impl!(@rel $this, $related, $ctx, {
+            $($rest)*
+        });
+    };
+
+    (@rel $this:ident, $($arg:ident),*, {
+        has_many $($field:ident),*;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@rel $this, $($arg),*, {
+            $(has_many stringify!($field), { data $this.$field.iter(); })*
+            $($rest)*
+        });
+    };
+
+    (@rel $this:ident, $($arg:ident),*, {
+        has_many_poly $($field:ident),*;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@rel $this, $($arg),*, {
+            $(has_many stringify!($field), { data $this.$field.iter(); })*
+            $($rest)*
+        });
+    };
+
+    (@rel $this:ident, $($arg:ident),*, {
+        has_one $($field:ident),*;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@rel $this, $($arg),*, {
+            $(has_one stringify!($field), { data $this.$field.as_ref(); })*
+            $($rest)*
+        });
+    };
+
+    (@has_many $this:ident, $related:ident, $key:ident, $ctx:ident, {
+        data $value:block
+        $($rest:tt)*
+    }) => {
+        let load = || -> Result<_, $crate::Error> { Ok($value) };
+        let mut rel = $crate::doc::Relationship::default();
+
+        rel.data = {
+            let included = $ctx.fork(data_kind(&load), &$key).try_included()?;
+
+            if included || $ctx.emits_linkage() {
+                let iter = expand_resource_impl!(@eval load, $key);
+                Some(expand_resource_impl!(@items $ctx, $key, included, iter).into())
+            } else {
+                None
+            }
+        };
+
+        {
+            let links = &mut rel.links;
+            expand_resource_impl!(@links $this, links, {
+                $($rest)*
+            });
+        }
+
+        {
+            let _meta = &mut rel.meta;
+            expand_resource_impl!(@meta $this, _meta, $ctx, {
+                $($rest)*
+            });
+        }
+
+        expand_resource_impl!(@count rel, Some(expand_resource_impl!(@eval load, $key).count()), {
+            $($rest)*
+        });
+
+        $related.insert($key, rel);
+    };
+
+    (@has_many $this:ident, $related:ident, $key:ident, $ctx:ident, {
+        data_some $value:block
+        $($rest:tt)*
+    }) => {
+        let load = || -> Result<_, $crate::Error> { Ok($value) };
+        let mut rel = $crate::doc::Relationship::default();
+
+        rel.data = {
+            let included = $ctx.fork(data_some_kind(&load), &$key).try_included()?;
+
+            if included || $ctx.emits_linkage() {
+                match expand_resource_impl!(@eval load, $key) {
+                    Some(iter) => {
+                        Some(expand_resource_impl!(@items $ctx, $key, included, iter).into())
+                    }
+                    None => None,
+                }
+            } else {
+                None
+            }
+        };
+
+        {
+            let links = &mut rel.links;
+            expand_resource_impl!(@links $this, links, {
+                $($rest)*
+            });
+        }
+
+        {
+            let _meta = &mut rel.meta;
+            expand_resource_impl!(@meta $this, _meta, $ctx, {
+                $($rest)*
+            });
+        }
+
+        expand_resource_impl!(@count rel, None, {
+            $($rest)*
+        });
+
+        $related.insert($key, rel);
+    };
+
+    (@has_one $this:ident, $related:ident, $key:ident, $ctx:ident, {
+        data $value:block
+        $($rest:tt)*
+    }) => {
+        let load = || -> Result<_, $crate::Error> { Ok($value) };
+        let mut rel = $crate::doc::Relationship::default();
+
+        rel.data = {
+            let included = $ctx.fork(data_kind(&load), &$key).try_included()?;
+
+            if included || $ctx.emits_linkage() {
+                let mut data = None;
+
+                if let Some(item) = expand_resource_impl!(@eval load, $key) {
+                    let kind = $crate::Resource::instance_kind(item);
+                    let mut ctx = $ctx.fork(kind.clone(), &$key);
+
+                    data = Some($crate::Resource::to_ident(item, &mut ctx)?);
+
+                    if included && ctx.visit(kind, $crate::Resource::id(item)) {
+                        let object = $crate::Resource::to_object(item, &mut ctx)?;
+
+                        ctx.leave();
+                        ctx.include(object);
+                    }
+                }
+
+                Some(data.into())
+            } else {
+                None
+            }
+        };
+
+        {
+            let _links = &mut rel.links;
+            expand_resource_impl!(@links $this, _links, {
+                $($rest)*
+            });
+        }
+
+        {
+            let _meta = &mut rel.meta;
+            expand_resource_impl!(@meta $this, _meta, $ctx, {
+                $($rest)*
+            });
+        }
+
+        $related.insert($key, rel);
+    };
+
+    // Evaluates the data block of a relationship. An error returned from the block is
+    // chained with an error that names the relationship.
+    (@eval $load:ident, $key:ident) => {
+        $crate::error::ResultExt::chain_err($load(), || {
+            format!(r#"failed to evaluate the data of relationship "{}""#, $key)
+        })?
+    };
+
+    // Renders each resource of a to-many relationship as an identifier and adds the
+    // resource to the included set of the context if `$included` is `true`.
+    (@items $ctx:ident, $key:ident, $included:ident, $iter:ident) => {{
+        let mut data = match $iter.size_hint() {
+            (_, Some(size)) => Vec::with_capacity(size),
+            _ => Vec::new(),
+        };
+
+        for item in $iter {
+            let kind = $crate::Resource::instance_kind(item);
+            let mut ctx = $ctx.fork(kind.clone(), &$key);
+
+            data.push($crate::Resource::to_ident(item, &mut ctx)?);
+
+            if $included && ctx.visit(kind, $crate::Resource::id(item)) {
+                let object = $crate::Resource::to_object(item, &mut ctx)?;
+
+                ctx.leave();
+                ctx.include(object);
+            }
+        }
+
+        data
+    }};
+
+    // Adds the number of resources in a to-many relationship to its meta if the body
+    // contains `count;`. The `$fallback` is only evaluated if the relationship was
+    // rendered without resource linkage.
+    (@count $rel:ident, $fallback:expr, { count; $($rest:tt)* }) => {
+        let count = match $rel.data {
+            Some($crate::doc::Data::Collection(ref data)) => Some(data.len()),
+            _ => $fallback,
+        };
+
+        if let Some(count) = count {
+            $rel = $rel.with_count(count);
+        }
+    };
+
+    (@count $rel:ident, $fallback:expr, { $skip:tt $($rest:tt)* }) => {
+        expand_resource_impl!(@count $rel, $fallback, { $($rest)* });
+    };
+
+    (@count $rel:ident, $fallback:expr, {}) => {};
+
+    (@links $this:ident, $links:ident, {
+        link $key:expr, { $($body:tt)* }
+        $($rest:tt)*
+    }) => {
+        {
+            let key = $key.parse::<$crate::value::Key>()?;
+            let link = expand_resource_impl!(@link $this, {
+                $($body)*
+            });
+
+            $links.insert(key, link);
+        }
+
+        expand_resource_impl!(@links $this, $links, {
+            $($rest)*
+        });
+    };
+
+    (@links $($args:ident),+, {
+        link $key:expr, $value:expr;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@links $($args),+, {
+            link $key, { href { $value } }
+            $($rest)*
+        });
+    };
+
+    (@link $this:ident, { href $value:block $($rest:tt)* }) => {{
+        let mut link = $value.parse::<$crate::doc::Link>()?;
+
+        {
+            let _meta = &link.meta;
+            expand_resource_impl!(@meta $this, _meta, {
+                $($rest)*
+            });
+        }
+
+        link
+    }};
+
+    (@meta $this:ident, $meta:ident, $ctx:ident, {
+        meta $key:expr, |$arg:ident| $value:block
+        $($rest:tt)*
+    }) => {
+        {
+            let key = $key.parse::<$crate::value::Key>()?;
+            let value = {
+                let $arg: &$crate::view::Context = &*$ctx;
+                $crate::to_value($value)?
+            };
+
+            $meta.insert(key, value);
+        }
+
+        expand_resource_impl!(@meta $this, $meta, $ctx, {
+            $($rest)*
+        });
+    };
+
+    (@meta $this:ident, $meta:ident, $ctx:ident, {
+        meta $key:expr, $value:block
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@meta $this, $meta, {
+            meta $key, $value
+        });
+
+        expand_resource_impl!(@meta $this, $meta, $ctx, {
+            $($rest)*
+        });
+    };
+
+    (@meta $this:ident, $meta:ident, {
+        meta $key:expr, $value:block
+        $($rest:tt)*
+    }) => {
+        {
+            let key = $key.parse::<$crate::value::Key>()?;
+            let value = $crate::to_value($value)?;
+
+            $meta.insert(key, value);
+        }
+
+        expand_resource_impl!(@meta $this, $meta, {
+            $($rest)*
+        });
+    };
+
+    (@object_meta $this:ident, $meta:ident, $ctx:ident, {
+        object_meta $key:expr, |$arg:ident| $value:block
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@meta $this, $meta, $ctx, {
+            meta $key, |$arg| $value
+        });
+
+        expand_resource_impl!(@object_meta $this, $meta, $ctx, {
+            $($rest)*
+        });
+    };
+
+    (@object_meta $this:ident, $meta:ident, $ctx:ident, {
+        object_meta $key:expr, $value:block
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@meta $this, $meta, {
+            meta $key, $value
+        });
+
+        expand_resource_impl!(@object_meta $this, $meta, $ctx, {
+            $($rest)*
+        });
     };
 
-    (@rel $this:ident, $($arg:ident),*, {
-        has_many $($field:ident),*;
+    (@ident_meta $this:ident, $meta:ident, $ctx:ident, {
+        ident_meta $key:expr, |$arg:ident| $value:block
         $($rest:tt)*
     }) => {
-        expand_resource_impl!(@rel $this, $($arg),*, {
-            $(has_many stringify!($field), { data $this.$field.iter(); })*
+        expand_resource_impl!(@meta $this, $meta, $ctx, {
+            meta $key, |$arg| $value
+        });
+
+        expand_resource_impl!(@ident_meta $this, $meta, $ctx, {
             $($rest)*
         });
     };
 
-    (@rel $this:ident, $($arg:ident),*, {
-        has_one $($field:ident),*;
+    (@ident_meta $this:ident, $meta:ident, $ctx:ident, {
+        ident_meta $key:expr, $value:block
         $($rest:tt)*
     }) => {
-        expand_resource_impl!(@rel $this, $($arg),*, {
-            $(has_one stringify!($field), { data $this.$field.as_ref(); })*
+        expand_resource_impl!(@meta $this, $meta, {
+            meta $key, $value
+        });
+
+        expand_resource_impl!(@ident_meta $this, $meta, $ctx, {
             $($rest)*
         });
     };
 
-    (@has_many $this:ident, $related:ident, $key:ident, $ctx:ident, {
-        data $value:block
+    (@doc_links $this:ident, $links:ident, {
+        doc_link $key:expr, { $($body:tt)* }
         $($rest:tt)*
     }) => {
-        let mut rel = $crate::doc::Relationship::new({
-            let mut ctx = $ctx.fork(iter_kind(&$value), &$key);
-            let mut data = match $value.size_hint() {
-                (_, Some(size)) => Vec::with_capacity(size),
-                _ => Vec::new(),
-            };
+        {
+            let key = $key.parse::<$crate::value::Key>()?;
+            let link = expand_resource_impl!(@link $this, {
+                $($body)*
+            });
 
-            if ctx.included() {
-                for item in $value {
-                    let object = $crate::Resource::to_object(item, &mut ctx)?;
-                    let ident = $crate::doc::Identifier::from(&object);
+            $links.insert(key, link);
+        }
 
-                    ctx.include(object);
-                    data.push(ident);
-                }
-            } else {
-                for item in $value {
-                    data.push($crate::Resource::to_ident(item, &mut ctx)?);
-                }
-            }
+        expand_resource_impl!(@doc_links $this, $links, {
+            $($rest)*
+        });
+    };
 
-            data.into()
+    (@doc_links $($args:ident),+, {
+        doc_link $key:expr, $value:expr;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@doc_links $($args),+, {
+            doc_link $key, { href { $value } }
+            $($rest)*
         });
+    };
 
+    (@doc_meta $this:ident, $meta:ident, {
+        doc_meta $key:expr, $value:block
+        $($rest:tt)*
+    }) => {
         {
-            let links = &mut rel.links;
-            expand_resource_impl!(@links $this, links, {
-                $($rest)*
-            });
-        }
+            let key = $key.parse::<$crate::value::Key>()?;
+            let value = $crate::to_value($value)?;
 
-        {
-            let _meta = &mut rel.meta;
-            expand_resource_impl!(@meta $this, _meta, {
-                $($rest)*
-            });
+            $meta.insert(key, value);
         }
 
-        $related.insert($key, rel);
+        expand_resource_impl!(@doc_meta $this, $meta, {
+            $($rest)*
+        });
     };
 
-    (@has_one $this:ident, $related:ident, $key:ident, $ctx:ident, {
-        data $value:block
+    (@deprecations $notes:ident, {
+        attr $field:ident, deprecated $note:expr;
         $($rest:tt)*
     }) => {
-        let mut rel = $crate::doc::Relationship::new({
-            let mut data = None;
+        $notes.insert(stringify!($field).parse()?, String::from($note));
+        expand_resource_impl!(@deprecations $notes, {
+            $($rest)*
+        });
+    };
+
+    (@schema $schema:ident, {
+        attr $field:ident, deprecated $note:expr;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@schema $schema, {
+            attr $field;
+            $($rest)*
+        });
+    };
 
-            if let Some(item) = $value {
-                let mut ctx = $ctx.fork(item_kind(item), &$key);
+    (@schema $schema:ident, { attr $field:ident; $($rest:tt)* }) => {
+        expand_resource_impl!(@schema $schema, {
+            attr stringify!($field), {}
+            $($rest)*
+        });
+    };
 
-                data = Some($crate::Resource::to_ident(item, &mut ctx)?);
+    (@schema $schema:ident, { attr_some $field:ident; $($rest:tt)* }) => {
+        expand_resource_impl!(@schema $schema, {
+            attr_some stringify!($field), {}
+            $($rest)*
+        });
+    };
 
-                if ctx.included() {
-                    let object = $crate::Resource::to_object(item, &mut ctx)?;
-                    ctx.include(object);
-                }
-            }
+    (@schema $schema:ident, { attrs $($field:ident),+; $($rest:tt)* }) => {
+        expand_resource_impl!(@schema $schema, {
+            $(attr $field;)+
+            $($rest)*
+        });
+    };
 
-            data.into()
+    (@schema $schema:ident, { attrs_some $($field:ident),+; $($rest:tt)* }) => {
+        expand_resource_impl!(@schema $schema, {
+            $(attr_some $field;)+
+            $($rest)*
         });
+    };
 
-        {
-            let _links = &mut rel.links;
-            expand_resource_impl!(@links $this, _links, {
-                $($rest)*
-            });
-        }
+    (@schema $schema:ident, {
+        attr $key:expr => $value:expr;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@schema $schema, {
+            attr $key, {}
+            $($rest)*
+        });
+    };
 
-        {
-            let _meta = &mut rel.meta;
-            expand_resource_impl!(@meta $this, _meta, {
-                $($rest)*
-            });
-        }
+    (@schema $schema:ident, {
+        attr_some $key:expr => $value:expr;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@schema $schema, {
+            attr_some $key, {}
+            $($rest)*
+        });
+    };
 
-        $related.insert($key, rel);
+    (@schema $schema:ident, {
+        attr $key:expr, |$arg:ident| $value:block
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@schema $schema, {
+            attr $key, {}
+            $($rest)*
+        });
     };
 
-    (@links $this:ident, $links:ident, {
-        link $key:expr, { $($body:tt)* }
+    (@schema $schema:ident, {
+        attr_some $key:expr, |$arg:ident| $value:block
         $($rest:tt)*
     }) => {
-        {
-            let key = $key.parse::<$crate::value::Key>()?;
-            let link = expand_resource_impl!(@link $this, {
-                $($body)*
-            });
+        expand_resource_impl!(@schema $schema, {
+            attr_some $key, {}
+            $($rest)*
+        });
+    };
 
-            $links.insert(key, link);
-        }
+    (@schema $schema:ident, {
+        attr $key:expr, $value:block
+        $($rest:tt)*
+    }) => {
+        let key = $key.parse::<$crate::value::Key>()?;
+        $schema.attributes.push($crate::schema::AttributeSchema::new(key));
 
-        expand_resource_impl!(@links $this, $links, {
+        expand_resource_impl!(@schema $schema, {
             $($rest)*
         });
     };
 
-    (@links $($args:ident),+, {
-        link $key:expr, $value:expr;
+    (@schema $schema:ident, {
+        attr_some $key:expr, $value:block
         $($rest:tt)*
     }) => {
-        expand_resource_impl!(@links $($args),+, {
-            link $key, { href { $value } }
+        let mut attr = $crate::schema::AttributeSchema::new($key.parse()?);
+
+        attr.optional = true;
+        $schema.attributes.push(attr);
+
+        expand_resource_impl!(@schema $schema, {
             $($rest)*
         });
     };
 
-    (@link $this:ident, { href $value:block $($rest:tt)* }) => {{
-        let mut link = $value.parse::<$crate::doc::Link>()?;
+    (@schema $schema:ident, {
+        has_one $($field:ident),*;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@schema $schema, {
+            $(has_one stringify!($field), {})*
+            $($rest)*
+        });
+    };
 
-        {
-            let _meta = &link.meta;
-            expand_resource_impl!(@meta $this, _meta, {
-                $($rest)*
-            });
-        }
+    (@schema $schema:ident, {
+        has_many $($field:ident),*;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@schema $schema, {
+            $(has_many stringify!($field), {})*
+            $($rest)*
+        });
+    };
 
-        link
-    }};
+    (@schema $schema:ident, {
+        has_many_poly $($field:ident),*;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@schema $schema, {
+            $(has_many stringify!($field), {})*
+            $($rest)*
+        });
+    };
 
-    (@meta $this:ident, $meta:ident, {
-        meta $key:expr, $value:block
+    (@schema $schema:ident, {
+        has_one $key:expr, { $($body:tt)* }
         $($rest:tt)*
     }) => {
-        {
-            let key = $key.parse::<$crate::value::Key>()?;
-            let value = $crate::to_value($value)?;
+        let key = $key.parse::<$crate::value::Key>()?;
+        $schema.relationships.push($crate::schema::RelationshipSchema::new(key, false));
 
-            $meta.insert(key, value);
-        }
+        expand_resource_impl!(@schema $schema, {
+            $($rest)*
+        });
+    };
 
-        expand_resource_impl!(@meta $this, $meta, {
+    (@schema $schema:ident, {
+        has_many $key:expr, { $($body:tt)* }
+        $($rest:tt)*
+    }) => {
+        let key = $key.parse::<$crate::value::Key>()?;
+        $schema.relationships.push($crate::schema::RelationshipSchema::new(key, true));
+
+        expand_resource_impl!(@schema $schema, {
+            $($rest)*
+        });
+    };
+
+    (@schema $schema:ident, {
+        has_many_poly $key:expr, { $($body:tt)* }
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@schema $schema, {
+            has_many $key, {}
+            $($rest)*
+        });
+    };
+
+    // Ignore deprecated attribute syntax in other scopes.
+    (@$scope:tt $($args:ident),+, {
+        attr $field:ident, deprecated $note:expr;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@$scope $($args),+, {
+            $($rest)*
+        });
+    };
+
+    // Ignore instance kind syntax in other scopes.
+    (@$scope:tt $($args:ident),+, {
+        kind |&$arg:ident| $value:block
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@$scope $($args),+, {
             $($rest)*
         });
     };
@@ -634,6 +2371,16 @@ macro_rules! expand_resource_impl {
         });
     };
 
+    // Ignore has_many_poly specific syntax in other scopes.
+    (@$scope:tt $($args:ident),+, {
+        has_many_poly $key:expr, { $($body:tt)* }
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@$scope $($args),+, {
+            $($rest)*
+        });
+    };
+
     // Ignore has_one specific syntax in other scopes.
     (@$scope:tt $($args:ident),+, {
         has_one $key:expr, { $($body:tt)* }
@@ -644,6 +2391,46 @@ macro_rules! expand_resource_impl {
         });
     };
 
+    // Ignore parse_id specific syntax in other scopes.
+    (@$scope:tt $($args:ident),+, {
+        parse_id |$arg:ident| -> $ret:ty $body:block
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@$scope $($args),+, {
+            $($rest)*
+        });
+    };
+
+    // Ignore renamed attribute syntax in other scopes.
+    (@$scope:tt $($args:ident),+, {
+        attr $key:expr => $value:expr;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@$scope $($args),+, {
+            $($rest)*
+        });
+    };
+
+    // Ignore renamed optional attribute syntax in other scopes.
+    (@$scope:tt $($args:ident),+, {
+        attr_some $key:expr => $value:expr;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@$scope $($args),+, {
+            $($rest)*
+        });
+    };
+
+    // Ignore context specific syntax in other scopes.
+    (@$scope:tt $($args:ident),+, {
+        $kwd:ident $key:expr, |$arg:ident| $value:block
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@$scope $($args),+, {
+            $($rest)*
+        });
+    };
+
     // Ignore link specific syntax in other scopes.
     (@$scope:tt $($args:ident),+, {
         link $key:expr, { $($body:tt)* }
@@ -654,6 +2441,16 @@ macro_rules! expand_resource_impl {
         });
     };
 
+    // Ignore doc_link specific syntax in other scopes.
+    (@$scope:tt $($args:ident),+, {
+        doc_link $key:expr, { $($body:tt)* }
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@$scope $($args),+, {
+            $($rest)*
+        });
+    };
+
     (@$scope:tt $($args:ident),+, {
         $kwd:ident $value:expr;
         $($rest:tt)*
@@ -725,8 +2522,40 @@ macro_rules! extract_resource_id {
 #[doc(hidden)]
 #[macro_export]
 macro_rules! extract_resource_kind {
+    ({ kind |&$arg:ident| $value:block $($rest:tt)* }) => {
+        extract_resource_kind!({ $($rest)* })
+    };
     ({ kind $value:block $($rest:tt)* }) => { $value };
     ({ kind $value:expr; $($rest:tt)* }) => { $value };
     ({ $skip:tt $($rest:tt)* }) => { extract_resource_kind!({ $($rest)* }) };
     ({ $($rest:tt)* }) => ();
 }
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! extract_resource_instance_kind {
+    ($target:ident, { kind |&$arg:ident| $value:block $($rest:tt)* }) => {
+        $crate::value::Key::from_raw($value.to_owned())
+    };
+    ($target:ident, { $skip:tt $($rest:tt)* }) => {
+        extract_resource_instance_kind!($target, { $($rest)* })
+    };
+    ($target:ident, { $($rest:tt)* }) => {
+        <$target as $crate::Resource>::kind()
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! expand_resource_parse_id {
+    ($target:ident, { parse_id |$arg:ident| -> $ret:ty $body:block $($rest:tt)* }) => {
+        impl $target {
+            /// Parses the components of an id that was rendered by this resource.
+            pub fn parse_id($arg: &str) -> Result<$ret, $crate::Error> $body
+        }
+    };
+    ($target:ident, { $skip:tt $($rest:tt)* }) => {
+        expand_resource_parse_id!($target, { $($rest)* });
+    };
+    ($target:ident, { $($rest:tt)* }) => ();
+}
@@ -0,0 +1,276 @@
+//! Describes the members of a resource type without rendering a value.
+//!
+//! The [`resource!`] macro implements [`Resource::schema`] by collecting the attributes
+//! and relationships that are declared in its body. Attributes may be annotated with a
+//! type hint, such as `attr title: "string";`, that is carried by the schema as-is.
+//!
+//! With the `codegen` feature enabled, schemas can be used to generate TypeScript type
+//! definitions with [`to_typescript`].
+//!
+//! # Example
+//!
+//! ```
+//! # #[macro_use]
+//! # extern crate json_api;
+//! #
+//! # use json_api::Error;
+//! #
+//! struct Post {
+//!     id: u64,
+//!     title: String,
+//!     body: Option<String>,
+//!     comments: Vec<Comment>,
+//! }
+//!
+//! resource!(Post, |&self| {
+//!     kind "posts";
+//!     id self.id;
+//!
+//!     attr title: "string";
+//!     attr_some body;
+//!     has_many comments;
+//! });
+//!
+//! struct Comment(u64);
+//!
+//! resource!(Comment, |&self| {
+//!     kind "comments";
+//!     id self.0;
+//! });
+//!
+//! # fn example() -> Result<(), Error> {
+//! use json_api::Resource;
+//!
+//! let schema = Post::schema()?;
+//!
+//! assert_eq!(schema.kind, "posts");
+//! assert_eq!(schema.attributes[0].name, "title");
+//! assert_eq!(schema.attributes[0].hint, Some("string".to_owned()));
+//! assert!(schema.attributes[1].optional);
+//! assert!(schema.relationships[0].to_many);
+//! # Ok(())
+//! # }
+//! #
+//! # fn main() {
+//! # example().unwrap();
+//! # }
+//! ```
+//!
+//! [`resource!`]: ../macro.resource.html
+//! [`Resource::schema`]: ../trait.Resource.html#method.schema
+//! [`to_typescript`]: ./fn.to_typescript.html
+
+#[cfg(feature = "codegen")]
+mod typescript;
+
+use error::Error;
+use resource::Resource;
+use value::collections::map;
+use value::{Key, Map};
+
+#[cfg(feature = "codegen")]
+pub use self::typescript::{to_typescript, TsOptions};
+
+/// The attributes and relationships of a resource type.
+///
+/// Members are listed in the order that they are declared.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResourceSchema {
+    /// The type of the resource.
+    pub kind: Key,
+
+    /// The attributes of the resource.
+    pub attributes: Vec<AttributeSchema>,
+
+    /// The relationships of the resource.
+    pub relationships: Vec<RelationshipSchema>,
+
+    /// Private field for backwards compatibility.
+    _ext: (),
+}
+
+impl ResourceSchema {
+    /// Returns a new `ResourceSchema` with the given type and no members.
+    pub fn new(kind: Key) -> Self {
+        ResourceSchema {
+            kind,
+            attributes: Default::default(),
+            relationships: Default::default(),
+            _ext: (),
+        }
+    }
+
+    /// Returns the attribute with the member name `name`.
+    pub fn attribute(&self, name: &str) -> Option<&AttributeSchema> {
+        self.attributes.iter().find(|attr| attr.name == name)
+    }
+
+    /// Returns the relationship with the member name `name`.
+    pub fn relationship(&self, name: &str) -> Option<&RelationshipSchema> {
+        self.relationships.iter().find(|rel| rel.name == name)
+    }
+}
+
+/// An attribute of a resource type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AttributeSchema {
+    /// The member name of the attribute.
+    pub name: Key,
+
+    /// An optional type hint for the value of the attribute. The hint is not
+    /// interpreted by this crate.
+    pub hint: Option<String>,
+
+    /// Whether or not the attribute is omitted when its value is `null`. This is `true`
+    /// for attributes that are declared with `attr_some`.
+    pub optional: bool,
+
+    /// Private field for backwards compatibility.
+    _ext: (),
+}
+
+impl AttributeSchema {
+    /// Returns a new required `AttributeSchema` without a type hint.
+    pub fn new(name: Key) -> Self {
+        AttributeSchema {
+            name,
+            hint: None,
+            optional: false,
+            _ext: (),
+        }
+    }
+}
+
+/// A relationship of a resource type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RelationshipSchema {
+    /// The member name of the relationship.
+    pub name: Key,
+
+    /// Whether the relationship is a to-many relationship rather than a to-one
+    /// relationship.
+    pub to_many: bool,
+
+    /// The type of the related resources, if it was declared. The type can be used to
+    /// find the schema of the related resources in a [`SchemaRegistry`].
+    ///
+    /// [`SchemaRegistry`]: ./struct.SchemaRegistry.html
+    pub kind: Option<Key>,
+
+    /// Private field for backwards compatibility.
+    _ext: (),
+}
+
+impl RelationshipSchema {
+    /// Returns a new `RelationshipSchema` without the type of the related resources.
+    pub fn new(name: Key, to_many: bool) -> Self {
+        RelationshipSchema {
+            name,
+            to_many,
+            kind: None,
+            _ext: (),
+        }
+    }
+}
+
+/// The schemas of the resource types that are known to an application, keyed by type.
+///
+/// A registry is used to follow the relationships of a resource type to the schemas
+/// of the related resource types.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// struct Post {
+///     id: u64,
+///     author: Option<User>,
+/// }
+///
+/// resource!(Post, |&self| {
+///     kind "posts";
+///     id self.id;
+///
+///     has_one author: "users";
+/// });
+///
+/// struct User {
+///     id: u64,
+///     name: String,
+/// }
+///
+/// resource!(User, |&self| {
+///     kind "users";
+///     id self.id;
+///
+///     attr name: "string";
+/// });
+///
+/// # fn example() -> Result<(), Error> {
+/// use json_api::schema::SchemaRegistry;
+///
+/// let mut registry = SchemaRegistry::new();
+///
+/// registry.register::<Post>()?.register::<User>()?;
+///
+/// let posts = registry.get("posts").unwrap();
+/// let author = posts.relationship("author").unwrap();
+/// let users = registry.get(author.kind.as_ref().unwrap()).unwrap();
+///
+/// assert!(users.attribute("name").is_some());
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SchemaRegistry {
+    schemas: Map<Key, ResourceSchema>,
+}
+
+impl SchemaRegistry {
+    /// Returns a new, empty registry.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds the [`schema`] of the resource `T` to the registry.
+    ///
+    /// [`schema`]: ../trait.Resource.html#method.schema
+    pub fn register<T: Resource>(&mut self) -> Result<&mut Self, Error> {
+        self.insert(T::schema()?);
+        Ok(self)
+    }
+
+    /// Adds `schema` to the registry. If the registry already contained a schema for
+    /// the same type, the previous schema is returned.
+    pub fn insert(&mut self, schema: ResourceSchema) -> Option<ResourceSchema> {
+        self.schemas.insert(schema.kind.clone(), schema)
+    }
+
+    /// Returns the schema of the resource type `kind`.
+    pub fn get(&self, kind: &str) -> Option<&ResourceSchema> {
+        self.schemas.get(kind)
+    }
+
+    /// Returns the number of schemas in the registry.
+    pub fn len(&self) -> usize {
+        self.schemas.len()
+    }
+
+    /// Returns `true` if the registry does not contain any schemas.
+    pub fn is_empty(&self) -> bool {
+        self.schemas.is_empty()
+    }
+
+    /// Returns an iterator visiting each schema in the order that they were added.
+    pub fn iter(&self) -> map::Values<Key, ResourceSchema> {
+        self.schemas.values()
+    }
+}
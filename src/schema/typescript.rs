@@ -0,0 +1,242 @@
+use std::fmt::Write;
+
+use schema::ResourceSchema;
+
+/// Options that control how TypeScript type definitions are generated.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TsOptions {
+    /// Prefix each interface with the `export` keyword. Defaults to `true`.
+    pub export: bool,
+
+    /// The number of spaces used for each level of indentation. Defaults to `2`.
+    pub indent: usize,
+
+    /// Private field for backwards compatibility.
+    _ext: (),
+}
+
+impl TsOptions {
+    /// Returns the default options.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl Default for TsOptions {
+    fn default() -> Self {
+        TsOptions {
+            export: true,
+            indent: 2,
+            _ext: (),
+        }
+    }
+}
+
+/// Generates TypeScript type definitions for the given resource types.
+///
+/// An interface is emitted for each resource, named after its type in PascalCase,
+/// along with a `ResourceIdentifier` interface and a generic `Document<T>` interface.
+/// Attributes are typed with the hint that they were declared with, or `unknown` if
+/// they do not have one. To-one relationships are typed as `ResourceIdentifier | null`
+/// and to-many relationships as `ResourceIdentifier[]`.
+///
+/// Resources are sorted by type and members are sorted by name, so the output does not
+/// depend on the order of `schemas` or the order that members are declared in.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// struct User {
+///     id: u64,
+///     name: String,
+/// }
+///
+/// resource!(User, |&self| {
+///     kind "users";
+///     id self.id;
+///     attr name: "string";
+/// });
+///
+/// # fn example() -> Result<(), Error> {
+/// use json_api::Resource;
+/// use json_api::schema::{self, TsOptions};
+///
+/// let output = schema::to_typescript(&[User::schema()?], &TsOptions::new());
+///
+/// assert!(output.contains("export interface Users {"));
+/// assert!(output.contains("    name: string;"));
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+pub fn to_typescript(schemas: &[ResourceSchema], opts: &TsOptions) -> String {
+    let mut schemas = schemas.iter().collect::<Vec<_>>();
+    let mut output = String::new();
+    let export = if opts.export { "export " } else { "" };
+    let tab = " ".repeat(opts.indent);
+
+    schemas.sort_by(|a, b| a.kind.cmp(&b.kind));
+
+    let included = if schemas.is_empty() {
+        "unknown[]".to_owned()
+    } else {
+        let names = schemas
+            .iter()
+            .map(|schema| interface_name(&schema.kind))
+            .collect::<Vec<_>>();
+
+        format!("Array<{}>", names.join(" | "))
+    };
+
+    let _ = writeln!(output, "// This file was generated by json-api. Do not edit it by hand.");
+    let _ = writeln!(output);
+
+    let _ = writeln!(output, "{}interface ResourceIdentifier {{", export);
+    let _ = writeln!(output, "{}type: string;", tab);
+    let _ = writeln!(output, "{}id: string;", tab);
+    let _ = writeln!(output, "{}meta?: {{ [key: string]: unknown }};", tab);
+    let _ = writeln!(output, "}}");
+    let _ = writeln!(output);
+
+    let _ = writeln!(output, "{}interface Document<T> {{", export);
+    let _ = writeln!(output, "{}data?: T;", tab);
+    let _ = writeln!(output, "{}included?: {};", tab, included);
+    let _ = writeln!(output, "{}errors?: unknown[];", tab);
+    let _ = writeln!(output, "{}links?: {{ [key: string]: unknown }};", tab);
+    let _ = writeln!(output, "{}meta?: {{ [key: string]: unknown }};", tab);
+    let _ = writeln!(output, "{}jsonapi?: {{ version?: string }};", tab);
+    let _ = writeln!(output, "}}");
+
+    for schema in schemas {
+        let mut attributes = schema.attributes.iter().collect::<Vec<_>>();
+        let mut relationships = schema.relationships.iter().collect::<Vec<_>>();
+
+        attributes.sort_by(|a, b| a.name.cmp(&b.name));
+        relationships.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let _ = writeln!(output);
+        let _ = writeln!(
+            output,
+            "{}interface {} {{",
+            export,
+            interface_name(&schema.kind)
+        );
+        let _ = writeln!(output, "{}type: {};", tab, quote(&schema.kind));
+        let _ = writeln!(output, "{}id: string;", tab);
+
+        if !attributes.is_empty() {
+            let _ = writeln!(output, "{}attributes: {{", tab);
+
+            for attr in attributes {
+                let _ = writeln!(
+                    output,
+                    "{0}{0}{1}{2}: {3};",
+                    tab,
+                    property_name(&attr.name),
+                    if attr.optional { "?" } else { "" },
+                    attr.hint.as_ref().map_or("unknown", |hint| hint.as_str())
+                );
+            }
+
+            let _ = writeln!(output, "{}}};", tab);
+        }
+
+        if !relationships.is_empty() {
+            let _ = writeln!(output, "{}relationships: {{", tab);
+
+            for rel in relationships {
+                let data = if rel.to_many {
+                    "ResourceIdentifier[]"
+                } else {
+                    "ResourceIdentifier | null"
+                };
+
+                let _ = writeln!(
+                    output,
+                    "{0}{0}{1}: {{ data: {2} }};",
+                    tab,
+                    property_name(&rel.name),
+                    data
+                );
+            }
+
+            let _ = writeln!(output, "{}}};", tab);
+        }
+
+        let _ = writeln!(output, "}}");
+    }
+
+    output
+}
+
+/// Converts a resource type such as `blog-posts` to an interface name such as
+/// `BlogPosts`.
+fn interface_name(kind: &str) -> String {
+    let mut name = String::with_capacity(kind.len());
+    let mut upper = true;
+
+    for value in kind.chars() {
+        if !value.is_ascii_alphanumeric() {
+            upper = true;
+        } else if upper {
+            name.push(value.to_ascii_uppercase());
+            upper = false;
+        } else {
+            name.push(value);
+        }
+    }
+
+    if name.starts_with(|value: char| value.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+
+    name
+}
+
+/// Returns `name` as-is if it is a valid identifier. Otherwise, `name` is quoted.
+fn property_name(name: &str) -> String {
+    let mut chars = name.chars();
+    let valid = match chars.next() {
+        Some(value) => {
+            (value.is_ascii_alphabetic() || value == '_' || value == '$')
+                && chars.all(|value| value.is_ascii_alphanumeric() || value == '_' || value == '$')
+        }
+        None => false,
+    };
+
+    if valid {
+        name.to_owned()
+    } else {
+        quote(name)
+    }
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{interface_name, property_name};
+
+    #[test]
+    fn names() {
+        assert_eq!(interface_name("posts"), "Posts");
+        assert_eq!(interface_name("blog-posts"), "BlogPosts");
+        assert_eq!(interface_name("blog_post_tags"), "BlogPostTags");
+        assert_eq!(interface_name("3d-models"), "_3dModels");
+
+        assert_eq!(property_name("title"), "title");
+        assert_eq!(property_name("publishedAt"), "publishedAt");
+        assert_eq!(property_name("published-at"), r#""published-at""#);
+        assert_eq!(property_name("1st"), r#""1st""#);
+    }
+}
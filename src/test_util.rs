@@ -0,0 +1,227 @@
+//! Assertions for testing the documents that are rendered by a `Resource`.
+//!
+//! This module is only compiled when the `test-util` feature is enabled. It is
+//! intended to be used from the test suite of a crate that implements `Resource`, by
+//! enabling the feature in `[dev-dependencies]`.
+//!
+//! # Example
+//!
+//! ```
+//! # #[macro_use]
+//! # extern crate json_api;
+//! # #[macro_use]
+//! # extern crate serde_json;
+//! #
+//! use json_api::test_util::assert_renders_to;
+//!
+//! struct Post {
+//!     id: u64,
+//!     title: String,
+//! }
+//!
+//! resource!(Post, |&self| {
+//!     kind "posts";
+//!     id self.id;
+//!     attrs title;
+//! });
+//!
+//! # fn main() {
+//! let post = Post {
+//!     id: 1,
+//!     title: "Hello, world!".to_owned(),
+//! };
+//!
+//! assert_renders_to(
+//!     &post,
+//!     None,
+//!     json!({
+//!         "data": {
+//!             "type": "posts",
+//!             "id": "1",
+//!             "attributes": {
+//!                 "title": "Hello, world!"
+//!             }
+//!         },
+//!         "jsonapi": {
+//!             "version": "1.0"
+//!         }
+//!     }),
+//! );
+//! # }
+//! ```
+
+use std::fmt::Write;
+
+use serde_json::{self, Value as JsonValue};
+
+use doc::Object;
+use query::Query;
+use view::Render;
+
+/// Renders `value` as a `Document<Object>` and asserts that it is equal to `expected`.
+///
+/// Members of a JSON object are compared without regard to their order. Arrays must
+/// contain the same elements in the same order.
+///
+/// # Panics
+///
+/// Panics if `value` fails to render or if the rendered document is not equal to
+/// `expected`. The message contains a line-by-line diff of the pretty-printed JSON,
+/// where lines that are only in `expected` are prefixed with `-` and lines that are
+/// only in the rendered document are prefixed with `+`.
+pub fn assert_renders_to<T>(value: T, query: Option<&Query>, expected: JsonValue)
+where
+    T: Render<Object>,
+{
+    let actual = match render_json(value, query) {
+        Ok(actual) => actual,
+        Err(e) => panic!("failed to render document: {}", e),
+    };
+
+    if actual != expected {
+        panic!(
+            "rendered document does not match the expected json (-expected +actual):\n{}",
+            diff(&pretty(&expected), &pretty(&actual))
+        );
+    }
+}
+
+/// Renders `value` as a `Document<Object>` and converts it to a `serde_json::Value`.
+///
+/// This is useful for assertions on a part of a document that [`assert_renders_to`]
+/// is not suited for.
+///
+/// [`assert_renders_to`]: ./fn.assert_renders_to.html
+pub fn render_json<T>(value: T, query: Option<&Query>) -> Result<JsonValue, ::Error>
+where
+    T: Render<Object>,
+{
+    Ok(serde_json::to_value(::to_doc(value, query)?)?)
+}
+
+fn pretty(value: &JsonValue) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+}
+
+/// Returns a line-by-line diff of `expected` and `actual` based on their longest
+/// common subsequence of lines.
+fn diff(expected: &str, actual: &str) -> String {
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+    let (rows, cols) = (expected.len(), actual.len());
+
+    // lcs[i][j] is the length of the longest common subsequence of expected[i..] and
+    // actual[j..].
+    let mut lcs = vec![vec![0; cols + 1]; rows + 1];
+
+    for i in (0..rows).rev() {
+        for j in (0..cols).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut output = String::new();
+    let (mut i, mut j) = (0, 0);
+
+    while i < rows || j < cols {
+        if i < rows && j < cols && expected[i] == actual[j] {
+            let _ = writeln!(output, "  {}", expected[i]);
+            i += 1;
+            j += 1;
+        } else if j < cols && (i == rows || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            let _ = writeln!(output, "+ {}", actual[j]);
+            j += 1;
+        } else {
+            let _ = writeln!(output, "- {}", expected[i]);
+            i += 1;
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{assert_renders_to, diff, render_json};
+    use query;
+
+    struct Post {
+        id: u64,
+        title: String,
+    }
+
+    resource!(Post, |&self| {
+        kind "posts";
+        id self.id;
+        attrs title;
+    });
+
+    fn post() -> Post {
+        Post {
+            id: 1,
+            title: "Hello".to_owned(),
+        }
+    }
+
+    #[test]
+    fn assert_renders_to_passes() {
+        let query = query::from_str("fields[posts]=").unwrap();
+
+        assert_renders_to(
+            &post(),
+            None,
+            json!({
+                "data": {
+                    "type": "posts",
+                    "id": "1",
+                    "attributes": { "title": "Hello" }
+                },
+                "jsonapi": { "version": "1.0" }
+            }),
+        );
+
+        assert_renders_to(
+            &[post()][..],
+            Some(&query),
+            json!({
+                "data": [{ "type": "posts", "id": "1" }],
+                "jsonapi": { "version": "1.0" }
+            }),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = r#"-       "title": "Goodbye""#)]
+    fn assert_renders_to_fails() {
+        assert_renders_to(
+            &post(),
+            None,
+            json!({
+                "data": {
+                    "type": "posts",
+                    "id": "1",
+                    "attributes": { "title": "Goodbye" }
+                },
+                "jsonapi": { "version": "1.0" }
+            }),
+        );
+    }
+
+    #[test]
+    fn render_json_value() {
+        let value = render_json(&post(), None).unwrap();
+        assert_eq!(value["data"]["attributes"]["title"], "Hello");
+    }
+
+    #[test]
+    fn diff_lines() {
+        let output = diff("a\nb\nc", "a\nx\nc\nd");
+        assert_eq!(output, "  a\n+ x\n- b\n  c\n+ d\n");
+    }
+}
@@ -3,17 +3,20 @@
 //! The types in this module are commonly used as the underlying data structure
 //! of arbitrary objects found in JSON API data.
 
+use std::cmp::Ordering;
 use std::fmt::{self, Debug, Formatter};
 use std::hash::Hash;
 use std::iter::FromIterator;
 use std::ops::RangeFull;
 
-use ordermap::{self, OrderMap};
+use indexmap::map;
+use indexmap::IndexMap;
 use serde::de::{Deserialize, Deserializer};
-use serde::ser::{Serialize, Serializer};
+use serde::ser::{Error as SerError, Serialize, SerializeMap, Serializer};
+use serde_json;
 
 use value::collections::Equivalent;
-use value::{Key, Value};
+use value::{ser, Key, Value};
 
 /// A hash map implementation with consistent ordering.
 #[derive(Clone, Eq, PartialEq)]
@@ -21,7 +24,7 @@ pub struct Map<K = Key, V = Value>
 where
     K: Eq + Hash,
 {
-    inner: OrderMap<K, V>,
+    inner: IndexMap<K, V>,
 }
 
 impl<K, V> Map<K, V>
@@ -71,7 +74,7 @@ where
     /// # }
     /// ```
     pub fn with_capacity(capacity: usize) -> Self {
-        let inner = OrderMap::with_capacity(capacity);
+        let inner = IndexMap::with_capacity(capacity);
         Map { inner }
     }
 
@@ -169,6 +172,34 @@ where
         Drain { iter }
     }
 
+    /// Gets the entry of the key for in-place manipulation. A key that is inserted
+    /// through a vacant entry is appended to the end of the map.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Map;
+    /// #
+    /// # fn main() {
+    /// let mut map = Map::new();
+    ///
+    /// for word in "x y x z x".split(' ') {
+    ///     *map.entry(word).or_insert(0) += 1;
+    /// }
+    ///
+    /// assert_eq!(map.get("x"), Some(&3));
+    /// assert_eq!(map.keys().collect::<Vec<_>>(), vec![&"x", &"y", &"z"]);
+    /// # }
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<K, V> {
+        match self.inner.entry(key) {
+            map::Entry::Occupied(inner) => Entry::Occupied(OccupiedEntry { inner }),
+            map::Entry::Vacant(inner) => Entry::Vacant(VacantEntry { inner }),
+        }
+    }
+
     /// Returns a reference to the value corresponding to the key.
     ///
     /// # Example
@@ -194,6 +225,175 @@ where
         self.inner.get(key)
     }
 
+    /// Returns the insertion-order index of the key along with references to the key
+    /// and value stored in the map.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Map;
+    /// #
+    /// # fn main() {
+    /// let mut map = Map::new();
+    ///
+    /// map.insert("x", 1);
+    /// map.insert("y", 2);
+    ///
+    /// assert_eq!(map.get_full("y"), Some((1, &"y", &2)));
+    /// assert_eq!(map.get_full("z"), None);
+    /// # }
+    /// ```
+    pub fn get_full<Q: ?Sized>(&self, key: &Q) -> Option<(usize, &K, &V)>
+    where
+        Q: Equivalent<K> + Hash,
+    {
+        self.inner.get_full(key)
+    }
+
+    /// Returns the insertion-order index of the key along with a reference to the key
+    /// and a mutable reference to the value stored in the map.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Map;
+    /// #
+    /// # fn main() {
+    /// let mut map = Map::new();
+    ///
+    /// map.insert("x", 1);
+    /// map.insert("y", 2);
+    ///
+    /// if let Some((index, _, value)) = map.get_full_mut("y") {
+    ///     *value += index;
+    /// }
+    ///
+    /// assert_eq!(map.get("y"), Some(&3));
+    /// # }
+    /// ```
+    pub fn get_full_mut<Q: ?Sized>(&mut self, key: &Q) -> Option<(usize, &K, &mut V)>
+    where
+        Q: Equivalent<K> + Hash,
+    {
+        self.inner.get_full_mut(key)
+    }
+
+    /// Returns references to the key and value at the insertion-order `index`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Map;
+    /// #
+    /// # fn main() {
+    /// let mut map = Map::new();
+    ///
+    /// map.insert("x", 1);
+    /// map.insert("y", 2);
+    ///
+    /// assert_eq!(map.get_index(0), Some((&"x", &1)));
+    /// assert_eq!(map.get_index(2), None);
+    /// # }
+    /// ```
+    pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        self.inner.get_index(index)
+    }
+
+    /// Returns a reference to the key and a mutable reference to the value at the
+    /// insertion-order `index`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Map;
+    /// #
+    /// # fn main() {
+    /// let mut map = Map::new();
+    ///
+    /// map.insert("x", 1);
+    /// map.insert("y", 2);
+    ///
+    /// if let Some((_, value)) = map.get_index_mut(0) {
+    ///     *value = 10;
+    /// }
+    ///
+    /// assert_eq!(map.get("x"), Some(&10));
+    /// # }
+    /// ```
+    pub fn get_index_mut(&mut self, index: usize) -> Option<(&K, &mut V)> {
+        self.inner.get_index_mut(index).map(|(key, value)| (&*key, value))
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Map;
+    /// #
+    /// # fn main() {
+    /// let mut map = Map::new();
+    ///
+    /// map.insert("x", 1);
+    ///
+    /// if let Some(value) = map.get_mut("x") {
+    ///     *value += 1;
+    /// }
+    ///
+    /// assert_eq!(map.get("x"), Some(&2));
+    /// # }
+    /// ```
+    pub fn get_mut<Q: ?Sized>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        Q: Equivalent<K> + Hash,
+    {
+        self.inner.get_mut(key)
+    }
+
+    /// Returns the insertion-order index of the key, if it is present in the map.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Map;
+    /// #
+    /// # fn main() {
+    /// let mut map = Map::new();
+    ///
+    /// map.insert("x", 1);
+    /// map.insert("y", 2);
+    ///
+    /// assert_eq!(map.index_of("y"), Some(1));
+    /// assert_eq!(map.index_of("z"), None);
+    /// # }
+    /// ```
+    pub fn index_of<Q: ?Sized>(&self, key: &Q) -> Option<usize>
+    where
+        Q: Equivalent<K> + Hash,
+    {
+        self.inner.get_full(key).map(|(index, _, _)| index)
+    }
+
+    /// Returns a reference to the key stored in the map that is equivalent to `key`.
+    pub(crate) fn get_key<Q: ?Sized>(&self, key: &Q) -> Option<&K>
+    where
+        Q: Equivalent<K> + Hash,
+    {
+        self.inner.get_full(key).map(|(_, key, _)| key)
+    }
+
     /// Inserts a key-value pair into the map.
     ///
     /// If a value already existed for key, that old value is returned in
@@ -345,9 +545,38 @@ where
         self.len() == 0
     }
 
+    /// Removes the last key-value pair from the map and returns it. Returns `None` if
+    /// the map is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Map;
+    /// #
+    /// # fn main() {
+    /// let mut map = Map::new();
+    ///
+    /// map.insert("x", 1);
+    /// map.insert("y", 2);
+    ///
+    /// assert_eq!(map.pop(), Some(("y", 2)));
+    /// assert_eq!(map.pop(), Some(("x", 1)));
+    /// assert_eq!(map.pop(), None);
+    /// # }
+    /// ```
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        self.inner.pop()
+    }
+
     /// Removes a key from the map, returning the value at the key if the key
     /// was previously in the map.
     ///
+    /// The remaining entries keep their insertion order. Entries that follow the
+    /// removed key are shifted down by one, so this operation is O(n) in the number of
+    /// entries after the key. Use [`swap_remove`] when order does not matter.
+    ///
     /// # Example
     ///
     /// ```
@@ -359,26 +588,27 @@ where
     /// let mut map = Map::new();
     ///
     /// map.insert("x", 1);
+    /// map.insert("y", 2);
+    /// map.insert("z", 3);
     ///
     /// assert_eq!(map.remove("x"), Some(1));
     /// assert_eq!(map.remove("x"), None);
+    /// assert_eq!(map.keys().collect::<Vec<_>>(), vec![&"y", &"z"]);
     /// # }
     /// ```
+    ///
+    /// [`swap_remove`]: #method.swap_remove
     pub fn remove<Q: ?Sized>(&mut self, key: &Q) -> Option<V>
     where
         Q: Equivalent<K> + Hash,
     {
-        self.inner.remove(key)
+        self.inner.shift_remove(key)
     }
 
     /// Reserves capacity for at least additional more elements to be inserted
     /// in the `Map`. The collection may reserve more space to avoid frequent
     /// reallocations.
     ///
-    /// # Note
-    ///
-    /// This method has yet to be fully implemented in the [`ordermap`] crate.
-    ///
     /// # Example
     ///
     /// ```
@@ -388,15 +618,168 @@ where
     /// #
     /// # fn main() {
     /// let mut map = Map::<Key, Value>::new();
+    ///
     /// map.reserve(10);
+    /// assert!(map.capacity() >= 10);
     /// # }
     /// ```
-    ///
-    /// [`ordermap`]: https://docs.rs/ordermap
     pub fn reserve(&mut self, additional: usize) {
         self.inner.reserve(additional);
     }
 
+    /// Retains only the entries for which `keep` returns `true`, preserving the order of
+    /// the remaining entries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Map;
+    /// #
+    /// # fn main() {
+    /// let mut map = Map::new();
+    ///
+    /// map.insert("x", 1);
+    /// map.insert("y", 2);
+    /// map.insert("z", 3);
+    /// map.retain(|_, value| *value != 2);
+    ///
+    /// assert_eq!(map.keys().collect::<Vec<_>>(), vec![&"x", &"z"]);
+    /// # }
+    /// ```
+    pub fn retain<F>(&mut self, keep: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.inner.retain(keep);
+    }
+
+    /// Shrinks the capacity of the map as much as possible while preserving the order
+    /// of its entries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Map;
+    /// #
+    /// # fn main() {
+    /// let mut map = Map::with_capacity(100);
+    ///
+    /// map.insert("x", 1);
+    /// map.insert("y", 2);
+    /// map.shrink_to_fit();
+    ///
+    /// assert!(map.capacity() >= 2 && map.capacity() < 100);
+    /// assert_eq!(map.keys().collect::<Vec<_>>(), vec![&"x", &"y"]);
+    /// # }
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        self.inner.shrink_to_fit();
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map. This is the same as [`remove`].
+    ///
+    /// [`remove`]: #method.remove
+    pub fn shift_remove<Q: ?Sized>(&mut self, key: &Q) -> Option<V>
+    where
+        Q: Equivalent<K> + Hash,
+    {
+        self.inner.shift_remove(key)
+    }
+
+    /// Sorts the entries of the map in place with the comparison function `compare`.
+    /// Entries that compare equal keep their relative order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Map;
+    /// #
+    /// # fn main() {
+    /// let mut map = Map::new();
+    ///
+    /// map.insert("x", 3);
+    /// map.insert("y", 1);
+    /// map.insert("z", 2);
+    /// map.sort_by(|_, a, _, b| a.cmp(b));
+    ///
+    /// assert_eq!(map.keys().collect::<Vec<_>>(), vec![&"y", &"z", &"x"]);
+    /// # }
+    /// ```
+    pub fn sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&K, &V, &K, &V) -> Ordering,
+    {
+        self.inner.sort_by(compare);
+    }
+
+    /// Sorts the entries of the map by key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Map;
+    /// #
+    /// # fn main() {
+    /// let mut map = Map::new();
+    ///
+    /// map.insert("z", 1);
+    /// map.insert("x", 2);
+    /// map.insert("y", 3);
+    /// map.sort_keys();
+    ///
+    /// assert_eq!(map.keys().collect::<Vec<_>>(), vec![&"x", &"y", &"z"]);
+    /// # }
+    /// ```
+    pub fn sort_keys(&mut self)
+    where
+        K: Ord,
+    {
+        self.inner.sort_keys();
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    ///
+    /// The last entry of the map takes the place of the removed key. This is O(1), but
+    /// it does not preserve the order of the remaining entries. Use [`remove`] when
+    /// order matters.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Map;
+    /// #
+    /// # fn main() {
+    /// let mut map = Map::new();
+    ///
+    /// map.insert("x", 1);
+    /// map.insert("y", 2);
+    /// map.insert("z", 3);
+    ///
+    /// assert_eq!(map.swap_remove("x"), Some(1));
+    /// assert_eq!(map.keys().collect::<Vec<_>>(), vec![&"z", &"y"]);
+    /// # }
+    /// ```
+    ///
+    /// [`remove`]: #method.remove
+    pub fn swap_remove<Q: ?Sized>(&mut self, key: &Q) -> Option<V>
+    where
+        Q: Equivalent<K> + Hash,
+    {
+        self.inner.swap_remove(key)
+    }
+
     /// Return an iterator visiting all values in the order in which they were
     /// inserted.
     ///
@@ -495,7 +878,7 @@ where
     where
         I: IntoIterator<Item = (K, V)>,
     {
-        let inner = OrderMap::from_iter(iter);
+        let inner = IndexMap::from_iter(iter);
         Map { inner }
     }
 }
@@ -546,7 +929,7 @@ where
     where
         D: Deserializer<'de>,
     {
-        OrderMap::deserialize(deserializer).map(|inner| Map { inner })
+        IndexMap::deserialize(deserializer).map(|inner| Map { inner })
     }
 }
 
@@ -559,13 +942,163 @@ where
     where
         S: Serializer,
     {
-        self.inner.serialize(serializer)
+        if !ser::sort_keys() {
+            return self.inner.serialize(serializer);
+        }
+
+        // Keys are compared by their JSON representation so any key that can be
+        // serialized can be sorted.
+        let mut entries = Vec::with_capacity(self.len());
+
+        for (key, value) in self {
+            let sort_key = serde_json::to_string(key).map_err(S::Error::custom)?;
+            entries.push((sort_key, key, value));
+        }
+
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut state = serializer.serialize_map(Some(entries.len()))?;
+
+        for (_, key, value) in entries {
+            state.serialize_entry(key, value)?;
+        }
+
+        state.end()
+    }
+}
+
+/// A view into a single entry of a `Map`, which may either be vacant or occupied.
+pub enum Entry<'a, K: 'a, V: 'a> {
+    /// An existing entry.
+    Occupied(OccupiedEntry<'a, K, V>),
+
+    /// A vacant entry.
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+impl<'a, K, V> Entry<'a, K, V>
+where
+    K: Eq + Hash,
+{
+    /// Returns a reference to the key of the entry.
+    pub fn key(&self) -> &K {
+        match *self {
+            Entry::Occupied(ref entry) => entry.key(),
+            Entry::Vacant(ref entry) => entry.key(),
+        }
+    }
+
+    /// Returns a mutable reference to the value of the entry, inserting `default` if
+    /// the entry is vacant.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Returns a mutable reference to the value of the entry, inserting the result of
+    /// `call` if the entry is vacant.
+    pub fn or_insert_with<F>(self, call: F) -> &'a mut V
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(call()),
+        }
+    }
+}
+
+/// An occupied entry of a `Map`.
+pub struct OccupiedEntry<'a, K: 'a, V: 'a> {
+    inner: map::OccupiedEntry<'a, K, V>,
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V>
+where
+    K: Eq + Hash,
+{
+    /// Returns a reference to the value of the entry.
+    pub fn get(&self) -> &V {
+        self.inner.get()
+    }
+
+    /// Returns a mutable reference to the value of the entry.
+    pub fn get_mut(&mut self) -> &mut V {
+        self.inner.get_mut()
+    }
+
+    /// Returns the insertion-order index of the entry.
+    pub fn index(&self) -> usize {
+        self.inner.index()
+    }
+
+    /// Replaces the value of the entry with `value`, returning the old value.
+    pub fn insert(&mut self, value: V) -> V {
+        self.inner.insert(value)
+    }
+
+    /// Converts the entry into a mutable reference to its value.
+    pub fn into_mut(self) -> &'a mut V {
+        self.inner.into_mut()
+    }
+
+    /// Returns a reference to the key of the entry.
+    pub fn key(&self) -> &K {
+        self.inner.key()
+    }
+
+    /// Removes the entry from the map, returning its value. Like [`Map::remove`], the
+    /// remaining entries keep their insertion order.
+    ///
+    /// [`Map::remove`]: ./struct.Map.html#method.remove
+    pub fn remove(self) -> V {
+        self.inner.shift_remove()
+    }
+
+    /// Removes the entry from the map, returning its value. Like [`Map::swap_remove`],
+    /// the last entry of the map takes the place of the removed entry.
+    ///
+    /// [`Map::swap_remove`]: ./struct.Map.html#method.swap_remove
+    pub fn swap_remove(self) -> V {
+        self.inner.swap_remove()
+    }
+}
+
+/// A vacant entry of a `Map`.
+pub struct VacantEntry<'a, K: 'a, V: 'a> {
+    inner: map::VacantEntry<'a, K, V>,
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V>
+where
+    K: Eq + Hash,
+{
+    /// Returns the index that the entry will have once a value is inserted.
+    pub fn index(&self) -> usize {
+        self.inner.index()
+    }
+
+    /// Inserts `value` at the end of the map, returning a mutable reference to it.
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.inner.insert(value)
+    }
+
+    /// Takes ownership of the key of the entry.
+    pub fn into_key(self) -> K {
+        self.inner.into_key()
+    }
+
+    /// Returns a reference to the key of the entry.
+    pub fn key(&self) -> &K {
+        self.inner.key()
     }
 }
 
 /// A draining iterator over the entries of a `Map`.
 pub struct Drain<'a, K: 'a, V: 'a> {
-    iter: ordermap::Drain<'a, K, V>,
+    iter: map::Drain<'a, K, V>,
 }
 
 impl<'a, K, V> Iterator for Drain<'a, K, V> {
@@ -582,7 +1115,7 @@ impl<'a, K, V> Iterator for Drain<'a, K, V> {
 
 /// An iterator over the entries of a `Map`.
 pub struct Iter<'a, K: 'a, V: 'a> {
-    iter: ordermap::Iter<'a, K, V>,
+    iter: map::Iter<'a, K, V>,
 }
 
 impl<'a, K, V> Iterator for Iter<'a, K, V> {
@@ -623,7 +1156,7 @@ impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {
 
 /// An mutable iterator over the entries of a `Map`.
 pub struct IterMut<'a, K: 'a, V: 'a> {
-    iter: ordermap::IterMut<'a, K, V>,
+    iter: map::IterMut<'a, K, V>,
 }
 
 impl<'a, K, V> Iterator for IterMut<'a, K, V> {
@@ -664,7 +1197,7 @@ impl<'a, K, V> ExactSizeIterator for IterMut<'a, K, V> {
 
 /// An owning iterator over the entries of a `Map`.
 pub struct IntoIter<K, V> {
-    iter: ordermap::IntoIter<K, V>,
+    iter: map::IntoIter<K, V>,
 }
 
 impl<K, V> Iterator for IntoIter<K, V> {
@@ -705,7 +1238,7 @@ impl<K, V> ExactSizeIterator for IntoIter<K, V> {
 
 /// An iterator over the keys of a `Map`.
 pub struct Keys<'a, K: 'a, V: 'a> {
-    iter: ordermap::Keys<'a, K, V>,
+    iter: map::Keys<'a, K, V>,
 }
 
 impl<'a, K, V> Iterator for Keys<'a, K, V> {
@@ -746,7 +1279,7 @@ impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> {
 
 /// An iterator over the values of a `Map`.
 pub struct Values<'a, K: 'a, V: 'a> {
-    iter: ordermap::Values<'a, K, V>,
+    iter: map::Values<'a, K, V>,
 }
 
 impl<'a, K, V> Iterator for Values<'a, K, V> {
@@ -787,7 +1320,7 @@ impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> {
 
 /// A mutable iterator over the values of a `Map`.
 pub struct ValuesMut<'a, K: 'a, V: 'a> {
-    iter: ordermap::ValuesMut<'a, K, V>,
+    iter: map::ValuesMut<'a, K, V>,
 }
 
 impl<'a, K, V> Iterator for ValuesMut<'a, K, V> {
@@ -825,3 +1358,89 @@ impl<'a, K, V> ExactSizeIterator for ValuesMut<'a, K, V> {
         self.iter.len()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Entry, Map};
+
+    #[test]
+    fn remove_preserves_order() {
+        let mut map = (0..10).map(|n| (n, n * 2)).collect::<Map<_, _>>();
+
+        assert_eq!(map.remove(&3), Some(6));
+        assert_eq!(map.remove(&0), Some(0));
+        assert_eq!(map.remove(&9), Some(18));
+        assert_eq!(map.remove(&3), None);
+
+        assert_eq!(map.keys().cloned().collect::<Vec<_>>(), [1, 2, 4, 5, 6, 7, 8]);
+        assert_eq!(map.index_of(&4), Some(2));
+        assert_eq!(map.get(&8), Some(&16));
+
+        if let Entry::Occupied(entry) = map.entry(2) {
+            assert_eq!(entry.remove(), 4);
+        }
+
+        assert_eq!(map.keys().cloned().collect::<Vec<_>>(), [1, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn capacity() {
+        let mut map = Map::<u32, u32>::new();
+
+        map.reserve(50);
+        assert!(map.capacity() >= 50);
+
+        map.extend((0..10).map(|n| (n, n)));
+        map.shrink_to_fit();
+
+        assert!(map.capacity() >= 10 && map.capacity() < 50);
+        assert_eq!(map.keys().cloned().collect::<Vec<_>>(), (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn removal() {
+        let mut map = (0..6).map(|n| (n, n)).collect::<Map<_, _>>();
+
+        assert_eq!(map.shift_remove(&1), Some(1));
+        assert_eq!(map.swap_remove(&0), Some(0));
+        assert_eq!(map.shift_remove(&0), None);
+        assert_eq!(map.keys().cloned().collect::<Vec<_>>(), [5, 2, 3, 4]);
+
+        map.retain(|key, value| {
+            *value *= 10;
+            key % 2 == 0
+        });
+
+        assert_eq!(map.get_index(0), Some((&2, &20)));
+        assert_eq!(map.get_index(1), Some((&4, &40)));
+        assert_eq!(map.get_index(2), None);
+    }
+
+    #[test]
+    fn get_mut_preserves_order() {
+        let mut map = Map::new();
+
+        map.insert("title", "Hello".to_owned());
+        map.insert("body", "Lorem ipsum".to_owned());
+        map.insert("summary", String::new());
+
+        if let Some(value) = map.get_mut("body") {
+            value.push_str(" dolor");
+        }
+
+        if let Some((index, _, value)) = map.get_full_mut("title") {
+            assert_eq!(index, 0);
+            value.push_str(", world!");
+        }
+
+        if let Some((key, value)) = map.get_index_mut(2) {
+            assert_eq!(*key, "summary");
+            value.push_str("...");
+        }
+
+        assert_eq!(map.keys().collect::<Vec<_>>(), [&"title", &"body", &"summary"]);
+        assert_eq!(map.get_index(0), Some((&"title", &"Hello, world!".to_owned())));
+        assert_eq!(map.get_full("body"), Some((1, &"body", &"Lorem ipsum dolor".to_owned())));
+        assert_eq!(map.get("summary").map(String::as_str), Some("..."));
+    }
+}
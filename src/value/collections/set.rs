@@ -1,5 +1,6 @@
 //! A hash set implemented as a `Map` where the value is `()`.
 
+use std::cmp::Ordering;
 use std::fmt::{self, Debug, Display, Formatter, Write};
 use std::hash::Hash;
 use std::iter::FromIterator;
@@ -162,6 +163,108 @@ impl<T: Eq + Hash> Set<T> {
         Drain { iter }
     }
 
+    /// Moves the elements of `other` into the set and returns the number of elements
+    /// that were not already present.
+    ///
+    /// Elements that are already present keep their position, and new elements are
+    /// appended in the order of `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Set;
+    /// #
+    /// # fn main() {
+    /// let mut set = vec![1, 2, 3].into_iter().collect::<Set<_>>();
+    /// let other = vec![3, 4].into_iter().collect();
+    ///
+    /// assert_eq!(set.extend_counting(other), 1);
+    /// assert_eq!(set.iter().cloned().collect::<Vec<_>>(), [1, 2, 3, 4]);
+    /// # }
+    /// ```
+    pub fn extend_counting(&mut self, other: Set<T>) -> usize {
+        let len = self.len();
+
+        self.extend(other);
+        self.len() - len
+    }
+
+    /// Returns a reference to the value in the set that is equivalent to `key`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Set;
+    /// #
+    /// # fn main() {
+    /// let mut set = Set::new();
+    ///
+    /// set.insert("x".to_owned());
+    /// assert_eq!(set.get("x"), Some(&"x".to_owned()));
+    /// assert_eq!(set.get("y"), None);
+    /// # }
+    /// ```
+    pub fn get<Q: ?Sized>(&self, key: &Q) -> Option<&T>
+    where
+        Q: Equivalent<T> + Hash,
+    {
+        self.inner.get_key(key)
+    }
+
+    /// Returns the insertion-order index of the value along with a reference to the
+    /// value stored in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Set;
+    /// #
+    /// # fn main() {
+    /// let mut set = Set::new();
+    ///
+    /// set.insert("x");
+    /// set.insert("y");
+    ///
+    /// assert_eq!(set.get_full("y"), Some((1, &"y")));
+    /// assert_eq!(set.get_full("z"), None);
+    /// # }
+    /// ```
+    pub fn get_full<Q: ?Sized>(&self, key: &Q) -> Option<(usize, &T)>
+    where
+        Q: Equivalent<T> + Hash,
+    {
+        self.inner.get_full(key).map(|(index, key, _)| (index, key))
+    }
+
+    /// Returns a reference to the value at the insertion-order `index`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Set;
+    /// #
+    /// # fn main() {
+    /// let mut set = Set::new();
+    ///
+    /// set.insert("x");
+    /// set.insert("y");
+    ///
+    /// assert_eq!(set.get_index(1), Some(&"y"));
+    /// assert_eq!(set.get_index(2), None);
+    /// # }
+    /// ```
+    pub fn get_index(&self, index: usize) -> Option<&T> {
+        self.inner.get_index(index).map(|(key, _)| key)
+    }
+
     /// Adds a value to the set.
     ///
     /// If the set did not have this value present, `true` is returned.
@@ -259,9 +362,37 @@ impl<T: Eq + Hash> Set<T> {
         self.inner.len()
     }
 
+    /// Removes the last value from the set and returns it. Returns `None` if the set
+    /// is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Set;
+    /// #
+    /// # fn main() {
+    /// let mut set = Set::new();
+    ///
+    /// set.insert(1);
+    /// set.insert(2);
+    ///
+    /// assert_eq!(set.pop(), Some(2));
+    /// assert_eq!(set.pop(), Some(1));
+    /// assert_eq!(set.pop(), None);
+    /// # }
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        self.inner.pop().map(|(key, _)| key)
+    }
+
     /// Removes a value from the set. Returns `true` if the value was present
     /// in the set.
     ///
+    /// The remaining values keep their insertion order. Use [`swap_remove`] when order
+    /// does not matter.
+    ///
     /// # Example
     ///
     /// ```
@@ -279,6 +410,8 @@ impl<T: Eq + Hash> Set<T> {
     /// assert_eq!(set.len(), 0);
     /// # }
     /// ```
+    ///
+    /// [`swap_remove`]: #method.swap_remove
     pub fn remove<Q: ?Sized>(&mut self, key: &Q) -> bool
     where
         Q: Equivalent<T> + Hash,
@@ -290,10 +423,6 @@ impl<T: Eq + Hash> Set<T> {
     /// in the `Set`. The collection may reserve more space to avoid frequent
     /// reallocations.
     ///
-    /// # Note
-    ///
-    /// This method has yet to be fully implemented in the [`ordermap`] crate.
-    ///
     /// # Example
     ///
     /// ```
@@ -303,14 +432,168 @@ impl<T: Eq + Hash> Set<T> {
     /// #
     /// # fn main() {
     /// let mut set = Set::<String>::new();
+    ///
     /// set.reserve(10);
+    /// assert!(set.capacity() >= 10);
     /// # }
     /// ```
-    ///
-    /// [`ordermap`]: https://docs.rs/ordermap
     pub fn reserve(&mut self, additional: usize) {
         self.inner.reserve(additional)
     }
+
+    /// Retains only the values for which `keep` returns `true`, preserving the order of
+    /// the remaining values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Set;
+    /// #
+    /// # fn main() {
+    /// let mut set = (1..6).collect::<Set<_>>();
+    ///
+    /// set.retain(|value| value % 2 == 1);
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![&1, &3, &5]);
+    /// # }
+    /// ```
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.inner.retain(|key, _| keep(key));
+    }
+
+    /// Shrinks the capacity of the set as much as possible while preserving the order
+    /// of its elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Set;
+    /// #
+    /// # fn main() {
+    /// let mut set = Set::with_capacity(100);
+    ///
+    /// set.insert("x");
+    /// set.insert("y");
+    /// set.insert("z");
+    /// set.remove("y");
+    /// set.shrink_to_fit();
+    ///
+    /// assert!(set.capacity() >= 2 && set.capacity() < 100);
+    /// assert_eq!(set.len(), 2);
+    /// # }
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        self.inner.shrink_to_fit()
+    }
+
+    /// Removes a value from the set. Returns `true` if the value was present
+    /// in the set. This is the same as [`remove`].
+    ///
+    /// [`remove`]: #method.remove
+    pub fn shift_remove<Q: ?Sized>(&mut self, key: &Q) -> bool
+    where
+        Q: Equivalent<T> + Hash,
+    {
+        self.inner.shift_remove(key).is_some()
+    }
+
+    /// Sorts the values of the set in place.
+    ///
+    /// The set keeps the new order, so values that are inserted afterwards are still
+    /// added to the end.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Set;
+    /// #
+    /// # fn main() {
+    /// let mut set = Set::new();
+    ///
+    /// set.insert("z");
+    /// set.insert("x");
+    /// set.insert("y");
+    /// set.sort();
+    ///
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![&"x", &"y", &"z"]);
+    /// # }
+    /// ```
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.inner.sort_keys();
+    }
+
+    /// Sorts the values of the set in place with the comparison function `compare`.
+    /// Values that compare equal keep their relative order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Set;
+    /// #
+    /// # fn main() {
+    /// let mut set = Set::new();
+    ///
+    /// set.insert("xyz");
+    /// set.insert("x");
+    /// set.insert("xy");
+    /// set.sort_by(|a, b| a.len().cmp(&b.len()));
+    ///
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![&"x", &"xy", &"xyz"]);
+    /// # }
+    /// ```
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.inner.sort_by(|a, _, b, _| compare(a, b));
+    }
+
+    /// Removes a value from the set. Returns `true` if the value was present
+    /// in the set.
+    ///
+    /// The last value of the set takes the place of the removed value. This is O(1),
+    /// but it does not preserve the order of the remaining values. Use [`remove`] when
+    /// order matters.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Set;
+    /// #
+    /// # fn main() {
+    /// let mut set = Set::new();
+    ///
+    /// set.insert("x");
+    /// set.insert("y");
+    /// set.insert("z");
+    ///
+    /// assert!(set.swap_remove("x"));
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![&"z", &"y"]);
+    /// # }
+    /// ```
+    ///
+    /// [`remove`]: #method.remove
+    pub fn swap_remove<Q: ?Sized>(&mut self, key: &Q) -> bool
+    where
+        Q: Equivalent<T> + Hash,
+    {
+        self.inner.swap_remove(key).is_some()
+    }
 }
 
 impl<T: Debug + Eq + Hash> Debug for Set<T> {
@@ -574,3 +857,61 @@ impl<T> ExactSizeIterator for IntoIter<T> {
         self.iter.len()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::Set;
+    use value::Key;
+
+    #[test]
+    fn removal() {
+        let mut set = (0..6).collect::<Set<_>>();
+
+        set.reserve(50);
+        assert!(set.capacity() >= 56);
+
+        assert!(set.shift_remove(&1));
+        assert!(set.swap_remove(&0));
+        assert!(!set.remove(&0));
+        assert_eq!(set.iter().cloned().collect::<Vec<_>>(), [5, 2, 3, 4]);
+
+        set.retain(|value| value % 2 == 0);
+        set.shrink_to_fit();
+
+        assert!(set.capacity() >= 2 && set.capacity() < 56);
+        assert_eq!(set.get_index(0), Some(&2));
+        assert_eq!(set.get_full(&4), Some((1, &4)));
+    }
+
+    #[test]
+    fn sort() {
+        let mut set = ["published-at", "author", "title"]
+            .iter()
+            .map(|key| key.parse())
+            .collect::<Result<Set<Key>, _>>()
+            .unwrap();
+
+        set.sort();
+        assert_eq!(
+            serde_json::to_string(&set).unwrap(),
+            r#"["author","published-at","title"]"#
+        );
+
+        set.sort_by(|a, b| b.cmp(a));
+        set.insert("body".parse().unwrap());
+        assert_eq!(set.to_string(), "title,published-at,author,body");
+        assert!(set.contains("author"));
+    }
+
+    #[test]
+    fn extend_counting() {
+        let mut set = vec!["b", "a", "c"].into_iter().collect::<Set<_>>();
+        let other = vec!["d", "a", "e", "b"].into_iter().collect();
+
+        assert_eq!(set.extend_counting(other), 2);
+        assert_eq!(set.iter().cloned().collect::<Vec<_>>(), ["b", "a", "c", "d", "e"]);
+        assert_eq!(set.extend_counting(Set::new()), 0);
+    }
+}
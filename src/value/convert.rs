@@ -1,26 +1,147 @@
 //! Functions that convert types to and from a `Value`.
 
+use std::convert::TryFrom;
+
 use serde::de::DeserializeOwned;
 use serde::ser::Serialize;
-use serde_json::{self, Value as JsonValue};
+use serde_json::Value as JsonValue;
 
-use error::Error;
-use value::Value;
+use error::{Error, ErrorKind};
+use value::{Key, Value};
+use value::ser::ValueSerializer;
 
 /// Convert a `T` into a `Value`.
+///
+/// The value is built directly without an intermediate `serde_json::Value`. The
+/// fields of a struct and the entries of a map are added in the order that they are
+/// serialized, so insertion order is preserved even when the `preserve_order` feature
+/// of `serde_json` is not enabled.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate serde_derive;
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # #[derive(Serialize)]
+/// # struct Post {
+/// #     title: String,
+/// #     body: String,
+/// # }
+/// #
+/// # fn example() -> Result<(), Error> {
+/// let post = Post {
+///     title: "Hello".to_owned(),
+///     body: "World".to_owned(),
+/// };
+///
+/// let value = json_api::to_value(&post)?;
+/// let keys = value.as_object().unwrap().keys().map(|key| &**key).collect::<Vec<_>>();
+///
+/// assert_eq!(keys, ["title", "body"]);
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
 pub fn to_value<T>(value: T) -> Result<Value, Error>
 where
     T: Serialize,
 {
-    from_json(serde_json::to_value(value)?)
+    value.serialize(ValueSerializer)
 }
 
 /// Interpret a `Value` as a type `T`.
+///
+/// Like [`to_value`], the conversion does not go through a `serde_json::Value`. The
+/// entries of an object are visited in the order that they are stored.
+///
+/// [`to_value`]: ./fn.to_value.html
 pub fn from_value<T>(value: Value) -> Result<T, Error>
 where
     T: DeserializeOwned,
 {
-    Ok(T::deserialize(to_json(value))?)
+    T::deserialize(value)
+}
+
+/// Convert a `serde_json::Value` into a `Value`.
+///
+/// The keys of the outermost object are checked since they become member names when the
+/// value is used as attributes or meta. The conversion fails with
+/// `ErrorKind::InvalidMemberName` if one of them is not a valid [member name]. Keys of
+/// nested objects are kept verbatim, like they are when a `Value` is deserialized.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate serde_json;
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use std::convert::TryFrom;
+/// use json_api::Value;
+///
+/// let value = Value::try_from(json!({ "title": "Hello, world!" }))?;
+/// assert_eq!(value.pointer("/title"), Some(&Value::from("Hello, world!")));
+///
+/// let value = Value::try_from(json!({ "meta": { "@title": "Hello, world!" } }))?;
+/// assert_eq!(value.pointer("/meta/@title"), Some(&Value::from("Hello, world!")));
+///
+/// let error = Value::try_from(json!({ "@title": "Hello, world!" }));
+/// assert!(error.unwrap_err().to_string().contains("@title"));
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [member name]: http://jsonapi.org/format/#document-member-names
+impl TryFrom<JsonValue> for Value {
+    type Error = Error;
+
+    fn try_from(value: JsonValue) -> Result<Self, Self::Error> {
+        from_json(value)
+    }
+}
+
+/// Convert a `Value` into a `serde_json::Value`.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate serde_json;
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::Value;
+/// use serde_json::Value as JsonValue;
+///
+/// let value: Value = r#"{ "title": "Hello, world!" }"#.parse()?;
+/// assert_eq!(JsonValue::from(value), json!({ "title": "Hello, world!" }));
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+impl From<Value> for JsonValue {
+    fn from(value: Value) -> Self {
+        to_json(value)
+    }
 }
 
 pub(crate) fn to_json(value: Value) -> JsonValue {
@@ -43,13 +164,262 @@ pub(crate) fn to_json(value: Value) -> JsonValue {
 
 pub(crate) fn from_json(value: JsonValue) -> Result<Value, Error> {
     match value {
-        JsonValue::Null => Ok(Value::Null),
-        JsonValue::Array(data) => data.into_iter().map(from_json).collect(),
-        JsonValue::Bool(data) => Ok(Value::Bool(data)),
-        JsonValue::Number(data) => Ok(Value::Number(data)),
         JsonValue::Object(data) => data.into_iter()
-            .map(|(k, v)| Ok((k.parse()?, from_json(v)?)))
+            .map(|(k, v)| match k.parse::<Key>() {
+                Ok(key) => Ok((key, from_json_raw(v))),
+                Err(e) => Err(Error::from(ErrorKind::InvalidMemberName(k, e.to_string()))),
+            })
+            .collect(),
+        value => Ok(from_json_raw(value)),
+    }
+}
+
+/// Converts a nested `serde_json::Value`. Like the `Deserialize` impl of `Value`, the
+/// keys of a nested object are kept verbatim.
+fn from_json_raw(value: JsonValue) -> Value {
+    match value {
+        JsonValue::Null => Value::Null,
+        JsonValue::Array(data) => data.into_iter().map(from_json_raw).collect(),
+        JsonValue::Bool(data) => Value::Bool(data),
+        JsonValue::Number(data) => Value::Number(data),
+        JsonValue::Object(data) => data.into_iter()
+            .map(|(k, v)| (Key::from_raw(k), from_json_raw(v)))
             .collect(),
-        JsonValue::String(data) => Ok(Value::String(data)),
+        JsonValue::String(data) => Value::String(data),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use serde_json::{self, Value as JsonValue};
+
+    use value::{self, Key, Map, Value};
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    #[serde(rename_all = "kebab-case")]
+    struct Post {
+        title: String,
+        body: Option<String>,
+        word_count: u32,
+        status: Status,
+        attachments: Vec<Attachment>,
+        meta: Map,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    #[serde(rename_all = "kebab-case")]
+    enum Status {
+        Draft,
+        Published(u64),
+        Scheduled { at: String },
+    }
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Attachment(String, f64);
+
+    fn get<'a>(value: &'a Value, key: &str) -> &'a Value {
+        value.as_object().unwrap().get(key).unwrap()
+    }
+
+    fn keys(value: &Value) -> Vec<&str> {
+        value.as_object().unwrap().keys().map(|key| &**key).collect()
+    }
+
+    fn post() -> Post {
+        let mut meta = Map::new();
+
+        meta.insert("z".parse().unwrap(), Value::from(1));
+        meta.insert("a".parse().unwrap(), Value::from(2));
+        meta.insert("m".parse().unwrap(), Value::from(3));
+
+        Post {
+            title: "Hello".to_owned(),
+            body: None,
+            word_count: 2,
+            status: Status::Scheduled {
+                at: "tomorrow".to_owned(),
+            },
+            attachments: vec![Attachment("cover.png".to_owned(), 1.5)],
+            meta,
+        }
+    }
+
+    fn from_str(source: &str) -> JsonValue {
+        serde_json::from_str(source).unwrap()
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let source = from_str(
+            r#"{
+                "title": "Hello, world!",
+                "published": true,
+                "rating": 4.5,
+                "tags": ["rust", null, 1],
+                "author": {
+                    "name": "Zachary",
+                    "social-media": {
+                        "accounts": ["twitter", "github"]
+                    }
+                }
+            }"#,
+        );
+
+        let value = Value::try_from(source.clone()).unwrap();
+        let account = value.pointer("/author/social-media/accounts/1");
+
+        assert_eq!(account, Some(&Value::from("github")));
+        assert_eq!(value.pointer("/tags/1"), Some(&Value::Null));
+        assert_eq!(JsonValue::from(value), source);
+    }
+
+    #[test]
+    fn json_invalid_key() {
+        let source = from_str(r#"{ "@accounts": ["twitter", "github"] }"#);
+        let error = Value::try_from(source).unwrap_err();
+        let message = error.to_string();
+
+        assert!(message.contains(r#""@accounts""#), "{}", message);
+        assert!(message.contains("reserved '@'"), "{}", message);
+
+        let source = from_str(r#"{ "bad key!": true }"#);
+        let error = Value::try_from(source).unwrap_err();
+
+        assert!(error.to_string().contains(r#""bad key!""#));
+    }
+
+    #[test]
+    fn json_nested_keys() {
+        let source = from_str(
+            r#"{
+                "data": [
+                    { "attributes": { "title": "Hello" } },
+                    { "attributes": { "bad key!": true, "$schema": "x" } }
+                ],
+                "config": { "camelCase": { "@id": 1 } }
+            }"#,
+        );
+
+        let value = Value::try_from(source.clone()).unwrap();
+        let config = get(&value, "config");
+
+        assert_eq!(keys(config), ["camelCase"]);
+        assert_eq!(keys(get(config, "camelCase")), ["@id"]);
+        assert_eq!(JsonValue::from(value), source);
+    }
+
+    #[test]
+    fn to_value_preserves_order() {
+        let value = value::to_value(post()).unwrap();
+        let meta = value.pointer("/meta").unwrap();
+
+        assert_eq!(
+            keys(&value),
+            ["title", "body", "word-count", "status", "attachments", "meta"]
+        );
+        assert_eq!(keys(meta), ["z", "a", "m"]);
+        assert_eq!(value.pointer("/body"), Some(&Value::Null));
+        assert_eq!(value.pointer("/status/scheduled/at"), Some(&"tomorrow".into()));
+        assert_eq!(value.pointer("/attachments/0/1"), Some(&1.5.into()));
+    }
+
+    #[test]
+    fn from_value_preserves_order() {
+        let value = value::to_value(post()).unwrap();
+        let map = value::from_value::<Map>(value.clone()).unwrap();
+
+        assert_eq!(map.keys().map(|key| &**key).collect::<Vec<_>>(), keys(&value));
+        assert_eq!(value::from_value::<Value>(value.clone()).unwrap(), value);
+        assert_eq!(value::from_value::<Post>(value).unwrap(), post());
+    }
+
+    #[test]
+    fn value_enum_variants() {
+        let statuses = [
+            Status::Draft,
+            Status::Published(1),
+            Status::Scheduled {
+                at: "now".to_owned(),
+            },
+        ];
+
+        for status in &statuses {
+            let value = value::to_value(status).unwrap();
+            assert_eq!(value::from_value::<Status>(value).unwrap(), *status);
+        }
+
+        assert_eq!(value::to_value(Status::Draft).unwrap(), Value::from("draft"));
+        assert!(value::from_value::<Status>(Value::from(1)).is_err());
+    }
+
+    #[test]
+    fn to_value_identity() {
+        let source = r#"{"b":[1,-1,1.5,null,true],"a":{"d":"x","c":{}}}"#;
+        let value: Value = serde_json::from_str(source).unwrap();
+
+        assert_eq!(value::to_value(&value).unwrap(), value);
+        assert_eq!(serde_json::to_string(&value::to_value(&value).unwrap()).unwrap(), source);
+    }
+
+    #[test]
+    fn to_value_keys() {
+        let mut map = ::std::collections::BTreeMap::new();
+
+        map.insert(1, "one");
+        map.insert(2, "two");
+
+        let value = value::to_value(&map).unwrap();
+
+        assert_eq!(keys(&value), ["1", "2"]);
+        assert_eq!(value::to_value(f64::NAN).unwrap(), Value::Null);
+
+        let mut map = Map::<String, u8>::new();
+
+        map.insert("@invalid".to_owned(), 1);
+        assert_eq!(keys(&value::to_value(&map).unwrap()), ["@invalid"]);
+
+        let mut map = Map::<Key, Value>::new();
+
+        map.insert("socialMedia".parse().unwrap(), Value::Null);
+        assert_eq!(keys(&value::to_value(&map).unwrap()), ["social-media"]);
+    }
+
+    #[test]
+    fn to_value_nested_keys() {
+        let json: JsonValue = serde_json::from_str(
+            r#"{"config": {"$schema": "x", "a.b": 1, "Some Key": [{"@id": 2}]}}"#,
+        ).unwrap();
+        let value = value::to_value(&json).unwrap();
+        let config = get(&value, "config");
+        let item = &get(config, "Some Key").as_array().unwrap()[0];
+
+        assert_eq!(keys(config), ["$schema", "Some Key", "a.b"]);
+        assert_eq!(keys(item), ["@id"]);
+        assert_eq!(JsonValue::from(value), json);
+    }
+
+    #[test]
+    fn deserialize_nested_keys() {
+        let source = r#"{"config":{"$schema":"x","a.b":{"camelCase":true}}}"#;
+        let value: Value = serde_json::from_str(source).unwrap();
+
+        let config = get(&value, "config");
+
+        assert_eq!(keys(config), ["$schema", "a.b"]);
+        assert_eq!(keys(get(config, "a.b")), ["camelCase"]);
+        assert_eq!(serde_json::to_string(&value).unwrap(), source);
+    }
+
+    #[test]
+    fn parse_keys() {
+        let source = r#"{"config":{"$schema":"x"},"Bad Key":{"camelCase":1}}"#;
+        let parsed = source.parse::<Value>().unwrap();
+
+        assert_eq!(parsed, serde_json::from_str::<Value>(source).unwrap());
+        assert_eq!(keys(&parsed), ["config", "Bad Key"]);
+        assert_eq!(keys(get(&parsed, "config")), ["$schema"]);
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), source);
     }
 }
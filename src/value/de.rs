@@ -0,0 +1,150 @@
+use serde::de::value::{MapDeserializer, SeqDeserializer, StringDeserializer};
+use serde::de::{self, Deserializer, EnumAccess, IntoDeserializer, Unexpected, VariantAccess,
+                Visitor};
+
+use error::Error;
+use value::Value;
+
+/// Deserialize a type directly from a `Value`.
+///
+/// The entries of an object are visited in the order that they are stored.
+impl<'de> Deserializer<'de> for Value {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Null => visitor.visit_unit(),
+            Value::Array(data) => {
+                let mut seq = SeqDeserializer::new(data.into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+
+                seq.end()?;
+                Ok(value)
+            }
+            Value::Bool(data) => visitor.visit_bool(data),
+            Value::Number(data) => if let Some(n) = data.as_u64() {
+                visitor.visit_u64(n)
+            } else if let Some(n) = data.as_i64() {
+                visitor.visit_i64(n)
+            } else {
+                visitor.visit_f64(data.as_f64().unwrap_or_default())
+            },
+            Value::Object(data) => {
+                let iter = data.into_iter().map(|(k, v)| (String::from(k), v));
+                let mut map = MapDeserializer::new(iter);
+                let value = visitor.visit_map(&mut map)?;
+
+                map.end()?;
+                Ok(value)
+            }
+            Value::String(data) => visitor.visit_string(data),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Null => visitor.visit_none(),
+            value => visitor.visit_some(value),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let (variant, value) = match self {
+            Value::String(variant) => (variant, None),
+            Value::Object(mut data) => match (data.pop(), data.is_empty()) {
+                (Some((variant, value)), true) => (variant.into(), Some(value)),
+                _ => bail!("expected an object with a single key for an enum"),
+            },
+            _ => bail!("expected a string or an object for an enum"),
+        };
+
+        visitor.visit_enum(EnumDeserializer { variant, value })
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes byte_buf unit
+        unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+struct EnumDeserializer {
+    variant: String,
+    value: Option<Value>,
+}
+
+impl<'de> EnumAccess<'de> for EnumDeserializer {
+    type Error = Error;
+    type Variant = VariantDeserializer;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let variant: StringDeserializer<Error> = self.variant.into_deserializer();
+        let variant = seed.deserialize(variant)?;
+
+        Ok((variant, VariantDeserializer(self.value)))
+    }
+}
+
+struct VariantDeserializer(Option<Value>);
+
+impl<'de> VariantAccess<'de> for VariantDeserializer {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.0 {
+            None | Some(Value::Null) => Ok(()),
+            Some(_) => Err(de::Error::invalid_type(Unexpected::Map, &"unit variant")),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match self.0 {
+            Some(value) => seed.deserialize(value),
+            None => Err(de::Error::invalid_type(Unexpected::UnitVariant, &"newtype variant")),
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Some(value @ Value::Array(_)) => value.deserialize_any(visitor),
+            _ => Err(de::Error::invalid_type(Unexpected::UnitVariant, &"tuple variant")),
+        }
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.0 {
+            Some(value @ Value::Object(_)) => value.deserialize_any(visitor),
+            _ => Err(de::Error::invalid_type(Unexpected::UnitVariant, &"struct variant")),
+        }
+    }
+}
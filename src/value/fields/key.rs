@@ -1,4 +1,5 @@
 use std::borrow::Borrow;
+use std::convert::TryFrom;
 use std::fmt::{self, Display, Formatter};
 use std::ops::Deref;
 use std::str::FromStr;
@@ -39,7 +40,30 @@ use sealed::Sealed;
 pub struct Key(String);
 
 impl Key {
-    #[doc(hidden)]
+    /// Returns a new `Key` from `value` without validating it or converting its
+    /// casing convention.
+    ///
+    /// This is useful for constant member names that are known to be valid, since it
+    /// avoids the cost of parsing. The caller is responsible for ensuring that `value`
+    /// is a valid, kebab-case member name. Otherwise, the `Key` will produce a
+    /// document that does not comply with the specification.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Key;
+    /// #
+    /// # fn main() {
+    /// let key = Key::from_raw("first-name".to_owned());
+    /// assert_eq!(key, "first-name");
+    ///
+    /// // Unlike Key::from_str, the casing convention is left as-is.
+    /// let key = Key::from_raw("firstName".to_owned());
+    /// assert_eq!(key, "firstName");
+    /// # }
+    /// ```
     #[inline]
     pub fn from_raw(value: String) -> Self {
         Key(value)
@@ -93,6 +117,10 @@ impl FromStr for Key {
             bail!("cannot be blank");
         }
 
+        if is_kebab_case(source) {
+            return Ok(Key(source.to_owned()));
+        }
+
         // We should reserve a bit more than what we need so in
         // the event that we end up converting camelCase to
         // kebab-case, we don't have to reallocate.
@@ -144,6 +172,27 @@ impl FromStr for Key {
     }
 }
 
+impl<'a> TryFrom<&'a str> for Key {
+    type Error = Error;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl TryFrom<String> for Key {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        // Reuse the buffer if parsing the value would not change it.
+        if is_kebab_case(&value) {
+            Ok(Key(value))
+        } else {
+            value.parse()
+        }
+    }
+}
+
 impl PartialEq<String> for Key {
     fn eq(&self, rhs: &String) -> bool {
         &self.0 == rhs
@@ -182,6 +231,13 @@ impl<'de> Deserialize<'de> for Key {
             {
                 value.parse().map_err(de::Error::custom)
             }
+
+            fn visit_string<E>(self, value: String) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Key::try_from(value).map_err(de::Error::custom)
+            }
         }
 
         deserializer.deserialize_str(KeyVisitor)
@@ -203,3 +259,62 @@ impl Sealed for Key {}
 fn as_lowercase(value: char) -> char {
     (value as u8 + 32) as char
 }
+
+/// Returns `true` if `value` is a member name that is already in kebab-case, meaning
+/// that `Key::from_str` would return it unchanged.
+fn is_kebab_case(value: &str) -> bool {
+    let mut prev = '-';
+
+    for next in value.chars() {
+        match next {
+            '\u{0000}'...'\u{002c}'
+            | '\u{002e}'...'\u{002f}'
+            | '\u{003a}'...'\u{0060}'
+            | '\u{007b}'...'\u{007f}' => return false,
+            '-' if prev == '-' => return false,
+            _ => prev = next,
+        }
+    }
+
+    prev != '-'
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use serde_json;
+
+    use super::{is_kebab_case, Key};
+
+    const SOURCES: &[(&str, Option<&str>)] = &[
+        ("articles", Some("articles")),
+        ("blog-posts", Some("blog-posts")),
+        ("x", Some("x")),
+        ("über-räume", Some("über-räume")),
+        ("a1-b2", Some("a1-b2")),
+        ("shoppingCarts", Some("shopping-carts")),
+        ("notification_settings", Some("notification-settings")),
+        ("first name", Some("first-name")),
+        ("a--b", Some("a-b")),
+        ("-a", None),
+        ("a-", None),
+        ("a.b", None),
+        ("", None),
+    ];
+
+    #[test]
+    fn kebab_case_fast_path() {
+        for &(source, expected) in SOURCES {
+            let value = serde_json::Value::String(source.to_owned());
+            let parsed = source.parse::<Key>().ok();
+            let deserialized = serde_json::from_value::<Key>(value).ok();
+
+            assert_eq!(is_kebab_case(source), expected == Some(source), "{}", source);
+            assert_eq!(parsed, expected.map(|value| Key::from_raw(value.to_owned())));
+            assert_eq!(deserialized, parsed);
+            assert_eq!(Key::try_from(source).ok(), parsed);
+            assert_eq!(Key::try_from(source.to_owned()).ok(), parsed);
+        }
+    }
+}
@@ -1,6 +1,10 @@
 use std::borrow::Borrow;
-use std::fmt::{self, Display, Formatter};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
 use std::iter::{Extend, FromIterator};
+use std::mem;
 use std::ops::Deref;
 use std::slice::Iter;
 use std::str::FromStr;
@@ -17,13 +21,49 @@ use value::Key;
 /// See also: [relationship path].
 ///
 /// [relationship path]: http://jsonapi.org/format/#fetching-includes
-#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct Path(Vec<Key>);
+pub struct Path(Segments);
+
+/// The number of keys that a `Path` can hold without allocating.
+const INLINE_CAPACITY: usize = 2;
+
+/// The keys of a `Path`. Most paths only have 1 or 2 keys, so they are stored inline
+/// until the path grows beyond `INLINE_CAPACITY`. The unused slots of an inline path
+/// contain empty keys, which do not allocate.
+enum Segments {
+    Inline(usize, [Key; INLINE_CAPACITY]),
+    Heap(Vec<Key>),
+}
+
+impl Segments {
+    fn empty() -> [Key; INLINE_CAPACITY] {
+        [Key::from_raw(String::new()), Key::from_raw(String::new())]
+    }
+
+    /// Moves the keys to the heap with room for at least `capacity` keys.
+    fn spill(&mut self, capacity: usize) -> &mut Vec<Key> {
+        if let Segments::Inline(len, ref mut keys) = *self {
+            let mut vec = Vec::with_capacity(capacity);
+
+            vec.extend(keys[..len].iter_mut().map(|key| mem::replace(key, take())));
+            *self = Segments::Heap(vec);
+        }
+
+        match *self {
+            Segments::Heap(ref mut vec) => vec,
+            Segments::Inline(..) => unreachable!(),
+        }
+    }
+}
+
+/// Returns an empty key that can be used as a placeholder.
+fn take() -> Key {
+    Key::from_raw(String::new())
+}
 
 impl Path {
     /// Constructs a new, empty `Path`.
     pub fn new() -> Self {
-        Default::default()
+        Path(Segments::Inline(0, Segments::empty()))
     }
 
     /// Constructs a new, empty `Path` with the specified capacity.
@@ -53,7 +93,41 @@ impl Path {
     /// # }
     /// ```
     pub fn with_capacity(capacity: usize) -> Self {
-        Path(Vec::with_capacity(capacity))
+        if capacity <= INLINE_CAPACITY {
+            Path::new()
+        } else {
+            Path(Segments::Heap(Vec::with_capacity(capacity)))
+        }
+    }
+
+    /// Constructs a `Path` from a slice of member names.
+    ///
+    /// Returns an error if a segment is not a valid member name. Unlike parsing a
+    /// dot-separated string, a segment that contains a `.` is rejected rather than split.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// # use json_api::value::Path;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// let path = Path::from_segments(&["posts", "comments", "author"])?;
+    ///
+    /// assert_eq!(path, "posts.comments.author");
+    /// assert!(Path::from_segments(&["posts", "comments.author"]).is_err());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn from_segments(segments: &[&str]) -> Result<Self, Error> {
+        segments.iter().map(|segment| segment.parse()).collect()
     }
 
     /// Returns the number of keys the path can hold without reallocating.
@@ -71,7 +145,10 @@ impl Path {
     /// # }
     /// ```
     pub fn capacity(&self) -> usize {
-        self.0.capacity()
+        match self.0 {
+            Segments::Inline(..) => INLINE_CAPACITY,
+            Segments::Heap(ref vec) => vec.capacity(),
+        }
     }
 
     /// Returns the number of chars in a `Path`.
@@ -100,7 +177,7 @@ impl Path {
     /// # }
     /// ```
     pub fn char_count(&self) -> usize {
-        let keys = &self.0;
+        let keys = &**self;
         let count = keys.len();
 
         if count > 0 {
@@ -139,7 +216,41 @@ impl Path {
     /// # }
     /// ```
     pub fn pop(&mut self) -> Option<Key> {
-        self.0.pop()
+        match self.0 {
+            Segments::Inline(0, _) => None,
+            Segments::Inline(ref mut len, ref mut keys) => {
+                *len -= 1;
+                Some(mem::replace(&mut keys[*len], take()))
+            }
+            Segments::Heap(ref mut vec) => vec.pop(),
+        }
+    }
+
+    /// Moves the keys of `other` to the back of a `Path`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// # use json_api::value::Path;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// let mut path = "posts".parse::<Path>()?;
+    ///
+    /// path.append("comments.author".parse()?);
+    /// assert_eq!(path, "posts.comments.author");
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn append(&mut self, other: Path) {
+        self.extend(other);
     }
 
     /// Appends a `Key` to the back of a `Path`.
@@ -168,7 +279,45 @@ impl Path {
     /// # }
     /// ```
     pub fn push(&mut self, key: Key) {
-        self.0.push(key);
+        match self.0 {
+            Segments::Inline(ref mut len, ref mut keys) if *len < INLINE_CAPACITY => {
+                keys[*len] = key;
+                *len += 1;
+            }
+            Segments::Inline(len, _) => self.0.spill(len * 2).push(key),
+            Segments::Heap(ref mut vec) => vec.push(key),
+        }
+    }
+
+    /// Appends a copy of each key in `other` to the back of a `Path`.
+    ///
+    /// This is equivalent to `path.join(&other)`, except the keys are added to `self`
+    /// rather than a new `Path`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// # use json_api::value::Path;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// let mut path = "posts".parse::<Path>()?;
+    /// let other = "comments.author".parse::<Path>()?;
+    ///
+    /// path.push_path(&other);
+    /// assert_eq!(path, "posts.comments.author");
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn push_path(&mut self, other: &Path) {
+        self.extend(other);
     }
 
     /// Reserves capacity for at least `additional` more keys to be inserted.
@@ -193,7 +342,14 @@ impl Path {
     /// # }
     /// ```
     pub fn reserve(&mut self, additional: usize) {
-        self.0.reserve(additional);
+        match self.0 {
+            Segments::Inline(len, _) if len + additional <= INLINE_CAPACITY => {}
+            Segments::Inline(len, _) => {
+                let capacity = len.checked_add(additional).expect("capacity overflow");
+                self.0.spill(capacity.max(len * 2));
+            }
+            Segments::Heap(ref mut vec) => vec.reserve(additional),
+        }
     }
 
     /// Reserves the minimum capacity for exactly `additional` more keys to be
@@ -220,7 +376,14 @@ impl Path {
     /// # }
     /// ```
     pub fn reserve_exact(&mut self, additional: usize) {
-        self.0.reserve_exact(additional);
+        match self.0 {
+            Segments::Inline(len, _) if len + additional <= INLINE_CAPACITY => {}
+            Segments::Inline(len, _) => {
+                let capacity = len.checked_add(additional).expect("capacity overflow");
+                self.0.spill(capacity);
+            }
+            Segments::Heap(ref mut vec) => vec.reserve_exact(additional),
+        }
     }
 
     /// Shrinks the capacity of the path as much as possible.
@@ -250,7 +413,13 @@ impl Path {
     /// # }
     /// ```
     pub fn shrink_to_fit(&mut self) {
-        self.0.shrink_to_fit();
+        let path = match self.0 {
+            Segments::Heap(ref mut vec) if vec.len() <= INLINE_CAPACITY => vec.drain(..).collect(),
+            Segments::Heap(ref mut vec) => return vec.shrink_to_fit(),
+            Segments::Inline(..) => return,
+        };
+
+        *self = path;
     }
 }
 
@@ -266,26 +435,58 @@ impl Borrow<[Key]> for Path {
     }
 }
 
+impl Clone for Path {
+    fn clone(&self) -> Self {
+        match self.0 {
+            Segments::Inline(len, ref keys) => Path(Segments::Inline(len, keys.clone())),
+            Segments::Heap(ref vec) => Path(Segments::Heap(vec.clone())),
+        }
+    }
+}
+
+impl Debug for Path {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("Path").field(&&**self).finish()
+    }
+}
+
+impl Default for Path {
+    fn default() -> Self {
+        Path::new()
+    }
+}
+
 impl Deref for Path {
     type Target = [Key];
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        match self.0 {
+            Segments::Inline(len, ref keys) => &keys[..len],
+            Segments::Heap(ref vec) => vec,
+        }
     }
 }
 
 impl Display for Path {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        f.write_str(&self.0.join("."))
+        f.write_str(&(**self).join("."))
     }
 }
 
+impl Eq for Path {}
+
 impl Extend<Key> for Path {
     fn extend<I>(&mut self, iter: I)
     where
         I: IntoIterator<Item = Key>,
     {
-        self.0.extend(iter);
+        let iter = iter.into_iter();
+
+        self.reserve(iter.size_hint().0);
+
+        for key in iter {
+            self.push(key);
+        }
     }
 }
 
@@ -315,7 +516,10 @@ impl FromIterator<Key> for Path {
     where
         I: IntoIterator<Item = Key>,
     {
-        Path(Vec::from_iter(iter))
+        let mut path = Path::new();
+
+        path.extend(iter);
+        path
     }
 }
 
@@ -327,12 +531,38 @@ impl FromStr for Path {
     }
 }
 
+impl Hash for Path {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
 impl IntoIterator for Path {
     type Item = Key;
     type IntoIter = <Vec<Key> as IntoIterator>::IntoIter;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter()
+        match self.0 {
+            Segments::Inline(len, keys) => {
+                let mut vec = Vec::from(keys);
+
+                vec.truncate(len);
+                vec.into_iter()
+            }
+            Segments::Heap(vec) => vec.into_iter(),
+        }
+    }
+}
+
+impl Ord for Path {
+    fn cmp(&self, other: &Path) -> Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl PartialEq for Path {
+    fn eq(&self, other: &Path) -> bool {
+        **self == **other
     }
 }
 
@@ -367,7 +597,34 @@ impl<'a> PartialEq<&'a str> for Path {
 
 impl PartialEq<String> for Path {
     fn eq(&self, rhs: &String) -> bool {
-        self == &*rhs
+        self == rhs.as_str()
+    }
+}
+
+impl PartialOrd for Path {
+    fn partial_cmp(&self, other: &Path) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Path {
+    type Error = Error;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl TryFrom<String> for Path {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.contains('.') {
+            value.parse()
+        } else {
+            // A path with a single segment can reuse the buffer of the string.
+            Key::try_from(value).map(|key| Path::from_iter(Some(key)))
+        }
     }
 }
 
@@ -482,7 +739,7 @@ impl Segment<Key> for Path {
     fn join(&self, other: Key) -> Path {
         let mut path = Path::with_capacity(self.len() + 1);
 
-        path.extend(self);
+        path.push_path(self);
         path.push(other);
 
         path
@@ -506,9 +763,120 @@ where
             _ => Path::new(),
         };
 
-        path.extend(self);
+        path.push_path(self);
         path.extend(iter);
 
         path
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::{Path, Segment};
+    use value::Key;
+
+    fn key(value: &str) -> Key {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn push_path_matches_join() {
+        let base = Path::from_segments(&["posts", "comments"]).unwrap();
+        let other = Path::from_segments(&["author", "avatar"]).unwrap();
+
+        let mut pushed = base.clone();
+        pushed.push_path(&other);
+        assert_eq!(pushed, base.join(&other));
+
+        let mut pushed = base.clone();
+        pushed.push_path(&Path::new());
+        assert_eq!(pushed, base.join(&Path::new()));
+
+        let mut pushed = Path::new();
+        pushed.push_path(&base);
+        pushed.push(key("author"));
+        assert_eq!(pushed, base.join(key("author")));
+
+        let mut appended = base.clone();
+        appended.append(other.clone());
+        assert_eq!(appended, base.join(&other));
+        assert_eq!(appended, "posts.comments.author.avatar");
+    }
+
+    #[test]
+    fn push_path_deep() {
+        let mut joined = Path::new();
+        let mut pushed = Path::new();
+
+        for n in 0..32 {
+            let segment = Path::from_segments(&[&format!("a{}", n), "b"]).unwrap();
+
+            joined = joined.join(&segment);
+            pushed.push_path(&segment);
+        }
+
+        assert_eq!(pushed.len(), 64);
+        assert_eq!(pushed, joined);
+    }
+
+    #[test]
+    fn inline_and_heap() {
+        let mut path = Path::new();
+
+        for (n, segment) in ["a", "b", "c", "d"].iter().enumerate() {
+            path.push(key(segment));
+            assert_eq!(path.len(), n + 1);
+        }
+
+        assert_eq!(path, "a.b.c.d");
+        assert_eq!(path.pop(), Some(key("d")));
+        assert_eq!(path.pop(), Some(key("c")));
+
+        let mut inline = Path::from_segments(&["a", "b"]).unwrap();
+
+        // Paths are equal regardless of how their keys are stored.
+        path.shrink_to_fit();
+        assert_eq!(path, inline);
+        assert_eq!(path.capacity(), 2);
+
+        inline.reserve(1);
+        assert!(inline.capacity() >= 3);
+        assert_eq!(inline, path);
+        assert_eq!(inline.clone().into_iter().collect::<Vec<_>>(), [key("a"), key("b")]);
+        assert_eq!(path.clone().into_iter().collect::<Vec<_>>(), [key("a"), key("b")]);
+        assert_eq!(format!("{:?}", path), r#"Path([Key("a"), Key("b")])"#);
+    }
+
+    #[test]
+    fn eq_string() {
+        let path = Path::from_segments(&["authors", "name"]).unwrap();
+
+        assert_eq!(path, "authors.name".to_owned());
+        assert_ne!(path, "authors".to_owned());
+    }
+
+    #[test]
+    fn from_segments() {
+        assert_eq!(Path::from_segments(&[]).unwrap(), Path::new());
+        assert_eq!(Path::from_segments(&["authors", "name"]).unwrap(), "authors.name");
+        assert!(Path::from_segments(&["authors", ""]).is_err());
+        assert!(Path::from_segments(&["authors.name"]).is_err());
+    }
+
+    #[test]
+    fn try_from() {
+        let expected = Path::from_segments(&["author", "first-name"]).unwrap();
+
+        assert_eq!(Path::try_from("author.firstName").unwrap(), expected);
+        assert_eq!(Path::try_from("author.firstName".to_owned()).unwrap(), expected);
+        assert_eq!(Path::try_from("author".to_owned()).unwrap(), "author");
+        assert_eq!(Path::try_from("blogPosts".to_owned()).unwrap(), "blog-posts");
+
+        for source in &["", "author.", "author.@name", "-author"] {
+            assert!(Path::try_from(*source).is_err(), "{}", source);
+            assert!(Path::try_from(source.to_string()).is_err(), "{}", source);
+        }
+    }
+}
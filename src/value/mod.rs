@@ -1,32 +1,49 @@
 //! Represent and interact with JSON API values.
 
 pub(crate) mod convert;
+mod de;
+pub(crate) mod ser;
+pub(crate) mod size;
 
 pub mod collections;
 pub mod fields;
+#[cfg(feature = "patch")]
+pub mod patch;
 
-use std::cmp::PartialEq;
+use std::cmp::{Ordering, PartialEq};
 use std::fmt::{self, Formatter};
 use std::iter::FromIterator;
+use std::mem;
 use std::str::FromStr;
 
 use serde::de::{Deserialize, Deserializer, Visitor};
 use serde::ser::{Serialize, Serializer};
+use serde_json;
 
 use error::Error;
+use value::size::EstimateSize;
 
 pub use serde_json::value::Number;
 
 pub use self::collections::{Map, Set};
 pub use self::convert::{from_value, to_value};
+pub use self::ser::{SerializeOptions, WithOptions};
 #[doc(no_inline)]
 pub use self::fields::{Key, Path};
 
 /// Represents any valid JSON API value.
 ///
 /// Like [`serde_json::Value`], but with spec compliance baked into the type
-/// system.
+/// system. A `serde_json::Value` can be converted with `Value::try_from` and
+/// converted back with `serde_json::Value::from`.
 ///
+/// The keys of an object nested in a `Value` are not member names of the document,
+/// so they are kept verbatim when a `Value` is deserialized or created with
+/// [`to_value`], or parsed with `str::parse`. This allows attributes to contain
+/// arbitrary JSON such as `{"$schema": "..."}`. Converting with `Value::try_from`
+/// checks the keys of the outermost object only.
+///
+/// [`to_value`]: ./fn.to_value.html
 /// [`serde_json::Value`]: https://docs.serde.rs/serde_json/enum.Value.html
 #[derive(Clone, Debug, PartialEq)]
 pub enum Value {
@@ -40,6 +57,18 @@ pub enum Value {
     Bool(bool),
 
     /// An integer or floating point value.
+    ///
+    /// Integers that fit in an `i64` or `u64` are stored exactly. Any other number,
+    /// including an integer that is too large for a `u64`, is stored as an `f64` and
+    /// only has about 15 significant digits. Large integers that are converted with
+    /// [`as_f64`] are rounded as well, so prefer [`as_i64`], [`as_u64`], or
+    /// [`to_decimal_string`] when precision matters. Values that require arbitrary
+    /// precision should be rendered as strings.
+    ///
+    /// [`as_f64`]: #method.as_f64
+    /// [`as_i64`]: #method.as_i64
+    /// [`as_u64`]: #method.as_u64
+    /// [`to_decimal_string`]: #method.to_decimal_string
     Number(Number),
 
     /// A JSON object as a hash table with consistent order. Keys are
@@ -307,6 +336,85 @@ impl Value {
         }
     }
 
+    /// Optionally get the underlying number as a string of decimal text. Returns `None`
+    /// if the `Value` is not a number.
+    ///
+    /// Unlike [`as_f64`], the text of an integer contains every digit of the integer,
+    /// even if it cannot be represented exactly by an `f64`. A floating point number
+    /// uses the shortest text that parses back to the same `f64`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Value;
+    /// #
+    /// # fn main() {
+    /// let integer = Value::from(9_007_199_254_740_993u64);
+    /// let float = Value::from(0.1);
+    ///
+    /// assert_eq!(integer.to_decimal_string(), Some("9007199254740993".to_owned()));
+    /// assert_eq!(integer.as_f64(), Some(9_007_199_254_740_992.0));
+    /// assert_eq!(float.to_decimal_string(), Some("0.1".to_owned()));
+    /// # }
+    /// ```
+    ///
+    /// [`as_f64`]: #method.as_f64
+    pub fn to_decimal_string(&self) -> Option<String> {
+        match *self {
+            Value::Number(ref n) => Some(n.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Parses a JSON number from `text` without converting an integer to an `f64`.
+    ///
+    /// An integer is stored exactly if it fits in an `i64` or `u64`. Otherwise, an
+    /// error is returned instead of silently losing precision. A number with a fraction
+    /// or an exponent is stored as an `f64`, since [`Number`] has no other
+    /// representation for it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::{Error, Value};
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// let value = Value::from_number_str("9223372036854775807")?;
+    /// assert_eq!(value.as_i64(), Some(9_223_372_036_854_775_807));
+    ///
+    /// let value = Value::from_number_str("2.5e3")?;
+    /// assert_eq!(value.as_f64(), Some(2500.0));
+    ///
+    /// assert!(Value::from_number_str("18446744073709551616").is_err());
+    /// assert!(Value::from_number_str("0x10").is_err());
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Number`]: ./struct.Number.html
+    pub fn from_number_str(text: &str) -> Result<Value, Error> {
+        if text.trim() != text {
+            bail!("number '{}' contains leading or trailing whitespace", text);
+        }
+
+        let number = serde_json::from_str::<Number>(text)?;
+        let integer = !text.contains(&['.', 'e', 'E'][..]);
+
+        if integer && number.is_f64() {
+            bail!("integer '{}' cannot be represented without losing precision", text);
+        }
+
+        Ok(Value::Number(number))
+    }
+
     /// Returns true if the `Value` is an array.
     ///
     /// For any `Value` on which `is_array` returns true, [`as_array`] and
@@ -569,6 +677,541 @@ impl Value {
             _ => false,
         }
     }
+
+    /// Returns an estimate of the length of the `Value` when it is serialized as
+    /// compact JSON, without serializing it.
+    ///
+    /// The estimate is exact unless the `Value` contains a floating point number, in
+    /// which case the estimate is an upper bound.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Value;
+    /// #
+    /// # fn main() {
+    /// let value = Value::from(vec!["a", "b"]);
+    /// assert_eq!(value.estimated_size(), r#"["a","b"]"#.len());
+    /// # }
+    /// ```
+    pub fn estimated_size(&self) -> usize {
+        EstimateSize::estimated_size(self)
+    }
+
+    /// Returns a reference to the value of the member `key` if the `Value` is an object
+    /// that contains it. Returns `None` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::{Error, Value};
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// let value = "{ \"title\": \"Hello\" }".parse::<Value>()?;
+    ///
+    /// assert_eq!(value.get("title"), Some(&"Hello".into()));
+    /// assert_eq!(value.get("body"), None);
+    /// assert_eq!(Value::from("title").get("title"), None);
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match *self {
+            Value::Object(ref data) => data.get(key),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the value of the member `key` if the `Value` is
+    /// an object that contains it. Returns `None` otherwise.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+        match *self {
+            Value::Object(ref mut data) => data.get_mut(key),
+            _ => None,
+        }
+    }
+
+    /// Returns a reference to the element at `index` if the `Value` is an array that
+    /// is long enough. Returns `None` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Value;
+    /// #
+    /// # fn main() {
+    /// let value = Value::from(vec!["a", "b"]);
+    ///
+    /// assert_eq!(value.get_index(1), Some(&"b".into()));
+    /// assert_eq!(value.get_index(2), None);
+    /// assert_eq!(Value::Null.get_index(0), None);
+    /// # }
+    /// ```
+    pub fn get_index(&self, index: usize) -> Option<&Value> {
+        match *self {
+            Value::Array(ref data) => data.get(index),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the element at `index` if the `Value` is an
+    /// array that is long enough. Returns `None` otherwise.
+    pub fn get_index_mut(&mut self, index: usize) -> Option<&mut Value> {
+        match *self {
+            Value::Array(ref mut data) => data.get_mut(index),
+            _ => None,
+        }
+    }
+
+    /// Replaces the `Value` with `null` and returns the previous value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::{Error, Value};
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// let mut value = "{ \"tags\": [\"json\", \"api\"] }".parse::<Value>()?;
+    /// let tags = value.get_mut("tags").map(Value::take);
+    ///
+    /// assert_eq!(tags, Some(Value::from(vec!["json", "api"])));
+    /// assert_eq!(value.get("tags"), Some(&Value::Null));
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn take(&mut self) -> Value {
+        mem::replace(self, Value::Null)
+    }
+
+    /// Replaces the `Value` with `value` and returns the previous value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::{Error, Value};
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// let mut value = "{ \"title\": \"To TDD or Not\" }".parse::<Value>()?;
+    /// let title = value.get_mut("title").map(|title| title.replace("TDD".into()));
+    ///
+    /// assert_eq!(title, Some(Value::from("To TDD or Not")));
+    /// assert_eq!(value.get("title"), Some(&Value::from("TDD")));
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn replace(&mut self, value: Value) -> Value {
+        mem::replace(self, value)
+    }
+
+    /// Returns a copy of the `Value` with the members of each object sorted by key.
+    ///
+    /// The order of the elements of an array is preserved. Since equality does not
+    /// depend on the order of the members of an object, this is mostly useful to
+    /// produce stable output, such as a snapshot in a test.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// # extern crate serde_json;
+    /// #
+    /// # use json_api::{Error, Value};
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// let value = r#"{ "b": [{ "d": 1, "c": 2 }], "a": null }"#.parse::<Value>()?;
+    /// let json = serde_json::to_string(&value.canonicalize())?;
+    ///
+    /// assert_eq!(json, r#"{"a":null,"b":[{"c":2,"d":1}]}"#);
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn canonicalize(&self) -> Value {
+        match *self {
+            Value::Array(ref data) => data.iter().map(Value::canonicalize).collect(),
+            Value::Object(ref data) => {
+                let mut map = data.iter()
+                    .map(|(key, value)| (key.clone(), value.canonicalize()))
+                    .collect::<Map>();
+
+                map.sort_keys();
+                Value::Object(map)
+            }
+            ref value => value.clone(),
+        }
+    }
+
+    /// Looks up a value by a [JSON pointer]. Returns `None` if the pointer is invalid
+    /// or does not reference a value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::{Error, Value};
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::value::Map;
+    ///
+    /// let mut map = Map::new();
+    /// map.insert("tags".parse()?, Value::from(vec!["json", "api"]));
+    ///
+    /// let value = Value::Object(map);
+    ///
+    /// assert_eq!(value.pointer("/tags/1"), Some(&"api".into()));
+    /// assert_eq!(value.pointer("/tags/2"), None);
+    /// assert_eq!(value.pointer("tags"), None);
+    /// assert_eq!(value.pointer(""), Some(&value));
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [JSON pointer]: https://tools.ietf.org/html/rfc6901
+    pub fn pointer(&self, pointer: &str) -> Option<&Value> {
+        let mut target = self;
+
+        for token in pointer_tokens(pointer)? {
+            target = match *target {
+                Value::Array(ref data) => data.get(parse_index(&token)?)?,
+                Value::Object(ref data) => data.get(&*token)?,
+                _ => return None,
+            };
+        }
+
+        Some(target)
+    }
+
+    /// Looks up a value by a [JSON pointer] and returns a mutable reference to it.
+    /// Returns `None` if the pointer is invalid or does not reference a value.
+    ///
+    /// [JSON pointer]: https://tools.ietf.org/html/rfc6901
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Value> {
+        let mut target = self;
+
+        for token in pointer_tokens(pointer)? {
+            target = match *target {
+                Value::Array(ref mut data) => data.get_mut(parse_index(&token)?)?,
+                Value::Object(ref mut data) => data.get_mut(&*token)?,
+                _ => return None,
+            };
+        }
+
+        Some(target)
+    }
+
+    /// Sets the value referenced by a [JSON pointer].
+    ///
+    /// Objects that are missing along the way are created. A `null` along the way is
+    /// replaced with an object. The final token of the pointer may be the index of an
+    /// existing element of an array, the length of the array, or `-` to append `value`
+    /// to the array.
+    ///
+    /// Returns an error if the pointer is invalid, a token that is not an index is used
+    /// to access an array, an index is out of bounds, a token is used to access a
+    /// value that is not an array or object, or a new member name is invalid. Objects
+    /// that were created before an error occurred are not removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::{Error, Value};
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// let mut value = Value::Null;
+    ///
+    /// value.set_pointer("/author/tags", Value::Array(Vec::new()))?;
+    /// value.set_pointer("/author/tags/-", "rust".into())?;
+    /// value.set_pointer("/author/name", "Ferris".into())?;
+    ///
+    /// assert_eq!(value.pointer("/author/tags/0"), Some(&"rust".into()));
+    /// assert_eq!(value.pointer("/author/name"), Some(&"Ferris".into()));
+    ///
+    /// assert!(value.set_pointer("/author/tags/name", true.into()).is_err());
+    /// assert!(value.set_pointer("/author/name/first", true.into()).is_err());
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [JSON pointer]: https://tools.ietf.org/html/rfc6901
+    pub fn set_pointer(&mut self, pointer: &str, value: Value) -> Result<(), Error> {
+        let mut tokens = match pointer_tokens(pointer) {
+            Some(tokens) => tokens,
+            None => bail!("invalid json pointer '{}'", pointer),
+        };
+
+        let last = match tokens.pop() {
+            Some(token) => token,
+            None => {
+                *self = value;
+                return Ok(());
+            }
+        };
+
+        let mut target = self;
+
+        for token in tokens {
+            if target.is_null() {
+                *target = Value::Object(Default::default());
+            }
+
+            target = match *target {
+                Value::Array(ref mut data) => {
+                    let len = data.len();
+
+                    match parse_index(&token) {
+                        Some(index) if index < len => &mut data[index],
+                        Some(index) => bail!("index {} is out of bounds in '{}'", index, pointer),
+                        None => bail!("'{}' is not an array index in '{}'", token, pointer),
+                    }
+                }
+                Value::Object(ref mut data) => {
+                    let key = token.parse::<Key>()?;
+
+                    if !data.contains_key(&key) {
+                        data.insert(key.clone(), Value::Object(Default::default()));
+                    }
+
+                    data.get_mut(&key).unwrap()
+                }
+                _ => bail!("'{}' does not reference an array or object in '{}'", token, pointer),
+            };
+        }
+
+        if target.is_null() {
+            *target = Value::Object(Default::default());
+        }
+
+        match *target {
+            Value::Array(ref mut data) => {
+                let len = data.len();
+                let index = match &*last {
+                    "-" => len,
+                    token => match parse_index(token) {
+                        Some(index) => index,
+                        None => bail!("'{}' is not an array index in '{}'", token, pointer),
+                    },
+                };
+
+                if index < len {
+                    data[index] = value;
+                } else if index == len {
+                    data.push(value);
+                } else {
+                    bail!("index {} is out of bounds in '{}'", index, pointer);
+                }
+            }
+            Value::Object(ref mut data) => {
+                data.insert(last.parse()?, value);
+            }
+            _ => bail!("'{}' does not reference an array or object in '{}'", last, pointer),
+        }
+
+        Ok(())
+    }
+
+    /// Compares two values with a total ordering. Values of different types are ordered
+    /// by their type: `null` < booleans < numbers < strings < arrays < objects.
+    ///
+    /// This is the same ordering as the [`PartialOrd`] implementation of `Value`,
+    /// except that numbers that cannot be compared are ordered as well. A `NaN` is
+    /// greater than every other number and equal to another `NaN`. A [`Number`] cannot
+    /// be constructed from `NaN`, so this only applies if the representation of a number
+    /// is not an `f64`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Value;
+    /// #
+    /// # fn main() {
+    /// let mut values = vec![
+    ///     Value::from("b"),
+    ///     Value::from(2),
+    ///     Value::Null,
+    ///     Value::from(1.5),
+    ///     Value::from("a"),
+    ///     Value::from(true),
+    /// ];
+    ///
+    /// values.sort_by(Value::cmp_total);
+    ///
+    /// assert_eq!(
+    ///     values,
+    ///     vec![
+    ///         Value::Null,
+    ///         Value::from(true),
+    ///         Value::from(1.5),
+    ///         Value::from(2),
+    ///         Value::from("a"),
+    ///         Value::from("b"),
+    ///     ]
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// [`Number`]: ./struct.Number.html
+    /// [`PartialOrd`]: #impl-PartialOrd%3CValue%3E
+    pub fn cmp_total(&self, other: &Value) -> Ordering {
+        compare(self, other, true).unwrap_or(Ordering::Equal)
+    }
+
+    /// Returns the position of the type of a value in the ordering of `Value`.
+    fn rank(&self) -> u8 {
+        match *self {
+            Value::Null => 0,
+            Value::Bool(_) => 1,
+            Value::Number(_) => 2,
+            Value::String(_) => 3,
+            Value::Array(_) => 4,
+            Value::Object(_) => 5,
+        }
+    }
+}
+
+/// Compares two values. If `total` is `true`, numbers that cannot be compared are
+/// ordered with `NaN` as the greatest number and the result is never `None`.
+fn compare(lhs: &Value, rhs: &Value, total: bool) -> Option<Ordering> {
+    match (lhs, rhs) {
+        (Value::Null, Value::Null) => Some(Ordering::Equal),
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        (Value::Number(a), Value::Number(b)) => compare_numbers(a, b, total),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        (Value::Array(a), Value::Array(b)) => {
+            compare_seq(a.iter().map(|v| (None, v)), b.iter().map(|v| (None, v)), total)
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            // Objects are equal regardless of the order of their members, so they are
+            // compared by their members in the order of their keys.
+            let mut a = a.iter().collect::<Vec<_>>();
+            let mut b = b.iter().collect::<Vec<_>>();
+
+            a.sort_by(|x, y| x.0.cmp(y.0));
+            b.sort_by(|x, y| x.0.cmp(y.0));
+
+            compare_seq(
+                a.into_iter().map(|(k, v)| (Some(k), v)),
+                b.into_iter().map(|(k, v)| (Some(k), v)),
+                total,
+            )
+        }
+        _ => Some(lhs.rank().cmp(&rhs.rank())),
+    }
+}
+
+/// Compares two sequences of optionally keyed values lexicographically.
+fn compare_seq<'a, I, J>(lhs: I, mut rhs: J, total: bool) -> Option<Ordering>
+where
+    I: Iterator<Item = (Option<&'a Key>, &'a Value)>,
+    J: Iterator<Item = (Option<&'a Key>, &'a Value)>,
+{
+    for (a_key, a) in lhs {
+        let (b_key, b) = match rhs.next() {
+            Some(item) => item,
+            None => return Some(Ordering::Greater),
+        };
+
+        match a_key.cmp(&b_key) {
+            Ordering::Equal => {}
+            ordering => return Some(ordering),
+        }
+
+        match compare(a, b, total) {
+            Some(Ordering::Equal) => {}
+            ordering => return ordering,
+        }
+    }
+
+    match rhs.next() {
+        Some(_) => Some(Ordering::Less),
+        None => Some(Ordering::Equal),
+    }
+}
+
+/// Compares two numbers by their numeric value. Integers are compared exactly. If
+/// two numbers have the same value but a different representation, such as `1` and
+/// `1.0`, the integer is less than the float to remain consistent with `PartialEq`.
+fn compare_numbers(lhs: &Number, rhs: &Number, total: bool) -> Option<Ordering> {
+    let ordering = if let (Some(a), Some(b)) = (lhs.as_i64(), rhs.as_i64()) {
+        Some(a.cmp(&b))
+    } else if let (Some(a), Some(b)) = (lhs.as_u64(), rhs.as_u64()) {
+        Some(a.cmp(&b))
+    } else {
+        let a = lhs.as_f64().unwrap_or(f64::NAN);
+        let b = rhs.as_f64().unwrap_or(f64::NAN);
+
+        match a.partial_cmp(&b) {
+            None if total => Some(a.is_nan().cmp(&b.is_nan())),
+            ordering => ordering,
+        }
+    };
+
+    ordering.map(|ordering| ordering.then_with(|| lhs.is_f64().cmp(&rhs.is_f64())))
+}
+
+/// Splits a JSON pointer into unescaped reference tokens. Returns `None` if the pointer
+/// is not empty and does not start with `/`.
+fn pointer_tokens(pointer: &str) -> Option<Vec<String>> {
+    if pointer.is_empty() {
+        return Some(Vec::new());
+    }
+
+    if !pointer.starts_with('/') {
+        return None;
+    }
+
+    let tokens = pointer[1..]
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect();
+
+    Some(tokens)
+}
+
+/// Parses an array index from a reference token. Leading zeros are not permitted.
+fn parse_index(token: &str) -> Option<usize> {
+    if token.starts_with('+') || (token.starts_with('0') && token.len() > 1) {
+        return None;
+    }
+
+    token.parse().ok()
 }
 
 /// Returns the `Value::Null`. This allows for better composition with `Option`
@@ -735,7 +1378,7 @@ impl FromStr for Value {
     type Err = Error;
 
     fn from_str(src: &str) -> Result<Self, Self::Err> {
-        convert::from_json(src.parse()?)
+        Ok(serde_json::from_str(src)?)
     }
 }
 
@@ -823,6 +1466,16 @@ impl PartialEq<str> for Value {
     }
 }
 
+/// Values are ordered by type and then by value. For more information, check out the
+/// documentation of [`Value::cmp_total`].
+///
+/// [`Value::cmp_total`]: #method.cmp_total
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
+        compare(self, other, false)
+    }
+}
+
 impl<'de> Deserialize<'de> for Value {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -843,6 +1496,9 @@ impl<'de> Deserialize<'de> for Value {
                 Ok(Value::Bool(value))
             }
 
+            // Integers are visited with `visit_i64` or `visit_u64` and stored exactly.
+            // Only numbers with a fraction or exponent, or integers that are too large
+            // for a `u64`, are visited as an `f64`.
             fn visit_f64<E>(self, value: f64) -> Result<Value, E> {
                 Ok(Value::from(value))
             }
@@ -885,10 +1541,9 @@ impl<'de> Deserialize<'de> for Value {
                 let mut map = Map::with_capacity(access.size_hint().unwrap_or(0));
 
                 while let Some(key) = access.next_key::<String>()? {
-                    let key = key.parse().map_err(Error::custom)?;
                     let value = access.next_value()?;
 
-                    map.insert(key, value);
+                    map.insert(Key::from_raw(key), value);
                 }
 
                 Ok(Value::Object(map))
@@ -918,7 +1573,7 @@ impl Serialize for Value {
         S: Serializer,
     {
         match *self {
-            Value::Null => serializer.serialize_none(),
+            Value::Null => serializer.serialize_unit(),
             Value::Array(ref value) => value.serialize(serializer),
             Value::Bool(value) => serializer.serialize_bool(value),
             Value::Number(ref value) => value.serialize(serializer),
@@ -927,3 +1582,253 @@ impl Serialize for Value {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use serde_json;
+
+    use super::Value;
+
+    fn from_str(source: &str) -> Value {
+        serde_json::from_str(source).unwrap()
+    }
+
+    #[test]
+    fn partial_cmp_types() {
+        let values = [
+            Value::Null,
+            Value::from(false),
+            Value::from(-1),
+            Value::from(""),
+            Value::Array(Vec::new()),
+            from_str("{}"),
+        ];
+
+        for (index, value) in values.iter().enumerate() {
+            for (other_index, other) in values.iter().enumerate() {
+                assert_eq!(value.partial_cmp(other), Some(index.cmp(&other_index)));
+                assert_eq!(value.cmp_total(other), index.cmp(&other_index));
+            }
+        }
+    }
+
+    #[test]
+    fn partial_cmp_values() {
+        let pairs = [
+            (Value::from(false), Value::from(true)),
+            (Value::from(-1), Value::from(1)),
+            (Value::from(1), Value::from(1.5)),
+            (Value::from(i64::MAX), Value::from(u64::MAX)),
+            (Value::from(u64::MAX - 1), Value::from(u64::MAX)),
+            (Value::from("a"), Value::from("b")),
+            (Value::from("a"), Value::from("ab")),
+            (from_str("[1, 2]"), from_str("[1, 3]")),
+            (from_str("[1, 2]"), from_str("[1, 2, 0]")),
+            (from_str("[1, 2, 0]"), from_str("[2]")),
+            (from_str(r#"{"a": 1}"#), from_str(r#"{"a": 2}"#)),
+            (from_str(r#"{"a": 1}"#), from_str(r#"{"a": 1, "b": 0}"#)),
+            (from_str(r#"{"a": 1, "b": 0}"#), from_str(r#"{"b": 0}"#)),
+        ];
+
+        for (lesser, greater) in &pairs {
+            assert!(lesser < greater, "{:?} < {:?}", lesser, greater);
+            assert_eq!(greater.cmp_total(lesser), Ordering::Greater);
+        }
+    }
+
+    #[test]
+    fn partial_cmp_consistent_with_eq() {
+        let a = from_str(r#"{"a": 1, "b": [true, null]}"#);
+        let b = from_str(r#"{"b": [true, null], "a": 1}"#);
+
+        assert_eq!(a, b);
+        assert_eq!(a.partial_cmp(&b), Some(Ordering::Equal));
+
+        // Numbers with the same value but a different representation are not equal.
+        assert_ne!(Value::from(1), Value::from(1.0));
+        assert_eq!(Value::from(1).partial_cmp(&1.0.into()), Some(Ordering::Less));
+        assert_eq!(Value::from(1.0).cmp_total(&Value::from(1.0)), Ordering::Equal);
+    }
+
+    #[test]
+    fn accessors() {
+        let mut value = from_str(r#"{"a": [{"b": 1}, 2]}"#);
+
+        assert_eq!(value.get("a").and_then(|a| a.get_index(1)), Some(&2.into()));
+        assert_eq!(value.get_index(0), None);
+        assert_eq!(from_str("[1]").get("0"), None);
+
+        if let Some(b) = value.get_mut("a").and_then(|a| a.get_index_mut(0)) {
+            *b = b.take().get("b").cloned().unwrap_or_default();
+        }
+
+        assert_eq!(value, from_str(r#"{"a": [1, 2]}"#));
+        assert_eq!(value.take(), from_str(r#"{"a": [1, 2]}"#));
+        assert_eq!(value, Value::Null);
+    }
+
+    #[test]
+    fn take_replace() {
+        let mut value = from_str(r#"{"a": {"b": [1, 2]}, "c": true}"#);
+
+        if let Some(map) = value.as_object_mut() {
+            let b = map.get_mut("a").map(Value::take);
+            let c = map.get_mut("c").map(|c| c.replace(b.unwrap_or_default()));
+
+            assert_eq!(c, Some(true.into()));
+        }
+
+        assert_eq!(value, from_str(r#"{"a": null, "c": {"b": [1, 2]}}"#));
+        assert_eq!(value.replace(1.into()), from_str(r#"{"a": null, "c": {"b": [1, 2]}}"#));
+        assert_eq!(value.take(), Value::from(1));
+        assert_eq!(value, Value::Null);
+    }
+
+    #[test]
+    fn canonicalize() {
+        let value = from_str(r#"{"z": {"y": [3, {"b": 1, "a": 2}], "x": 1.5}, "a": "z"}"#);
+        let canonical = value.canonicalize();
+
+        assert_eq!(canonical, value);
+        assert_eq!(
+            serde_json::to_string(&canonical).unwrap(),
+            r#"{"a":"z","z":{"x":1.5,"y":[3,{"a":2,"b":1}]}}"#
+        );
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let sources = [
+            "null",
+            "true",
+            "-9223372036854775808",
+            "18446744073709551615",
+            "-1.5",
+            "1e300",
+            r#""\"quoted\" \u00e9""#,
+            r#"[[], {}, [null, false]]"#,
+            r#"{"b": {"c": [1, "2", 3.5]}, "a": {}}"#,
+        ];
+
+        for source in &sources {
+            let value = from_str(source);
+            let json = serde_json::to_string(&value).unwrap();
+
+            assert_eq!(from_str(&json), value, "{}", source);
+            assert_eq!(serde_json::to_string(&from_str(&json)).unwrap(), json);
+        }
+    }
+
+    #[test]
+    fn pointer_escaped() {
+        let value = from_str(r#"{"a": [{"b": 1}]}"#);
+
+        assert_eq!(value.pointer("/a/0/b"), Some(&1.into()));
+        assert_eq!(value.pointer("/a/00/b"), None);
+        assert_eq!(value.pointer("/a/-"), None);
+        assert_eq!(value.pointer("/a~0/0"), None);
+    }
+
+    #[test]
+    fn set_pointer_deep() {
+        let mut value = from_str(r#"{"data": {"attributes": {"title": "a"}}}"#);
+
+        value
+            .set_pointer("/data/attributes/title", "b".into())
+            .unwrap();
+
+        assert_eq!(value, from_str(r#"{"data": {"attributes": {"title": "b"}}}"#));
+
+        if let Some(title) = value.pointer_mut("/data/attributes/title") {
+            *title = "c".into();
+        }
+
+        assert_eq!(value.pointer("/data/attributes/title"), Some(&"c".into()));
+    }
+
+    #[test]
+    fn set_pointer_intermediate() {
+        let mut value = from_str(r#"{"data": {"tags": [null]}}"#);
+
+        value
+            .set_pointer("/data/meta/counts/views", 10.into())
+            .unwrap();
+        value
+            .set_pointer("/data/tags/0/name", "rust".into())
+            .unwrap();
+        value.set_pointer("/data/tags/1", "json".into()).unwrap();
+        value.set_pointer("/data/tags/-", "api".into()).unwrap();
+
+        assert_eq!(
+            value,
+            from_str(
+                r#"{
+                    "data": {
+                        "tags": [{"name": "rust"}, "json", "api"],
+                        "meta": {"counts": {"views": 10}}
+                    }
+                }"#
+            )
+        );
+    }
+
+    #[test]
+    fn set_pointer_mismatch() {
+        let mut value = from_str(r#"{"tags": ["a"], "title": "b"}"#);
+        let expected = value.clone();
+
+        assert!(value.set_pointer("/tags/name", true.into()).is_err());
+        assert!(value.set_pointer("/tags/name/first", true.into()).is_err());
+        assert!(value.set_pointer("/tags/5", true.into()).is_err());
+        assert!(value.set_pointer("/title/first", true.into()).is_err());
+        assert!(value.set_pointer("/-invalid", true.into()).is_err());
+        assert!(value.set_pointer("title", true.into()).is_err());
+        assert_eq!(value, expected);
+
+        value.set_pointer("", true.into()).unwrap();
+        assert_eq!(value, Value::Bool(true));
+    }
+
+    #[test]
+    fn large_integer_precision() {
+        let max = from_str("9223372036854775806");
+        let min = from_str("-9223372036854775807");
+
+        assert_eq!(max.as_i64(), Some(i64::MAX - 1));
+        assert_eq!(min.as_i64(), Some(i64::MIN + 1));
+        assert_eq!(max.to_decimal_string(), Some("9223372036854775806".to_owned()));
+        assert_eq!(serde_json::to_string(&min).unwrap(), "-9223372036854775807");
+
+        let parsed = Value::from_number_str("9223372036854775806").unwrap();
+
+        assert_eq!(parsed, max);
+        assert_eq!(parsed.as_i64(), Some(i64::MAX - 1));
+        assert_ne!(
+            parsed.as_f64().map(|n| n.to_string()),
+            parsed.to_decimal_string()
+        );
+    }
+
+    #[test]
+    fn from_number_str() {
+        let cases = [
+            ("0", Value::from(0)),
+            ("-9223372036854775808", Value::from(i64::MIN)),
+            ("18446744073709551615", Value::from(u64::MAX)),
+            ("-1.5", Value::from(-1.5)),
+            ("1e2", Value::from(100.0)),
+        ];
+
+        for (text, expected) in &cases {
+            assert_eq!(Value::from_number_str(text).unwrap(), *expected, "{}", text);
+        }
+
+        for text in &["", " 1", "1 ", "01", "1.", "+1", "NaN", "18446744073709551616"] {
+            assert!(Value::from_number_str(text).is_err(), "{}", text);
+        }
+
+        assert_eq!(Value::from("1").to_decimal_string(), None);
+    }
+}
@@ -0,0 +1,327 @@
+//! Apply a *[JSON Patch]* to a `Value`.
+//!
+//! This module is only available when the `patch` feature is enabled.
+//!
+//! [JSON Patch]: https://tools.ietf.org/html/rfc6902
+
+use error::Error;
+use value::{parse_index, pointer_tokens, Value};
+
+/// A single operation of a JSON Patch document.
+///
+/// Paths are expressed as a [JSON pointer].
+///
+/// [JSON pointer]: https://tools.ietf.org/html/rfc6901
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase", tag = "op")]
+pub enum PatchOp {
+    /// Adds `value` to an object or inserts it into an array at `path`. A value that
+    /// already exists at `path` in an object is replaced.
+    Add { path: String, value: Value },
+
+    /// Removes the value at `path`.
+    Remove { path: String },
+
+    /// Replaces the value at `path` with `value`.
+    Replace { path: String, value: Value },
+
+    /// Removes the value at `from` and adds it at `path`.
+    Move { from: String, path: String },
+
+    /// Adds a copy of the value at `from` at `path`.
+    Copy { from: String, path: String },
+
+    /// Tests that the value at `path` is equal to `value`.
+    Test { path: String, value: Value },
+}
+
+/// Applies a sequence of operations to `target`.
+///
+/// The operations are applied in order. If any operation fails, an error is returned
+/// and `target` is left unchanged.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// # extern crate serde_json;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::value::patch::{self, PatchOp};
+/// use json_api::Value;
+///
+/// let mut value: Value = serde_json::from_str(r#"{
+///     "title": "Hello, world!",
+///     "tags": ["rust"]
+/// }"#)?;
+///
+/// let ops: Vec<PatchOp> = serde_json::from_str(r#"[
+///     { "op": "test", "path": "/title", "value": "Hello, world!" },
+///     { "op": "replace", "path": "/title", "value": "Hello, JSON API!" },
+///     { "op": "add", "path": "/tags/-", "value": "json-api" }
+/// ]"#)?;
+///
+/// patch::apply(&mut value, &ops)?;
+///
+/// assert_eq!(value.pointer("/title"), Some(&"Hello, JSON API!".into()));
+/// assert_eq!(value.pointer("/tags/1"), Some(&"json-api".into()));
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+pub fn apply(target: &mut Value, ops: &[PatchOp]) -> Result<(), Error> {
+    let mut value = target.clone();
+
+    for op in ops {
+        apply_op(&mut value, op)?;
+    }
+
+    *target = value;
+    Ok(())
+}
+
+fn apply_op(target: &mut Value, op: &PatchOp) -> Result<(), Error> {
+    match *op {
+        PatchOp::Add { ref path, ref value } => add(target, path, value.clone()),
+        PatchOp::Remove { ref path } => remove(target, path).map(|_| ()),
+        PatchOp::Replace { ref path, ref value } => {
+            *lookup_mut(target, path)? = value.clone();
+            Ok(())
+        }
+        PatchOp::Move { ref from, ref path } => {
+            if from == path {
+                return Ok(());
+            }
+
+            if path.starts_with(from.as_str()) && path[from.len()..].starts_with('/') {
+                bail!("cannot move '{}' into one of its children '{}'", from, path);
+            }
+
+            let value = remove(target, from)?;
+            add(target, path, value)
+        }
+        PatchOp::Copy { ref from, ref path } => {
+            let value = lookup_mut(target, from)?.clone();
+            add(target, path, value)
+        }
+        PatchOp::Test { ref path, ref value } => {
+            if *lookup_mut(target, path)? != *value {
+                bail!("test failed for '{}'", path);
+            }
+
+            Ok(())
+        }
+    }
+}
+
+fn add(target: &mut Value, path: &str, value: Value) -> Result<(), Error> {
+    let (parent, token) = match split(path)? {
+        Some(parts) => parts,
+        None => {
+            *target = value;
+            return Ok(());
+        }
+    };
+
+    match *lookup_mut(target, parent)? {
+        Value::Array(ref mut data) => {
+            let index = match &*token {
+                "-" => data.len(),
+                token => index(token, data.len() + 1, path)?,
+            };
+
+            data.insert(index, value);
+        }
+        Value::Object(ref mut data) => {
+            data.insert(token.parse()?, value);
+        }
+        _ => bail!("'{}' does not reference an array or object", parent),
+    }
+
+    Ok(())
+}
+
+fn remove(target: &mut Value, path: &str) -> Result<Value, Error> {
+    let (parent, token) = match split(path)? {
+        Some(parts) => parts,
+        None => bail!("cannot remove the root of a value"),
+    };
+
+    match *lookup_mut(target, parent)? {
+        Value::Array(ref mut data) => {
+            let index = index(&token, data.len(), path)?;
+            Ok(data.remove(index))
+        }
+        Value::Object(ref mut data) => match data.remove(&*token) {
+            Some(value) => Ok(value),
+            None => bail!("'{}' does not reference a value", path),
+        },
+        _ => bail!("'{}' does not reference an array or object", parent),
+    }
+}
+
+fn lookup_mut<'a>(target: &'a mut Value, path: &str) -> Result<&'a mut Value, Error> {
+    match target.pointer_mut(path) {
+        Some(value) => Ok(value),
+        None => bail!("'{}' does not reference a value", path),
+    }
+}
+
+/// Parses an array index that must be less than `bound`.
+fn index(token: &str, bound: usize, path: &str) -> Result<usize, Error> {
+    match parse_index(token) {
+        Some(index) if index < bound => Ok(index),
+        Some(index) => bail!("index {} is out of bounds in '{}'", index, path),
+        None => bail!("'{}' is not an array index in '{}'", token, path),
+    }
+}
+
+/// Splits a pointer into the pointer of the parent and the unescaped last token.
+/// Returns `None` if the pointer references the root of a value.
+fn split(path: &str) -> Result<Option<(&str, String)>, Error> {
+    let token = match pointer_tokens(path) {
+        Some(mut tokens) => tokens.pop(),
+        None => bail!("invalid json pointer '{}'", path),
+    };
+
+    Ok(token.map(|token| (&path[..path.rfind('/').unwrap_or(0)], token)))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::{apply, PatchOp};
+    use value::Value;
+
+    fn value(source: &str) -> Value {
+        serde_json::from_str(source).unwrap()
+    }
+
+    fn patch(target: &str, ops: &str) -> Result<Value, ::error::Error> {
+        let mut target = value(target);
+        let ops = serde_json::from_str::<Vec<PatchOp>>(ops).unwrap();
+
+        apply(&mut target, &ops).map(|_| target)
+    }
+
+    #[test]
+    fn patch_add() {
+        let actual = patch(
+            r#"{"tags":["a","c"]}"#,
+            r#"[
+                {"op":"add","path":"/title","value":"Hello"},
+                {"op":"add","path":"/tags/1","value":"b"},
+                {"op":"add","path":"/tags/-","value":"d"}
+            ]"#,
+        );
+
+        assert_eq!(
+            actual.unwrap(),
+            value(r#"{"tags":["a","b","c","d"],"title":"Hello"}"#)
+        );
+
+        assert!(patch(r#"{"tags":[]}"#, r#"[{"op":"add","path":"/tags/1","value":1}]"#).is_err());
+        assert!(patch("{}", r#"[{"op":"add","path":"/a/b","value":1}]"#).is_err());
+    }
+
+    #[test]
+    fn patch_remove() {
+        let actual = patch(
+            r#"{"title":"Hello","tags":["a","b","c"]}"#,
+            r#"[
+                {"op":"remove","path":"/title"},
+                {"op":"remove","path":"/tags/1"}
+            ]"#,
+        );
+
+        assert_eq!(actual.unwrap(), value(r#"{"tags":["a","c"]}"#));
+        assert!(patch("{}", r#"[{"op":"remove","path":"/title"}]"#).is_err());
+        assert!(patch("{}", r#"[{"op":"remove","path":""}]"#).is_err());
+    }
+
+    #[test]
+    fn patch_replace() {
+        let actual = patch(
+            r#"{"title":"Hello","tags":["a"]}"#,
+            r#"[
+                {"op":"replace","path":"/title","value":"World"},
+                {"op":"replace","path":"/tags/0","value":"b"}
+            ]"#,
+        );
+
+        assert_eq!(actual.unwrap(), value(r#"{"title":"World","tags":["b"]}"#));
+        assert!(patch("{}", r#"[{"op":"replace","path":"/title","value":1}]"#).is_err());
+        assert_eq!(
+            patch("{}", r#"[{"op":"replace","path":"","value":1}]"#).unwrap(),
+            value("1")
+        );
+    }
+
+    #[test]
+    fn patch_move() {
+        let actual = patch(
+            r#"{"author":{"name":"Ferris"},"tags":["a","b"]}"#,
+            r#"[
+                {"op":"move","from":"/author/name","path":"/name"},
+                {"op":"move","from":"/tags/0","path":"/tags/-"}
+            ]"#,
+        );
+
+        assert_eq!(
+            actual.unwrap(),
+            value(r#"{"author":{},"tags":["b","a"],"name":"Ferris"}"#)
+        );
+
+        assert!(patch(
+            r#"{"author":{"name":"Ferris"}}"#,
+            r#"[{"op":"move","from":"/author","path":"/author/name/first"}]"#,
+        ).is_err());
+    }
+
+    #[test]
+    fn patch_copy() {
+        let actual = patch(
+            r#"{"author":{"name":"Ferris"}}"#,
+            r#"[{"op":"copy","from":"/author","path":"/editor"}]"#,
+        );
+
+        assert_eq!(
+            actual.unwrap(),
+            value(r#"{"author":{"name":"Ferris"},"editor":{"name":"Ferris"}}"#)
+        );
+    }
+
+    #[test]
+    fn patch_test() {
+        let source = r#"{"title":"Hello","tags":["a"]}"#;
+        let actual = patch(
+            source,
+            r#"[
+                {"op":"test","path":"/title","value":"Hello"},
+                {"op":"test","path":"/tags","value":["a"]}
+            ]"#,
+        );
+
+        assert_eq!(actual.unwrap(), value(source));
+    }
+
+    #[test]
+    fn patch_test_failed() {
+        let ops = serde_json::from_str::<Vec<PatchOp>>(
+            r#"[
+                {"op":"replace","path":"/title","value":"World"},
+                {"op":"test","path":"/title","value":"Hello"}
+            ]"#,
+        ).unwrap();
+        let mut target = value(r#"{"title":"Hello"}"#);
+
+        assert!(apply(&mut target, &ops).is_err());
+        assert_eq!(target, value(r#"{"title":"Hello"}"#));
+    }
+}
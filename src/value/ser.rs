@@ -0,0 +1,523 @@
+use std::cell::Cell;
+use std::io::Write;
+
+use serde::ser::{self, Serialize, Serializer};
+use serde_json;
+
+use error::Error;
+use value::{Key, Map, Number, Value};
+
+thread_local! {
+    static SORT_KEYS: Cell<bool> = Cell::new(false);
+}
+
+/// Options that control how a value is serialized.
+///
+/// The default value of each option preserves the output of `serde_json`.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// # extern crate serde_json;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::value::{SerializeOptions, Value};
+///
+/// let value: Value = serde_json::from_str(r#"{"title":"Hello","body":"World"}"#)?;
+/// let mut options = SerializeOptions::new();
+///
+/// options.sort_keys = true;
+///
+/// let json = options.to_string(&value)?;
+/// assert_eq!(json, r#"{"body":"World","title":"Hello"}"#);
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SerializeOptions {
+    /// Serialize the entries of every [`Map`] in sorted key order. Defaults to `false`.
+    ///
+    /// The maps themselves are not modified. Arrays and the elements of a [`Set`] are
+    /// serialized in the order that they are stored.
+    ///
+    /// [`Map`]: ./collections/map/struct.Map.html
+    /// [`Set`]: ./collections/set/struct.Set.html
+    pub sort_keys: bool,
+
+    /// Private field for backwards compatibility.
+    _ext: (),
+}
+
+impl SerializeOptions {
+    /// Returns the default serialize options.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns a wrapper around `value` that is serialized with the options.
+    ///
+    /// The wrapper can be passed to any serializer, not just the JSON serializer
+    /// used by the other methods of this type.
+    pub fn wrap<'a, T: Serialize>(&'a self, value: &'a T) -> WithOptions<'a, T> {
+        WithOptions {
+            options: self,
+            value,
+        }
+    }
+
+    /// Serialize `value` as a string of JSON.
+    pub fn to_string<T: Serialize>(&self, value: &T) -> Result<String, Error> {
+        Ok(serde_json::to_string(&self.wrap(value))?)
+    }
+
+    /// Serialize `value` as a pretty-printed string of JSON.
+    pub fn to_string_pretty<T: Serialize>(&self, value: &T) -> Result<String, Error> {
+        Ok(serde_json::to_string_pretty(&self.wrap(value))?)
+    }
+
+    /// Serialize `value` as JSON into the IO stream.
+    pub fn to_writer<W, T>(&self, writer: W, value: &T) -> Result<(), Error>
+    where
+        W: Write,
+        T: Serialize,
+    {
+        serde_json::to_writer(writer, &self.wrap(value))?;
+        Ok(())
+    }
+}
+
+/// A value that is serialized with a set of [`SerializeOptions`].
+///
+/// Returned from [`SerializeOptions::wrap`].
+///
+/// [`SerializeOptions`]: ./struct.SerializeOptions.html
+/// [`SerializeOptions::wrap`]: ./struct.SerializeOptions.html#method.wrap
+#[derive(Clone, Copy, Debug)]
+pub struct WithOptions<'a, T: 'a> {
+    options: &'a SerializeOptions,
+    value: &'a T,
+}
+
+impl<'a, T: Serialize> Serialize for WithOptions<'a, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let _guard = SortKeys::set(self.options.sort_keys);
+        self.value.serialize(serializer)
+    }
+}
+
+/// Returns `true` if the entries of a map should be serialized in sorted key order.
+pub(crate) fn sort_keys() -> bool {
+    SORT_KEYS.with(Cell::get)
+}
+
+/// Restores the previous value of the `sort_keys` flag when dropped.
+struct SortKeys(bool);
+
+impl SortKeys {
+    fn set(value: bool) -> Self {
+        SortKeys(SORT_KEYS.with(|flag| flag.replace(value)))
+    }
+}
+
+impl Drop for SortKeys {
+    fn drop(&mut self) {
+        SORT_KEYS.with(|flag| flag.set(self.0));
+    }
+}
+
+/// A serializer that builds a `Value` directly.
+///
+/// Entries are added to a [`Map`] in the order that they are serialized, so the
+/// declaration order of struct fields and the insertion order of nested maps is
+/// preserved regardless of how `serde_json` is configured.
+///
+/// [`Map`]: ./collections/map/struct.Map.html
+pub(crate) struct ValueSerializer;
+
+impl Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    type SerializeSeq = SerializeVec;
+    type SerializeTuple = SerializeVec;
+    type SerializeTupleStruct = SerializeVec;
+    type SerializeTupleVariant = SerializeTupleVariant;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeMap;
+    type SerializeStructVariant = SerializeStructVariant;
+
+    fn serialize_bool(self, value: bool) -> Result<Value, Error> {
+        Ok(Value::Bool(value))
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<Value, Error> {
+        self.serialize_i64(i64::from(value))
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<Value, Error> {
+        self.serialize_i64(i64::from(value))
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<Value, Error> {
+        self.serialize_i64(i64::from(value))
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<Value, Error> {
+        Ok(Value::Number(value.into()))
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<Value, Error> {
+        self.serialize_u64(u64::from(value))
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<Value, Error> {
+        self.serialize_u64(u64::from(value))
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<Value, Error> {
+        self.serialize_u64(u64::from(value))
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<Value, Error> {
+        Ok(Value::Number(value.into()))
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<Value, Error> {
+        self.serialize_f64(f64::from(value))
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<Value, Error> {
+        // Like `serde_json`, non-finite numbers are represented as null.
+        Ok(Number::from_f64(value).map_or(Value::Null, Value::Number))
+    }
+
+    fn serialize_char(self, value: char) -> Result<Value, Error> {
+        Ok(Value::String(value.to_string()))
+    }
+
+    fn serialize_str(self, value: &str) -> Result<Value, Error> {
+        Ok(Value::String(value.to_owned()))
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<Value, Error> {
+        Ok(value.iter().map(|byte| Value::Number((*byte).into())).collect())
+    }
+
+    fn serialize_none(self) -> Result<Value, Error> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Value, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value, Error> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Value, Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Value, Error> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Value, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, Error> {
+        let mut map = Map::with_capacity(1);
+
+        map.insert(variant.parse()?, value.serialize(self)?);
+        Ok(Value::Object(map))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeVec, Error> {
+        Ok(SerializeVec {
+            vec: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeVec, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, len: usize) -> Result<SerializeVec, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeTupleVariant, Error> {
+        Ok(SerializeTupleVariant {
+            name: variant.parse()?,
+            vec: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<SerializeMap, Error> {
+        Ok(SerializeMap {
+            map: Map::with_capacity(len.unwrap_or(0)),
+            next_key: None,
+        })
+    }
+
+    fn serialize_struct(self, _: &'static str, len: usize) -> Result<SerializeMap, Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeStructVariant, Error> {
+        Ok(SerializeStructVariant {
+            name: variant.parse()?,
+            map: Map::with_capacity(len),
+        })
+    }
+}
+
+pub(crate) struct SerializeVec {
+    vec: Vec<Value>,
+}
+
+impl ser::SerializeSeq for SerializeVec {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.vec.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::Array(self.vec))
+    }
+}
+
+impl ser::SerializeTuple for SerializeVec {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeVec {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+pub(crate) struct SerializeTupleVariant {
+    name: Key,
+    vec: Vec<Value>,
+}
+
+impl ser::SerializeTupleVariant for SerializeTupleVariant {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.vec.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        let mut map = Map::with_capacity(1);
+
+        map.insert(self.name, Value::Array(self.vec));
+        Ok(Value::Object(map))
+    }
+}
+
+pub(crate) struct SerializeMap {
+    map: Map,
+    next_key: Option<Key>,
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Error> {
+        // Map keys are kept verbatim since a nested object is not a member of the
+        // document. Numeric keys are accepted for parity with the JSON serializer.
+        let key = match key.serialize(ValueSerializer)? {
+            Value::String(key) => Key::from_raw(key),
+            Value::Number(key) => Key::from_raw(key.to_string()),
+            _ => bail!("map keys must be a string"),
+        };
+
+        self.next_key = Some(key);
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        match self.next_key.take() {
+            Some(key) => {
+                self.map.insert(key, value.serialize(ValueSerializer)?);
+                Ok(())
+            }
+            None => bail!("serialize_value called before serialize_key"),
+        }
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::Object(self.map))
+    }
+}
+
+impl ser::SerializeStruct for SerializeMap {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.map.insert(key.parse()?, value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+pub(crate) struct SerializeStructVariant {
+    name: Key,
+    map: Map,
+}
+
+impl ser::SerializeStructVariant for SerializeStructVariant {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.map.insert(key.parse()?, value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        let mut map = Map::with_capacity(1);
+
+        map.insert(self.name, Value::Object(self.map));
+        Ok(Value::Object(map))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::{sort_keys, SerializeOptions};
+    use doc::{Document, Object};
+    use value::Value;
+
+    // Only the entries of maps are sorted. The members of a struct are serialized in
+    // the order that they are declared.
+    const SORTED: &str = concat!(
+        r#"{"data":{"attributes":{"body":"World","title":"Hello"},"id":"1","#,
+        r#""type":"articles","meta":{"a":1,"b":[{"x":1,"y":2}]}},"#,
+        r#""jsonapi":{"version":"1.0"}}"#,
+    );
+
+    fn parse(source: &str) -> Document<Object> {
+        serde_json::from_str(source).unwrap()
+    }
+
+    fn sorted() -> SerializeOptions {
+        let mut options = SerializeOptions::new();
+
+        options.sort_keys = true;
+        options
+    }
+
+    #[test]
+    fn sort_keys_document() {
+        let first = parse(
+            r#"{"data":{"type":"articles","id":"1",
+                "attributes":{"title":"Hello","body":"World"},
+                "meta":{"b":[{"y":2,"x":1}],"a":1}}}"#,
+        );
+        let second = parse(
+            r#"{"data":{"type":"articles","id":"1",
+                "attributes":{"body":"World","title":"Hello"},
+                "meta":{"a":1,"b":[{"x":1,"y":2}]}}}"#,
+        );
+
+        assert_eq!(sorted().to_string(&first).unwrap(), SORTED);
+        assert_eq!(sorted().to_string(&second).unwrap(), SORTED);
+        assert!(!sort_keys());
+    }
+
+    #[test]
+    fn sort_keys_value() {
+        let source = r#"{"b":["y","x"],"a":{"d":1,"c":2}}"#;
+        let value: Value = serde_json::from_str(source).unwrap();
+        let json = sorted().to_string(&value).unwrap();
+
+        assert_eq!(json, r#"{"a":{"c":2,"d":1},"b":["y","x"]}"#);
+    }
+
+    #[test]
+    fn sort_keys_default() {
+        let source = r#"{"b":["y","x"],"a":{"d":1,"c":2}}"#;
+        let value: Value = serde_json::from_str(source).unwrap();
+
+        assert_eq!(SerializeOptions::new().to_string(&value).unwrap(), source);
+        assert_eq!(serde_json::to_string(&value).unwrap(), source);
+    }
+}
@@ -0,0 +1,217 @@
+//! Estimates of the serialized length of a type.
+//!
+//! Each estimate is an upper bound of the length of the compact JSON representation
+//! that `serde_json` would produce. Only floating point numbers are not measured
+//! exactly.
+
+use std::hash::Hash;
+use std::ops::Deref;
+
+use value::{Key, Map, Number, Set, Value};
+
+/// The maximum length of a serialized `f64`.
+const MAX_FLOAT_SIZE: usize = 24;
+
+pub trait EstimateSize {
+    fn estimated_size(&self) -> usize;
+}
+
+/// Accumulates the size of a struct that is serialized as a JSON object.
+pub(crate) struct StructSize {
+    len: usize,
+    size: usize,
+}
+
+impl StructSize {
+    pub fn new() -> Self {
+        StructSize { len: 0, size: 2 }
+    }
+
+    pub fn field<T>(&mut self, key: &str, value: &T) -> &mut Self
+    where
+        T: EstimateSize + ?Sized,
+    {
+        self.size += key.estimated_size() + value.estimated_size() + 1;
+        self.len += 1;
+        self
+    }
+
+    pub fn end(&self) -> usize {
+        self.size + self.len.saturating_sub(1)
+    }
+}
+
+pub(crate) fn array<I>(iter: I) -> usize
+where
+    I: IntoIterator,
+    I::Item: EstimateSize,
+{
+    let (len, size) = iter.into_iter().fold((0usize, 0), |(len, size), item| {
+        (len + 1, size + item.estimated_size())
+    });
+
+    size + len.saturating_sub(1) + 2
+}
+
+impl EstimateSize for bool {
+    fn estimated_size(&self) -> usize {
+        if *self {
+            4
+        } else {
+            5
+        }
+    }
+}
+
+impl EstimateSize for str {
+    fn estimated_size(&self) -> usize {
+        self.bytes().fold(2, |size, byte| {
+            size + match byte {
+                b'"' | b'\\' | b'\n' | b'\r' | b'\t' | 0x08 | 0x0C => 2,
+                byte if byte < 0x20 => 6,
+                _ => 1,
+            }
+        })
+    }
+}
+
+impl EstimateSize for String {
+    fn estimated_size(&self) -> usize {
+        self.as_str().estimated_size()
+    }
+}
+
+impl EstimateSize for Key {
+    fn estimated_size(&self) -> usize {
+        self.deref().estimated_size()
+    }
+}
+
+impl EstimateSize for Number {
+    fn estimated_size(&self) -> usize {
+        fn digits(mut value: u64) -> usize {
+            let mut count = 1;
+
+            while value >= 10 {
+                value /= 10;
+                count += 1;
+            }
+
+            count
+        }
+
+        if let Some(value) = self.as_u64() {
+            digits(value)
+        } else if let Some(value) = self.as_i64() {
+            digits(value.wrapping_neg() as u64) + 1
+        } else {
+            MAX_FLOAT_SIZE
+        }
+    }
+}
+
+impl EstimateSize for Value {
+    fn estimated_size(&self) -> usize {
+        match *self {
+            Value::Null => 4,
+            Value::Array(ref data) => array(data),
+            Value::Bool(data) => data.estimated_size(),
+            Value::Number(ref data) => data.estimated_size(),
+            Value::Object(ref data) => data.estimated_size(),
+            Value::String(ref data) => data.estimated_size(),
+        }
+    }
+}
+
+impl<T: EstimateSize> EstimateSize for Option<T> {
+    fn estimated_size(&self) -> usize {
+        match *self {
+            Some(ref value) => value.estimated_size(),
+            None => 4,
+        }
+    }
+}
+
+impl<T: EstimateSize> EstimateSize for Box<T> {
+    fn estimated_size(&self) -> usize {
+        (**self).estimated_size()
+    }
+}
+
+impl<T: EstimateSize> EstimateSize for Vec<T> {
+    fn estimated_size(&self) -> usize {
+        array(self)
+    }
+}
+
+impl<'a, T: EstimateSize + ?Sized> EstimateSize for &'a T {
+    fn estimated_size(&self) -> usize {
+        (**self).estimated_size()
+    }
+}
+
+impl<K, V> EstimateSize for Map<K, V>
+where
+    K: Deref<Target = str> + Eq + Hash,
+    V: EstimateSize,
+{
+    fn estimated_size(&self) -> usize {
+        let mut state = StructSize::new();
+
+        for (key, value) in self {
+            state.field(key, value);
+        }
+
+        state.end()
+    }
+}
+
+impl<T: EstimateSize + Eq + Hash> EstimateSize for Set<T> {
+    fn estimated_size(&self) -> usize {
+        array(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use value::Value;
+
+    const SOURCES: &[&str] = &[
+        "null",
+        "true",
+        "false",
+        "0",
+        "18446744073709551615",
+        "-9223372036854775808",
+        "-1",
+        r#""""#,
+        r#""tab\there \"quoted\" \\ \u0000""#,
+        r#""🦀""#,
+        "[]",
+        "[1,[2,3],{}]",
+        r#"{"a":{"b-c":[null,"d"]},"e":1}"#,
+    ];
+
+    #[test]
+    fn value_estimated_size() {
+        for source in SOURCES {
+            let value = serde_json::from_str::<Value>(source).unwrap();
+            let actual = serde_json::to_string(&value).unwrap().len();
+
+            assert_eq!(value.estimated_size(), actual, "{}", source);
+        }
+    }
+
+    #[test]
+    fn value_estimated_size_float() {
+        for source in &["1.5", "-0.1", "1e300", "[2.2250738585072014e-308]"] {
+            let value = serde_json::from_str::<Value>(source).unwrap();
+            let actual = serde_json::to_string(&value).unwrap().len();
+            let estimate = value.estimated_size();
+
+            assert!(estimate >= actual, "{}: {} < {}", source, estimate, actual);
+        }
+    }
+}
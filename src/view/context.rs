@@ -1,8 +1,13 @@
-use doc::Object;
+use doc::{Identifier, Object};
+use error::Error;
 use query::Query;
-use value::Set;
+use value::{Map, Set};
 use value::fields::{Key, Path, Segment};
 
+/// The notes of deprecated fields that were rendered, keyed by resource type and
+/// member name.
+pub(crate) type Deprecations = Map<(Key, Key), String>;
+
 /// A data structure containing render context that can be "forked" and passed
 /// to a child context.
 ///
@@ -20,10 +25,17 @@ use value::fields::{Key, Path, Segment};
 /// [sparse field-sets]: http://jsonapi.org/format/#fetching-sparse-fieldsets
 #[derive(Debug)]
 pub struct Context<'v> {
+    deprecated: Option<&'v mut Deprecations>,
+    depth: Option<usize>,
     incl: &'v mut Set<Object>,
     kind: Key,
+    limit: Option<usize>,
+    linkage: bool,
+    mark: usize,
     path: Path,
     query: Option<&'v Query>,
+    strict: bool,
+    visited: Vec<(Key, String)>,
 }
 
 impl<'v> Context<'v> {
@@ -57,46 +69,406 @@ impl<'v> Context<'v> {
         Context {
             kind,
             query,
+            deprecated: None,
+            depth: None,
+            limit: None,
+            linkage: true,
+            mark: included.len(),
             incl: included,
             path: Path::new(),
+            strict: false,
+            visited: Vec::new(),
+        }
+    }
+
+    /// Returns the query that the document is being rendered with.
+    pub fn query(&self) -> Option<&'v Query> {
+        self.query
+    }
+
+    /// Returns the include path of the current context. The path of a root context is
+    /// empty.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the maximum depth of an include path that is rendered. Returns `None`
+    /// if there is no limit.
+    pub fn max_depth(&self) -> Option<usize> {
+        self.depth
+    }
+
+    /// Sets the maximum depth of an include path that is rendered. The limit is
+    /// inherited by any child context created with the `fork` method.
+    ///
+    /// Related resources beyond the limit are only rendered as resource identifiers.
+    /// If the context is [strict], [`try_included`] returns an error instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::query::Query;
+    /// use json_api::value::Set;
+    /// use json_api::view::Context;
+    ///
+    /// let query = Query::builder()
+    ///     .include("author")
+    ///     .include("author.comments")
+    ///     .build()?;
+    /// let mut included = Set::new();
+    /// let mut ctx = Context::new("posts".parse()?, Some(&query), &mut included);
+    ///
+    /// ctx.set_max_depth(Some(1));
+    ///
+    /// let mut author = ctx.fork("users".parse()?, &"author".parse()?);
+    /// assert!(author.included());
+    ///
+    /// let comments = author.fork("comments".parse()?, &"comments".parse()?);
+    /// assert!(!comments.included());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [strict]: #method.set_strict
+    /// [`try_included`]: #method.try_included
+    pub fn set_max_depth(&mut self, max: Option<usize>) {
+        self.depth = max;
+    }
+
+    /// Returns `true` if an include path that exceeds the [maximum depth] is an error.
+    ///
+    /// [maximum depth]: #method.set_max_depth
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Sets whether an include path that exceeds the [maximum depth] is an error rather
+    /// than silently truncated. The flag is inherited by any child context created with
+    /// the `fork` method.
+    ///
+    /// [maximum depth]: #method.set_max_depth
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Returns the maximum number of resources that can be included in the document.
+    /// Returns `None` if there is no limit.
+    pub fn max_included(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Sets the maximum number of resources that can be included in the document. The
+    /// limit is inherited by any child context created with the `fork` method.
+    ///
+    /// Once the limit is reached, [`include`] returns `false` and [`included`] returns
+    /// `false` so related resources are only rendered as resource identifiers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::Object;
+    /// use json_api::value::Set;
+    /// use json_api::view::Context;
+    ///
+    /// let mut included = Set::new();
+    /// let mut ctx = Context::new("posts".parse()?, None, &mut included);
+    ///
+    /// ctx.set_max_included(Some(1));
+    ///
+    /// assert!(ctx.include(Object::new("users".parse()?, "1".to_owned())));
+    /// assert!(!ctx.include(Object::new("users".parse()?, "2".to_owned())));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`include`]: #method.include
+    /// [`included`]: #method.included
+    pub fn set_max_included(&mut self, max: Option<usize>) {
+        self.limit = max;
+    }
+
+    /// Returns `true` if resource linkage is rendered for relationships that are not
+    /// included in the document. Defaults to `true`.
+    pub fn emits_linkage(&self) -> bool {
+        self.linkage
+    }
+
+    /// Sets whether resource linkage is rendered for relationships that are not included
+    /// in the document. The flag is inherited by any child context created with the
+    /// `fork` method.
+    ///
+    /// When the flag is `false`, a relationship that is not included is rendered without
+    /// a `data` member. Relationships declared with the [`resource!`] macro do not
+    /// evaluate their `data` block at all in that case, so related resources are never
+    /// fetched or iterated. The block is still evaluated for relationships that are
+    /// included, or when the flag is `true`.
+    ///
+    /// [`resource!`]: ../macro.resource.html
+    pub fn set_emit_linkage(&mut self, emit: bool) {
+        self.linkage = emit;
+    }
+
+    /// Returns `true` if deprecated fields that are rendered with this context are
+    /// recorded so they can be advertised in the `meta` of the document.
+    ///
+    /// Recording is enabled when a document is rendered with the
+    /// [`advertise_deprecations`] option.
+    ///
+    /// [`advertise_deprecations`]: ./struct.Options.html#structfield.advertise_deprecations
+    pub fn advertises_deprecations(&self) -> bool {
+        self.deprecated.is_some()
+    }
+
+    /// Records that the deprecated field `name` of the current resource type was
+    /// rendered along with a `note` that describes the deprecation. Only the first
+    /// note recorded for a field of a given type is kept.
+    ///
+    /// This method has no effect unless [`advertises_deprecations`] returns `true`.
+    ///
+    /// [`advertises_deprecations`]: #method.advertises_deprecations
+    pub fn deprecate(&mut self, name: &Key, note: &str) {
+        if let Some(ref mut deprecated) = self.deprecated {
+            let key = (self.kind.clone(), name.clone());
+
+            if !deprecated.contains_key(&key) {
+                deprecated.insert(key, note.to_owned());
+            }
         }
     }
 
+    /// Enables recording deprecated fields into `deprecated`. The storage is shared
+    /// with every child context created with the `fork` method.
+    pub(crate) fn set_deprecations(&mut self, deprecated: &'v mut Deprecations) {
+        self.deprecated = Some(deprecated);
+    }
+
     /// Returns true if the field name is present in the current context's
     /// field-set or the current context's field-set does not exist.
+    ///
+    /// Without a query, every field is rendered.
     pub fn field(&self, name: &str) -> bool {
-        self.query
-            .and_then(|q| q.fields.get(&self.kind))
-            .map_or(true, |f| f.contains(name))
+        match self.query {
+            Some(query) => query.fieldset(&self.kind).contains(name),
+            None => true,
+        }
     }
 
     /// Creates a new child context from `self`.
     pub fn fork(&mut self, kind: Key, key: &Key) -> Context {
         Context {
             kind,
+            deprecated: match self.deprecated {
+                Some(ref mut deprecated) => Some(&mut **deprecated),
+                None => None,
+            },
+            depth: self.depth,
+            limit: self.limit,
+            linkage: self.linkage,
+            mark: self.incl.len(),
             incl: self.incl,
             path: self.path.join(key),
             query: self.query,
+            strict: self.strict,
+            visited: self.visited.clone(),
         }
     }
 
+    /// Marks the resource with the given `kind` and `id` as being rendered by the
+    /// current context. The resource is visible to every child context created with
+    /// the `fork` method until `leave` is called.
+    ///
+    /// If the resource is already being rendered by a parent context, the relationship
+    /// is a cycle and `false` is returned. In that case, the resource should be
+    /// rendered as a resource identifier and `leave` should not be called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::value::Set;
+    /// use json_api::view::Context;
+    ///
+    /// let mut included = Set::new();
+    /// let mut ctx = Context::new("comments".parse()?, None, &mut included);
+    ///
+    /// assert!(ctx.visit("comments".parse()?, "1".to_owned()));
+    ///
+    /// {
+    ///     let mut replies = ctx.fork("comments".parse()?, &"replies".parse()?);
+    ///
+    ///     assert!(!replies.visit("comments".parse()?, "1".to_owned()));
+    ///     assert!(replies.visit("comments".parse()?, "2".to_owned()));
+    /// }
+    ///
+    /// ctx.leave();
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn visit(&mut self, kind: Key, id: String) -> bool {
+        let visited = self.visited
+            .iter()
+            .any(|&(ref k, ref i)| *k == kind && *i == id);
+
+        if !visited {
+            self.visited.push((kind, id));
+        }
+
+        !visited
+    }
+
+    /// Removes the resource that was most recently marked with `visit`.
+    pub fn leave(&mut self) {
+        self.visited.pop();
+    }
+
     /// Adds the `value` to the context's included resource set.
     ///
     /// If the set did not have this value present, `true` is returned.
     ///
     /// If the set did have this value present, `false` is returned.
+    ///
+    /// If the [maximum number of included resources] has been reached, `value` is not
+    /// added and `false` is returned. Resources that were included while `value` was
+    /// being rendered are removed as well, since they would not be reachable from the
+    /// primary data of the document.
+    ///
+    /// [maximum number of included resources]: #method.set_max_included
     pub fn include(&mut self, value: Object) -> bool {
-        self.incl.insert(value)
+        if self.is_full() && !self.incl.contains(&value) {
+            while self.incl.len() > self.mark {
+                self.incl.pop();
+            }
+        }
+
+        let inserted = !self.is_full() && self.incl.insert(value);
+
+        self.mark = self.incl.len();
+        inserted
+    }
+
+    /// Returns `true` if a resource with the same `type` and `id` as `ident` has
+    /// already been included in the document.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::{Identifier, Object};
+    /// use json_api::value::Set;
+    /// use json_api::view::Context;
+    ///
+    /// let mut included = Set::new();
+    /// let mut ctx = Context::new("posts".parse()?, None, &mut included);
+    /// let ident = Identifier::new("users".parse()?, "1".to_owned());
+    ///
+    /// assert!(!ctx.is_included(&ident));
+    /// assert_eq!(ctx.included_len(), 0);
+    ///
+    /// ctx.include(Object::new("users".parse()?, "1".to_owned()));
+    ///
+    /// assert!(ctx.is_included(&ident));
+    /// assert_eq!(ctx.included_len(), 1);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn is_included(&self, ident: &Identifier) -> bool {
+        self.incl.contains(ident)
+    }
+
+    /// Returns the number of resources that have been included in the document.
+    pub fn included_len(&self) -> usize {
+        self.incl.len()
     }
 
     /// Returns `true` if the context is valid with respect to parent context(s).
     ///
     /// If there is no parent context (i.e the current context represents the primary
-    /// data of the document), this will always return `false`.
+    /// data of the document) or the context does not have a query, this will always
+    /// return `false`.
     ///
     /// if there is a parent context and this function returns `false`, this context can
-    /// should be ignored.
+    /// should be ignored. This function also returns `false` once the [maximum number of
+    /// included resources] has been reached.
+    ///
+    /// [maximum number of included resources]: #method.set_max_included
     pub fn included(&self) -> bool {
-        self.query.map_or(false, |q| q.include.contains(&self.path))
+        self.try_included().unwrap_or(false)
+    }
+
+    /// Returns `true` if the context is valid with respect to parent context(s) and
+    /// the [maximum depth] has not been exceeded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the context is [strict] and the include path of the
+    /// context exceeds the maximum depth.
+    ///
+    /// [maximum depth]: #method.set_max_depth
+    /// [strict]: #method.set_strict
+    pub fn try_included(&self) -> Result<bool, Error> {
+        let requested = match self.query {
+            Some(query) => query.include.contains(&self.path),
+            None => false,
+        };
+
+        match self.depth {
+            Some(max) if requested && self.path.len() > max => {
+                if self.strict {
+                    bail!(
+                        "include path '{}' exceeds the maximum depth of {}",
+                        self.path,
+                        max
+                    );
+                }
+
+                Ok(false)
+            }
+            _ => Ok(requested && !self.is_full()),
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        match self.limit {
+            Some(max) => self.incl.len() >= max,
+            None => false,
+        }
     }
 }
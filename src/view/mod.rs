@@ -9,7 +9,10 @@
 //! [functions exported from the crate root]: ../index.html#functions
 
 mod context;
+mod options;
 mod render;
 
 pub use self::context::Context;
+pub(crate) use self::context::Deprecations;
+pub use self::options::Options;
 pub use self::render::Render;
@@ -0,0 +1,255 @@
+use http::Uri;
+
+use doc::{Document, Link, PrimaryData};
+use error::Error;
+use query::{self, Query};
+use value::Key;
+
+/// Options that control how a document is rendered.
+///
+/// The default value of each option preserves the behavior of [`Render::render`].
+///
+/// [`Render::render`]: ./trait.Render.html#tymethod.render
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Options {
+    /// Advertise the deprecated fields that are rendered in the `deprecations` member
+    /// of the document's top-level `meta`. Defaults to `false`.
+    ///
+    /// Each entry is an object with the `type` and `field` of a deprecated field and a
+    /// `note` that describes the deprecation. A field is only advertised once per
+    /// document, and only if it is present in the field-set of its type. Fields are
+    /// marked as deprecated with the `deprecated` keyword of the [`resource!`] macro.
+    ///
+    /// [`resource!`]: ../macro.resource.html
+    pub advertise_deprecations: bool,
+
+    /// Keep the id of a resource when it is rendered as a [`NewObject`], so that it is
+    /// sent as a *[client-generated id]*. Defaults to `false`.
+    ///
+    /// [`NewObject`]: ../doc/struct.NewObject.html
+    /// [client-generated id]: http://jsonapi.org/format/#crud-creating-client-ids
+    pub client_id: bool,
+
+    /// Emit a top-level `self` link. Defaults to `false`.
+    ///
+    /// A member document prefers the `self` link of its primary data, which is hoisted
+    /// to the top-level of the document along with the rest of the object's links. A
+    /// collection document uses the [`request_uri`] with the query re-serialized in
+    /// canonical form so the link reflects the current page. A `self` link that is
+    /// already present in the document is never replaced.
+    ///
+    /// [`request_uri`]: #structfield.request_uri
+    pub emit_self_link: bool,
+
+    /// The URI of the request that the document is being rendered for. Any query string
+    /// is replaced with the query that the document was rendered with.
+    pub request_uri: Option<Uri>,
+
+    /// Private field for backwards compatibility.
+    _ext: (),
+}
+
+impl Options {
+    /// Returns the default render options.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Applies the options to a document that was rendered with the given query.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::Document;
+    /// use json_api::doc::{Data, Object};
+    /// use json_api::query::Query;
+    /// use json_api::view::Options;
+    ///
+    /// let query = Query::builder().page(2, Some(10)).build()?;
+    /// let mut options = Options::new();
+    /// let mut doc = Document::Ok {
+    ///     data: Data::Collection(Vec::<Object>::new()),
+    ///     included: Default::default(),
+    ///     jsonapi: Default::default(),
+    ///     links: Default::default(),
+    ///     meta: Default::default(),
+    /// };
+    ///
+    /// options.emit_self_link = true;
+    /// options.request_uri = Some("/articles?page[number]=1".parse()?);
+    /// options.apply(&mut doc, Some(&query))?;
+    ///
+    /// let link = doc.self_link().unwrap().reference();
+    /// assert_eq!(link, "/articles?page%5Bnumber%5D=2&page%5Bsize%5D=10");
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn apply<T>(&self, doc: &mut Document<T>, query: Option<&Query>) -> Result<(), Error>
+    where
+        T: PrimaryData,
+    {
+        if !self.emit_self_link || !doc.is_ok() || doc.self_link().is_some() {
+            return Ok(());
+        }
+
+        let link = match self.request_link(query)? {
+            Some(link) => link,
+            None => return Ok(()),
+        };
+
+        doc.links_mut().insert(Key::from_raw("self".to_owned()), link);
+        Ok(())
+    }
+
+    fn request_link(&self, query: Option<&Query>) -> Result<Option<Link>, Error> {
+        let uri = match self.request_uri {
+            Some(ref uri) => uri,
+            None => return Ok(None),
+        };
+
+        let mut href = uri.to_string();
+        let query = match query {
+            Some(value) => query::to_string(value)?,
+            None => match uri.query() {
+                Some(value) => query::to_string(&query::from_str(value)?)?,
+                None => String::new(),
+            },
+        };
+
+        if let Some(index) = href.find('?') {
+            href.truncate(index);
+        }
+
+        if !query.is_empty() {
+            href.push('?');
+            href.push_str(&query);
+        }
+
+        href.parse().map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Options;
+
+    use doc::{Data, Document, Link, Object};
+    use query::Query;
+    use value::{Key, Map};
+
+    fn collection() -> Document<Object> {
+        Document::Ok {
+            data: Data::Collection(Vec::new()),
+            included: Default::default(),
+            jsonapi: Default::default(),
+            links: Default::default(),
+            meta: Default::default(),
+        }
+    }
+
+    fn member(links: Map<Key, Link>) -> Document<Object> {
+        let object = Object::new("articles".parse().unwrap(), "1".to_owned());
+
+        Document::Ok {
+            links,
+            data: Data::Member(Box::new(Some(object))),
+            included: Default::default(),
+            jsonapi: Default::default(),
+            meta: Default::default(),
+        }
+    }
+
+    fn options(request_uri: &str) -> Options {
+        let mut options = Options::new();
+
+        options.emit_self_link = true;
+        options.request_uri = Some(request_uri.parse().unwrap());
+        options
+    }
+
+    #[test]
+    fn self_link_disabled() {
+        let mut options = options("/articles");
+        let mut doc = collection();
+
+        options.emit_self_link = false;
+        options.apply(&mut doc, None).unwrap();
+
+        assert!(doc.self_link().is_none());
+    }
+
+    #[test]
+    fn self_link_collection() {
+        let query = Query::builder()
+            .include("author")
+            .page(3, None)
+            .build()
+            .unwrap();
+        let mut doc = collection();
+
+        options("/articles?page[number]=2")
+            .apply(&mut doc, Some(&query))
+            .unwrap();
+
+        assert_eq!(
+            doc.self_link().unwrap().reference(),
+            "/articles?include=author&page%5Bnumber%5D=3"
+        );
+
+        let mut doc = collection();
+
+        options("/articles?sort=title&include=author")
+            .apply(&mut doc, None)
+            .unwrap();
+
+        assert_eq!(
+            doc.self_link().unwrap().reference(),
+            "/articles?include=author&sort=title"
+        );
+    }
+
+    #[test]
+    fn self_link_member_hoisted() {
+        let mut links = Map::new();
+        let mut doc = {
+            links.insert("self".parse().unwrap(), "/articles/1".parse().unwrap());
+            member(links)
+        };
+
+        options("/articles/1?include=author")
+            .apply(&mut doc, None)
+            .unwrap();
+
+        assert_eq!(doc.self_link().unwrap().reference(), "/articles/1");
+    }
+
+    #[test]
+    fn self_link_member_request() {
+        let mut doc = member(Map::new());
+
+        options("/articles/1").apply(&mut doc, None).unwrap();
+        assert_eq!(doc.self_link().unwrap().reference(), "/articles/1");
+    }
+
+    #[test]
+    fn self_link_precedence() {
+        let mut doc = collection();
+
+        doc.set_self_link("/articles?page%5Bnumber%5D=1".parse().unwrap());
+        options("/articles?page[number]=2")
+            .apply(&mut doc, None)
+            .unwrap();
+
+        assert_eq!(doc.self_link().unwrap().reference(), "/articles?page%5Bnumber%5D=1");
+    }
+}
@@ -1,6 +1,7 @@
 use doc::{Data, Document, PrimaryData};
 use error::Error;
 use query::Query;
+use view::Options;
 
 /// A trait to render a given type as a document.
 ///
@@ -21,6 +22,24 @@ pub trait Render<T: PrimaryData> {
     /// [`Resource`]: ../trait.Resource.html
     /// [`resource!`]: ../macro.resource.html
     fn render(self, query: Option<&Query>) -> Result<Document<T>, Error>;
+
+    /// Attempts to render the given type as a document with the given render options.
+    ///
+    /// The default implementation calls [`render`] and then applies `options` to the
+    /// resulting document. Types that implement the [`Resource`] trait override this
+    /// method to support options that affect how resources are rendered.
+    ///
+    /// [`render`]: #tymethod.render
+    /// [`Resource`]: ../trait.Resource.html
+    fn render_with(self, query: Option<&Query>, options: &Options) -> Result<Document<T>, Error>
+    where
+        Self: Sized,
+    {
+        let mut doc = self.render(query)?;
+
+        options.apply(&mut doc, query)?;
+        Ok(doc)
+    }
 }
 
 impl<D, T> Render<D> for Option<T>
@@ -40,4 +59,16 @@ where
             }),
         }
     }
+
+    fn render_with(self, query: Option<&Query>, options: &Options) -> Result<Document<D>, Error> {
+        match self {
+            Some(value) => value.render_with(query, options),
+            None => {
+                let mut doc = None::<T>.render(query)?;
+
+                options.apply(&mut doc, query)?;
+                Ok(doc)
+            }
+        }
+    }
 }
@@ -0,0 +1,322 @@
+//! Compile-time assertions for the public API of the crate.
+//!
+//! Each function in this file names a public item with its documented signature. If an
+//! item is moved, renamed, or its signature changes, this file will fail to compile.
+
+extern crate json_api;
+extern crate serde_json;
+
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::io;
+use std::str::FromStr;
+
+use json_api::doc::{self, Data, Document, ErrorObject, ErrorSource, IdentRef, Identifier, JsonApi,
+                    Link, LinkBuilder, NewObject, Object, ObjectBuilder, Pointer, PrimaryData,
+                    Relationship, RelationshipBuilder, TypeRegistry, Version};
+use json_api::http::{self, Uri};
+use json_api::query::{self, Builder, Direction, Fieldset, FieldsetIter, Page, Query, Sort};
+use json_api::value::collections::{map, set, Equivalent};
+use json_api::value::fields::{Key, Path, Segment};
+use json_api::value::{self, Map, Number, SerializeOptions, Set, Value};
+use json_api::view::{Context, Options, Render};
+use json_api::{Error, Resource};
+
+type Rendered<T> = Result<Document<T>, Error>;
+type Included = (Object, Set<Object>);
+type Keep = fn(&Key, &mut Value) -> bool;
+type Streamed = fn(io::Sink, Vec<Never>, Option<&'static Query>) -> Result<(), Error>;
+
+#[test]
+fn crate_root() {
+    let _: &str = json_api::MEDIA_TYPE;
+    let _: fn() -> (http::header::HeaderName, http::header::HeaderValue) =
+        json_api::content_type_header;
+    let _: fn(Document<Object>) -> Result<Value, Error> = json_api::from_doc::<Object, Value>;
+    let _: fn(Document<Object>) -> Result<Value, Error> =
+        json_api::from_doc_typed::<Object, Value>;
+    let _: fn(Document<Object>) -> Result<Value, Error> =
+        json_api::from_doc_strict::<Object, Value>;
+    let _: fn(io::Empty) -> Result<Value, Error> = json_api::from_reader::<_, Object, Value>;
+    let _: fn(&[u8]) -> Result<Value, Error> = json_api::from_slice::<Object, Value>;
+    let _: fn(&str) -> Result<Value, Error> = json_api::from_str::<Object, Value>;
+    let _: fn(io::Empty) -> Rendered<Object> = json_api::parse_reader::<_, Object>;
+    let _: fn(&[u8]) -> Rendered<Object> = json_api::parse_slice::<Object>;
+    let _: fn(&str) -> Rendered<Object> = json_api::parse_str::<Object>;
+
+    let _: fn(Object, Option<&Query>) -> Rendered<Object> =
+        json_api::to_doc::<Object, Object>;
+    let _: fn(Object, Option<&Query>, &Options) -> Rendered<Object> =
+        json_api::to_doc_with::<Object, Object>;
+    let _: fn(&[Never], &Query, u64, &Uri) -> Rendered<Object> =
+        json_api::to_doc_paginated::<Never>;
+    let _: fn(&[Never], Option<&Query>) -> Rendered<Identifier> =
+        json_api::to_doc_identifiers::<Never>;
+    let _: fn(&[Never], &Query, u64, &Uri) -> Rendered<Identifier> =
+        json_api::to_doc_identifiers_paginated::<Never>;
+    let _: fn(&Never, Option<&Query>) -> Result<Object, Error> = json_api::to_object::<Never>;
+    let _: fn(&Never, Option<&Query>) -> Result<Included, Error> =
+        json_api::to_object_with_included::<Never>;
+    let _: fn(Object, Option<&Query>) -> Result<String, Error> =
+        json_api::to_string::<Object, Object>;
+    let _: fn(Object, Option<&Query>) -> Result<String, Error> =
+        json_api::to_string_pretty::<Object, Object>;
+    let _: fn(Object, Option<&Query>) -> Result<Vec<u8>, Error> =
+        json_api::to_vec::<Object, Object>;
+    let _: fn(Object, Option<&Query>) -> Result<Vec<u8>, Error> =
+        json_api::to_vec_pretty::<Object, Object>;
+    let _: fn(io::Sink, Object, Option<&Query>) -> Result<(), Error> =
+        json_api::to_writer::<_, Object, Object>;
+    let _: fn(io::Sink, Object, Option<&Query>) -> Result<(), Error> =
+        json_api::to_writer_pretty::<_, Object, Object>;
+    let _: Streamed = json_api::to_writer_streamed::<_, Vec<Never>>;
+    let _: Streamed = json_api::to_writer_streamed_pretty::<_, Vec<Never>>;
+
+    let _: fn(Value) -> Result<String, Error> = json_api::from_value::<String>;
+    let _: fn(String) -> Result<Value, Error> = json_api::to_value::<String>;
+    let _: fn(&'static str) -> Result<Value, Error> = json_api::to_value::<&str>;
+
+    fn resource<T: Resource>(value: &T, ctx: &mut Context) -> Result<(), Error> {
+        let _: Key = T::kind();
+        let _: Key = value.instance_kind();
+        let _: String = value.id();
+        let _: Identifier = value.to_ident(ctx)?;
+        let _: Object = value.to_object(ctx)?;
+        let _: NewObject = value.to_new_object(ctx)?;
+        let _: Map<Key, Link> = value.doc_links()?;
+        let _: Map = value.doc_meta()?;
+        let _: Map<Key, String> = T::deprecations()?;
+
+        Ok(())
+    }
+
+    let _ = resource::<Never>;
+}
+
+#[test]
+fn doc() {
+    fn primary_data<T: PrimaryData>() {}
+
+    primary_data::<Identifier>();
+    primary_data::<NewObject>();
+    primary_data::<Object>();
+
+    let _: fn(Document<Identifier>) -> Result<Value, Error> = doc::from_doc::<Identifier, Value>;
+    let _: fn(&Document<Object>) -> bool = Document::is_ok;
+    let _: fn(&Document<Object>) -> bool = Document::is_err;
+    let _: fn(&Document<Object>) -> bool = Document::is_meta;
+    let _: fn(Map) -> Document<Object> = Document::meta_only;
+    let _: fn(&Document<Object>) -> Result<Value, Error> = Document::decode::<Value>;
+    let _: fn(&Document<Object>) -> &Map<Key, Link> = Document::links;
+    let _: fn(&mut Document<Object>) -> &mut Map<Key, Link> = Document::links_mut;
+    let _: fn(&Document<Object>) -> &Map = Document::meta;
+    let _: fn(&mut Document<Object>) -> &mut Map = Document::meta_mut;
+    let _: fn(&Document<Object>) -> Option<&Link> = Document::self_link;
+    let _: fn(&mut Document<Object>, Link) -> Option<Link> = Document::set_self_link;
+
+    let _: fn(Option<Object>) -> Data<Object> = Data::from;
+    let _: fn(Vec<Object>) -> Data<Object> = Data::from;
+    let _: fn(Object) -> Data<Object> = Data::from;
+    let _: fn(Data<Object>) -> Result<Option<Object>, Error> = Data::expect_member;
+    let _: fn(Data<Object>) -> Result<Vec<Object>, Error> = Data::expect_collection;
+    let _: fn(&Data<Object>) -> &[Object] = Data::as_slice;
+    let _: fn(&Data<Object>) -> usize = Data::len;
+    let _: fn(&Data<Object>) -> bool = Data::is_empty;
+    let _ = Data::<Object>::map::<Identifier, fn(Object) -> Identifier>;
+    let _: fn(&[u8]) -> Result<Option<Identifier>, Error> = doc::parse_linkage_member;
+    let _: fn(&Document<Object>) -> Vec<doc::Violation> = doc::validate;
+    let _: fn(&[u8]) -> Result<Vec<Identifier>, Error> = doc::parse_linkage_collection;
+
+    let _: fn(Option<_>) -> ErrorObject = ErrorObject::new;
+    let _: fn(Option<String>, Option<Pointer>) -> ErrorSource = ErrorSource::from_parts;
+    let _: fn(Pointer) -> ErrorSource = ErrorSource::from;
+
+    let _: fn() -> Pointer = Pointer::root;
+    let _: fn() -> Pointer = Pointer::data;
+    let _: fn(&str) -> Pointer = Pointer::attribute;
+    let _: fn(&str) -> Pointer = Pointer::relationship;
+    let _: fn(&str, usize) -> Pointer = Pointer::relationship_data;
+    let _: for<'a> fn(&'a mut Pointer, &str) -> &'a mut Pointer = Pointer::push;
+    let _: fn(&mut Pointer, usize) -> &mut Pointer = Pointer::push_index;
+    let _: fn(&str) -> Result<Pointer, Error> = Pointer::from_str;
+    let _: fn(String) -> Pointer = Pointer::from;
+
+    let _: fn(Key, String) -> Identifier = Identifier::new;
+    let _: fn(&'static Object) -> Identifier = Identifier::from;
+
+    let _: fn(&'static Key, &'static str) -> IdentRef<'static> = IdentRef::new;
+    let _: fn(&'static Identifier) -> IdentRef<'static> = IdentRef::from;
+    let _: fn(&'static Set<Object>, &IdentRef<'static>) -> Option<&'static Object> =
+        Set::get::<IdentRef<'static>>;
+    let _: fn(&'static Set<Identifier>, &IdentRef<'static>) -> Option<&'static Identifier> =
+        Set::get::<IdentRef<'static>>;
+
+    let _: fn(Version) -> JsonApi = JsonApi::new;
+    let _: fn(&str) -> Result<Version, Error> = Version::from_str;
+
+    let _: fn(String) -> LinkBuilder = Link::builder::<String>;
+    let _: fn(&mut LinkBuilder) -> Result<Link, Error> = LinkBuilder::build;
+    let _: fn(&str) -> Result<Link, Error> = Link::from_str;
+    let _: fn(&str) -> Result<Link, Error> = Link::parse_relaxed;
+    let _: fn(&Link) -> Cow<str> = Link::reference;
+    let _: fn(&Link) -> bool = Link::is_template;
+
+    let _: fn(Key) -> NewObject = NewObject::new;
+    let _: fn(Key, String) -> Object = Object::new;
+    let _: fn(Key, String) -> ObjectBuilder = Object::builder;
+    let _: fn(&mut ObjectBuilder) -> Result<Object, Error> = ObjectBuilder::build;
+    let _: fn(&Object) -> Option<&Link> = Object::self_link;
+    let _: fn(&mut Object, Link) -> Option<Link> = Object::set_self_link;
+    let _: fn(&mut Object) = Object::strip_private;
+
+    let _: fn(&Object, &TypeRegistry) -> Result<(), Error> = Object::validate_linkage;
+    let _: fn(&NewObject, &TypeRegistry) -> Result<(), Error> = NewObject::validate_linkage;
+
+    let _: fn() -> TypeRegistry = TypeRegistry::new;
+    let _: fn(&mut TypeRegistry) -> &mut TypeRegistry = TypeRegistry::register::<Never>;
+    let _: fn(&mut TypeRegistry, Key) -> bool = TypeRegistry::insert;
+    let _: fn(&TypeRegistry, &str) -> bool = TypeRegistry::contains;
+
+    let _: fn(Data<Identifier>) -> Relationship = Relationship::new;
+    let _: fn() -> RelationshipBuilder = Relationship::builder;
+    let _: fn(&Never) -> Result<Relationship, Error> = Relationship::of::<Never>;
+    let _: fn(&[Never]) -> Result<Relationship, Error> = Relationship::of_many::<Never>;
+    let _: fn(&mut RelationshipBuilder) -> Result<Relationship, Error> = RelationshipBuilder::build;
+    let _: fn(Data<Identifier>, &str, &str) -> Result<Relationship, Error> =
+        Relationship::with_links;
+    let _: fn(&Relationship) -> Option<&Link> = Relationship::related_link;
+    let _: fn(&Relationship) -> Option<&Link> = Relationship::self_link;
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecated() {
+    let _: fn(Option<String>, Option<String>) -> ErrorSource = ErrorSource::new;
+}
+
+#[test]
+fn query() {
+    let _: fn() -> Query = Query::new;
+    let _: fn() -> Builder = Query::builder;
+    let _: fn(&mut Builder) -> Result<Query, Error> = Builder::build;
+    let _: fn(&mut Builder, u64, Option<u64>) -> &mut Builder = Builder::page;
+    let _: fn(&mut Builder, Key, Set) -> &mut Builder = Builder::fields_set;
+    let _: for<'a> fn(&'a Query, &Key) -> Fieldset<'a> = Query::fieldset;
+    let _: fn(&Query, &Query) -> bool = Query::semantic_eq;
+    let _: fn(&Fieldset<'static>, &str) -> bool = Fieldset::contains;
+    let _: fn(&Fieldset<'static>) -> bool = Fieldset::is_restricted;
+    let _: fn(&Fieldset<'static>) -> FieldsetIter<'static> = Fieldset::iter;
+
+    let _: fn(&[u8]) -> Result<Query, Error> = query::from_slice;
+    let _: fn(&str) -> Result<Query, Error> = query::from_str;
+    let _: fn(&Query) -> Result<String, Error> = query::to_string;
+    let _: fn(&Query) -> Result<Vec<u8>, Error> = query::to_vec;
+
+    let _: fn(u64, Option<u64>) -> Page = Page::new;
+    let _: fn(u64, Option<u64>) -> Page = Page::with_offset;
+    let _: fn(Path, Direction) -> Sort = Sort::new;
+    let _: fn(&Sort) -> Sort = Sort::reverse;
+    let _: fn(&Direction) -> bool = Direction::is_asc;
+    let _: fn(&Direction) -> bool = Direction::is_desc;
+}
+
+#[test]
+fn value() {
+    fn equivalent<Q: ?Sized + Equivalent<Key>>() {}
+    fn segment<T: Segment<Key>>() {}
+
+    equivalent::<str>();
+    segment::<Key>();
+    segment::<Path>();
+
+    let _: fn(Value) -> Result<String, Error> = value::from_value::<String>;
+    let _: fn(String) -> Result<Value, Error> = value::to_value::<String>;
+    let _: fn(serde_json::Value) -> Result<Value, Error> = Value::try_from;
+    let _: fn(Value) -> serde_json::Value = serde_json::Value::from;
+    let _: fn(&Value) -> Option<String> = Value::to_decimal_string;
+    let _: fn(&str) -> Result<Value, Error> = Value::from_number_str;
+
+    let _: fn(&str) -> Result<Key, Error> = Key::from_str;
+    let _: fn(String) -> Result<Key, Error> = Key::try_from;
+    let _: fn(&'static str) -> Result<Path, Error> = Path::try_from;
+    let _: fn(String) -> Key = Key::from_raw;
+    let _: fn(&str) -> Result<Path, Error> = Path::from_str;
+    let _: fn() -> Path = Path::new;
+    let _: fn(&[&str]) -> Result<Path, Error> = Path::from_segments;
+    let _: fn(&mut Path, &Path) = Path::push_path;
+    let _: fn(&mut Path, Path) = Path::append;
+
+    let _: fn() -> Map = Map::new;
+    let _: fn(usize) -> Map = Map::with_capacity;
+    let _: fn(&Map, &str) -> Option<usize> = Map::index_of::<str>;
+    let _: fn(&mut Map) = Map::shrink_to_fit;
+    let _: fn(&mut Map, Keep) = Map::retain::<Keep>;
+    let _: fn() -> Set = Set::new;
+    let _: fn(usize) -> Set = Set::with_capacity;
+    let _: fn(&mut Set) = Set::shrink_to_fit;
+
+    let _: Option<map::Iter<Key, Value>> = None;
+    let _: Option<set::Iter<Key>> = None;
+    let _: Option<Number> = None;
+
+    let _: fn() -> SerializeOptions = SerializeOptions::new;
+    let _: fn(&SerializeOptions, &Value) -> Result<String, Error> =
+        SerializeOptions::to_string::<Value>;
+    let _: fn(&SerializeOptions, &Value) -> Result<String, Error> =
+        SerializeOptions::to_string_pretty::<Value>;
+}
+
+#[test]
+fn view() {
+    let _: fn(Key, Option<&'static Query>, &'static mut Set<Object>) -> Context<'static> =
+        Context::new;
+    let _: fn(&Context<'static>, &str) -> bool = Context::field;
+    let _: fn(&Context<'static>) -> Option<&'static Query> = Context::query;
+    let _: for<'a> fn(&'a Context<'static>) -> &'a Path = Context::path;
+    let _: fn(&Context<'static>) -> Result<bool, Error> = Context::try_included;
+    let _: fn(&mut Context<'static>, Option<usize>) = Context::set_max_depth;
+    let _: fn(&mut Context<'static>, bool) = Context::set_strict;
+    let _: fn(&Context<'static>) -> bool = Context::emits_linkage;
+    let _: fn(&mut Context<'static>, bool) = Context::set_emit_linkage;
+    let _: fn(&mut Context<'static>, Key, String) -> bool = Context::visit;
+    let _: fn(&mut Context<'static>) = Context::leave;
+    let _: fn(&Context<'static>, &Identifier) -> bool = Context::is_included;
+    let _: fn(&Context<'static>) -> usize = Context::included_len;
+    let _: fn(&Context<'static>) -> bool = Context::advertises_deprecations;
+    let _: fn(&mut Context<'static>, &Key, &str) = Context::deprecate;
+    let _: fn() -> Options = Options::new;
+
+    fn apply(options: &Options, doc: &mut Document<Object>) -> Result<(), Error> {
+        options.apply(doc, None)
+    }
+
+    let _ = apply;
+
+    let _: fn(Object, Option<&Query>) -> Rendered<Object> =
+        <Object as Render<Object>>::render;
+    let _: fn(Object, Option<&Query>, &Options) -> Rendered<Object> =
+        <Object as Render<Object>>::render_with;
+    let _: fn(Vec<Object>, Option<&Query>) -> Rendered<Identifier> =
+        <Vec<Object> as Render<Identifier>>::render;
+}
+
+/// A type that is never constructed. Used to name the methods of `Resource`.
+enum Never {}
+
+impl Resource for Never {
+    fn kind() -> Key {
+        Key::from_str("never").unwrap()
+    }
+
+    fn id(&self) -> String {
+        match *self {}
+    }
+
+    fn to_ident(&self, _: &mut Context) -> Result<Identifier, Error> {
+        match *self {}
+    }
+
+    fn to_object(&self, _: &mut Context) -> Result<Object, Error> {
+        match *self {}
+    }
+}
@@ -0,0 +1,167 @@
+#![cfg(feature = "atomic")]
+
+extern crate json_api;
+extern crate serde;
+#[macro_use]
+extern crate serde_json;
+
+use json_api::doc::atomic::{OperationCode, OperationData, OperationsDocument,
+                            OperationsResult, Ref};
+use json_api::doc::{Data, Identifier, NewObject};
+use serde::Serialize;
+use serde_json::Value;
+
+const OPERATIONS: &str = include_str!("fixtures/atomic-operations.json");
+const RESULTS: &str = include_str!("fixtures/atomic-results.json");
+
+/// Serializes `doc` and removes the `jsonapi` member that is always emitted.
+fn to_value<T: Serialize>(doc: &T) -> Value {
+    let mut value = serde_json::to_value(doc).unwrap();
+
+    value.as_object_mut().unwrap().remove("jsonapi");
+    value
+}
+
+#[test]
+fn operations_round_trip() {
+    let doc: OperationsDocument = serde_json::from_str(OPERATIONS).unwrap();
+    let ops = &doc.operations;
+    let codes = ops.iter().map(|op| op.op).collect::<Vec<_>>();
+
+    assert_eq!(
+        codes,
+        [
+            OperationCode::Add,
+            OperationCode::Update,
+            OperationCode::Remove,
+            OperationCode::Update,
+            OperationCode::Update,
+            OperationCode::Add,
+            OperationCode::Update,
+            OperationCode::Remove,
+        ]
+    );
+
+    match ops[0].data {
+        Some(OperationData::NewObject(ref obj)) => assert_eq!(obj.kind, "articles"),
+        ref data => panic!("unexpected data {:?}", data),
+    }
+
+    match ops[1].data {
+        Some(OperationData::Object(ref obj)) => assert_eq!(obj.id, "13"),
+        ref data => panic!("unexpected data {:?}", data),
+    }
+
+    assert_eq!(
+        ops[2].target,
+        Some(Ref::new("articles".parse().unwrap(), "13".to_owned()))
+    );
+    assert_eq!(
+        ops[3].data,
+        Some(Identifier::new("people".parse().unwrap(), "9".to_owned()).into())
+    );
+    assert_eq!(ops[4].data, Some(Data::Member(Box::new(None)).into()));
+
+    for op in &ops[3..] {
+        assert!(op.is_relationship());
+    }
+
+    let value = to_value(&doc);
+
+    assert_eq!(value, serde_json::from_str::<Value>(OPERATIONS).unwrap());
+    assert_eq!(serde_json::from_value::<OperationsDocument>(value).unwrap(), doc);
+}
+
+#[test]
+fn results_round_trip() {
+    let doc: OperationsResult = serde_json::from_str(RESULTS).unwrap();
+
+    assert_eq!(doc.results.len(), 3);
+    assert!(doc.results[1].data.is_none());
+    assert!(doc.results[2].data.is_none());
+
+    match doc.results[0].data {
+        Some(Data::Member(ref data)) => {
+            let obj = data.as_ref().as_ref().unwrap();
+
+            assert_eq!(obj.id, "13");
+            assert_eq!(obj.links.get("self").unwrap().reference(), "http://example.com/blogPosts/13");
+        }
+        ref data => panic!("unexpected data {:?}", data),
+    }
+
+    let value = to_value(&doc);
+
+    assert_eq!(value, serde_json::from_str::<Value>(RESULTS).unwrap());
+    assert_eq!(serde_json::from_value::<OperationsResult>(value).unwrap(), doc);
+}
+
+#[test]
+fn operation_data_from() {
+    let ident = Identifier::new("tags".parse().unwrap(), "2".to_owned());
+
+    assert_eq!(
+        OperationData::from(vec![ident.clone()]),
+        OperationData::Linkage(Data::Collection(vec![ident.clone()]))
+    );
+    assert_eq!(
+        OperationData::from(ident.clone()),
+        OperationData::Linkage(Data::Member(Box::new(Some(ident))))
+    );
+    assert_eq!(
+        OperationData::from(NewObject::new("tags".parse().unwrap())),
+        OperationData::NewObject(NewObject::new("tags".parse().unwrap()))
+    );
+}
+
+#[test]
+fn add_and_relate_with_lid() {
+    // The "add and relate" example of the atomic operations extension.
+    let source = json!({
+        "atomic:operations": [{
+            "op": "add",
+            "data": {
+                "type": "authors",
+                "lid": "a",
+                "attributes": { "name": "dgeb" }
+            }
+        }, {
+            "op": "add",
+            "data": {
+                "type": "articles",
+                "attributes": { "title": "JSON API paints my bikeshed!" },
+                "relationships": {
+                    "author": {
+                        "data": { "type": "authors", "lid": "a" }
+                    }
+                }
+            }
+        }]
+    });
+    let doc: OperationsDocument = serde_json::from_value(source.clone()).unwrap();
+    let ops = &doc.operations;
+
+    match ops[0].data {
+        Some(OperationData::NewObject(ref obj)) => {
+            assert_eq!(obj.id, None);
+            assert_eq!(obj.lid, Some("a".to_owned()));
+        }
+        ref data => panic!("unexpected data {:?}", data),
+    }
+
+    match ops[1].data {
+        Some(OperationData::NewObject(ref obj)) => {
+            let author = obj.relationships.get("author").unwrap();
+            let expected = Identifier::local("authors".parse().unwrap(), "a".to_owned());
+
+            assert_eq!(author.data, Some(Data::Member(Box::new(Some(expected.clone())))));
+            assert_eq!(
+                Ref::from(expected),
+                serde_json::from_value(json!({ "type": "authors", "lid": "a" })).unwrap()
+            );
+        }
+        ref data => panic!("unexpected data {:?}", data),
+    }
+
+    assert_eq!(to_value(&doc), source);
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "codegen")]
+
+#[macro_use]
+extern crate json_api;
+
+use json_api::Resource;
+use json_api::schema::{self, TsOptions};
+
+struct Post {
+    id: u64,
+    title: String,
+    body: Option<String>,
+    published_at: Option<String>,
+    author: Option<User>,
+    comments: Vec<Post>,
+}
+
+resource!(Post, |&self| {
+    kind "blog-posts";
+    id self.id;
+
+    attr title: "string";
+    attr_some body: "string";
+    attr "published-at" => self.published_at;
+    attr "word-count", { self.title.split_whitespace().count() }
+
+    has_one author;
+    has_many comments;
+
+    meta "draft", { self.published_at.is_none() }
+});
+
+struct User {
+    id: u64,
+    name: String,
+    tags: Vec<String>,
+}
+
+resource!(User, |&self| {
+    kind "users";
+    id self.id;
+
+    attrs tags;
+    attr name: "string";
+});
+
+#[test]
+fn schema() {
+    let schema = Post::schema().unwrap();
+    let attrs = schema
+        .attributes
+        .iter()
+        .map(|attr| (&*attr.name, attr.hint.as_ref().map(String::as_str), attr.optional))
+        .collect::<Vec<_>>();
+    let rels = schema
+        .relationships
+        .iter()
+        .map(|rel| (&*rel.name, rel.to_many))
+        .collect::<Vec<_>>();
+
+    assert_eq!(schema.kind, "blog-posts");
+    assert_eq!(
+        attrs,
+        [
+            ("title", Some("string"), false),
+            ("body", Some("string"), true),
+            ("published-at", None, false),
+            ("word-count", None, false),
+        ]
+    );
+    assert_eq!(rels, [("author", false), ("comments", true)]);
+}
+
+#[test]
+fn typescript() {
+    let schemas = [User::schema().unwrap(), Post::schema().unwrap()];
+    let output = schema::to_typescript(&schemas, &TsOptions::new());
+
+    assert_eq!(output, include_str!("fixtures/blog.d.ts"));
+}
+
+#[test]
+fn typescript_is_sorted() {
+    let opts = TsOptions::new();
+    let forward = [Post::schema().unwrap(), User::schema().unwrap()];
+    let reverse = [User::schema().unwrap(), Post::schema().unwrap()];
+
+    assert_eq!(schema::to_typescript(&forward, &opts), schema::to_typescript(&reverse, &opts));
+}
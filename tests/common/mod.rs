@@ -0,0 +1,350 @@
+//! Resources shared by the integration tests that render documents.
+
+#![allow(dead_code)]
+
+use std::cell::Cell;
+
+use json_api::doc::{Data, Identifier, Object};
+use json_api::value::{Key, Set};
+use json_api::view::Context;
+use json_api::{Error, Resource};
+
+pub struct Article {
+    pub id: u64,
+    pub comments: Vec<Comment>,
+}
+
+resource!(Article, |&self| {
+    kind "articles";
+    id self.id;
+
+    has_many comments;
+
+    doc_meta "primary", "articles";
+});
+
+pub struct Comment {
+    pub id: u64,
+    pub author: Option<User>,
+}
+
+resource!(Comment, |&self| {
+    kind "comments";
+    id self.id;
+
+    has_one author;
+
+    doc_meta "primary", "comments";
+});
+
+pub struct User {
+    pub id: u64,
+}
+
+resource!(User, |&self| {
+    kind "users";
+    id self.id;
+});
+
+pub struct Node {
+    pub id: u64,
+    pub children: Vec<Node>,
+}
+
+resource!(Node, |&self| {
+    kind "nodes";
+    id self.id;
+
+    has_many children;
+});
+
+pub struct Feed {
+    pub id: u64,
+    pub items: Vec<User>,
+    pub reads: Cell<usize>,
+}
+
+resource!(Feed, |&self| {
+    kind "feeds";
+    id self.id;
+
+    has_many "items", {
+        data self.items.iter().inspect(|_| self.reads.set(self.reads.get() + 1));
+    }
+});
+
+pub struct Playlist {
+    pub id: u64,
+    pub songs: Vec<User>,
+    pub reads: Cell<usize>,
+}
+
+resource!(Playlist, |&self| {
+    kind "playlists";
+    id self.id;
+
+    has_many "songs", {
+        data self.songs.iter().inspect(|_| self.reads.set(self.reads.get() + 1));
+        count;
+    }
+});
+
+pub struct Post {
+    pub id: u64,
+    pub title: String,
+    pub body: String,
+}
+
+resource!(Post, |&self| {
+    kind "posts";
+    id self.id;
+
+    attrs title, body;
+});
+
+pub struct Summary {
+    pub id: u64,
+    pub computed: Cell<usize>,
+}
+
+resource!(Summary, |&self| {
+    kind "summaries";
+    id self.id;
+
+    attr "preview", |ctx| {
+        self.computed.set(self.computed.get() + 1);
+        ctx.query().map_or(0, |query| query.include.len())
+    }
+
+    attr "title", { "Summary" }
+});
+
+pub enum Activity {
+    Post(Post),
+    User(User),
+}
+
+impl Resource for Activity {
+    fn kind() -> Key {
+        "activities".parse().unwrap()
+    }
+
+    fn instance_kind(&self) -> Key {
+        match *self {
+            Activity::Post(ref post) => post.instance_kind(),
+            Activity::User(ref user) => user.instance_kind(),
+        }
+    }
+
+    fn id(&self) -> String {
+        match *self {
+            Activity::Post(ref post) => post.id(),
+            Activity::User(ref user) => user.id(),
+        }
+    }
+
+    fn to_ident(&self, ctx: &mut Context) -> Result<Identifier, Error> {
+        match *self {
+            Activity::Post(ref post) => post.to_ident(ctx),
+            Activity::User(ref user) => user.to_ident(ctx),
+        }
+    }
+
+    fn to_object(&self, ctx: &mut Context) -> Result<Object, Error> {
+        match *self {
+            Activity::Post(ref post) => post.to_object(ctx),
+            Activity::User(ref user) => user.to_object(ctx),
+        }
+    }
+}
+
+pub struct Timeline {
+    pub id: u64,
+    pub activity: Vec<Activity>,
+}
+
+resource!(Timeline, |&self| {
+    kind "timelines";
+    id self.id;
+
+    has_many_poly activity;
+});
+
+pub enum Animal {
+    Cat(u64),
+    Dog(u64),
+}
+
+resource!(Animal, |&self| {
+    kind "animals";
+
+    kind |&self| {
+        match *self {
+            Animal::Cat(_) => "cats",
+            Animal::Dog(_) => "dogs",
+        }
+    }
+
+    id match *self {
+        Animal::Cat(id) | Animal::Dog(id) => id,
+    };
+
+    attr "sound", match *self {
+        Animal::Cat(_) => "meow",
+        Animal::Dog(_) => "woof",
+    };
+});
+
+pub struct Shelter {
+    pub id: u64,
+    pub animals: Vec<Animal>,
+}
+
+resource!(Shelter, |&self| {
+    kind "shelters";
+    id self.id;
+
+    has_many animals;
+});
+
+pub struct Team {
+    pub id: u64,
+    pub members: Option<Vec<User>>,
+}
+
+resource!(Team, |&self| {
+    kind "teams";
+    id self.id;
+
+    has_many "members", {
+        data_some self.members.as_ref().map(|members| members.iter());
+        meta "loaded", self.members.is_some();
+    }
+
+    has_one "owner", {
+        data {
+            let members = self.members.as_ref().ok_or("members are not loaded")?;
+            members.first()
+        }
+    }
+});
+
+pub struct Author {
+    pub id: u64,
+    pub counted: Cell<usize>,
+    pub stamped: Cell<usize>,
+}
+
+resource!(Author, |&self| {
+    kind "authors";
+    id self.id;
+
+    meta "stamp", {
+        self.stamped.set(self.stamped.get() + 1);
+        "v1"
+    }
+
+    object_meta "post-count", |ctx| {
+        self.counted.set(self.counted.get() + 1);
+        ctx.path().len()
+    }
+});
+
+pub struct Byline {
+    pub id: u64,
+    pub authors: Vec<Author>,
+}
+
+resource!(Byline, |&self| {
+    kind "bylines";
+    id self.id;
+
+    has_many authors;
+});
+
+pub struct Reviewer {
+    pub id: u64,
+}
+
+resource!(Reviewer, |&self| {
+    kind "reviewers";
+    id self.id;
+
+    meta "active", true;
+    object_meta "review-count", { self.id * 10 }
+    ident_meta "weight", |ctx| { ctx.path().len() }
+});
+
+pub struct Review {
+    pub id: u64,
+    pub reviewers: Vec<Reviewer>,
+}
+
+resource!(Review, |&self| {
+    kind "reviews";
+    id self.id;
+
+    has_many reviewers;
+});
+
+pub struct Member {
+    pub id: u64,
+    pub handle: String,
+    pub nickname: String,
+}
+
+resource!(Member, |&self| {
+    kind "members";
+    id self.id;
+
+    attrs handle;
+    attr nickname, deprecated "use handle instead";
+});
+
+pub struct Report {
+    pub id: u64,
+}
+
+fn summarize(_: &Report) -> String {
+    panic!("attributes of an identifier document must not be computed");
+}
+
+resource!(Report, |&self| {
+    kind "reports";
+    id self.id;
+
+    attr "summary", { summarize(self) };
+
+    doc_meta "primary", "reports";
+});
+
+pub fn article() -> Article {
+    Article {
+        id: 1,
+        comments: vec![
+            Comment {
+                id: 1,
+                author: Some(User { id: 1 }),
+            },
+            Comment {
+                id: 2,
+                author: Some(User { id: 2 }),
+            },
+        ],
+    }
+}
+
+pub fn idents(included: &Set<Object>) -> Vec<String> {
+    included
+        .iter()
+        .map(|object| format!("{}:{}", object.kind, object.id))
+        .collect()
+}
+
+pub fn linkage(object: &Object, key: &str) -> Vec<Identifier> {
+    match object.relationships.get(key).and_then(|rel| rel.data.as_ref()) {
+        Some(Data::Collection(data)) => data.clone(),
+        Some(Data::Member(data)) => data.iter().cloned().collect(),
+        None => Vec::new(),
+    }
+}
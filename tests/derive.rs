@@ -0,0 +1,134 @@
+#![cfg(feature = "derive")]
+
+extern crate json_api;
+
+use json_api::doc::{Data, Document, Object};
+use json_api::query::{self, Query};
+use json_api::value::Set;
+use json_api::view::Context;
+use json_api::Resource;
+
+#[derive(Resource)]
+#[json_api(kind = "articles")]
+struct Article {
+    #[json_api(id)]
+    id: u64,
+    #[json_api(attr)]
+    title: String,
+    #[json_api(attr, rename = "publishedAt")]
+    published_at: Option<String>,
+    #[json_api(has_one)]
+    author: Option<User>,
+    #[json_api(has_many, rename = "replies")]
+    comments: Vec<Comment>,
+    #[allow(dead_code)]
+    secret: String,
+}
+
+#[derive(Resource)]
+#[json_api(kind = "comments")]
+struct Comment {
+    #[json_api(id)]
+    id: u64,
+    #[json_api(attr)]
+    body: String,
+}
+
+#[derive(Resource)]
+#[json_api(kind = "users")]
+struct User {
+    #[json_api(id)]
+    id: String,
+}
+
+#[derive(Resource)]
+#[json_api(kind = "wrappers")]
+struct Wrapper<T: ToString> {
+    #[json_api(id)]
+    id: T,
+}
+
+fn article() -> Article {
+    Article {
+        id: 1,
+        title: "Hello, world!".to_owned(),
+        published_at: None,
+        author: Some(User {
+            id: "alfred".to_owned(),
+        }),
+        comments: vec![
+            Comment {
+                id: 1,
+                body: "First".to_owned(),
+            },
+            Comment {
+                id: 2,
+                body: "Second".to_owned(),
+            },
+        ],
+        secret: "hunter2".to_owned(),
+    }
+}
+
+fn render(query: Option<&Query>) -> (Object, Set<Object>) {
+    let article = article();
+    let mut included = Set::new();
+    let object = {
+        let mut ctx = Context::new(Article::kind(), query, &mut included);
+        article.to_object(&mut ctx).unwrap()
+    };
+
+    (object, included)
+}
+
+#[test]
+fn derive_kind_and_id() {
+    assert_eq!(Article::kind(), "articles");
+    assert_eq!(article().id(), "1");
+    assert_eq!(Wrapper { id: 'x' }.id(), "x");
+}
+
+#[test]
+fn derive_attributes() {
+    let (object, _) = render(None);
+
+    assert_eq!(object.attributes.get("title"), Some(&"Hello, world!".into()));
+    assert_eq!(object.attributes.get("published-at"), Some(&json_api::Value::Null));
+    assert!(!object.attributes.contains_key("secret"));
+    assert!(!object.attributes.contains_key("author"));
+}
+
+#[test]
+fn derive_relationships() {
+    let query = query::from_str("include=replies&fields[articles]=replies").unwrap();
+    let (object, included) = render(Some(&query));
+
+    assert!(object.attributes.is_empty());
+    assert!(!object.relationships.contains_key("author"));
+
+    match object.relationships.get("replies").and_then(|rel| rel.data.as_ref()) {
+        Some(Data::Collection(data)) => assert_eq!(data.len(), 2),
+        _ => panic!("expected resource linkage for replies"),
+    }
+
+    let kinds = included.iter().map(|item| item.kind.to_string());
+    assert_eq!(kinds.collect::<Vec<_>>(), vec!["comments", "comments"]);
+}
+
+#[test]
+fn derive_to_doc() {
+    let doc: Document<Object> = json_api::to_doc(&article(), None).unwrap();
+
+    match doc {
+        Document::Ok {
+            data: Data::Member(data),
+            ..
+        } => {
+            let object = data.unwrap();
+
+            assert_eq!(object.id, "1");
+            assert!(object.relationships.contains_key("author"));
+        }
+        _ => panic!("expected a member document"),
+    }
+}
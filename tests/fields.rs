@@ -0,0 +1,106 @@
+#[macro_use]
+extern crate json_api;
+extern crate serde_json;
+
+mod common;
+
+use std::cell::Cell;
+
+use json_api::doc::{Document, Object};
+use json_api::query::{self, Query};
+use json_api::value::{Set, Value};
+use json_api::view::{Context, Options};
+use json_api::Resource;
+
+use common::{Member, Post, Summary};
+
+#[test]
+fn fields_excluded() {
+    let post = Post {
+        id: 1,
+        title: "Hello".to_owned(),
+        body: "World".to_owned(),
+    };
+    let query = query::from_str("fields[posts]=-body").unwrap();
+    let object = {
+        let mut included = Set::new();
+        let mut ctx = Context::new(Post::kind(), Some(&query), &mut included);
+
+        post.to_object(&mut ctx).unwrap()
+    };
+
+    assert_eq!(object.attributes.get("title"), Some(&"Hello".into()));
+    assert_eq!(object.attributes.get("body"), None);
+
+    let query = query::from_str("fields[posts]=body").unwrap();
+    let json = json_api::to_string::<_, Object>(&post, Some(&query)).unwrap();
+
+    assert!(json.contains(r#""body":"World""#));
+    assert!(!json.contains("title"));
+}
+
+#[test]
+fn attr_context() {
+    let summary = Summary {
+        id: 1,
+        computed: Cell::new(0),
+    };
+    let render = |query: &Query| {
+        let mut included = Set::new();
+        let mut ctx = Context::new(Summary::kind(), Some(query), &mut included);
+
+        summary.to_object(&mut ctx).unwrap()
+    };
+
+    let query = query::from_str("fields[summaries]=title").unwrap();
+    let object = render(&query);
+
+    // The attribute block is not evaluated when the field is not requested.
+    assert_eq!(summary.computed.get(), 0);
+    assert_eq!(object.attributes.get("preview"), None);
+
+    let query = query::from_str("fields[summaries]=preview&include=a,b").unwrap();
+    let object = render(&query);
+
+    assert_eq!(summary.computed.get(), 1);
+    assert_eq!(object.attributes.get("preview"), Some(&2.into()));
+    assert_eq!(object.attributes.get("title"), None);
+}
+
+#[test]
+fn deprecations() {
+    let members = (1..4)
+        .map(|id| Member {
+            id,
+            handle: format!("member-{}", id),
+            nickname: format!("Member {}", id),
+        })
+        .collect::<Vec<_>>();
+    let mut options = Options::new();
+
+    // Deprecations are not advertised by default.
+    let doc: Document<Object> = json_api::to_doc_with(&members[..], None, &options).unwrap();
+    assert!(!doc.meta().contains_key("deprecations"));
+
+    // A deprecated field is advertised once for the entire collection.
+    options.advertise_deprecations = true;
+
+    let doc: Document<Object> = json_api::to_doc_with(&members[..], None, &options).unwrap();
+    let expected = serde_json::from_str::<Value>(
+        r#"[{ "type": "members", "field": "nickname", "note": "use handle instead" }]"#,
+    ).unwrap();
+
+    assert_eq!(doc.meta().get("deprecations"), Some(&expected));
+
+    // A deprecated field that is not rendered is not advertised.
+    let query = query::from_str("fields[members]=handle").unwrap();
+    let doc: Document<Object> =
+        json_api::to_doc_with(&members[..], Some(&query), &options).unwrap();
+
+    assert!(!doc.meta().contains_key("deprecations"));
+
+    let notes = Member::deprecations().unwrap();
+
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes.get("nickname"), Some(&"use handle instead".to_owned()));
+}
@@ -0,0 +1,40 @@
+// This file was generated by json-api. Do not edit it by hand.
+
+export interface ResourceIdentifier {
+  type: string;
+  id: string;
+  meta?: { [key: string]: unknown };
+}
+
+export interface Document<T> {
+  data?: T;
+  included?: Array<BlogPosts | Users>;
+  errors?: unknown[];
+  links?: { [key: string]: unknown };
+  meta?: { [key: string]: unknown };
+  jsonapi?: { version?: string };
+}
+
+export interface BlogPosts {
+  type: "blog-posts";
+  id: string;
+  attributes: {
+    body?: string;
+    "published-at": unknown;
+    title: string;
+    "word-count": unknown;
+  };
+  relationships: {
+    author: { data: ResourceIdentifier | null };
+    comments: { data: ResourceIdentifier[] };
+  };
+}
+
+export interface Users {
+  type: "users";
+  id: string;
+  attributes: {
+    name: string;
+    tags: unknown;
+  };
+}
@@ -0,0 +1,214 @@
+#[macro_use]
+extern crate json_api;
+
+mod common;
+
+use std::cell::Cell;
+
+use json_api::doc::{Data, Document, Object};
+use json_api::query::Query;
+use json_api::value::Set;
+use json_api::view::Context;
+use json_api::{Error, Resource};
+
+use common::{article, idents, linkage, Article, Feed, Node, Post, User};
+
+fn render(max: Option<usize>) -> Result<(Object, Set<Object>), Error> {
+    let article = article();
+    let query = Query::builder()
+        .include("comments")
+        .include("comments.author")
+        .build()?;
+    let mut included = Set::new();
+    let object = {
+        let mut ctx = Context::new(Article::kind(), Some(&query), &mut included);
+
+        ctx.set_max_included(max);
+        article.to_object(&mut ctx)?
+    };
+
+    Ok((object, included))
+}
+
+fn render_depth(max: usize, strict: bool) -> Result<Set<Object>, Error> {
+    let article = article();
+    let query = Query::builder()
+        .include("comments")
+        .include("comments.author")
+        .build()?;
+    let mut included = Set::new();
+
+    {
+        let mut ctx = Context::new(Article::kind(), Some(&query), &mut included);
+
+        ctx.set_max_depth(Some(max));
+        ctx.set_strict(strict);
+        article.to_object(&mut ctx)?;
+    }
+
+    Ok(included)
+}
+
+fn render_feed(query: Option<&Query>, linkage: bool) -> Result<(Object, usize), Error> {
+    let feed = Feed {
+        id: 1,
+        items: vec![User { id: 1 }, User { id: 2 }],
+        reads: Cell::new(0),
+    };
+    let mut included = Set::new();
+    let object = {
+        let mut ctx = Context::new(Feed::kind(), query, &mut included);
+
+        ctx.set_emit_linkage(linkage);
+        feed.to_object(&mut ctx)?
+    };
+
+    Ok((object, feed.reads.get()))
+}
+
+#[test]
+fn include_unlimited() {
+    let (object, included) = render(None).unwrap();
+
+    assert_eq!(
+        idents(&included),
+        vec!["users:1", "comments:1", "users:2", "comments:2"]
+    );
+    assert_eq!(linkage(&object, "comments").len(), 2);
+}
+
+#[test]
+fn include_limit() {
+    let (object, included) = render(Some(2)).unwrap();
+
+    // The second comment and its author are not included, but the article still
+    // contains an identifier for each comment.
+    assert_eq!(idents(&included), vec!["users:1", "comments:1"]);
+    assert_eq!(linkage(&object, "comments").len(), 2);
+
+    let comment = included.iter().nth(1).unwrap();
+    assert_eq!(linkage(comment, "author").len(), 1);
+}
+
+#[test]
+fn include_limit_linkage() {
+    let (object, included) = render(Some(1)).unwrap();
+
+    // The author of the first comment fills the remaining capacity before the comment
+    // is included. It is removed so every included resource is reachable from the
+    // primary data.
+    assert_eq!(idents(&included), vec!["comments:1"]);
+    assert_eq!(linkage(&object, "comments").len(), 2);
+
+    let comment = included.iter().next().unwrap();
+    assert_eq!(linkage(comment, "author").len(), 1);
+}
+
+#[test]
+fn include_depth() {
+    let included = render_depth(1, false).unwrap();
+    assert_eq!(idents(&included), vec!["comments:1", "comments:2"]);
+
+    let included = render_depth(2, false).unwrap();
+    assert_eq!(included.len(), 4);
+}
+
+#[test]
+fn include_depth_strict() {
+    assert!(render_depth(1, true).is_err());
+    assert!(render_depth(2, true).is_ok());
+}
+
+#[test]
+fn include_cycle() {
+    let node = Node {
+        id: 1,
+        children: vec![
+            Node {
+                id: 1,
+                children: Vec::new(),
+            },
+            Node {
+                id: 2,
+                children: Vec::new(),
+            },
+        ],
+    };
+    let query = Query::builder().include("children").build().unwrap();
+    let doc: Document<Object> = json_api::to_doc(&node, Some(&query)).unwrap();
+
+    match doc {
+        Document::Ok { data, included, .. } => {
+            let object = match data {
+                Data::Member(data) => data.unwrap(),
+                Data::Collection(_) => panic!("expected a member"),
+            };
+
+            // The first child references the primary data, so it is only rendered as
+            // a resource identifier.
+            assert_eq!(idents(&included), vec!["nodes:2"]);
+            assert_eq!(linkage(&object, "children").len(), 2);
+        }
+        Document::Err { .. } | Document::Meta { .. } => panic!("expected a document with data"),
+    }
+}
+
+#[test]
+fn query_none() {
+    let mut included = Set::new();
+    let object = {
+        let mut ctx = Context::new(Article::kind(), None, &mut included);
+        article().to_object(&mut ctx).unwrap()
+    };
+
+    assert!(included.is_empty());
+    assert!(object.relationships.contains_key("comments"));
+
+    let post = Post {
+        id: 1,
+        title: "Hello".to_owned(),
+        body: "World".to_owned(),
+    };
+    let doc = json_api::to_doc::<_, Object>(&post, None).unwrap();
+
+    match doc {
+        Document::Ok { data, included, .. } => {
+            let object = match data {
+                Data::Member(object) => object.unwrap(),
+                Data::Collection(_) => panic!("expected a single resource"),
+            };
+
+            assert!(included.is_empty());
+            assert_eq!(object.attributes.get("title"), Some(&"Hello".into()));
+            assert_eq!(object.attributes.get("body"), Some(&"World".into()));
+        }
+        _ => panic!("expected a document with data"),
+    }
+
+    let doc = json_api::to_doc::<_, Object>(&article(), None).unwrap();
+
+    match doc {
+        Document::Ok { included, .. } => assert!(included.is_empty()),
+        _ => panic!("expected a document with data"),
+    }
+}
+
+#[test]
+fn linkage_disabled() {
+    let (object, reads) = render_feed(None, false).unwrap();
+
+    // The data block of a relationship that is not included is never iterated.
+    assert_eq!(reads, 0);
+    assert_eq!(object.relationships.get("items").unwrap().data, None);
+
+    let (object, reads) = render_feed(None, true).unwrap();
+
+    assert_eq!(reads, 2);
+    assert_eq!(linkage(&object, "items").len(), 2);
+
+    let query = Query::builder().include("items").build().unwrap();
+    let (object, reads) = render_feed(Some(&query), false).unwrap();
+
+    assert_eq!(reads, 2);
+    assert_eq!(linkage(&object, "items").len(), 2);
+}
@@ -0,0 +1,103 @@
+#[macro_use]
+extern crate json_api;
+
+mod common;
+
+use std::cell::Cell;
+
+use json_api::doc::{Document, Object};
+use json_api::query::{self, Query};
+use json_api::value::{Map, Set};
+use json_api::view::Context;
+use json_api::Resource;
+
+use common::{article, linkage, Author, Byline, Review, Reviewer};
+
+#[test]
+fn doc_meta_primary_only() {
+    let article = article();
+    let query = Query::builder().include("comments").build().unwrap();
+    let doc: Document<Object> = json_api::to_doc(&article, Some(&query)).unwrap();
+
+    // Only the primary data contributes to the top-level meta of the document.
+    assert_eq!(doc.meta().get("primary"), Some(&"articles".into()));
+
+    let doc: Document<Object> = json_api::to_doc(&article.comments[..], None).unwrap();
+    assert_eq!(doc.meta().get("primary"), Some(&"comments".into()));
+}
+
+#[test]
+fn object_meta() {
+    let byline = Byline {
+        id: 1,
+        authors: (1..4)
+            .map(|id| Author {
+                id,
+                counted: Cell::new(0),
+                stamped: Cell::new(0),
+            })
+            .collect(),
+    };
+    let counts = |byline: &Byline| {
+        byline
+            .authors
+            .iter()
+            .map(|author| (author.counted.get(), author.stamped.get()))
+            .collect::<Vec<_>>()
+    };
+
+    // Identifiers do not evaluate object meta.
+    let object = json_api::to_object(&byline, None).unwrap();
+    let data = linkage(&object, "authors");
+
+    assert_eq!(counts(&byline), vec![(0, 1), (0, 1), (0, 1)]);
+    assert!(data.iter().all(|ident| !ident.meta.contains_key("post-count")));
+    assert!(data.iter().all(|ident| ident.meta.contains_key("stamp")));
+
+    // Each included resource evaluates its object meta once. Shared meta is evaluated
+    // for both its identifier and its resource object.
+    let query = query::from_str("include=authors").unwrap();
+    let (_, included) = json_api::to_object_with_included(&byline, Some(&query)).unwrap();
+
+    assert_eq!(counts(&byline), vec![(1, 3), (1, 3), (1, 3)]);
+    assert!(included.iter().all(|object| {
+        object.meta.get("post-count") == Some(&1.into()) && object.meta.contains_key("stamp")
+    }));
+}
+
+#[test]
+fn ident_meta() {
+    let keys = |meta: &Map| meta.keys().map(|key| key.to_string()).collect::<Vec<_>>();
+    let reviewer = Reviewer { id: 1 };
+    let review = Review {
+        id: 1,
+        reviewers: vec![Reviewer { id: 2 }],
+    };
+
+    // Identifier meta and object meta are disjoint as declared.
+    let object = json_api::to_object(&reviewer, None).unwrap();
+    let ident = {
+        let mut included = Set::new();
+        let mut ctx = Context::new(Reviewer::kind(), None, &mut included);
+
+        reviewer.to_ident(&mut ctx).unwrap()
+    };
+
+    assert_eq!(keys(&object.meta), vec!["active", "review-count"]);
+    assert_eq!(keys(&ident.meta), vec!["active", "weight"]);
+
+    let object = json_api::to_object(&review, None).unwrap();
+    let data = linkage(&object, "reviewers");
+
+    assert_eq!(keys(&data[0].meta), vec!["active", "weight"]);
+    assert_eq!(data[0].meta.get("weight"), Some(&1.into()));
+
+    // Object meta stays on the resource object when the resource is included.
+    let query = query::from_str("include=reviewers").unwrap();
+    let (object, included) = json_api::to_object_with_included(&review, Some(&query)).unwrap();
+    let data = linkage(&object, "reviewers");
+
+    assert_eq!(keys(&data[0].meta), vec!["active", "weight"]);
+    assert_eq!(keys(&included.iter().next().unwrap().meta), vec!["active", "review-count"]);
+    assert!(included.iter().all(|object| !object.meta.contains_key("weight")));
+}
@@ -0,0 +1,158 @@
+#![cfg(feature = "metrics")]
+
+#[macro_use]
+extern crate json_api;
+
+use std::sync::{Mutex, MutexGuard};
+use std::thread;
+
+use json_api::doc::{Document, Object};
+use json_api::metrics::{self, MetricsSnapshot};
+use json_api::query::{self, Query};
+
+/// Counters are shared by every test in this binary, so tests that assert exact
+/// deltas must not run concurrently.
+static LOCK: Mutex<()> = Mutex::new(());
+
+struct Post {
+    id: u64,
+    title: String,
+    author: Option<User>,
+}
+
+resource!(Post, |&self| {
+    kind "posts";
+    id self.id;
+
+    attrs title;
+    has_one author;
+});
+
+struct User {
+    id: u64,
+    name: String,
+}
+
+resource!(User, |&self| {
+    kind "users";
+    id self.id;
+
+    attrs name;
+});
+
+fn lock() -> MutexGuard<'static, ()> {
+    LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn post(id: u64) -> Post {
+    Post {
+        id,
+        title: format!("Post {}", id),
+        author: Some(User {
+            id,
+            name: format!("User {}", id),
+        }),
+    }
+}
+
+fn delta(before: &MetricsSnapshot, after: &MetricsSnapshot) -> [u64; 5] {
+    [
+        after.documents_rendered - before.documents_rendered,
+        after.documents_parsed - before.documents_parsed,
+        after.parse_failures - before.parse_failures,
+        after.bytes_serialized - before.bytes_serialized,
+        after.included_resources_emitted - before.included_resources_emitted,
+    ]
+}
+
+#[test]
+fn render_and_serialize() {
+    let _guard = lock();
+    let query: Query = query::from_str("include=author").unwrap();
+    let posts = [post(1), post(2)];
+
+    let before = metrics::snapshot();
+    let doc: Document<Object> = json_api::to_doc(&posts[..], Some(&query)).unwrap();
+    let after = metrics::snapshot();
+
+    assert_eq!(delta(&before, &after), [1, 0, 0, 0, 2]);
+    drop(doc);
+
+    let before = metrics::snapshot();
+    let json = json_api::to_vec::<_, Object>(&post(1), None).unwrap();
+    let after = metrics::snapshot();
+
+    assert_eq!(delta(&before, &after), [1, 0, 0, json.len() as u64, 0]);
+
+    let mut buf = Vec::new();
+    let before = metrics::snapshot();
+    json_api::to_writer_pretty::<_, _, Object>(&mut buf, &post(1), Some(&query)).unwrap();
+    let after = metrics::snapshot();
+
+    assert_eq!(delta(&before, &after), [1, 0, 0, buf.len() as u64, 1]);
+
+    let mut buf = Vec::new();
+    let before = metrics::snapshot();
+    json_api::to_writer_streamed(&mut buf, (1..4).map(post), Some(&query)).unwrap();
+    let after = metrics::snapshot();
+
+    assert_eq!(delta(&before, &after), [1, 0, 0, buf.len() as u64, 3]);
+}
+
+#[test]
+fn parse() {
+    let _guard = lock();
+    let json = json_api::to_vec::<_, Object>(&post(1), None).unwrap();
+
+    let before = metrics::snapshot();
+    let _: Document<Object> = json_api::parse_slice(&json).unwrap();
+    let _: Document<Object> = json_api::parse_str(r#"{ "data": null }"#).unwrap();
+    let after = metrics::snapshot();
+
+    assert_eq!(delta(&before, &after), [0, 2, 0, 0, 0]);
+
+    let before = metrics::snapshot();
+    let result = json_api::from_slice::<Object, Object>(b"{ \"data\": ");
+    let after = metrics::snapshot();
+
+    assert!(result.is_err());
+    assert_eq!(delta(&before, &after), [0, 0, 1, 0, 0]);
+}
+
+#[test]
+fn reset() {
+    let _guard = lock();
+
+    json_api::to_string::<_, Object>(&post(1), None).unwrap();
+    metrics::reset();
+
+    assert_eq!(metrics::snapshot(), MetricsSnapshot::default());
+}
+
+#[test]
+fn concurrent_updates() {
+    let _guard = lock();
+    let before = metrics::snapshot();
+
+    let handles = (0..8)
+        .map(|n| {
+            thread::spawn(move || {
+                for id in 0..25 {
+                    let json = json_api::to_string::<_, Object>(&post(n * 25 + id), None).unwrap();
+                    let _: Document<Object> = json_api::parse_str(&json).unwrap();
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let after = metrics::snapshot();
+
+    assert_eq!(after.documents_rendered - before.documents_rendered, 200);
+    assert_eq!(after.documents_parsed - before.documents_parsed, 200);
+    assert_eq!(after.parse_failures, before.parse_failures);
+    assert!(after.bytes_serialized > before.bytes_serialized);
+}
@@ -0,0 +1,146 @@
+extern crate json_api;
+extern crate serde_json;
+
+use json_api::doc::{Data, Document, InvalidLinks, Link, Object, ParseOptions, Relationship};
+use json_api::error::ErrorKind;
+
+const FIXTURE: &str = include_str!("fixtures/invalid-link.json");
+
+fn parse(invalid_links: InvalidLinks) -> Result<Document<Object>, json_api::Error> {
+    let mut options = ParseOptions::new();
+
+    options.invalid_links = invalid_links;
+    options.parse_str(FIXTURE)
+}
+
+fn author(doc: &Document<Object>) -> &Relationship {
+    match *doc {
+        Document::Ok { ref data, .. } => match *data {
+            Data::Member(ref item) => {
+                let item = item.as_ref().as_ref().unwrap();
+                item.relationships.get("author").unwrap()
+            }
+            _ => panic!("expected a single resource"),
+        },
+        _ => panic!("expected a document with data"),
+    }
+}
+
+#[test]
+fn invalid_link_error() {
+    let error = parse(InvalidLinks::Error).unwrap_err();
+
+    match *error.kind() {
+        ErrorKind::InvalidLink(ref href, ref pointer, ref reason) => {
+            assert_eq!(href, "http://exa mple.com/people/9");
+            assert_eq!(
+                pointer.as_ref().map(String::as_str),
+                Some("/data/relationships/author/links/related")
+            );
+            assert_eq!(reason, "invalid uri character ' '");
+        }
+        ref kind => panic!("unexpected error: {}", kind),
+    }
+
+    let default = json_api::parse_str::<Object>(FIXTURE).unwrap_err();
+    assert_eq!(default.to_string(), error.to_string());
+}
+
+#[test]
+fn invalid_link_drop() {
+    let doc = parse(InvalidLinks::Drop).unwrap();
+    let links = &author(&doc).links;
+
+    assert_eq!(links.len(), 1);
+    assert_eq!(links.get("self").unwrap().reference(), "/articles/1/relationships/author");
+    assert!(doc.links().contains_key("self"));
+}
+
+#[test]
+fn invalid_link_raw() {
+    let doc = parse(InvalidLinks::Raw).unwrap();
+    let links = &author(&doc).links;
+
+    assert_eq!(links.len(), 2);
+    assert_eq!(links.get("related").unwrap().reference(), "http://exa mple.com/people/9");
+    assert!(links.get("related").unwrap().uri().is_none());
+    assert!(links.get("self").unwrap().uri().is_some());
+}
+
+#[test]
+fn invalid_link_truncated() {
+    let href = format!("/{}", "a b".repeat(40));
+    let error = json_api::Error::invalid_link(&href, None, "invalid uri character ' '");
+    let message = error.to_string();
+
+    assert!(message.starts_with(&format!(r#"invalid link "{}...""#, &href[..64])));
+    assert!(message.ends_with(": invalid uri character ' '"));
+}
+
+#[test]
+fn attribute_nested_keys() {
+    let doc: Document<Object> = ParseOptions::new()
+        .parse_str(
+            r#"{
+                "data": {
+                    "type": "widgets",
+                    "id": "1",
+                    "attributes": {
+                        "config": {"$schema": "x", "a.b": 1, "Some Key": 2}
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+    let item = match doc {
+        Document::Ok { data: Data::Member(item), .. } => item.unwrap(),
+        _ => panic!("expected a single resource"),
+    };
+    let config = item.attributes.get("config").and_then(|value| value.as_object());
+    let keys = config.unwrap().keys().map(|key| &**key).collect::<Vec<_>>();
+
+    assert_eq!(keys, ["$schema", "a.b", "Some Key"]);
+}
+
+#[test]
+fn invalid_link_jsonapi() {
+    let json = r#"{
+        "meta": {},
+        "jsonapi": {
+            "version": "1.1",
+            "ext": ["https://jsonapi.org/ext/atomic"],
+            "profile": ["/profiles/timestamps", "http://exa mple.com"]
+        }
+    }"#;
+
+    let error = json_api::parse_str::<Object>(json).unwrap_err();
+
+    match *error.kind() {
+        ErrorKind::InvalidLink(ref href, ref pointer, _) => {
+            assert_eq!(href, "http://exa mple.com");
+            assert_eq!(pointer.as_ref().map(String::as_str), Some("/jsonapi/profile/1"));
+        }
+        ref kind => panic!("unexpected error: {}", kind),
+    }
+
+    let mut options = ParseOptions::new();
+
+    options.invalid_links = InvalidLinks::Drop;
+
+    match options.parse_str::<Object>(json).unwrap() {
+        Document::Meta { jsonapi, .. } => {
+            assert_eq!(jsonapi.ext.len(), 1);
+            assert_eq!(jsonapi.profile, ["/profiles/timestamps"]);
+        }
+        _ => panic!("expected a meta document"),
+    }
+}
+
+#[test]
+fn invalid_link_serde() {
+    let error = serde_json::from_str::<Document<Object>>(FIXTURE).unwrap_err();
+
+    assert!(error.to_string().contains("/data/relationships/author/links/related"));
+    assert!(serde_json::from_str::<Link>(r#""http://exa mple.com""#).is_ok());
+}
@@ -1,15 +1,18 @@
 extern crate json_api;
 #[macro_use]
-extern crate ordermap;
+extern crate indexmap;
+extern crate serde_qs;
 
 use json_api::Error;
-use json_api::query::{self, Direction, Query};
-use ordermap::OrderMap;
+use json_api::error::ErrorKind;
+use json_api::query::{self, Direction, ParseOptions, Query, Sort};
+use json_api::value::{Key, Path, Set, Value};
+use indexmap::IndexMap;
 
-type Mapping = OrderMap<&'static str, Query>;
+type Mapping = IndexMap<&'static str, Query>;
 
 fn from_mapping() -> Result<Mapping, Error> {
-    Ok(ordermap!{
+    Ok(indexmap!{
         "" => Default::default(),
         "fields%5Barticles%5D=title" => Query::builder()
             .fields("articles", vec!["title"])
@@ -26,6 +29,9 @@ fn from_mapping() -> Result<Mapping, Error> {
         "filter%5Busers.name%5D=Alfred+Pennyworth" => Query::builder()
             .filter("users.name", "Alfred Pennyworth")
             .build()?,
+        "filter%5Bdeleted-at%5D=" => Query::builder()
+            .filter("deleted-at", Value::Null)
+            .build()?,
         "include=author" => Query::builder()
             .include("author")
             .build()?,
@@ -46,6 +52,12 @@ fn from_mapping() -> Result<Mapping, Error> {
         "page%5Bnumber%5D=2&page%5Bsize%5D=15" => Query::builder()
             .page(2, Some(15))
             .build()?,
+        "page%5Boffset%5D=40&page%5Blimit%5D=20" => Query::builder()
+            .page_offset(40, Some(20))
+            .build()?,
+        "page%5Boffset%5D=0" => Query::builder()
+            .page_offset(0, None)
+            .build()?,
         "sort=-published-at" => Query::builder()
             .sort("published-at", Direction::Desc)
             .build()?,
@@ -125,3 +137,199 @@ fn query_to_vec() {
         assert_eq!(actual, expected.to_owned().into_bytes());
     }
 }
+
+#[test]
+fn query_display() {
+    for (expected, source) in to_mapping().unwrap() {
+        assert_eq!(format!("{}", source), expected);
+    }
+}
+
+#[test]
+fn query_filter_null() {
+    let query = query::from_str(concat!(
+        "filter%5Bauthor%5D%5Bname%5D=&",
+        "filter%5Bdeleted-at%5D=null&",
+        "filter%5Btitle%5D=nullable"
+    )).unwrap();
+    let value = |key: &str| query.filter.get(&key.parse::<Path>().unwrap()).cloned();
+
+    assert_eq!(value("deleted-at"), Some(Value::Null));
+    assert_eq!(value("author").and_then(|author| author.get("name").cloned()), Some(Value::Null));
+    assert_eq!(value("title"), Some(Value::from("nullable")));
+    assert_eq!(
+        query::to_string(&query).unwrap(),
+        concat!(
+            "filter%5Bauthor%5D%5Bname%5D=&",
+            "filter%5Bdeleted-at%5D=&",
+            "filter%5Btitle%5D=nullable"
+        )
+    );
+}
+
+#[test]
+fn query_dotted_fields() {
+    let bracketed = query::from_str(concat!(
+        "fields%5Barticles%5D=title,body&",
+        "fields%5Bpeople%5D=-email&",
+        "include=author"
+    )).unwrap();
+    let dotted = "fields.articles=title,body&fields.people=-email&include=author";
+    let mut options = ParseOptions::new();
+
+    assert!(query::from_str(dotted).is_err());
+    assert!(options.parse_str(dotted).is_err());
+
+    options.dotted_fields = true;
+
+    let query = options.parse_str(dotted).unwrap();
+
+    assert_eq!(query.fields, bracketed.fields);
+    assert_eq!(query, bracketed);
+    assert_eq!(options.parse_str("fields%2Earticles=title").unwrap().fields.len(), 1);
+    assert_eq!(
+        query::to_string(&query).unwrap(),
+        query::to_string(&bracketed).unwrap()
+    );
+}
+
+#[test]
+fn query_semantic_eq() {
+    let a = Query::builder()
+        .fields("articles", vec!["title", "body"])
+        .fields("users", vec!["name"])
+        .filter("author.name", "Alfred")
+        .filter("published", true)
+        .include("author")
+        .include("comments")
+        .page(1, None)
+        .sort("title", Direction::Asc)
+        .build()
+        .unwrap();
+    let b = Query::builder()
+        .fields("users", vec!["name"])
+        .fields("articles", vec!["body", "title"])
+        .filter("published", true)
+        .filter("author.name", "Alfred")
+        .include("comments")
+        .include("author")
+        .sort("title", Direction::Asc)
+        .build()
+        .unwrap();
+
+    // The first page is requested explicitly by `a`, which is the default for `b`.
+    assert!(a.semantic_eq(&b));
+    assert!(b.semantic_eq(&a));
+    assert_ne!(a, b);
+
+    let a = query::from_str("sort=title,-published-at").unwrap();
+    let b = query::from_str("sort=-published-at,title").unwrap();
+
+    // Sort instructions are applied in order.
+    assert!(!a.semantic_eq(&b));
+    assert!(a.semantic_eq(&a));
+}
+
+#[test]
+fn query_sort_display() {
+    let sort = ["published-at", "-title", "-author.name"]
+        .iter()
+        .map(|item| item.parse())
+        .collect::<Result<Set<Sort>, Error>>()
+        .unwrap();
+    let text = sort.to_string();
+
+    assert_eq!(text, "published-at,-title,-author.name");
+    assert_eq!(text.parse::<Set<Sort>>().unwrap(), sort);
+
+    // The value of the sort parameter is the same string with each comma encoded.
+    let query = Query::builder()
+        .sort("published-at", Direction::Asc)
+        .sort("title", Direction::Desc)
+        .sort("author.name", Direction::Desc)
+        .build()
+        .unwrap();
+    let encoded = query::to_string(&query).unwrap();
+
+    assert_eq!(query.sort, sort);
+    assert_eq!(encoded, format!("sort={}", text.replace(',', "%2C")));
+    assert_eq!(query::from_str(&encoded).unwrap().sort, sort);
+
+    assert_eq!(Set::<Sort>::new().to_string(), "");
+    assert_eq!(sort.iter().take(1).cloned().collect::<Set<_>>().to_string(), "published-at");
+}
+
+#[test]
+fn query_fields_set() {
+    let allowed = ["title", "body", "published-at"]
+        .iter()
+        .map(|field| field.parse())
+        .collect::<Result<Set<Key>, Error>>()
+        .unwrap();
+
+    let query = Query::builder()
+        .fields_set("articles".parse().unwrap(), allowed)
+        .fields("users", vec!["name"])
+        .build()
+        .unwrap();
+    let expected = Query::builder()
+        .fields("articles", vec!["title", "body", "published-at"])
+        .fields("users", vec!["name"])
+        .build()
+        .unwrap();
+
+    assert_eq!(query, expected);
+    assert_eq!(
+        query::to_string(&query).unwrap(),
+        query::to_string(&expected).unwrap()
+    );
+
+    // A typed field-set replaces an excluded field-set of the same type.
+    let query = Query::builder()
+        .fields("articles", vec!["-body"])
+        .fields_set("articles".parse().unwrap(), Set::new())
+        .build()
+        .unwrap();
+
+    assert!(!query.fieldset(&"articles".parse().unwrap()).contains("title"));
+    assert!(query.excluded.is_empty());
+}
+
+#[test]
+fn query_fields_replaced() {
+    let kind = "articles".parse::<Key>().unwrap();
+
+    let query = Query::builder()
+        .fields("articles", vec!["-body"])
+        .fields("articles", vec!["title"])
+        .build()
+        .unwrap();
+
+    assert!(query.excluded.is_empty());
+    assert!(query.fieldset(&kind).contains("title"));
+    assert!(!query.fieldset(&kind).contains("body"));
+
+    let query = Query::builder()
+        .fields("articles", vec!["title"])
+        .fields("articles", vec!["-body"])
+        .build()
+        .unwrap();
+
+    assert!(query.fields.is_empty());
+    assert!(query.fieldset(&kind).contains("title"));
+    assert!(!query.fieldset(&kind).contains("body"));
+}
+
+#[test]
+fn query_parameter_error_isolated() {
+    assert!(serde_qs::from_str::<Query>("sort=&page[size]=abc").is_err());
+
+    let error = query::from_str("include=author&sort=").unwrap_err();
+
+    match *error.kind() {
+        ErrorKind::QueryParameter(ref name, _) => {
+            assert_eq!(name.as_ref().map(String::as_str), Some("sort"));
+        }
+        ref kind => panic!("unexpected error: {}", kind),
+    }
+}
@@ -0,0 +1,251 @@
+//! Drives the in-memory server from `examples/reference_server` through a scripted
+//! conversation.
+
+#[macro_use]
+extern crate json_api;
+#[macro_use]
+extern crate serde_json;
+
+#[path = "../examples/reference_server/server.rs"]
+mod server;
+
+use json_api::MEDIA_TYPE;
+use json_api::doc::{self, Document, Object};
+use json_api::http::Response;
+use json_api::http::header::{CONTENT_TYPE, LOCATION};
+use serde_json::Value;
+
+use server::Server;
+
+fn body(resp: &Response<Vec<u8>>) -> Value {
+    assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), MEDIA_TYPE);
+    serde_json::from_slice(resp.body()).unwrap()
+}
+
+/// Asserts that `resp` succeeded with a document that conforms to the specification
+/// and returns its body.
+fn ok(resp: &Response<Vec<u8>>, status: u16) -> Value {
+    let doc: Document<Object> = json_api::parse_slice(resp.body()).unwrap();
+
+    assert_eq!(resp.status().as_u16(), status, "{}", body(resp));
+    assert_eq!(doc::validate(&doc), []);
+    body(resp)
+}
+
+/// Asserts that `resp` failed with `status` and returns the status and source of
+/// each error in the error document.
+fn errors(resp: &Response<Vec<u8>>, status: u16) -> Vec<Value> {
+    let body = body(resp);
+
+    assert_eq!(resp.status().as_u16(), status, "{}", body);
+    body["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|error| json!({ "status": error["status"], "source": error["source"] }))
+        .collect()
+}
+
+fn ids(data: &Value) -> Vec<&str> {
+    data.as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["id"].as_str().unwrap())
+        .collect()
+}
+
+#[test]
+fn list_articles() {
+    let mut server = Server::new();
+    let query = "include=author&fields%5Barticles%5D=title,author&sort=-title&page%5Bsize%5D=2";
+    let body = ok(&server.handle("GET", "/articles", query, b""), 200);
+
+    assert_eq!(ids(&body["data"]), ["2", "1"]);
+    assert_eq!(body["data"][0]["attributes"], json!({ "title": "Rust in production" }));
+    assert_eq!(body["data"][0]["relationships"]["comments"], Value::Null);
+    assert_eq!(ids(&body["included"]), ["10", "9"]);
+    assert_eq!(body["meta"], json!({ "total": 3 }));
+    assert_eq!(
+        body["links"]["next"],
+        concat!(
+            "/articles?fields%5Barticles%5D=title%2Cauthor&include=author&",
+            "page%5Bnumber%5D=2&page%5Bsize%5D=2&sort=-title"
+        )
+    );
+
+    let query = "sort=author.name,title&page%5Bnumber%5D=2&page%5Bsize%5D=2";
+    let body = ok(&server.handle("GET", "/articles", query, b""), 200);
+
+    assert_eq!(ids(&body["data"]), ["1"]);
+    assert_eq!(body["links"]["next"], Value::Null);
+    assert!(body["links"]["prev"].is_string());
+}
+
+#[test]
+fn show_article() {
+    let mut server = Server::new();
+    let query = "include=comments,comments.author";
+    let body = ok(&server.handle("GET", "/articles/1", query, b""), 200);
+
+    assert_eq!(body["data"]["id"], "1");
+    assert_eq!(body["data"]["attributes"]["title"], "JSON API paints my bikeshed!");
+    assert_eq!(ids(&body["data"]["relationships"]["comments"]["data"]), ["5", "12"]);
+    assert_eq!(ids(&body["included"]), ["2", "5", "9", "12"]);
+}
+
+#[test]
+fn create_article() {
+    let mut server = Server::new();
+    let request = json!({
+        "data": {
+            "type": "articles",
+            "id": "7",
+            "attributes": { "title": "Client ids", "body": "Generated by the client." }
+        }
+    });
+    let resp = server.handle("POST", "/articles", "", request.to_string().as_bytes());
+    let body = ok(&resp, 201);
+
+    assert_eq!(resp.headers().get(LOCATION).unwrap(), "/articles/7");
+    assert_eq!(body["data"]["id"], "7");
+    assert_eq!(body["data"]["attributes"]["title"], "Client ids");
+
+    let body = ok(&server.handle("GET", "/articles/7", "", b""), 200);
+    assert_eq!(body["data"]["attributes"]["body"], "Generated by the client.");
+
+    // The same client id can not be used twice.
+    let resp = server.handle("POST", "/articles", "", request.to_string().as_bytes());
+    assert_eq!(
+        errors(&resp, 409),
+        [json!({ "status": "409", "source": { "pointer": "/data/id" } })]
+    );
+
+    // A server generated id is used when the client does not provide one.
+    let request = json!({
+        "data": { "type": "articles", "attributes": { "title": "Server ids" } }
+    });
+    let resp = server.handle("POST", "/articles", "", request.to_string().as_bytes());
+
+    assert_eq!(ok(&resp, 201)["data"]["id"], "8");
+}
+
+#[test]
+fn update_article() {
+    let mut server = Server::new();
+    let request = json!({
+        "data": {
+            "type": "articles",
+            "id": "2",
+            "attributes": { "title": "Rust in production, revisited" }
+        }
+    });
+    let resp = server.handle("PATCH", "/articles/2", "", request.to_string().as_bytes());
+    let body = ok(&resp, 200);
+
+    // Attributes that are not present in the request are left as-is.
+    assert_eq!(body["data"]["attributes"]["title"], "Rust in production, revisited");
+    assert_eq!(body["data"]["attributes"]["body"], "The body of Rust in production.");
+
+    let request = json!({ "data": { "type": "articles", "id": "3" } });
+    let resp = server.handle("PATCH", "/articles/2", "", request.to_string().as_bytes());
+
+    assert_eq!(
+        errors(&resp, 409),
+        [json!({ "status": "409", "source": { "pointer": "/data/id" } })]
+    );
+}
+
+#[test]
+fn relationships() {
+    let mut server = Server::new();
+    let path = "/articles/1/relationships/comments";
+    let body = ok(&server.handle("GET", path, "", b""), 200);
+
+    assert_eq!(
+        body["data"],
+        json!([{ "id": "5", "type": "comments" }, { "id": "12", "type": "comments" }])
+    );
+
+    let request = json!({
+        "data": [{ "type": "comments", "id": "14" }, { "type": "comments", "id": "5" }]
+    });
+    let resp = server.handle("PATCH", path, "", request.to_string().as_bytes());
+
+    assert_eq!(resp.status().as_u16(), 204);
+    assert!(resp.body().is_empty());
+    assert_eq!(ids(&ok(&server.handle("GET", path, "", b""), 200)["data"]), ["14", "5"]);
+
+    let request = json!({
+        "data": [
+            { "type": "comments", "id": "12" },
+            { "type": "comments", "id": "13" },
+            { "type": "comments", "id": "12" },
+            { "type": "users", "id": "9" }
+        ]
+    });
+    let resp = server.handle("PATCH", path, "", request.to_string().as_bytes());
+
+    assert_eq!(
+        errors(&resp, 422),
+        [
+            json!({ "status": "422", "source": { "pointer": "/data/2" } }),
+            json!({ "status": "409", "source": { "pointer": "/data/3/type" } }),
+        ]
+    );
+
+    let request = json!({ "data": [{ "type": "comments", "id": "99" }] });
+    let resp = server.handle("PATCH", path, "", request.to_string().as_bytes());
+
+    assert_eq!(
+        errors(&resp, 404),
+        [json!({ "status": "404", "source": { "pointer": "/data/0/id" } })]
+    );
+    assert_eq!(ids(&ok(&server.handle("GET", path, "", b""), 200)["data"]), ["14", "5"]);
+}
+
+#[test]
+fn malformed_requests() {
+    let mut server = Server::new();
+
+    let resp = server.handle("GET", "/articles", "page%5Bnumber%5D=first", b"");
+    assert_eq!(
+        errors(&resp, 400),
+        [json!({ "status": "400", "source": { "parameter": "page[number]" } })]
+    );
+
+    let resp = server.handle("GET", "/articles", "sort=editor.name", b"");
+    assert_eq!(
+        errors(&resp, 400),
+        [json!({ "status": "400", "source": { "parameter": "sort" } })]
+    );
+
+    let resp = server.handle("GET", "/articles/42", "", b"");
+    assert_eq!(errors(&resp, 404), [json!({ "status": "404", "source": null })]);
+
+    let resp = server.handle("DELETE", "/articles/1", "", b"");
+    assert_eq!(errors(&resp, 404), [json!({ "status": "404", "source": null })]);
+
+    let resp = server.handle("POST", "/articles", "", b"{\"data\": ");
+    assert_eq!(errors(&resp, 400), [json!({ "status": "400", "source": null })]);
+
+    let request = json!({ "data": { "type": "people", "attributes": { "title": "x" } } });
+    let resp = server.handle("POST", "/articles", "", request.to_string().as_bytes());
+    assert_eq!(
+        errors(&resp, 409),
+        [json!({ "status": "409", "source": { "pointer": "/data/type" } })]
+    );
+
+    let request = json!({ "data": { "type": "articles", "attributes": { "title": 1 } } });
+    let resp = server.handle("POST", "/articles", "", request.to_string().as_bytes());
+    assert_eq!(
+        errors(&resp, 422),
+        [json!({ "status": "422", "source": { "pointer": "/data/attributes/title" } })]
+    );
+
+    let request = json!({ "data": { "type": "articles", "attributes": {} } });
+    let resp = server.handle("POST", "/articles", "", request.to_string().as_bytes());
+    assert_eq!(
+        errors(&resp, 422),
+        [json!({ "status": "422", "source": { "pointer": "/data/attributes/title" } })]
+    );
+}
@@ -0,0 +1,172 @@
+#[macro_use]
+extern crate json_api;
+
+mod common;
+
+use std::cell::Cell;
+
+use json_api::doc::{Data, Document, Object};
+use json_api::query;
+use json_api::value::{Set, Value};
+use json_api::view::Context;
+use json_api::Resource;
+
+use common::{idents, linkage, Activity, Animal, Playlist, Post, Shelter, Team, Timeline, User};
+
+#[test]
+fn has_many_count() {
+    for &emit in &[true, false] {
+        let playlist = Playlist {
+            id: 1,
+            songs: vec![User { id: 1 }, User { id: 2 }, User { id: 3 }],
+            reads: Cell::new(0),
+        };
+        let mut included = Set::new();
+        let object = {
+            let mut ctx = Context::new(Playlist::kind(), None, &mut included);
+
+            ctx.set_emit_linkage(emit);
+            playlist.to_object(&mut ctx).unwrap()
+        };
+        let songs = object.relationships.get("songs").unwrap();
+
+        // The data block is evaluated once whether or not linkage is rendered.
+        assert_eq!(playlist.reads.get(), 3);
+        assert_eq!(songs.meta.get("count"), Some(&Value::from(3)));
+        assert_eq!(linkage(&object, "songs").len(), if emit { 3 } else { 0 });
+    }
+}
+
+#[test]
+fn has_many_poly() {
+    let timeline = Timeline {
+        id: 1,
+        activity: vec![
+            Activity::Post(Post {
+                id: 1,
+                title: "Hello".to_owned(),
+                body: "World".to_owned(),
+            }),
+            Activity::User(User { id: 1 }),
+            Activity::Post(Post {
+                id: 2,
+                title: "Goodbye".to_owned(),
+                body: "World".to_owned(),
+            }),
+        ],
+    };
+    let query = query::from_str("include=activity&fields[posts]=title").unwrap();
+    let mut included = Set::new();
+    let object = {
+        let mut ctx = Context::new(Timeline::kind(), Some(&query), &mut included);
+
+        timeline.to_object(&mut ctx).unwrap()
+    };
+
+    let data = linkage(&object, "activity")
+        .into_iter()
+        .map(|ident| format!("{}:{}", ident.kind, ident.id))
+        .collect::<Vec<_>>();
+
+    assert_eq!(data, vec!["posts:1", "users:1", "posts:2"]);
+    assert_eq!(idents(&included), vec!["posts:1", "users:1", "posts:2"]);
+
+    // The sparse fieldset of each member is selected by its own type.
+    for object in included.iter().filter(|object| object.kind == "posts") {
+        assert!(object.attributes.contains_key("title"));
+        assert!(!object.attributes.contains_key("body"));
+    }
+}
+
+#[test]
+fn instance_kind() {
+    let animals = vec![Animal::Cat(1), Animal::Dog(1), Animal::Cat(2)];
+    let query = query::from_str("fields[dogs]=").unwrap();
+    let doc: Document<Object> = json_api::to_doc(&animals[..], Some(&query)).unwrap();
+
+    assert_eq!(Animal::kind(), "animals");
+    assert_eq!(Animal::Dog(1).instance_kind(), "dogs");
+
+    match doc {
+        Document::Ok {
+            data: Data::Collection(data),
+            ..
+        } => {
+            let kinds = data.iter().map(|obj| obj.kind.to_string()).collect::<Vec<_>>();
+            let sounds = data.iter()
+                .map(|obj| obj.attributes.contains_key("sound"))
+                .collect::<Vec<_>>();
+
+            assert_eq!(kinds, vec!["cats", "dogs", "cats"]);
+            assert_eq!(sounds, vec![true, false, true]);
+        }
+        _ => panic!("expected a document with primary data"),
+    }
+
+    let shelter = Shelter { id: 1, animals };
+    let query = query::from_str("include=animals&fields[cats]=").unwrap();
+    let (object, included) = json_api::to_object_with_included(&shelter, Some(&query)).unwrap();
+    let data = linkage(&object, "animals")
+        .into_iter()
+        .map(|ident| format!("{}:{}", ident.kind, ident.id))
+        .collect::<Vec<_>>();
+
+    assert_eq!(data, vec!["cats:1", "dogs:1", "cats:2"]);
+    assert_eq!(idents(&included), vec!["cats:1", "dogs:1", "cats:2"]);
+
+    for object in &included {
+        let expected = object.kind == "dogs";
+        assert_eq!(object.attributes.contains_key("sound"), expected);
+    }
+}
+
+#[test]
+fn has_many_unloaded() {
+    let query = query::from_str("include=members&fields[teams]=members").unwrap();
+    let team = Team {
+        id: 1,
+        members: None,
+    };
+    let (object, included) = json_api::to_object_with_included(&team, Some(&query)).unwrap();
+    let members = object.relationships.get("members").unwrap();
+
+    assert!(members.data.is_none());
+    assert_eq!(members.meta.get("loaded"), Some(&false.into()));
+    assert!(included.is_empty());
+
+    let team = Team {
+        id: 1,
+        members: Some(Vec::new()),
+    };
+    let object = json_api::to_object(&team, Some(&query)).unwrap();
+    let members = object.relationships.get("members").unwrap();
+
+    assert_eq!(members.data, Some(Data::Collection(Vec::new())));
+
+    let team = Team {
+        id: 1,
+        members: Some(vec![User { id: 1 }, User { id: 2 }]),
+    };
+    let (object, included) = json_api::to_object_with_included(&team, Some(&query)).unwrap();
+
+    assert_eq!(linkage(&object, "members").len(), 2);
+    assert_eq!(idents(&included), vec!["users:1", "users:2"]);
+}
+
+#[test]
+fn relationship_data_error() {
+    let team = Team {
+        id: 1,
+        members: None,
+    };
+    let e = json_api::to_object(&team, None).unwrap_err();
+    let messages = e.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(
+        messages,
+        vec![
+            r#"failed to evaluate the data of relationship "owner""#,
+            "members are not loaded",
+        ]
+    );
+}
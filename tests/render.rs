@@ -0,0 +1,229 @@
+#[macro_use]
+extern crate json_api;
+#[macro_use]
+extern crate serde_json;
+
+mod common;
+
+use json_api::doc::{Data, Document, NewObject, Object};
+use json_api::query;
+use json_api::value::Map;
+use json_api::view::Options;
+
+use common::{article, idents, Comment, Report, User};
+
+fn comments(count: u64) -> impl Iterator<Item = Comment> {
+    (1..count + 1).map(|id| Comment {
+        id,
+        author: Some(User { id: id % 3 }),
+    })
+}
+
+#[test]
+fn to_object_standalone() {
+    let article = article();
+    let query = query::from_str("include=comments&fields[comments]=author").unwrap();
+    let (object, included) = json_api::to_object_with_included(&article, Some(&query)).unwrap();
+
+    assert_eq!(idents(&included), vec!["comments:1", "comments:2"]);
+    assert_eq!(
+        serde_json::to_value(json_api::to_object(&article, Some(&query)).unwrap()).unwrap(),
+        serde_json::to_value(&object).unwrap()
+    );
+
+    // The object is identical to the primary data of a document rendered with the
+    // same query.
+    match json_api::to_doc::<_, Object>(&article, Some(&query)).unwrap() {
+        Document::Ok {
+            data: Data::Member(data),
+            included: expected,
+            ..
+        } => {
+            assert_eq!(
+                serde_json::to_value(&object).unwrap(),
+                serde_json::to_value(data.as_ref().as_ref().unwrap()).unwrap()
+            );
+            assert_eq!(
+                serde_json::to_value(&included).unwrap(),
+                serde_json::to_value(&expected).unwrap()
+            );
+        }
+        _ => panic!("expected a document with primary data"),
+    }
+}
+
+#[test]
+fn to_doc_paginated() {
+    let users = vec![User { id: 11 }, User { id: 12 }];
+    let query = query::from_str("page[number]=3&page[size]=2&sort=-id").unwrap();
+    let base = "https://example.com/users".parse().unwrap();
+    let doc = json_api::to_doc_paginated(&users, &query, 9, &base).unwrap();
+    let href = |number: u64| {
+        format!(
+            "https://example.com/users?page%5Bnumber%5D={}&page%5Bsize%5D=2&sort=-id",
+            number
+        )
+    };
+    let links = doc.links()
+        .iter()
+        .map(|(key, link)| (key.to_string(), link.to_string()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        links,
+        vec![
+            (
+                "first".to_owned(),
+                "https://example.com/users?page%5Bsize%5D=2&sort=-id".to_owned(),
+            ),
+            ("prev".to_owned(), href(2)),
+            ("next".to_owned(), href(4)),
+            ("last".to_owned(), href(5)),
+        ]
+    );
+    assert_eq!(doc.meta().get("total"), Some(&9.into()));
+
+    match doc {
+        Document::Ok {
+            data: Data::Collection(data),
+            ..
+        } => assert_eq!(data.len(), 2),
+        _ => panic!("expected a document with primary data"),
+    }
+}
+
+#[test]
+fn streamed_collection() {
+    let query = query::from_str("include=author").unwrap();
+    let collected = comments(10).collect::<Vec<_>>();
+
+    let expected = json_api::to_vec::<_, Object>(&collected[..], Some(&query)).unwrap();
+    let mut actual = Vec::new();
+
+    json_api::to_writer_streamed(&mut actual, comments(10), Some(&query)).unwrap();
+    assert_eq!(String::from_utf8(actual).unwrap(), String::from_utf8(expected).unwrap());
+
+    let expected = json_api::to_vec_pretty::<_, Object>(&collected[..], None).unwrap();
+    let mut actual = Vec::new();
+
+    json_api::to_writer_streamed_pretty(&mut actual, comments(10), None).unwrap();
+    assert_eq!(String::from_utf8(actual).unwrap(), String::from_utf8(expected).unwrap());
+
+    let mut actual = Vec::new();
+
+    json_api::to_writer_streamed(&mut actual, comments(0), None).unwrap();
+    assert_eq!(actual, br#"{"data":[],"jsonapi":{"version":"1.0"}}"#.to_vec());
+}
+
+#[test]
+fn streamed_collection_error() {
+    struct Tagged {
+        id: u64,
+        tags: Map<Option<String>, bool>,
+    }
+
+    resource!(Tagged, |&self| {
+        kind "tagged";
+        id self.id;
+
+        attrs tags;
+    });
+
+    let items = (1..4).map(|id| {
+        let mut tags = Map::new();
+
+        tags.insert(if id == 2 { None } else { Some("valid".to_owned()) }, true);
+        Tagged { id, tags }
+    });
+
+    let mut buf = Vec::new();
+    let err = json_api::to_writer_streamed(&mut buf, items, None).unwrap_err();
+
+    assert_eq!(err.to_string(), "map keys must be a string");
+    assert!(String::from_utf8(buf).unwrap().starts_with(r#"{"data":[{"#));
+}
+
+#[test]
+fn identifiers_only() {
+    let reports = (1..4).map(|id| Report { id }).collect::<Vec<_>>();
+    let query = query::from_str("page[number]=1&page[size]=3").unwrap();
+    let base = "/reports".parse().unwrap();
+    let doc = json_api::to_doc_identifiers_paginated(&reports, &query, 7, &base).unwrap();
+
+    match doc {
+        Document::Ok {
+            data: Data::Collection(ref data),
+            ..
+        } => {
+            let ids = data.iter().map(|ident| ident.id.as_str()).collect::<Vec<_>>();
+            assert_eq!(ids, ["1", "2", "3"]);
+        }
+        _ => panic!("unexpected document: {:?}", doc),
+    }
+
+    assert_eq!(doc.meta().get("total"), Some(&7.into()));
+    assert_eq!(doc.meta().get("primary"), Some(&"reports".into()));
+    assert!(doc.links().contains_key("next"));
+
+    let doc = json_api::to_doc_identifiers(&reports[..1], None).unwrap();
+    let json = serde_json::to_value(&doc).unwrap();
+
+    assert_eq!(json["data"], json!([{ "id": "1", "type": "reports" }]));
+}
+
+#[test]
+fn new_object_from_resource() {
+    let article = article();
+    let query = query::from_str("include=comments").unwrap();
+    let doc: Document<NewObject> = json_api::to_doc(&article, Some(&query)).unwrap();
+    let json = serde_json::to_value(&doc).unwrap();
+
+    // The id is omitted and nothing is included, even if the query asks for it.
+    assert_eq!(
+        json,
+        json!({
+            "data": {
+                "type": "articles",
+                "relationships": {
+                    "comments": {
+                        "data": [
+                            { "id": "1", "type": "comments" },
+                            { "id": "2", "type": "comments" }
+                        ]
+                    }
+                }
+            },
+            "jsonapi": { "version": "1.0" },
+            "meta": { "primary": "articles" }
+        })
+    );
+
+    let json = json_api::to_string::<_, NewObject>(&article.comments[..], None).unwrap();
+    let data = serde_json::from_str::<serde_json::Value>(&json).unwrap()["data"].take();
+
+    assert_eq!(data[0], json!({
+        "type": "comments",
+        "relationships": {
+            "author": { "data": { "id": "1", "type": "users" } }
+        }
+    }));
+    assert_eq!(data.as_array().map(Vec::len), Some(2));
+
+    let mut options = Options::new();
+
+    options.client_id = true;
+
+    let doc: Document<NewObject> = json_api::to_doc_with(&article, None, &options).unwrap();
+    let json = serde_json::to_value(&doc).unwrap();
+
+    assert_eq!(json["data"]["id"], "1");
+    assert_eq!(json["data"]["type"], "articles");
+
+    let objects = vec![NewObject::new("users".parse().unwrap()); 2];
+    let doc: Document<NewObject> = json_api::to_doc(objects, None).unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&doc).unwrap()["data"],
+        json!([{ "type": "users" }, { "type": "users" }])
+    );
+}
@@ -0,0 +1,73 @@
+extern crate json_api;
+
+use json_api::doc::{self, Document, Object, Severity};
+
+const INVALID: &str = include_str!("fixtures/validate-invalid.json");
+const VALID: &str = include_str!("fixtures/validate-valid.json");
+
+fn violations(source: &str) -> Vec<(Severity, String, String)> {
+    let doc: Document<Object> = json_api::parse_str(source).unwrap();
+
+    doc::validate(&doc)
+        .into_iter()
+        .map(|item| (item.severity, item.pointer.to_string(), item.message))
+        .collect()
+}
+
+#[test]
+fn validate_valid() {
+    assert_eq!(violations(VALID), []);
+}
+
+#[test]
+fn validate_invalid() {
+    let expected = vec![
+        (
+            Severity::Warning,
+            "/data/0/links/self".to_owned(),
+            "link object contains members that are not part of JSON API 1.0: title".to_owned(),
+        ),
+        (
+            Severity::Error,
+            "/data/0/relationships/tags".to_owned(),
+            "relationship object must contain data, links, or meta".to_owned(),
+        ),
+        (
+            Severity::Error,
+            "/data/2/id".to_owned(),
+            "resource object is missing an id".to_owned(),
+        ),
+        (
+            Severity::Error,
+            "/included/1".to_owned(),
+            "included resource duplicates the primary resource articles:2".to_owned(),
+        ),
+        (
+            Severity::Error,
+            "/included/2".to_owned(),
+            "included resource comments:5 is not linked to the primary data".to_owned(),
+        ),
+    ];
+
+    assert_eq!(violations(INVALID), expected);
+}
+
+#[test]
+fn validate_errors() {
+    let source = r#"{
+        "errors": [{
+            "status": "404",
+            "title": "Not Found",
+            "links": { "about": { "href": "/errors/404", "rel": "help" } }
+        }]
+    }"#;
+
+    assert_eq!(
+        violations(source),
+        [(
+            Severity::Warning,
+            "/errors/0/links/about".to_owned(),
+            "link object contains members that are not part of JSON API 1.0: rel".to_owned(),
+        )]
+    );
+}